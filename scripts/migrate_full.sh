@@ -17,6 +17,9 @@ SOLANA_RPC_PROVIDER_URL=https://devnet.helius-rpc.com/?api-key=6b3f6b84-a89d-485
 # STEP 3 Migrate Central state
 ts-node migrate-central-state-v2.ts
 
+# STEP 3b Create the protocol config, required by the claims of the ACCESS NFT program
+ts-node set-protocol-config.ts
+
 # STEP 4 Setup fee split
 
 # STEP 5 OPTIONAL - set freeze authority
\ No newline at end of file
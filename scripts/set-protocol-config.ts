@@ -0,0 +1,66 @@
+import fs from "fs";
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  TransactionMessage,
+  VersionedTransaction
+} from "@solana/web3.js";
+
+import { ACCESS_NFT_PROGRAM_SIGNER, adminSetProtocolConfig } from "../smart-contract/js";
+
+const {
+  SOLANA_RPC_PROVIDER_URL, PROGRAM_PUBKEY, AUTHORITY_KEYPAIR, ACCESS_NFT_PROGRAM_SIGNER_PUBKEY
+} = process.env;
+
+if (SOLANA_RPC_PROVIDER_URL == null)
+  throw new Error("SOLANA_RPC_PROVIDER_URL must be set.");
+if (PROGRAM_PUBKEY == null)
+  throw new Error("PROGRAM_PUBKEY must be set.");
+if (AUTHORITY_KEYPAIR == null)
+  throw new Error("AUTHORITY_KEYPAIR must be set.");
+
+// The Solana RPC connection
+const connection = new Connection(SOLANA_RPC_PROVIDER_URL);
+
+// The central state authority, also paying for the protocol config account
+const authorityKeypair = Keypair.fromSecretKey(
+  Uint8Array.from(JSON.parse(fs.readFileSync(AUTHORITY_KEYPAIR).toString()))
+);
+
+// The signer of the ACCESS NFT program, the mainnet one by default
+const accessNftProgramSigner = ACCESS_NFT_PROGRAM_SIGNER_PUBKEY == null
+  ? ACCESS_NFT_PROGRAM_SIGNER
+  : new PublicKey(ACCESS_NFT_PROGRAM_SIGNER_PUBKEY);
+
+const setProtocolConfig = async () => {
+  const ix = await adminSetProtocolConfig(
+    connection,
+    authorityKeypair.publicKey, // Fee payer
+    accessNftProgramSigner,
+    new PublicKey(PROGRAM_PUBKEY), // Program ID
+  );
+
+  const messageV0 = new TransactionMessage({
+    payerKey: authorityKeypair.publicKey,
+    recentBlockhash: (await connection.getLatestBlockhash()).blockhash,
+    instructions: [ix],
+  }).compileToV0Message();
+
+  const transaction = new VersionedTransaction(messageV0);
+  transaction.sign([authorityKeypair]);
+
+  const tx = await connection.sendTransaction(transaction, {
+    preflightCommitment: "confirmed",
+    skipPreflight: false
+  });
+
+  console.log(`Set the protocol config with the ACCESS NFT program signer ${accessNftProgramSigner.toBase58()} ${tx}`);
+};
+
+setProtocolConfig()
+  .then(() => process.exit(0))
+  .catch((error) => {
+    console.error(error);
+    process.exit(1);
+  });
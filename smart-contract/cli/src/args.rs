@@ -0,0 +1,221 @@
+//! Command line arguments
+//!
+//! The read only commands are grouped under `show` and never need the keypair, every other
+//! command builds a transaction.
+use access_protocol_client::access_protocol::{
+    self, instruction::ProgramInstruction, state::FeeRecipient,
+    utils::is_admin_renouncable_instruction,
+};
+use clap::{ArgEnum, Parser, Subcommand};
+use num_traits::FromPrimitive;
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Display,
+    Json,
+}
+
+#[derive(Parser, Debug)]
+#[clap(
+    name = "access-cli",
+    version,
+    about = "Operate the Access Protocol program"
+)]
+pub struct Cli {
+    /// The RPC endpoint
+    #[clap(
+        long,
+        short = 'u',
+        global = true,
+        default_value = "https://api.mainnet-beta.solana.com"
+    )]
+    pub url: String,
+
+    /// The keypair file signing and paying for the transactions, defaults to the Solana CLI keypair
+    #[clap(long, short = 'k', global = true)]
+    pub keypair: Option<String>,
+
+    /// The program id
+    #[clap(long, global = true, default_value_t = access_protocol::ID)]
+    pub program_id: Pubkey,
+
+    /// The mint of a tenant central state, the original central state is used by default
+    #[clap(long, global = true)]
+    pub mint: Option<Pubkey>,
+
+    /// Simulate the transaction instead of sending it
+    #[clap(long, global = true)]
+    pub dry_run: bool,
+
+    /// The output format
+    #[clap(long, global = true, arg_enum, default_value = "display")]
+    pub output: OutputFormat,
+
+    #[clap(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Read and print accounts of the program
+    #[clap(subcommand)]
+    Show(ShowCommand),
+    #[clap(flatten)]
+    Transaction(TransactionCommand),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ShowCommand {
+    /// Show the central state
+    CentralState,
+    /// Decode and show an account of any type
+    Account { address: Pubkey },
+    /// Show the pool of an owner
+    Pool { owner: Pubkey },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TransactionCommand {
+    /// Manage stake pools
+    #[clap(subcommand)]
+    Pool(PoolCommand),
+    /// Stake in a pool, the stake account is created if needed
+    Stake { pool_owner: Pubkey, amount: u64 },
+    /// Unstake from a pool
+    Unstake { pool_owner: Pubkey, amount: u64 },
+    /// Claim the staker rewards in a pool, or the rewards of the own pool with `--pool-rewards`
+    Claim {
+        #[clap(
+            required_unless_present = "pool-rewards",
+            conflicts_with = "pool-rewards"
+        )]
+        pool_owner: Option<Pubkey>,
+        #[clap(long)]
+        pool_rewards: bool,
+    },
+    /// Crank a pool
+    Crank { pool_owner: Pubkey },
+    /// Migrate a stake account, a bond V2 account or the central state to its latest layout
+    MigrateAccount { address: Pubkey },
+    /// Manage bond V2 accounts
+    #[clap(subcommand)]
+    BondV2(BondV2Command),
+    /// Manage the royalty account of the keypair
+    #[clap(subcommand)]
+    Royalty(RoyaltyCommand),
+    /// Manage the protocol fees
+    #[clap(subcommand)]
+    Fees(FeesCommand),
+    /// Admin operations signed by the central state authority
+    #[clap(subcommand)]
+    Admin(AdminCommand),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PoolCommand {
+    /// Create the pool of the keypair
+    Create {
+        #[clap(long)]
+        minimum_stake_amount: u64,
+    },
+    /// Activate a pool
+    Activate { owner: Pubkey },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BondV2Command {
+    /// Create a bond V2 account
+    Create {
+        pool_owner: Pubkey,
+        /// The owner of the bond, defaults to the keypair
+        #[clap(long)]
+        owner: Option<Pubkey>,
+        #[clap(long)]
+        unlock_timestamp: Option<i64>,
+    },
+    /// Lock tokens of the keypair in a bond V2 account
+    Add {
+        pool_owner: Pubkey,
+        amount: u64,
+        /// The owner of the bond, defaults to the keypair
+        #[clap(long)]
+        owner: Option<Pubkey>,
+        #[clap(long)]
+        unlock_timestamp: Option<i64>,
+    },
+    /// Unlock a bond V2 account of the keypair
+    Unlock {
+        pool_owner: Pubkey,
+        #[clap(long)]
+        unlock_timestamp: Option<i64>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RoyaltyCommand {
+    /// Redirect a part of the rewards of the keypair to a token account
+    Create {
+        recipient_ata: Pubkey,
+        #[clap(long)]
+        basis_points: u16,
+        /// Unix timestamp after which the royalty stops applying
+        #[clap(long)]
+        expiration_date: u64,
+    },
+    /// Close the royalty account of the keypair
+    Close,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum FeesCommand {
+    /// Distribute the collected fees to the fee recipients
+    Distribute,
+    /// Replace the fee recipients
+    Setup {
+        /// A fee recipient as `OWNER:PERCENTAGE`, can be repeated
+        #[clap(long = "recipient", required = true, parse(try_from_str = parse_recipient))]
+        recipients: Vec<FeeRecipient>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AdminCommand {
+    /// Freeze or unfreeze an account, freezing the central state freezes the program
+    Freeze { account: Pubkey },
+    /// Renounce the admin permission over an instruction, this cannot be undone
+    Renounce {
+        /// The admin instruction, e.g. `admin-mint` or `AdminMint`
+        #[clap(parse(try_from_str = parse_admin_instruction))]
+        instruction: ProgramInstruction,
+    },
+}
+
+/// Parses a fee recipient written as `OWNER:PERCENTAGE`
+pub fn parse_recipient(s: &str) -> Result<FeeRecipient, String> {
+    let (owner, percentage) = s
+        .split_once(':')
+        .ok_or_else(|| format!("Expected OWNER:PERCENTAGE, got {}", s))?;
+    Ok(FeeRecipient {
+        owner: owner
+            .parse()
+            .map_err(|_| format!("Invalid owner {}", owner))?,
+        percentage: percentage
+            .parse()
+            .map_err(|_| format!("Invalid percentage {}", percentage))?,
+    })
+}
+
+/// Parses the name of an instruction the admin can renounce, in kebab or Pascal case
+pub fn parse_admin_instruction(s: &str) -> Result<ProgramInstruction, String> {
+    let normalize = |name: &str| name.replace(['-', '_'], "").to_lowercase();
+    let name = normalize(s);
+    let instruction = (0..=u8::MAX)
+        .filter_map(ProgramInstruction::from_u8)
+        .find(|ix| normalize(&format!("{:?}", ix)) == name)
+        .ok_or_else(|| format!("Unknown instruction {}", s))?;
+    if !is_admin_renouncable_instruction(&instruction) {
+        return Err(format!("{} is not an admin instruction", s));
+    }
+    Ok(instruction)
+}
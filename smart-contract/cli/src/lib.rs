@@ -0,0 +1,2 @@
+//! Arguments of the `access-cli` command line tool, exposed to test their parsing
+pub mod args;
@@ -0,0 +1,163 @@
+//! Command line tool for the Access Protocol program
+//!
+//! Every command that changes the chain state builds a single transaction signed and paid by the
+//! keypair, `--dry-run` simulates it instead of sending it.
+use std::error::Error;
+
+use access_cli::args::{
+    AdminCommand, BondV2Command, Cli, Command, FeesCommand, OutputFormat, PoolCommand,
+    RoyaltyCommand, ShowCommand, TransactionCommand,
+};
+use access_protocol_client::AccessProtocolClient;
+use clap::Parser;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair},
+    signer::Signer,
+};
+
+mod output;
+mod transaction;
+
+fn load_keypair(path: Option<&str>) -> Result<Keypair, Box<dyn Error>> {
+    let path = match path {
+        Some(path) => path.to_owned(),
+        None => format!("{}/.config/solana/id.json", std::env::var("HOME")?),
+    };
+    read_keypair_file(&path).map_err(|e| format!("Cannot read the keypair {}: {}", path, e).into())
+}
+
+fn show(
+    client: &AccessProtocolClient,
+    command: ShowCommand,
+    format: OutputFormat,
+) -> Result<(), Box<dyn Error>> {
+    match command {
+        ShowCommand::CentralState => {
+            let central_state = client.central_state()?;
+            output::print_central_state(&client.central_state_key(), &central_state, format);
+        }
+        ShowCommand::Account { address } => {
+            let account = client.account(&address)?;
+            output::print_account(&address, &account, format);
+        }
+        ShowCommand::Pool { owner } => {
+            let stake_pool = client.stake_pool(&owner)?;
+            output::print_stake_pool(&client.stake_pool_key(&owner), &stake_pool, format);
+        }
+    }
+    Ok(())
+}
+
+fn build_instructions(
+    client: &AccessProtocolClient,
+    signer: &Pubkey,
+    command: TransactionCommand,
+) -> Result<Vec<Instruction>, Box<dyn Error>> {
+    let instructions = match command {
+        TransactionCommand::Pool(PoolCommand::Create {
+            minimum_stake_amount,
+        }) => client.create_stake_pool(signer, signer, minimum_stake_amount),
+        TransactionCommand::Pool(PoolCommand::Activate { owner }) => {
+            vec![client.activate_stake_pool(&owner)]
+        }
+        TransactionCommand::Stake { pool_owner, amount } => {
+            client.stake(signer, &pool_owner, amount)?
+        }
+        TransactionCommand::Unstake { pool_owner, amount } => {
+            vec![client.unstake(signer, &pool_owner, amount)]
+        }
+        // `--pool-rewards` conflicts with the pool owner
+        TransactionCommand::Claim {
+            pool_owner: Some(pool_owner),
+            ..
+        } => vec![client.claim_rewards(signer, &pool_owner)?],
+        TransactionCommand::Claim {
+            pool_owner: None, ..
+        } => vec![client.claim_pool_rewards(signer)?],
+        TransactionCommand::Crank { pool_owner } => vec![client.crank(&pool_owner)],
+        TransactionCommand::MigrateAccount { address } => {
+            vec![client.migrate_account(signer, &address)]
+        }
+        TransactionCommand::BondV2(BondV2Command::Create {
+            pool_owner,
+            owner,
+            unlock_timestamp,
+        }) => vec![client.create_bond_v2(
+            &owner.unwrap_or(*signer),
+            &pool_owner,
+            unlock_timestamp,
+            signer,
+        )],
+        TransactionCommand::BondV2(BondV2Command::Add {
+            pool_owner,
+            amount,
+            owner,
+            unlock_timestamp,
+        }) => vec![client.add_to_bond_v2(
+            signer,
+            &owner.unwrap_or(*signer),
+            &pool_owner,
+            unlock_timestamp,
+            amount,
+        )],
+        TransactionCommand::BondV2(BondV2Command::Unlock {
+            pool_owner,
+            unlock_timestamp,
+        }) => vec![client.unlock_bond_v2(signer, &pool_owner, unlock_timestamp)],
+        TransactionCommand::Royalty(RoyaltyCommand::Create {
+            recipient_ata,
+            basis_points,
+            expiration_date,
+        }) => vec![client.create_royalty_account(
+            signer,
+            &recipient_ata,
+            basis_points,
+            expiration_date,
+            signer,
+        )],
+        TransactionCommand::Royalty(RoyaltyCommand::Close) => {
+            vec![client.close_royalty_account(signer)?]
+        }
+        TransactionCommand::Fees(FeesCommand::Distribute) => vec![client.distribute_fees()?],
+        TransactionCommand::Fees(FeesCommand::Setup { recipients }) => {
+            vec![client.admin_setup_fee_split(signer, recipients)]
+        }
+        TransactionCommand::Admin(AdminCommand::Freeze { account }) => {
+            vec![client.admin_freeze(signer, &account)]
+        }
+        TransactionCommand::Admin(AdminCommand::Renounce { instruction }) => {
+            vec![client.admin_renounce(signer, instruction)]
+        }
+    };
+    Ok(instructions)
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let rpc = RpcClient::new_with_commitment(cli.url, CommitmentConfig::confirmed());
+    let client = match cli.mint {
+        Some(mint) => AccessProtocolClient::new_tenant(rpc, cli.program_id, &mint)?,
+        None => AccessProtocolClient::new(rpc, cli.program_id)?,
+    };
+
+    match cli.command {
+        Command::Show(command) => show(&client, command, cli.output),
+        Command::Transaction(command) => {
+            let payer = load_keypair(cli.keypair.as_deref())?;
+            let instructions = build_instructions(&client, &payer.pubkey(), command)?;
+            let outcome = transaction::execute(client.rpc(), &payer, &instructions, cli.dry_run)?;
+            output::print_outcome(&outcome, cli.output);
+            Ok(())
+        }
+    }
+}
+
+fn main() {
+    if let Err(err) = run(Cli::parse()) {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    }
+}
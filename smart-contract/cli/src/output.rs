@@ -0,0 +1,105 @@
+//! Human readable and JSON output
+use access_cli::args::OutputFormat;
+use access_protocol_client::access_protocol::decoder::ProtocolAccount;
+use access_protocol_client::access_protocol::state::{CentralStateV2, StakePoolHeader};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::transaction::Outcome;
+
+fn print(value: Value, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => println!("{}", value),
+        OutputFormat::Display => {
+            if let Value::Object(fields) = value {
+                for (name, value) in fields {
+                    match value {
+                        Value::String(s) => println!("{}: {}", name, s),
+                        other => println!("{}: {}", name, other),
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn print_central_state(key: &Pubkey, central_state: &CentralStateV2, format: OutputFormat) {
+    let recipients: Vec<Value> = central_state
+        .recipients
+        .iter()
+        .map(|r| json!({"owner": r.owner.to_string(), "percentage": r.percentage}))
+        .collect();
+    print(
+        json!({
+            "address": key.to_string(),
+            "tag": format!("{:?}", central_state.tag),
+            "daily_inflation": central_state.daily_inflation,
+            "token_mint": central_state.token_mint.to_string(),
+            "authority": central_state.authority.to_string(),
+            "freeze_authority": central_state.freeze_authority.to_string(),
+            "creation_time": central_state.creation_time,
+            "total_staked": central_state.total_staked,
+            "total_staked_snapshot": central_state.total_staked_snapshot,
+            "total_boost": central_state.total_boost,
+            "total_boost_snapshot": central_state.total_boost_snapshot,
+            "last_snapshot_offset": central_state.last_snapshot_offset,
+            "ix_gate": format!("{:#034x}", central_state.ix_gate),
+            "admin_ix_gate": format!("{:#034x}", central_state.admin_ix_gate),
+            "fee_basis_points": central_state.fee_basis_points,
+            "last_fee_distribution_time": central_state.last_fee_distribution_time,
+            "exits_frozen_since": central_state.exits_frozen_since,
+            "exits_unfrozen_since": central_state.exits_unfrozen_since,
+            "emission_cap": central_state.emission_cap,
+            "staker_rewards_minted": central_state.staker_rewards_minted,
+            "pool_rewards_minted": central_state.pool_rewards_minted,
+            "royalties_minted": central_state.royalties_minted,
+            "admin_minted": central_state.admin_minted,
+            "bonds_minted": central_state.bonds_minted,
+            "fees_burned": central_state.fees_burned,
+            "recipients": recipients,
+        }),
+        format,
+    )
+}
+
+pub fn print_stake_pool(key: &Pubkey, header: &StakePoolHeader, format: OutputFormat) {
+    print(
+        json!({
+            "address": key.to_string(),
+            "tag": header.tag,
+            "owner": Pubkey::new_from_array(header.owner).to_string(),
+            "vault": Pubkey::new_from_array(header.vault).to_string(),
+            "minimum_stake_amount": header.minimum_stake_amount,
+            "total_staked": header.total_staked,
+            "current_day_idx": header.current_day_idx,
+            "last_claimed_offset": header.last_claimed_offset,
+            "stakers_part": header.stakers_part,
+        }),
+        format,
+    )
+}
+
+pub fn print_account(key: &Pubkey, account: &ProtocolAccount, format: OutputFormat) {
+    let mut value = json!({ "address": key.to_string() });
+    if let (Value::Object(fields), Value::Object(account)) = (&mut value, account.to_json()) {
+        fields.extend(account);
+    }
+    print(value, format)
+}
+
+pub fn print_outcome(outcome: &Outcome, format: OutputFormat) {
+    let value = match outcome {
+        Outcome::Sent { signature } => json!({ "signature": signature.to_string() }),
+        Outcome::Simulated {
+            err,
+            logs,
+            units_consumed,
+        } => json!({
+            "simulated": true,
+            "err": err,
+            "units_consumed": units_consumed,
+            "logs": logs,
+        }),
+    };
+    print(value, format)
+}
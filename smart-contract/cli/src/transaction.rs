@@ -0,0 +1,48 @@
+//! Sending and simulating transactions
+use solana_client::{client_error::ClientError, rpc_client::RpcClient};
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
+};
+
+/// The result of a command changing the chain state
+pub enum Outcome {
+    Sent {
+        signature: Signature,
+    },
+    Simulated {
+        err: Option<String>,
+        logs: Vec<String>,
+        units_consumed: Option<u64>,
+    },
+}
+
+/// Sends the instructions in one transaction paid by `payer`, or simulates it if `dry_run` is set
+pub fn execute(
+    rpc: &RpcClient,
+    payer: &Keypair,
+    instructions: &[Instruction],
+    dry_run: bool,
+) -> Result<Outcome, ClientError> {
+    let blockhash = rpc.get_latest_blockhash()?;
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &[payer],
+        blockhash,
+    );
+
+    if dry_run {
+        let result = rpc.simulate_transaction(&transaction)?.value;
+        return Ok(Outcome::Simulated {
+            err: result.err.map(|e| e.to_string()),
+            logs: result.logs.unwrap_or_default(),
+            units_consumed: result.units_consumed,
+        });
+    }
+
+    let signature = rpc.send_and_confirm_transaction(&transaction)?;
+    Ok(Outcome::Sent { signature })
+}
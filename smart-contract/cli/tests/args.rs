@@ -0,0 +1,209 @@
+use access_cli::args::{
+    parse_admin_instruction, parse_recipient, AdminCommand, BondV2Command, Cli, Command,
+    FeesCommand, OutputFormat, ShowCommand, TransactionCommand,
+};
+use access_protocol_client::access_protocol::{self, instruction::ProgramInstruction};
+use clap::{CommandFactory, ErrorKind, Parser};
+use solana_sdk::pubkey::Pubkey;
+
+fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+    Cli::try_parse_from(std::iter::once("access-cli").chain(args.iter().copied()))
+}
+
+#[test]
+fn definitions() {
+    // Checks the arguments of every subcommand, including the ones not parsed below
+    Cli::command().debug_assert();
+}
+
+#[test]
+fn global_options() {
+    let owner = Pubkey::new_unique();
+    let cli = parse(&["show", "pool", &owner.to_string()]).unwrap();
+    assert_eq!(cli.url, "https://api.mainnet-beta.solana.com");
+    assert_eq!(cli.program_id, access_protocol::ID);
+    assert_eq!(cli.output, OutputFormat::Display);
+    assert!(cli.keypair.is_none() && cli.mint.is_none() && !cli.dry_run);
+    assert!(matches!(cli.command, Command::Show(ShowCommand::Pool { owner: o }) if o == owner));
+
+    // The global options are accepted after the subcommand
+    let mint = Pubkey::new_unique();
+    let cli = parse(&[
+        "stake",
+        &owner.to_string(),
+        "10000",
+        "--dry-run",
+        "--output",
+        "json",
+        "--mint",
+        &mint.to_string(),
+        "-k",
+        "id.json",
+    ])
+    .unwrap();
+    assert!(cli.dry_run);
+    assert_eq!(cli.output, OutputFormat::Json);
+    assert_eq!(cli.mint, Some(mint));
+    assert_eq!(cli.keypair.as_deref(), Some("id.json"));
+    assert!(matches!(
+        cli.command,
+        Command::Transaction(TransactionCommand::Stake { pool_owner, amount: 10_000 })
+            if pool_owner == owner
+    ));
+
+    assert_eq!(
+        parse(&["show", "central-state", "--output", "yaml"])
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidValue
+    );
+}
+
+#[test]
+fn show_commands() {
+    assert!(matches!(
+        parse(&["show", "central-state"]).unwrap().command,
+        Command::Show(ShowCommand::CentralState)
+    ));
+    let address = Pubkey::new_unique();
+    assert!(matches!(
+        parse(&["show", "account", &address.to_string()]).unwrap().command,
+        Command::Show(ShowCommand::Account { address: a }) if a == address
+    ));
+    assert_eq!(
+        parse(&["show", "account", "not-a-key"]).unwrap_err().kind(),
+        ErrorKind::ValueValidation
+    );
+    assert_eq!(
+        parse(&["show"]).unwrap_err().kind(),
+        ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+    );
+}
+
+#[test]
+fn claim() {
+    let owner = Pubkey::new_unique();
+    assert!(matches!(
+        parse(&["claim", &owner.to_string()]).unwrap().command,
+        Command::Transaction(TransactionCommand::Claim {
+            pool_owner: Some(o),
+            pool_rewards: false,
+        }) if o == owner
+    ));
+    assert!(matches!(
+        parse(&["claim", "--pool-rewards"]).unwrap().command,
+        Command::Transaction(TransactionCommand::Claim {
+            pool_owner: None,
+            pool_rewards: true,
+        })
+    ));
+    assert_eq!(
+        parse(&["claim"]).unwrap_err().kind(),
+        ErrorKind::MissingRequiredArgument
+    );
+    assert_eq!(
+        parse(&["claim", &owner.to_string(), "--pool-rewards"])
+            .unwrap_err()
+            .kind(),
+        ErrorKind::ArgumentConflict
+    );
+}
+
+#[test]
+fn bond_v2() {
+    let pool_owner = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let cli = parse(&[
+        "bond-v2",
+        "add",
+        &pool_owner.to_string(),
+        "5000",
+        "--owner",
+        &owner.to_string(),
+        "--unlock-timestamp",
+        "1700000000",
+    ])
+    .unwrap();
+    assert!(matches!(
+        cli.command,
+        Command::Transaction(TransactionCommand::BondV2(BondV2Command::Add {
+            pool_owner: p,
+            amount: 5_000,
+            owner: Some(o),
+            unlock_timestamp: Some(1_700_000_000),
+        })) if p == pool_owner && o == owner
+    ));
+    assert!(matches!(
+        parse(&["bond-v2", "unlock", &pool_owner.to_string()])
+            .unwrap()
+            .command,
+        Command::Transaction(TransactionCommand::BondV2(BondV2Command::Unlock {
+            unlock_timestamp: None,
+            ..
+        }))
+    ));
+}
+
+#[test]
+fn fee_recipients() {
+    let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let cli = parse(&[
+        "fees",
+        "setup",
+        "--recipient",
+        &format!("{}:60", first),
+        "--recipient",
+        &format!("{}:40", second),
+    ])
+    .unwrap();
+    match cli.command {
+        Command::Transaction(TransactionCommand::Fees(FeesCommand::Setup { recipients })) => {
+            let recipients: Vec<_> = recipients.iter().map(|r| (r.owner, r.percentage)).collect();
+            assert_eq!(recipients, vec![(first, 60), (second, 40)]);
+        }
+        _ => panic!("Expected the fee setup"),
+    }
+    assert_eq!(
+        parse(&["fees", "setup"]).unwrap_err().kind(),
+        ErrorKind::MissingRequiredArgument
+    );
+
+    assert!(parse_recipient(&first.to_string()).is_err());
+    assert!(parse_recipient("not-a-key:50").is_err());
+    assert!(parse_recipient(&format!("{}:fifty", first)).is_err());
+    assert!(parse_recipient(&format!("{}:-1", first)).is_err());
+}
+
+#[test]
+fn renounce() {
+    for name in ["admin-mint", "AdminMint", "admin_mint", "ADMIN-MINT"] {
+        assert!(matches!(
+            parse_admin_instruction(name),
+            Ok(ProgramInstruction::AdminMint)
+        ));
+    }
+    assert!(matches!(
+        parse(&["admin", "renounce", "admin-set-emission-cap"])
+            .unwrap()
+            .command,
+        Command::Transaction(TransactionCommand::Admin(AdminCommand::Renounce {
+            instruction: ProgramInstruction::AdminSetEmissionCap,
+        }))
+    ));
+
+    // Only the admin instructions can be renounced, by name
+    assert_eq!(
+        parse_admin_instruction("stake").unwrap_err(),
+        "stake is not an admin instruction"
+    );
+    assert_eq!(
+        parse_admin_instruction("18").unwrap_err(),
+        "Unknown instruction 18"
+    );
+    assert_eq!(
+        parse(&["admin", "renounce", "admin-mnit"])
+            .unwrap_err()
+            .kind(),
+        ErrorKind::ValueValidation
+    );
+}
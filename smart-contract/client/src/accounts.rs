@@ -0,0 +1,127 @@
+//! Fetching and decoding of the program accounts
+use access_protocol::decoder::ProtocolAccount;
+use access_protocol::state::{
+    BondV2Account, CentralStateV2, PoolFeeOverride, ProtocolConfig, RoyaltyAccount, StakeAccount,
+    StakePoolHeader, StakePoolHeaped, Tag,
+};
+use access_protocol::versioning::{read_header, Versioned};
+use borsh::BorshDeserialize;
+use solana_client::rpc_client::RpcClient;
+use solana_program::pubkey::Pubkey;
+
+use crate::error::ClientError;
+
+/// Fetches the data of an account, returns `None` if it does not exist
+pub fn fetch_account_data(rpc: &RpcClient, key: &Pubkey) -> Result<Option<Vec<u8>>, ClientError> {
+    let account = rpc
+        .get_account_with_commitment(key, rpc.commitment())?
+        .value;
+    Ok(account.map(|a| a.data))
+}
+
+/// Fetches the data of an account, returns `None` if it does not exist or was closed
+fn fetch_live_account_data(rpc: &RpcClient, key: &Pubkey) -> Result<Option<Vec<u8>>, ClientError> {
+    match fetch_account_data(rpc, key)? {
+        Some(data) if !matches!(read_header(&data), Err(_) | Ok((Tag::Deleted, _))) => {
+            Ok(Some(data))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Fetches and deserializes an account, returns `None` if it does not exist or was closed
+pub fn fetch_optional<T: BorshDeserialize>(
+    rpc: &RpcClient,
+    key: &Pubkey,
+) -> Result<Option<T>, ClientError> {
+    let data = match fetch_live_account_data(rpc, key)? {
+        Some(data) => data,
+        None => return Ok(None),
+    };
+    let result =
+        T::deserialize(&mut &data[..]).map_err(|_| ClientError::InvalidAccountData(*key))?;
+    Ok(Some(result))
+}
+
+/// Fetches an account with a versioned layout, decoded from any of its layouts
+pub fn fetch_optional_versioned<T: Versioned>(
+    rpc: &RpcClient,
+    key: &Pubkey,
+) -> Result<Option<T>, ClientError> {
+    let data = match fetch_live_account_data(rpc, key)? {
+        Some(data) => data,
+        None => return Ok(None),
+    };
+    let result = T::load(&data).map_err(|_| ClientError::InvalidAccountData(*key))?;
+    Ok(Some(result))
+}
+
+/// Fetches and deserializes an account which has to exist
+pub fn fetch<T: BorshDeserialize>(rpc: &RpcClient, key: &Pubkey) -> Result<T, ClientError> {
+    fetch_optional(rpc, key)?.ok_or(ClientError::AccountNotFound(*key))
+}
+
+/// Fetches a central state
+pub fn fetch_central_state(rpc: &RpcClient, key: &Pubkey) -> Result<CentralStateV2, ClientError> {
+    fetch_optional_versioned(rpc, key)?.ok_or(ClientError::AccountNotFound(*key))
+}
+
+/// Fetches the header of a stake pool
+pub fn fetch_stake_pool(rpc: &RpcClient, key: &Pubkey) -> Result<StakePoolHeader, ClientError> {
+    fetch(rpc, key)
+}
+
+/// Fetches a stake pool with its rewards buffer
+pub fn fetch_stake_pool_with_balances(
+    rpc: &RpcClient,
+    key: &Pubkey,
+) -> Result<StakePoolHeaped, ClientError> {
+    let data = fetch_account_data(rpc, key)?.ok_or(ClientError::AccountNotFound(*key))?;
+    if data.len() < std::mem::size_of::<StakePoolHeader>() {
+        return Err(ClientError::InvalidAccountData(*key));
+    }
+    Ok(StakePoolHeaped::from_buffer(&data))
+}
+
+/// Fetches a stake account
+pub fn fetch_stake_account(rpc: &RpcClient, key: &Pubkey) -> Result<StakeAccount, ClientError> {
+    fetch_optional_versioned(rpc, key)?.ok_or(ClientError::AccountNotFound(*key))
+}
+
+/// Fetches a bond V2 account
+pub fn fetch_bond_v2_account(rpc: &RpcClient, key: &Pubkey) -> Result<BondV2Account, ClientError> {
+    fetch_optional_versioned(rpc, key)?.ok_or(ClientError::AccountNotFound(*key))
+}
+
+/// Fetches a royalty account if it exists
+pub fn fetch_royalty_account(
+    rpc: &RpcClient,
+    key: &Pubkey,
+) -> Result<Option<RoyaltyAccount>, ClientError> {
+    fetch_optional(rpc, key)
+}
+
+/// Fetches a pool fee override if it exists
+pub fn fetch_pool_fee_override(
+    rpc: &RpcClient,
+    key: &Pubkey,
+) -> Result<Option<PoolFeeOverride>, ClientError> {
+    fetch_optional(rpc, key)
+}
+
+/// Fetches a protocol config if it exists
+pub fn fetch_protocol_config(
+    rpc: &RpcClient,
+    key: &Pubkey,
+) -> Result<Option<ProtocolConfig>, ClientError> {
+    fetch_optional(rpc, key)
+}
+
+/// Fetches an account of any type, decoded from its tag
+pub fn fetch_protocol_account(
+    rpc: &RpcClient,
+    key: &Pubkey,
+) -> Result<ProtocolAccount, ClientError> {
+    let data = fetch_account_data(rpc, key)?.ok_or(ClientError::AccountNotFound(*key))?;
+    ProtocolAccount::decode(&data).map_err(|_| ClientError::InvalidAccountData(*key))
+}
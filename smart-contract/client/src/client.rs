@@ -0,0 +1,1184 @@
+//! High level client bound to a central state
+use access_protocol::decoder::ProtocolAccount;
+use access_protocol::governance::VoterWeightAction;
+use access_protocol::instruction::{
+    activate_stake_pool, add_to_bond_v2, admin_freeze, admin_renounce, admin_set_emission_cap,
+    admin_set_governance_config, admin_set_role, admin_setup_fee_split, change_pool_controls,
+    claim_bond_v2_rewards, claim_pool_rewards, claim_receipt_rewards, claim_rewards,
+    close_royalty_account, crank, create_bond_v2, create_receipt_mint, create_royalty_account,
+    create_stake_account, create_stake_pool, distribute_fees, emergency_withdraw, migrate_account,
+    refresh_bond_v2_boost, set_pool_allowlist_entry, stake, unlock_bond_v2, unstake,
+    update_max_voter_weight_record, update_voter_weight_record, ProgramInstruction,
+};
+use access_protocol::rewards::{
+    claimable_bond_v2_rewards, claimable_pool_rewards, claimable_staker_rewards, ClaimableRewards,
+};
+use access_protocol::state::{
+    BondV2Account, CentralStateV2, FeeRecipient, ProtocolConfig, RoyaltyAccount, StakeAccount,
+    StakePoolHeader,
+};
+use solana_client::rpc_client::RpcClient;
+use solana_program::{instruction::Instruction, pubkey::Pubkey, system_program};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+use crate::accounts::{
+    fetch_account_data, fetch_bond_v2_account, fetch_central_state, fetch_optional_versioned,
+    fetch_protocol_account, fetch_protocol_config, fetch_royalty_account, fetch_stake_account,
+    fetch_stake_pool, fetch_stake_pool_with_balances,
+};
+use crate::error::ClientError;
+use crate::pda;
+
+/// A client bound to the original central state or to a tenant central state
+pub struct AccessProtocolClient {
+    rpc: RpcClient,
+    program_id: Pubkey,
+    central_state: Pubkey,
+    scope: Option<Pubkey>,
+    mint: Pubkey,
+    token_program: Pubkey,
+    transfer_hook_accounts: Vec<Pubkey>,
+}
+
+impl AccessProtocolClient {
+    /// Creates a client for the original central state
+    pub fn new(rpc: RpcClient, program_id: Pubkey) -> Result<Self, ClientError> {
+        let central_state = pda::central_state(&program_id);
+        Self::load(rpc, program_id, central_state)
+    }
+
+    /// Creates a client for the tenant central state of `mint`
+    pub fn new_tenant(
+        rpc: RpcClient,
+        program_id: Pubkey,
+        mint: &Pubkey,
+    ) -> Result<Self, ClientError> {
+        let central_state = pda::tenant_central_state(mint, &program_id);
+        Self::load(rpc, program_id, central_state)
+    }
+
+    /// Creates a client for a central state whose mint and token program are known, without
+    /// fetching them. The pools and royalty accounts of a tenant central state are scoped to it
+    pub fn new_with_mint(
+        rpc: RpcClient,
+        program_id: Pubkey,
+        central_state: Pubkey,
+        mint: Pubkey,
+        token_program: Pubkey,
+    ) -> Self {
+        let scope = Some(central_state).filter(|key| key != &pda::central_state(&program_id));
+        Self {
+            rpc,
+            program_id,
+            central_state,
+            scope,
+            mint,
+            token_program,
+            transfer_hook_accounts: vec![],
+        }
+    }
+
+    fn load(
+        rpc: RpcClient,
+        program_id: Pubkey,
+        central_state: Pubkey,
+    ) -> Result<Self, ClientError> {
+        let mint = fetch_central_state(&rpc, &central_state)?.token_mint;
+        let token_program = rpc
+            .get_account_with_commitment(&mint, rpc.commitment())?
+            .value
+            .ok_or(ClientError::AccountNotFound(mint))?
+            .owner;
+        Ok(Self::new_with_mint(
+            rpc,
+            program_id,
+            central_state,
+            mint,
+            token_program,
+        ))
+    }
+
+    /// The underlying RPC client
+    pub fn rpc(&self) -> &RpcClient {
+        &self.rpc
+    }
+
+    /// The program id
+    pub fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    /// The key of the central state
+    pub fn central_state_key(&self) -> Pubkey {
+        self.central_state
+    }
+
+    /// The token mint of the central state
+    pub fn mint(&self) -> Pubkey {
+        self.mint
+    }
+
+    /// The token program owning the mint, either SPL Token or Token-2022
+    pub fn token_program(&self) -> Pubkey {
+        self.token_program
+    }
+
+    /// Sets the accounts of the transfer hook of a Token-2022 mint, passed to the instructions
+    /// moving tokens. They are resolved with `spl_transfer_hook_interface::offchain`
+    pub fn set_transfer_hook_accounts(&mut self, transfer_hook_accounts: Vec<Pubkey>) {
+        self.transfer_hook_accounts = transfer_hook_accounts;
+    }
+
+    // A skipped optional account, set to the program id when the transfer hook accounts follow
+    fn skipped_account(&self) -> Option<&Pubkey> {
+        if self.transfer_hook_accounts.is_empty() {
+            None
+        } else {
+            Some(&self.program_id)
+        }
+    }
+
+    // The program id is not writable in a transaction, the skipped accounts are made read-only
+    fn demote_skipped_accounts(&self, mut instruction: Instruction) -> Instruction {
+        for meta in &mut instruction.accounts {
+            if meta.pubkey == self.program_id {
+                meta.is_writable = false;
+            }
+        }
+        instruction
+    }
+
+    // The signer of the Access NFT program is only checked when it signs, the program id stands in
+    // for it until the protocol config is set
+    fn access_nft_signer(&self) -> Result<Pubkey, ClientError> {
+        let key = pda::protocol_config(&self.central_state, &self.program_id);
+        Ok(fetch_protocol_config(&self.rpc, &key)?
+            .map_or(self.program_id, |config| config.access_nft_program_signer))
+    }
+
+    ////////////////////////////////////////////////////////////
+    // Addresses
+    ////////////////////////////////////////////////////////////
+
+    /// The stake pool of `pool_owner`
+    pub fn stake_pool_key(&self, pool_owner: &Pubkey) -> Pubkey {
+        pda::stake_pool(pool_owner, self.scope.as_ref(), &self.program_id)
+    }
+
+    /// The stake account of `owner` in the pool of `pool_owner`
+    pub fn stake_account_key(&self, owner: &Pubkey, pool_owner: &Pubkey) -> Pubkey {
+        pda::stake_account(owner, &self.stake_pool_key(pool_owner), &self.program_id).0
+    }
+
+    /// The bond V2 account of `owner` in the pool of `pool_owner`
+    pub fn bond_v2_key(
+        &self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+    ) -> Pubkey {
+        pda::bond_v2_account(
+            owner,
+            &self.stake_pool_key(pool_owner),
+            unlock_timestamp,
+            &self.program_id,
+        )
+    }
+
+    /// The royalty account of `payer`
+    pub fn royalty_account_key(&self, payer: &Pubkey) -> Pubkey {
+        pda::royalty_account(payer, self.scope.as_ref(), &self.program_id)
+    }
+
+    /// The token account of `owner` for the mint of the central state
+    pub fn ata(&self, owner: &Pubkey) -> Pubkey {
+        pda::ata(owner, &self.mint, &self.token_program)
+    }
+
+    ////////////////////////////////////////////////////////////
+    // Accounts
+    ////////////////////////////////////////////////////////////
+
+    /// Fetches the central state
+    pub fn central_state(&self) -> Result<CentralStateV2, ClientError> {
+        fetch_central_state(&self.rpc, &self.central_state)
+    }
+
+    /// Fetches an account of the program without knowing its type beforehand
+    pub fn account(&self, key: &Pubkey) -> Result<ProtocolAccount, ClientError> {
+        fetch_protocol_account(&self.rpc, key)
+    }
+
+    /// Fetches the protocol config of the central state
+    pub fn protocol_config(&self) -> Result<ProtocolConfig, ClientError> {
+        let key = pda::protocol_config(&self.central_state, &self.program_id);
+        fetch_protocol_config(&self.rpc, &key)?.ok_or(ClientError::ProtocolConfigNotFound)
+    }
+
+    /// Fetches the header of the stake pool of `pool_owner`
+    pub fn stake_pool(&self, pool_owner: &Pubkey) -> Result<StakePoolHeader, ClientError> {
+        fetch_stake_pool(&self.rpc, &self.stake_pool_key(pool_owner))
+    }
+
+    /// Fetches the stake account of `owner` in the pool of `pool_owner`
+    pub fn stake_account(
+        &self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+    ) -> Result<StakeAccount, ClientError> {
+        fetch_stake_account(&self.rpc, &self.stake_account_key(owner, pool_owner))
+    }
+
+    /// Fetches a bond V2 account of `owner` in the pool of `pool_owner`
+    pub fn bond_v2(
+        &self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+    ) -> Result<BondV2Account, ClientError> {
+        fetch_bond_v2_account(
+            &self.rpc,
+            &self.bond_v2_key(owner, pool_owner, unlock_timestamp),
+        )
+    }
+
+    /// Fetches the royalty account of `payer` if it exists
+    pub fn royalty_account(&self, payer: &Pubkey) -> Result<Option<RoyaltyAccount>, ClientError> {
+        fetch_royalty_account(&self.rpc, &self.royalty_account_key(payer))
+    }
+
+    /// The royalty recipient that has to be passed along a claim of `payer`
+    ///
+    /// Expired royalty accounts are ignored by the program so passing their recipient is harmless
+    fn royalty_ata(&self, payer: &Pubkey) -> Result<Option<Pubkey>, ClientError> {
+        Ok(self.royalty_account(payer)?.map(|r| r.recipient_ata))
+    }
+
+    /// The current cluster time
+    fn now(&self) -> Result<i64, ClientError> {
+        let slot = self.rpc.get_slot()?;
+        Ok(self.rpc.get_block_time(slot)?)
+    }
+
+    ////////////////////////////////////////////////////////////
+    // Rewards
+    ////////////////////////////////////////////////////////////
+
+    /// The amounts `claim_rewards` would mint for `owner` in the pool of `pool_owner`
+    pub fn claimable_rewards(
+        &self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+    ) -> Result<ClaimableRewards, ClientError> {
+        let central_state = self.central_state()?;
+        let stake_pool =
+            fetch_stake_pool_with_balances(&self.rpc, &self.stake_pool_key(pool_owner))?;
+        let stake_account = self.stake_account(owner, pool_owner)?;
+        let royalty_account = self.royalty_account(owner)?;
+        Ok(claimable_staker_rewards(
+            &central_state,
+            &stake_pool,
+            &stake_account,
+            royalty_account.as_ref(),
+            self.now()?,
+            false,
+        )?)
+    }
+
+    /// The amounts `claim_pool_rewards` would mint for `pool_owner`
+    pub fn claimable_pool_rewards(
+        &self,
+        pool_owner: &Pubkey,
+    ) -> Result<ClaimableRewards, ClientError> {
+        let central_state = self.central_state()?;
+        let stake_pool =
+            fetch_stake_pool_with_balances(&self.rpc, &self.stake_pool_key(pool_owner))?;
+        let royalty_account = self.royalty_account(pool_owner)?;
+        Ok(claimable_pool_rewards(
+            &central_state,
+            &stake_pool,
+            royalty_account.as_ref(),
+            self.now()?,
+        )?)
+    }
+
+    /// The amounts `claim_bond_v2_rewards` would mint for a bond V2 account of `owner`
+    pub fn claimable_bond_v2_rewards(
+        &self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+    ) -> Result<ClaimableRewards, ClientError> {
+        let central_state = self.central_state()?;
+        let stake_pool =
+            fetch_stake_pool_with_balances(&self.rpc, &self.stake_pool_key(pool_owner))?;
+        let bond_v2_account = self.bond_v2(owner, pool_owner, unlock_timestamp)?;
+        let royalty_account = self.royalty_account(owner)?;
+        Ok(claimable_bond_v2_rewards(
+            &central_state,
+            &stake_pool,
+            &bond_v2_account,
+            royalty_account.as_ref(),
+            self.now()?,
+        )?)
+    }
+
+    ////////////////////////////////////////////////////////////
+    // Instructions
+    ////////////////////////////////////////////////////////////
+
+    /// Creates the vault and the stake pool of `pool_owner`
+    pub fn create_stake_pool(
+        &self,
+        pool_owner: &Pubkey,
+        fee_payer: &Pubkey,
+        minimum_stake_amount: u64,
+    ) -> Vec<Instruction> {
+        let stake_pool_key = self.stake_pool_key(pool_owner);
+        let create_vault = create_associated_token_account_idempotent(
+            fee_payer,
+            &stake_pool_key,
+            &self.mint,
+            &self.token_program,
+        );
+        let create_pool = create_stake_pool(
+            self.program_id,
+            create_stake_pool::Accounts {
+                stake_pool_account: &stake_pool_key,
+                system_program: &system_program::ID,
+                owner: pool_owner,
+                fee_payer,
+                vault: &self.ata(&stake_pool_key),
+                central_state: &self.central_state,
+            },
+            create_stake_pool::Params {
+                minimum_stake_amount,
+            },
+        );
+        vec![create_vault, create_pool]
+    }
+
+    /// Activates the stake pool of `pool_owner`, signed by the central state authority
+    pub fn activate_stake_pool(&self, pool_owner: &Pubkey) -> Instruction {
+        activate_stake_pool(
+            self.program_id,
+            activate_stake_pool::Accounts {
+                stake_pool: &self.stake_pool_key(pool_owner),
+                central_state: &self.central_state,
+            },
+            activate_stake_pool::Params {},
+        )
+    }
+
+    /// Sets the cap, the pause and the allowlist of the stake pool of `pool_owner`
+    pub fn change_pool_controls(
+        &self,
+        pool_owner: &Pubkey,
+        max_total_staked: u64,
+        paused: bool,
+        allowlist_enabled: bool,
+        fee_payer: &Pubkey,
+    ) -> Instruction {
+        let stake_pool_key = self.stake_pool_key(pool_owner);
+        change_pool_controls(
+            self.program_id,
+            change_pool_controls::Accounts {
+                stake_pool: &stake_pool_key,
+                stake_pool_owner: pool_owner,
+                central_state: &self.central_state,
+                pool_controls: &pda::pool_controls(&stake_pool_key, &self.program_id),
+                fee_payer,
+                system_program: &system_program::ID,
+            },
+            change_pool_controls::Params {
+                max_total_staked,
+                paused,
+                allowlist_enabled,
+            },
+        )
+    }
+
+    /// Adds `staker` to the allowlist of the stake pool of `pool_owner`, or removes it
+    pub fn set_pool_allowlist_entry(
+        &self,
+        pool_owner: &Pubkey,
+        staker: &Pubkey,
+        allowed: bool,
+        fee_payer: &Pubkey,
+    ) -> Instruction {
+        let stake_pool_key = self.stake_pool_key(pool_owner);
+        set_pool_allowlist_entry(
+            self.program_id,
+            set_pool_allowlist_entry::Accounts {
+                stake_pool: &stake_pool_key,
+                stake_pool_owner: pool_owner,
+                central_state: &self.central_state,
+                allowlist_entry: &pda::pool_allowlist_entry(
+                    &stake_pool_key,
+                    staker,
+                    &self.program_id,
+                ),
+                fee_payer,
+                system_program: &system_program::ID,
+            },
+            set_pool_allowlist_entry::Params {
+                staker: *staker,
+                allowed,
+            },
+        )
+    }
+
+    /// Creates the stake account of `owner` in the pool of `pool_owner`
+    pub fn create_stake_account(
+        &self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        fee_payer: &Pubkey,
+    ) -> Instruction {
+        let stake_pool_key = self.stake_pool_key(pool_owner);
+        let (stake_account_key, nonce) =
+            pda::stake_account(owner, &stake_pool_key, &self.program_id);
+        create_stake_account(
+            self.program_id,
+            create_stake_account::Accounts {
+                stake_account: &stake_account_key,
+                system_program: &system_program::ID,
+                stake_pool: &stake_pool_key,
+                fee_payer,
+                central_state: &self.central_state,
+                pool_controls: Some(&pda::pool_controls(&stake_pool_key, &self.program_id)),
+                allowlist_entry: Some(&pda::pool_allowlist_entry(
+                    &stake_pool_key,
+                    owner,
+                    &self.program_id,
+                )),
+            },
+            create_stake_account::Params {
+                nonce,
+                owner: *owner,
+            },
+        )
+    }
+
+    /// Stakes `amount` from the token account of `owner`
+    ///
+    /// The stake account is created first if it does not exist yet
+    pub fn stake(
+        &self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        amount: u64,
+    ) -> Result<Vec<Instruction>, ClientError> {
+        let stake_pool_key = self.stake_pool_key(pool_owner);
+        let stake_account_key = self.stake_account_key(owner, pool_owner);
+        let mut instructions = vec![];
+        if fetch_optional_versioned::<StakeAccount>(&self.rpc, &stake_account_key)?.is_none() {
+            instructions.push(self.create_stake_account(owner, pool_owner, owner));
+        }
+        instructions.push(self.demote_skipped_accounts(stake(
+            self.program_id,
+            stake::Accounts {
+                central_state: &self.central_state,
+                stake_account: &stake_account_key,
+                stake_pool: &stake_pool_key,
+                token_owner: owner,
+                source_token: &self.ata(owner),
+                spl_token_program: &self.token_program,
+                vault: &self.ata(&stake_pool_key),
+                central_state_vault: &self.ata(&self.central_state),
+                pool_fee_override: Some(&pda::pool_fee_override(&stake_pool_key, &self.program_id)),
+                mint: &self.mint,
+                pool_controls: Some(&pda::pool_controls(&stake_pool_key, &self.program_id)),
+                allowlist_entry: Some(&pda::pool_allowlist_entry(
+                    &stake_pool_key,
+                    owner,
+                    &self.program_id,
+                )),
+                receipt_mint: self.skipped_account(),
+                receipt_account: self.skipped_account(),
+                receipt_reserve: self.skipped_account(),
+                transfer_hook_accounts: &self.transfer_hook_accounts,
+            },
+            stake::Params { amount },
+        )));
+        Ok(instructions)
+    }
+
+    /// Unstakes `amount` to the token account of `owner`
+    pub fn unstake(&self, owner: &Pubkey, pool_owner: &Pubkey, amount: u64) -> Instruction {
+        let stake_pool_key = self.stake_pool_key(pool_owner);
+        self.demote_skipped_accounts(unstake(
+            self.program_id,
+            unstake::Accounts {
+                central_state: &self.central_state,
+                stake_account: &self.stake_account_key(owner, pool_owner),
+                stake_pool: &stake_pool_key,
+                owner,
+                destination_token: &self.ata(owner),
+                spl_token_program: &self.token_program,
+                vault: &self.ata(&stake_pool_key),
+                mint: &self.mint,
+                receipt_mint: self.skipped_account(),
+                receipt_account: self.skipped_account(),
+                receipt_reserve: self.skipped_account(),
+                transfer_hook_accounts: &self.transfer_hook_accounts,
+            },
+            unstake::Params { amount },
+        ))
+    }
+
+    /// Cranks the stake pool of `pool_owner`
+    pub fn crank(&self, pool_owner: &Pubkey) -> Instruction {
+        let stake_pool_key = self.stake_pool_key(pool_owner);
+        crank(
+            self.program_id,
+            crank::Accounts {
+                stake_pool: &stake_pool_key,
+                central_state: &self.central_state,
+                pool_boost: Some(&pda::pool_boost(&stake_pool_key, &self.program_id)),
+            },
+            crank::Params {},
+        )
+    }
+
+    /// Claims the staker rewards of `owner` to their token account
+    pub fn claim_rewards(
+        &self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+    ) -> Result<Instruction, ClientError> {
+        let access_nft_signer = self.access_nft_signer()?;
+        let royalty_ata = self.royalty_ata(owner)?;
+        Ok(claim_rewards(
+            self.program_id,
+            claim_rewards::Accounts {
+                stake_pool: &self.stake_pool_key(pool_owner),
+                stake_account: &self.stake_account_key(owner, pool_owner),
+                owner,
+                rewards_destination: &self.ata(owner),
+                central_state: &self.central_state,
+                mint: &self.mint,
+                access_nft_signer: &access_nft_signer,
+                spl_token_program: &self.token_program,
+                owner_royalty_account: &self.royalty_account_key(owner),
+                royalty_ata: royalty_ata.as_ref(),
+                protocol_config: None,
+            },
+            claim_rewards::Params {
+                allow_zero_rewards: false,
+            },
+            false,
+        ))
+    }
+
+    /// Claims the pool rewards of `pool_owner` to their token account
+    pub fn claim_pool_rewards(&self, pool_owner: &Pubkey) -> Result<Instruction, ClientError> {
+        let royalty_ata = self.royalty_ata(pool_owner)?;
+        Ok(claim_pool_rewards(
+            self.program_id,
+            claim_pool_rewards::Accounts {
+                stake_pool: &self.stake_pool_key(pool_owner),
+                owner: pool_owner,
+                rewards_destination: &self.ata(pool_owner),
+                central_state: &self.central_state,
+                mint: &self.mint,
+                spl_token_program: &self.token_program,
+                owner_royalty_account: &self.royalty_account_key(pool_owner),
+                royalty_ata: royalty_ata.as_ref(),
+            },
+            claim_pool_rewards::Params {},
+            true,
+        ))
+    }
+
+    /// Creates a bond V2 account of `owner` in the pool of `pool_owner`
+    pub fn create_bond_v2(
+        &self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+        fee_payer: &Pubkey,
+    ) -> Instruction {
+        create_bond_v2(
+            self.program_id,
+            create_bond_v2::Accounts {
+                bond_v2_account: &self.bond_v2_key(owner, pool_owner, unlock_timestamp),
+                system_program: &system_program::ID,
+                pool: &self.stake_pool_key(pool_owner),
+                fee_payer,
+                central_state: &self.central_state,
+            },
+            create_bond_v2::Params {
+                unlock_timestamp,
+                owner: *owner,
+            },
+        )
+    }
+
+    /// Locks `amount` from the token account of `from` in a bond V2 account of `owner`
+    pub fn add_to_bond_v2(
+        &self,
+        from: &Pubkey,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+        amount: u64,
+    ) -> Instruction {
+        let stake_pool_key = self.stake_pool_key(pool_owner);
+        add_to_bond_v2(
+            self.program_id,
+            add_to_bond_v2::Accounts {
+                from,
+                from_ata: &self.ata(from),
+                bond_v2_account: &self.bond_v2_key(owner, pool_owner, unlock_timestamp),
+                central_state: &self.central_state,
+                central_state_vault: &self.ata(&self.central_state),
+                pool: &stake_pool_key,
+                pool_vault: &self.ata(&stake_pool_key),
+                mint: &self.mint,
+                spl_token_program: &self.token_program,
+                system_program: &system_program::ID,
+                pool_fee_override: Some(&pda::pool_fee_override(&stake_pool_key, &self.program_id)),
+                pool_controls: Some(&pda::pool_controls(&stake_pool_key, &self.program_id)),
+                allowlist_entry: Some(&pda::pool_allowlist_entry(
+                    &stake_pool_key,
+                    owner,
+                    &self.program_id,
+                )),
+                boost_config: Some(&pda::boost_config(&self.central_state, &self.program_id)),
+                pool_boost: Some(&pda::pool_boost(&stake_pool_key, &self.program_id)),
+                transfer_hook_accounts: &self.transfer_hook_accounts,
+            },
+            add_to_bond_v2::Params { amount },
+        )
+    }
+
+    /// Claims the rewards of a bond V2 account of `owner` to their token account
+    pub fn claim_bond_v2_rewards(
+        &self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+    ) -> Result<Instruction, ClientError> {
+        let access_nft_signer = self.access_nft_signer()?;
+        let royalty_ata = self.royalty_ata(owner)?;
+        Ok(claim_bond_v2_rewards(
+            self.program_id,
+            claim_bond_v2_rewards::Accounts {
+                pool: &self.stake_pool_key(pool_owner),
+                bond_v2_account: &self.bond_v2_key(owner, pool_owner, unlock_timestamp),
+                owner,
+                rewards_destination: &self.ata(owner),
+                central_state: &self.central_state,
+                mint: &self.mint,
+                access_nft_signer: &access_nft_signer,
+                spl_token_program: &self.token_program,
+                owner_royalty_account: &self.royalty_account_key(owner),
+                royalty_ata: royalty_ata.as_ref(),
+                protocol_config: None,
+            },
+            claim_bond_v2_rewards::Params {},
+            false,
+        ))
+    }
+
+    /// Settles the rewards of a bond V2 account of `owner` and refreshes its boost
+    pub fn refresh_bond_v2_boost(
+        &self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+        fee_payer: &Pubkey,
+    ) -> Instruction {
+        let stake_pool_key = self.stake_pool_key(pool_owner);
+        refresh_bond_v2_boost(
+            self.program_id,
+            refresh_bond_v2_boost::Accounts {
+                central_state: &self.central_state,
+                bond_v2_account: &self.bond_v2_key(owner, pool_owner, unlock_timestamp),
+                pool: &stake_pool_key,
+                pool_boost: &pda::pool_boost(&stake_pool_key, &self.program_id),
+                boost_config: &pda::boost_config(&self.central_state, &self.program_id),
+                fee_payer,
+                system_program: &system_program::ID,
+            },
+            refresh_bond_v2_boost::Params {},
+        )
+    }
+
+    /// Creates the receipt mint of the stake pool of `pool_owner` along with its reserve
+    pub fn create_receipt_mint(&self, pool_owner: &Pubkey, fee_payer: &Pubkey) -> Vec<Instruction> {
+        let stake_pool_key = self.stake_pool_key(pool_owner);
+        let receipt_mint = pda::receipt_mint(&stake_pool_key, &self.program_id);
+        let (receipt_stake_account, _) =
+            pda::stake_account(&receipt_mint, &stake_pool_key, &self.program_id);
+        let create_reserve = create_associated_token_account_idempotent(
+            fee_payer,
+            &receipt_mint,
+            &self.mint,
+            &self.token_program,
+        );
+        let create_mint = create_receipt_mint(
+            self.program_id,
+            create_receipt_mint::Accounts {
+                central_state: &self.central_state,
+                stake_pool: &stake_pool_key,
+                pool_owner,
+                receipt_mint: &receipt_mint,
+                receipt_stake_account: &receipt_stake_account,
+                receipt_reserve: &self.ata(&receipt_mint),
+                mint: &self.mint,
+                spl_token_program: &self.token_program,
+                fee_payer,
+                system_program: &system_program::ID,
+            },
+            create_receipt_mint::Params {},
+        );
+        vec![create_reserve, create_mint]
+    }
+
+    /// Stakes `amount` from the token account of `owner` for receipts of the pool of `pool_owner`
+    ///
+    /// The receipt token account of `owner` is created first, the rewards of the receipt holders
+    /// must be claimed before minting
+    pub fn stake_for_receipts(
+        &self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        amount: u64,
+    ) -> Vec<Instruction> {
+        let stake_pool_key = self.stake_pool_key(pool_owner);
+        let receipt_mint = pda::receipt_mint(&stake_pool_key, &self.program_id);
+        let (receipt_stake_account, _) =
+            pda::stake_account(&receipt_mint, &stake_pool_key, &self.program_id);
+        let receipt_account = pda::ata(owner, &receipt_mint, &self.token_program);
+        vec![
+            create_associated_token_account_idempotent(
+                owner,
+                owner,
+                &receipt_mint,
+                &self.token_program,
+            ),
+            self.claim_receipt_rewards(pool_owner),
+            stake(
+                self.program_id,
+                stake::Accounts {
+                    central_state: &self.central_state,
+                    stake_account: &receipt_stake_account,
+                    stake_pool: &stake_pool_key,
+                    token_owner: owner,
+                    source_token: &self.ata(owner),
+                    spl_token_program: &self.token_program,
+                    vault: &self.ata(&stake_pool_key),
+                    central_state_vault: &self.ata(&self.central_state),
+                    pool_fee_override: Some(&pda::pool_fee_override(
+                        &stake_pool_key,
+                        &self.program_id,
+                    )),
+                    mint: &self.mint,
+                    pool_controls: Some(&pda::pool_controls(&stake_pool_key, &self.program_id)),
+                    allowlist_entry: Some(&pda::pool_allowlist_entry(
+                        &stake_pool_key,
+                        &receipt_mint,
+                        &self.program_id,
+                    )),
+                    receipt_mint: Some(&receipt_mint),
+                    receipt_account: Some(&receipt_account),
+                    receipt_reserve: Some(&self.ata(&receipt_mint)),
+                    transfer_hook_accounts: &self.transfer_hook_accounts,
+                },
+                stake::Params { amount },
+            ),
+        ]
+    }
+
+    /// Redeems `amount` receipts of `owner` of the pool of `pool_owner` to their token account,
+    /// after claiming the rewards of the receipt holders
+    pub fn redeem_receipts(
+        &self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        amount: u64,
+    ) -> Vec<Instruction> {
+        let stake_pool_key = self.stake_pool_key(pool_owner);
+        let receipt_mint = pda::receipt_mint(&stake_pool_key, &self.program_id);
+        let (receipt_stake_account, _) =
+            pda::stake_account(&receipt_mint, &stake_pool_key, &self.program_id);
+        vec![
+            self.claim_receipt_rewards(pool_owner),
+            unstake(
+                self.program_id,
+                unstake::Accounts {
+                    central_state: &self.central_state,
+                    stake_account: &receipt_stake_account,
+                    stake_pool: &stake_pool_key,
+                    owner,
+                    destination_token: &self.ata(owner),
+                    spl_token_program: &self.token_program,
+                    vault: &self.ata(&stake_pool_key),
+                    mint: &self.mint,
+                    receipt_mint: Some(&receipt_mint),
+                    receipt_account: Some(&pda::ata(owner, &receipt_mint, &self.token_program)),
+                    receipt_reserve: Some(&self.ata(&receipt_mint)),
+                    transfer_hook_accounts: &self.transfer_hook_accounts,
+                },
+                unstake::Params { amount },
+            ),
+        ]
+    }
+
+    /// Claims the rewards of the receipt holders of the pool of `pool_owner` to the receipt reserve
+    pub fn claim_receipt_rewards(&self, pool_owner: &Pubkey) -> Instruction {
+        let stake_pool_key = self.stake_pool_key(pool_owner);
+        let receipt_mint = pda::receipt_mint(&stake_pool_key, &self.program_id);
+        let (receipt_stake_account, _) =
+            pda::stake_account(&receipt_mint, &stake_pool_key, &self.program_id);
+        claim_receipt_rewards(
+            self.program_id,
+            claim_receipt_rewards::Accounts {
+                central_state: &self.central_state,
+                stake_pool: &stake_pool_key,
+                receipt_stake_account: &receipt_stake_account,
+                receipt_mint: &receipt_mint,
+                receipt_reserve: &self.ata(&receipt_mint),
+                mint: &self.mint,
+                spl_token_program: &self.token_program,
+            },
+            claim_receipt_rewards::Params {
+                allow_zero_rewards: true,
+            },
+        )
+    }
+
+    /// Updates the voter weight record of `voter`, to be sent in the transaction of the governance
+    /// action
+    ///
+    /// `positions` are the pool owners with the unlock timestamps of the bonds V2 of `voter` in
+    /// their pool, the stake account of `voter` is counted when it exists
+    pub fn update_voter_weight_record(
+        &self,
+        voter: &Pubkey,
+        positions: &[(Pubkey, Vec<Option<i64>>)],
+        weight_action: Option<VoterWeightAction>,
+        weight_action_target: Option<Pubkey>,
+        fee_payer: &Pubkey,
+    ) -> Result<Instruction, ClientError> {
+        let mut voter_accounts = Vec::with_capacity(positions.len() * 2);
+        for (pool_owner, unlock_timestamps) in positions {
+            voter_accounts.push(self.stake_pool_key(pool_owner));
+            let stake_account = self.stake_account_key(voter, pool_owner);
+            if self.account_exists(&stake_account)? {
+                voter_accounts.push(stake_account);
+            }
+            for unlock_timestamp in unlock_timestamps {
+                voter_accounts.push(self.bond_v2_key(voter, pool_owner, *unlock_timestamp));
+            }
+        }
+        Ok(update_voter_weight_record(
+            self.program_id,
+            update_voter_weight_record::Accounts {
+                central_state: &self.central_state,
+                governance_config: &pda::governance_config(&self.central_state, &self.program_id),
+                boost_config: &pda::boost_config(&self.central_state, &self.program_id),
+                voter_weight_record: &pda::voter_weight_record(
+                    &self.central_state,
+                    voter,
+                    &self.program_id,
+                ),
+                voter,
+                fee_payer,
+                system_program: &system_program::ID,
+                voter_accounts: &voter_accounts,
+            },
+            update_voter_weight_record::Params {
+                weight_action,
+                weight_action_target,
+            },
+        ))
+    }
+
+    /// Updates the max voter weight record of the central state to its total staked
+    pub fn update_max_voter_weight_record(&self, fee_payer: &Pubkey) -> Instruction {
+        update_max_voter_weight_record(
+            self.program_id,
+            update_max_voter_weight_record::Accounts {
+                central_state: &self.central_state,
+                governance_config: &pda::governance_config(&self.central_state, &self.program_id),
+                max_voter_weight_record: &pda::max_voter_weight_record(
+                    &self.central_state,
+                    &self.program_id,
+                ),
+                fee_payer,
+                system_program: &system_program::ID,
+            },
+            update_max_voter_weight_record::Params {},
+        )
+    }
+
+    /// Unlocks a bond V2 account of `owner` to their token account
+    pub fn unlock_bond_v2(
+        &self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+    ) -> Instruction {
+        let stake_pool_key = self.stake_pool_key(pool_owner);
+        unlock_bond_v2(
+            self.program_id,
+            unlock_bond_v2::Accounts {
+                central_state: &self.central_state,
+                bond_v2_account: &self.bond_v2_key(owner, pool_owner, unlock_timestamp),
+                owner,
+                destination_account: &self.ata(owner),
+                pool: &stake_pool_key,
+                pool_vault: &self.ata(&stake_pool_key),
+                spl_token_program: &self.token_program,
+                mint: &self.mint,
+                pool_boost: Some(&pda::pool_boost(&stake_pool_key, &self.program_id)),
+                transfer_hook_accounts: &self.transfer_hook_accounts,
+            },
+            unlock_bond_v2::Params {},
+        )
+    }
+
+    /// Redirects `royalty_basis_points` of the rewards of `payer` to `recipient_ata`
+    pub fn create_royalty_account(
+        &self,
+        payer: &Pubkey,
+        recipient_ata: &Pubkey,
+        royalty_basis_points: u16,
+        expiration_date: u64,
+        fee_payer: &Pubkey,
+    ) -> Instruction {
+        create_royalty_account(
+            self.program_id,
+            create_royalty_account::Accounts {
+                royalty_account: &self.royalty_account_key(payer),
+                fee_payer,
+                royalty_payer: payer,
+                system_program: &system_program::ID,
+                central_state: &self.central_state,
+            },
+            create_royalty_account::Params {
+                royalty_basis_points,
+                expiration_date,
+                royalty_ata: *recipient_ata,
+            },
+        )
+    }
+
+    /// Closes the royalty account of `payer`, the rent goes back to the original fee payer
+    pub fn close_royalty_account(&self, payer: &Pubkey) -> Result<Instruction, ClientError> {
+        let key = self.royalty_account_key(payer);
+        let royalty_account = self
+            .royalty_account(payer)?
+            .ok_or(ClientError::AccountNotFound(key))?;
+        Ok(close_royalty_account(
+            self.program_id,
+            close_royalty_account::Accounts {
+                royalty_account: &key,
+                royalty_payer: payer,
+                rent_destination: &royalty_account.rent_payer,
+                central_state: &self.central_state,
+            },
+            close_royalty_account::Params {},
+        ))
+    }
+
+    /// Distributes the collected fees to the fee recipients of the central state
+    pub fn distribute_fees(&self) -> Result<Instruction, ClientError> {
+        let mut token_accounts = self
+            .central_state()?
+            .get_fee_recipient_atas(&self.token_program);
+        // The keeper accounts are skipped before the transfer hook accounts
+        if !self.transfer_hook_accounts.is_empty() {
+            token_accounts.extend([self.program_id, self.program_id]);
+            token_accounts.extend(&self.transfer_hook_accounts);
+        }
+        let mut instruction = distribute_fees(
+            self.program_id,
+            distribute_fees::Accounts {
+                central_state: &self.central_state,
+                central_state_vault: &self.ata(&self.central_state),
+                spl_token_program: &self.token_program,
+                mint: &self.mint,
+                token_accounts: &token_accounts,
+            },
+            distribute_fees::Params {},
+        );
+        if !self.transfer_hook_accounts.is_empty() {
+            // Unlike the token accounts, the skipped accounts and the hook accounts are read-only
+            let skipped = instruction.accounts.len() - self.transfer_hook_accounts.len() - 2;
+            for meta in &mut instruction.accounts[skipped..] {
+                meta.is_writable = false;
+            }
+        }
+        Ok(instruction)
+    }
+
+    /// Withdraws the principal of the stake account of `owner`, or of its bond V2 unlocking at
+    /// `bond_unlock_timestamp`, after the maximum freeze duration
+    pub fn emergency_withdraw(
+        &self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        bond_unlock_timestamp: Option<Option<i64>>,
+    ) -> Instruction {
+        let stake_pool_key = self.stake_pool_key(pool_owner);
+        let position = match bond_unlock_timestamp {
+            Some(unlock_timestamp) => self.bond_v2_key(owner, pool_owner, unlock_timestamp),
+            None => self.stake_account_key(owner, pool_owner),
+        };
+        emergency_withdraw(
+            self.program_id,
+            emergency_withdraw::Accounts {
+                central_state: &self.central_state,
+                stake_pool: &stake_pool_key,
+                position: &position,
+                owner,
+                destination_token: &self.ata(owner),
+                vault: &self.ata(&stake_pool_key),
+                spl_token_program: &self.token_program,
+                mint: &self.mint,
+                pool_boost: Some(&pda::pool_boost(&stake_pool_key, &self.program_id)),
+                receipt_mint: self.skipped_account(),
+                receipt_account: self.skipped_account(),
+                receipt_reserve: self.skipped_account(),
+                transfer_hook_accounts: &self.transfer_hook_accounts,
+            },
+            emergency_withdraw::Params {},
+        )
+    }
+
+    /// Migrates a stake account, a bond V2 account or the central state to its latest layout
+    pub fn migrate_account(&self, fee_payer: &Pubkey, account: &Pubkey) -> Instruction {
+        migrate_account(
+            self.program_id,
+            migrate_account::Accounts {
+                fee_payer,
+                account,
+                system_program: &system_program::ID,
+            },
+            migrate_account::Params {},
+        )
+    }
+
+    ////////////////////////////////////////////////////////////
+    // Admin
+    ////////////////////////////////////////////////////////////
+
+    /// Freezes or unfreezes a program account, or the whole program if `account` is the central state
+    pub fn admin_freeze(&self, authority: &Pubkey, account: &Pubkey) -> Instruction {
+        admin_freeze(
+            self.program_id,
+            admin_freeze::Accounts {
+                authority,
+                account_to_freeze: account,
+                central_state: &self.central_state,
+                role_table: Some(&pda::role_table(&self.central_state, &self.program_id)),
+            },
+            admin_freeze::Params {},
+        )
+    }
+
+    /// Permanently renounces the admin permission over `ix`
+    pub fn admin_renounce(&self, authority: &Pubkey, ix: ProgramInstruction) -> Instruction {
+        admin_renounce(
+            self.program_id,
+            admin_renounce::Accounts {
+                central_state: &self.central_state,
+                authority,
+                role_table: Some(&pda::role_table(&self.central_state, &self.program_id)),
+            },
+            admin_renounce::Params { ix },
+        )
+    }
+
+    /// Replaces the fee recipients of the central state
+    pub fn admin_setup_fee_split(
+        &self,
+        authority: &Pubkey,
+        recipients: Vec<FeeRecipient>,
+    ) -> Instruction {
+        admin_setup_fee_split(
+            self.program_id,
+            admin_setup_fee_split::Accounts {
+                authority,
+                central_state: &self.central_state,
+                role_table: Some(&pda::role_table(&self.central_state, &self.program_id)),
+            },
+            admin_setup_fee_split::Params { recipients },
+        )
+    }
+
+    /// Sets the SPL Governance realm the voter weight records are computed for
+    pub fn admin_set_governance_config(
+        &self,
+        authority: &Pubkey,
+        realm: &Pubkey,
+        governing_token_mint: &Pubkey,
+        lock_weighting: bool,
+        fee_payer: &Pubkey,
+    ) -> Instruction {
+        admin_set_governance_config(
+            self.program_id,
+            admin_set_governance_config::Accounts {
+                authority,
+                central_state: &self.central_state,
+                governance_config: &pda::governance_config(&self.central_state, &self.program_id),
+                fee_payer,
+                system_program: &system_program::ID,
+                role_table: Some(&pda::role_table(&self.central_state, &self.program_id)),
+            },
+            admin_set_governance_config::Params {
+                realm: *realm,
+                governing_token_mint: *governing_token_mint,
+                lock_weighting,
+            },
+        )
+    }
+
+    /// Delegates the admin instruction `ix` to `new_authority`, signed by the current authority of
+    /// the role. The first call makes the role table the authority of the central state
+    pub fn admin_set_role(
+        &self,
+        authority: &Pubkey,
+        ix: ProgramInstruction,
+        new_authority: &Pubkey,
+        fee_payer: &Pubkey,
+    ) -> Instruction {
+        admin_set_role(
+            self.program_id,
+            admin_set_role::Accounts {
+                authority,
+                central_state: &self.central_state,
+                role_table: &pda::role_table(&self.central_state, &self.program_id),
+                fee_payer,
+                system_program: &system_program::ID,
+            },
+            admin_set_role::Params {
+                ix,
+                authority: *new_authority,
+            },
+        )
+    }
+
+    /// Sets the maximum of the supply of the mint, 0 removes the cap
+    pub fn admin_set_emission_cap(&self, authority: &Pubkey, emission_cap: u64) -> Instruction {
+        admin_set_emission_cap(
+            self.program_id,
+            admin_set_emission_cap::Accounts {
+                authority,
+                central_state: &self.central_state,
+                mint: &self.mint,
+                role_table: Some(&pda::role_table(&self.central_state, &self.program_id)),
+            },
+            admin_set_emission_cap::Params { emission_cap },
+        )
+    }
+
+    /// Returns `true` if `key` holds an account
+    pub fn account_exists(&self, key: &Pubkey) -> Result<bool, ClientError> {
+        Ok(fetch_account_data(&self.rpc, key)?.is_some())
+    }
+}
@@ -0,0 +1,19 @@
+//! Errors returned by the client
+use access_protocol::error::AccessError;
+use solana_program::pubkey::Pubkey;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[allow(missing_docs)]
+pub enum ClientError {
+    #[error("RPC error: {0}")]
+    Rpc(#[from] solana_client::client_error::ClientError),
+    #[error("Account {0} not found")]
+    AccountNotFound(Pubkey),
+    #[error("Account {0} could not be deserialized")]
+    InvalidAccountData(Pubkey),
+    #[error("The protocol config account of the central state does not exist")]
+    ProtocolConfigNotFound,
+    #[error("The program would fail with: {0}")]
+    Program(#[from] AccessError),
+}
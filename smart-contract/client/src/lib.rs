@@ -0,0 +1,15 @@
+//! Rust client for the Access Protocol program
+//!
+//! Derives the program accounts, fetches and decodes them and assembles complete instructions
+//! on top of the raw builders of `access_protocol::instruction`.
+#![warn(missing_docs)]
+
+pub use access_protocol;
+
+pub mod accounts;
+pub mod client;
+pub mod error;
+pub mod pda;
+
+pub use client::AccessProtocolClient;
+pub use error::ClientError;
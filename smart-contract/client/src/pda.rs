@@ -0,0 +1,111 @@
+//! Derivation of the program addresses
+//!
+//! The `scope` is the key of a tenant central state, it is `None` for the original central state
+use access_protocol::governance::{MaxVoterWeightRecord, VoterWeightRecord};
+use access_protocol::state::{
+    BondV2Account, BoostConfig, CentralState, CentralStateV2, FeeDistributionConfig,
+    GovernanceConfig, PoolAllowlistEntry, PoolBoost, PoolControls, PoolFeeOverride, ProtocolConfig,
+    RoleTable, RoyaltyAccount, StakeAccount, StakePool, StakePoolHeader,
+};
+use solana_program::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+/// The original central state derived from the program id only
+pub fn central_state(program_id: &Pubkey) -> Pubkey {
+    CentralState::find_key(program_id).0
+}
+
+/// The tenant central state of a token mint
+pub fn tenant_central_state(mint: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    CentralStateV2::find_tenant_key(mint, program_id).0
+}
+
+/// The stake pool of an owner
+pub fn stake_pool(owner: &Pubkey, scope: Option<&Pubkey>, program_id: &Pubkey) -> Pubkey {
+    StakePool::find_scoped_key(owner, scope, program_id).0
+}
+
+/// The stake account of an owner in a stake pool
+pub fn stake_account(owner: &Pubkey, stake_pool: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    StakeAccount::find_key(owner, stake_pool, program_id)
+}
+
+/// The bond V2 account of an owner in a stake pool
+pub fn bond_v2_account(
+    owner: &Pubkey,
+    stake_pool: &Pubkey,
+    unlock_timestamp: Option<i64>,
+    program_id: &Pubkey,
+) -> Pubkey {
+    BondV2Account::create_key(owner, stake_pool, unlock_timestamp, program_id).0
+}
+
+/// The royalty account of a royalty payer
+pub fn royalty_account(payer: &Pubkey, scope: Option<&Pubkey>, program_id: &Pubkey) -> Pubkey {
+    RoyaltyAccount::create_scoped_key(payer, scope, program_id).0
+}
+
+/// The fee override of a stake pool
+pub fn pool_fee_override(stake_pool: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    PoolFeeOverride::create_key(stake_pool, program_id).0
+}
+
+/// The fee distribution config of a central state
+pub fn fee_distribution_config(central_state: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    FeeDistributionConfig::create_key(central_state, program_id).0
+}
+
+/// The protocol config of a central state
+pub fn protocol_config(central_state: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    ProtocolConfig::create_key(central_state, program_id).0
+}
+
+/// The boost config of a central state
+pub fn boost_config(central_state: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    BoostConfig::create_key(central_state, program_id).0
+}
+
+/// The pool boost of a stake pool
+pub fn pool_boost(stake_pool: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    PoolBoost::create_key(stake_pool, program_id).0
+}
+
+/// The pool controls of a stake pool
+pub fn pool_controls(stake_pool: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    PoolControls::create_key(stake_pool, program_id).0
+}
+
+/// The allowlist entry of a staker in a stake pool
+pub fn pool_allowlist_entry(stake_pool: &Pubkey, staker: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    PoolAllowlistEntry::create_key(stake_pool, staker, program_id).0
+}
+
+/// The receipt mint of a stake pool, the stake account of the receipt holders is owned by it
+pub fn receipt_mint(stake_pool: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    StakePoolHeader::find_receipt_mint(stake_pool, program_id).0
+}
+
+/// The governance config of a central state
+pub fn governance_config(central_state: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    GovernanceConfig::create_key(central_state, program_id).0
+}
+
+/// The voter weight record of a voter
+pub fn voter_weight_record(central_state: &Pubkey, voter: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    VoterWeightRecord::create_key(central_state, voter, program_id).0
+}
+
+/// The max voter weight record of a central state
+pub fn max_voter_weight_record(central_state: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    MaxVoterWeightRecord::create_key(central_state, program_id).0
+}
+
+/// The role table of a central state, its authority once the roles are enabled
+pub fn role_table(central_state: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    RoleTable::create_key(central_state, program_id).0
+}
+
+/// The token account of an owner, stake pool vaults and the central state vault are ATAs as well
+pub fn ata(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, token_program)
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use access_protocol::state::{StakeAccount, Tag};
+use access_protocol::versioning::{set_tag, Migrate};
+use access_protocol_client::accounts::{fetch_optional, fetch_stake_account};
+use access_protocol_client::ClientError;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use borsh::BorshSerialize;
+use serde_json::json;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_request::RpcRequest;
+use solana_program::pubkey::Pubkey;
+
+// A client answering the account request with `data`, owned by the program
+fn rpc_with_account(data: Option<&[u8]>) -> RpcClient {
+    let value = data.map(|data| {
+        json!({
+            "lamports": 1_000_000,
+            "data": [STANDARD.encode(data), "base64"],
+            "owner": access_protocol::ID.to_string(),
+            "executable": false,
+            "rentEpoch": 0,
+        })
+    });
+    let mut mocks = HashMap::new();
+    mocks.insert(
+        RpcRequest::GetAccountInfo,
+        json!({ "context": { "slot": 1 }, "value": value }),
+    );
+    RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks)
+}
+
+#[test]
+fn versioned_accounts() {
+    let key = Pubkey::new_unique();
+    let mut stake_account = StakeAccount::new(Pubkey::new_unique(), Pubkey::new_unique(), 1_000);
+    stake_account.stake_amount = 10_000;
+    stake_account.last_claimed_index = 42;
+
+    // The accounts are decoded from the layout with the version byte and from the previous one
+    for data in [
+        stake_account.try_to_vec().unwrap(),
+        stake_account.to_previous().unwrap().try_to_vec().unwrap(),
+    ] {
+        let fetched = fetch_stake_account(&rpc_with_account(Some(&data)), &key).unwrap();
+        assert_eq!(fetched.owner, stake_account.owner);
+        assert_eq!(fetched.stake_pool, stake_account.stake_pool);
+        assert_eq!(fetched.stake_amount, 10_000);
+        assert_eq!(fetched.last_claimed_index, 42);
+    }
+
+    // The closed accounts are not returned
+    let mut data = stake_account.try_to_vec().unwrap();
+    set_tag(&mut data, Tag::Deleted).unwrap();
+    assert!(matches!(
+        fetch_stake_account(&rpc_with_account(Some(&data)), &key),
+        Err(ClientError::AccountNotFound(k)) if k == key
+    ));
+    assert!(
+        fetch_optional::<StakeAccount>(&rpc_with_account(None), &key)
+            .unwrap()
+            .is_none()
+    );
+
+    // The truncated accounts are rejected
+    assert!(matches!(
+        fetch_stake_account(&rpc_with_account(Some(&[Tag::StakeAccount as u8, 1, 2])), &key),
+        Err(ClientError::InvalidAccountData(k)) if k == key
+    ));
+}
@@ -0,0 +1,198 @@
+use access_protocol::instruction::ProgramInstruction;
+use access_protocol_client::{pda, AccessProtocolClient};
+use solana_client::rpc_client::RpcClient;
+use solana_program::instruction::Instruction;
+use solana_program::pubkey::Pubkey;
+use solana_program::system_program;
+
+// The account metas follow the `Accounts` of the program instructions, the instructions built
+// here do not fetch any account
+
+fn client(central_state: Pubkey, mint: Pubkey) -> AccessProtocolClient {
+    AccessProtocolClient::new_with_mint(
+        RpcClient::new_mock("succeeds".to_string()),
+        access_protocol::ID,
+        central_state,
+        mint,
+        spl_token::ID,
+    )
+}
+
+// The key, writable and signer flags of the account metas
+fn metas(instruction: &Instruction) -> Vec<(Pubkey, bool, bool)> {
+    instruction
+        .accounts
+        .iter()
+        .map(|meta| (meta.pubkey, meta.is_writable, meta.is_signer))
+        .collect()
+}
+
+fn ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    pda::ata(owner, mint, &spl_token::ID)
+}
+
+#[test]
+fn unstake() {
+    let program_id = access_protocol::ID;
+    let central_state = pda::central_state(&program_id);
+    let mint = Pubkey::new_unique();
+    let mut client = client(central_state, mint);
+    let owner = Pubkey::new_unique();
+    let pool_owner = Pubkey::new_unique();
+    let stake_pool = pda::stake_pool(&pool_owner, None, &program_id);
+    let (stake_account, _) = pda::stake_account(&owner, &stake_pool, &program_id);
+
+    let instruction = client.unstake(&owner, &pool_owner, 1_000);
+    assert_eq!(instruction.program_id, program_id);
+    assert_eq!(instruction.data[0], ProgramInstruction::Unstake as u8);
+    assert_eq!(instruction.data[1..], 1_000u64.to_le_bytes());
+    let accounts = vec![
+        (central_state, true, false),
+        (stake_account, true, false),
+        (stake_pool, true, false),
+        (owner, false, true),
+        (ata(&owner, &mint), true, false),
+        (spl_token::ID, false, false),
+        (ata(&stake_pool, &mint), true, false),
+        (mint, false, false),
+    ];
+    assert_eq!(metas(&instruction), accounts);
+
+    // The skipped receipt accounts are read-only program ids before the transfer hook accounts
+    let transfer_hook_accounts = vec![
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    client.set_transfer_hook_accounts(transfer_hook_accounts.clone());
+    let instruction = client.unstake(&owner, &pool_owner, 1_000);
+    let mut accounts = accounts;
+    accounts.extend([(program_id, false, false); 3]);
+    accounts.extend(
+        transfer_hook_accounts
+            .iter()
+            .map(|key| (*key, false, false)),
+    );
+    assert_eq!(metas(&instruction), accounts);
+}
+
+#[test]
+fn tenant_central_state() {
+    let program_id = access_protocol::ID;
+    let mint = Pubkey::new_unique();
+    let central_state = pda::tenant_central_state(&mint, &program_id);
+    let client = client(central_state, mint);
+    let pool_owner = Pubkey::new_unique();
+
+    // The pools of a tenant central state are scoped to it
+    let stake_pool = pda::stake_pool(&pool_owner, Some(&central_state), &program_id);
+    assert_eq!(client.stake_pool_key(&pool_owner), stake_pool);
+    assert_ne!(stake_pool, pda::stake_pool(&pool_owner, None, &program_id));
+    assert_eq!(
+        client.royalty_account_key(&pool_owner),
+        pda::royalty_account(&pool_owner, Some(&central_state), &program_id)
+    );
+
+    let instruction = client.crank(&pool_owner);
+    assert_eq!(instruction.data, vec![ProgramInstruction::Crank as u8]);
+    assert_eq!(
+        metas(&instruction),
+        vec![
+            (stake_pool, true, false),
+            (central_state, true, false),
+            (pda::pool_boost(&stake_pool, &program_id), false, false),
+        ]
+    );
+}
+
+#[test]
+fn claim_receipt_rewards() {
+    let program_id = access_protocol::ID;
+    let central_state = pda::central_state(&program_id);
+    let mint = Pubkey::new_unique();
+    let client = client(central_state, mint);
+    let pool_owner = Pubkey::new_unique();
+    let stake_pool = pda::stake_pool(&pool_owner, None, &program_id);
+    let receipt_mint = pda::receipt_mint(&stake_pool, &program_id);
+
+    // The stake account of the receipt holders is owned by the receipt mint
+    let instruction = client.claim_receipt_rewards(&pool_owner);
+    assert_eq!(
+        instruction.data[0],
+        ProgramInstruction::ClaimReceiptRewards as u8
+    );
+    assert_eq!(
+        metas(&instruction),
+        vec![
+            (central_state, true, false),
+            (stake_pool, true, false),
+            (
+                pda::stake_account(&receipt_mint, &stake_pool, &program_id).0,
+                true,
+                false
+            ),
+            (receipt_mint, false, false),
+            (ata(&receipt_mint, &mint), true, false),
+            (mint, true, false),
+            (spl_token::ID, false, false),
+        ]
+    );
+}
+
+#[test]
+fn migrate_account() {
+    let program_id = access_protocol::ID;
+    let central_state = pda::central_state(&program_id);
+    let client = client(central_state, Pubkey::new_unique());
+    let fee_payer = Pubkey::new_unique();
+    let stake_account = Pubkey::new_unique();
+
+    let instruction = client.migrate_account(&fee_payer, &stake_account);
+    assert_eq!(
+        instruction.data,
+        vec![ProgramInstruction::MigrateAccount as u8]
+    );
+    assert_eq!(
+        metas(&instruction),
+        vec![
+            (fee_payer, true, true),
+            (stake_account, true, false),
+            (system_program::ID, false, false),
+        ]
+    );
+}
+
+#[test]
+fn admin_instructions() {
+    let program_id = access_protocol::ID;
+    let central_state = pda::central_state(&program_id);
+    let mint = Pubkey::new_unique();
+    let client = client(central_state, mint);
+    let authority = Pubkey::new_unique();
+
+    // The role table is passed to the admin instructions in case the roles are enabled
+    let instruction = client.admin_set_emission_cap(&authority, 42);
+    assert_eq!(
+        instruction.data[0],
+        ProgramInstruction::AdminSetEmissionCap as u8
+    );
+    assert_eq!(instruction.data[1..], 42u64.to_le_bytes());
+    assert_eq!(
+        metas(&instruction),
+        vec![
+            (authority, false, true),
+            (central_state, true, false),
+            (mint, false, false),
+            (pda::role_table(&central_state, &program_id), false, false),
+        ]
+    );
+
+    let instruction = client.admin_renounce(&authority, ProgramInstruction::AdminMint);
+    assert_eq!(
+        instruction.data,
+        vec![
+            ProgramInstruction::AdminRenounce as u8,
+            ProgramInstruction::AdminMint as u8
+        ]
+    );
+}
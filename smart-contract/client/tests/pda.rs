@@ -0,0 +1,202 @@
+use access_protocol::governance::{MaxVoterWeightRecord, VoterWeightRecord};
+use access_protocol::state::{CentralStateV2, StakeAccount, StakePool};
+use access_protocol_client::pda;
+use solana_program::pubkey::Pubkey;
+
+// The addresses are derived from the seeds of the program, a changed seed breaks every client
+
+fn find(seeds: &[&[u8]], program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(seeds, program_id).0
+}
+
+#[test]
+fn central_states() {
+    let program_id = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let central_state = pda::central_state(&program_id);
+    assert_eq!(central_state, find(&[program_id.as_ref()], &program_id));
+    assert_eq!(
+        pda::tenant_central_state(&mint, &program_id),
+        find(&[program_id.as_ref(), mint.as_ref()], &program_id)
+    );
+    let (_, bump) = CentralStateV2::find_key(&program_id);
+    assert_eq!(
+        CentralStateV2::create_key(&bump, &program_id).unwrap(),
+        central_state
+    );
+}
+
+#[test]
+fn pool_accounts() {
+    let program_id = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let staker = Pubkey::new_unique();
+    let scope = pda::tenant_central_state(&Pubkey::new_unique(), &program_id);
+
+    // The pools of a tenant central state are scoped to it
+    let stake_pool = pda::stake_pool(&owner, None, &program_id);
+    assert_eq!(
+        stake_pool,
+        find(&[b"stake_pool", owner.as_ref()], &program_id)
+    );
+    let tenant_pool = pda::stake_pool(&owner, Some(&scope), &program_id);
+    assert_eq!(
+        tenant_pool,
+        find(
+            &[b"stake_pool", owner.as_ref(), scope.as_ref()],
+            &program_id
+        )
+    );
+    let (_, bump) = StakePool::find_scoped_key(&owner, Some(&scope), &program_id);
+    assert_eq!(
+        StakePool::create_scoped_key(&bump, &owner, Some(&scope), &program_id).unwrap(),
+        tenant_pool
+    );
+
+    // The stake accounts are checked with their bump by the program
+    let (stake_account, bump) = pda::stake_account(&staker, &stake_pool, &program_id);
+    assert_eq!(
+        stake_account,
+        find(
+            &[b"stake_account", staker.as_ref(), stake_pool.as_ref()],
+            &program_id
+        )
+    );
+    assert_eq!(
+        StakeAccount::create_key(&bump, &staker, &stake_pool, &program_id).unwrap(),
+        stake_account
+    );
+
+    let unlock_timestamp = 1_700_000_000i64;
+    assert_eq!(
+        pda::bond_v2_account(&staker, &stake_pool, Some(unlock_timestamp), &program_id),
+        find(
+            &[
+                b"bond_v2_account",
+                staker.as_ref(),
+                stake_pool.as_ref(),
+                &unlock_timestamp.to_le_bytes(),
+            ],
+            &program_id
+        )
+    );
+    assert_eq!(
+        pda::bond_v2_account(&staker, &stake_pool, None, &program_id),
+        find(
+            &[
+                b"bond_v2_account",
+                staker.as_ref(),
+                stake_pool.as_ref(),
+                &0i64.to_le_bytes(),
+            ],
+            &program_id
+        )
+    );
+
+    for (key, seed) in [
+        (
+            pda::pool_fee_override(&stake_pool, &program_id),
+            &b"pool_fee_override"[..],
+        ),
+        (
+            pda::pool_boost(&stake_pool, &program_id),
+            &b"pool_boost"[..],
+        ),
+        (
+            pda::pool_controls(&stake_pool, &program_id),
+            &b"pool_controls"[..],
+        ),
+        (
+            pda::receipt_mint(&stake_pool, &program_id),
+            &b"receipt_mint"[..],
+        ),
+    ] {
+        assert_eq!(key, find(&[seed, stake_pool.as_ref()], &program_id));
+    }
+    assert_eq!(
+        pda::pool_allowlist_entry(&stake_pool, &staker, &program_id),
+        find(
+            &[b"pool_allowlist", stake_pool.as_ref(), staker.as_ref()],
+            &program_id
+        )
+    );
+}
+
+#[test]
+fn central_state_accounts() {
+    let program_id = Pubkey::new_unique();
+    let central_state = pda::central_state(&program_id);
+    let payer = Pubkey::new_unique();
+
+    assert_eq!(
+        pda::royalty_account(&payer, None, &program_id),
+        find(&[b"royalty_account", payer.as_ref()], &program_id)
+    );
+    assert_eq!(
+        pda::royalty_account(&payer, Some(&central_state), &program_id),
+        find(
+            &[b"royalty_account", payer.as_ref(), central_state.as_ref()],
+            &program_id
+        )
+    );
+
+    for (key, seed) in [
+        (
+            pda::fee_distribution_config(&central_state, &program_id),
+            &b"fee_distribution_config"[..],
+        ),
+        (
+            pda::protocol_config(&central_state, &program_id),
+            &b"protocol_config"[..],
+        ),
+        (
+            pda::boost_config(&central_state, &program_id),
+            &b"boost_config"[..],
+        ),
+        (
+            pda::governance_config(&central_state, &program_id),
+            &b"governance_config"[..],
+        ),
+        (
+            pda::role_table(&central_state, &program_id),
+            &b"role_table"[..],
+        ),
+    ] {
+        assert_eq!(key, find(&[seed, central_state.as_ref()], &program_id));
+    }
+
+    // The governance records use the seeds of the plugin interface
+    assert_eq!(
+        pda::voter_weight_record(&central_state, &payer, &program_id),
+        find(
+            &[
+                VoterWeightRecord::SEED,
+                central_state.as_ref(),
+                payer.as_ref()
+            ],
+            &program_id
+        )
+    );
+    assert_eq!(
+        pda::max_voter_weight_record(&central_state, &program_id),
+        find(
+            &[MaxVoterWeightRecord::SEED, central_state.as_ref()],
+            &program_id
+        )
+    );
+}
+
+#[test]
+fn associated_token_accounts() {
+    let owner = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    for token_program in [spl_token::ID, Pubkey::new_unique()] {
+        assert_eq!(
+            pda::ata(&owner, &mint, &token_program),
+            find(
+                &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+                &spl_associated_token_account::ID
+            )
+        );
+    }
+}
@@ -4,6 +4,7 @@ import {
   adminChangeFreezeAuthorityInstruction,
   adminProgramFreezeInstruction,
   adminRenounceInstruction,
+  adminSetProtocolConfigInstruction,
   adminSetProtocolFeeInstruction,
   adminSetupFeeSplitInstruction,
   changeCentralStateAuthorityInstruction,
@@ -20,6 +21,7 @@ import {
   createStakeAccountInstruction,
   createStakePoolInstruction,
   distributeFeesInstruction,
+  migrateAccountInstruction,
   migrateCentralStateV2Instruction,
   stakeInstruction,
   TaggedInstruction,
@@ -36,6 +38,7 @@ import {
   CentralState,
   CentralStateV2,
   FeeRecipient,
+  ProtocolConfig,
   RoyaltyAccount,
   StakeAccount,
   StakePool
@@ -266,6 +269,7 @@ export const crank = (
  * @param authority The central state authority (only key that will be able to perform admin operations)
  * @param mint The ACS token mint
  * @param programId The ACCESS program ID
+ * @param accessNftProgramSigner The signer of the ACCESS NFT program, stored in the protocol config
  * @returns ix The instruction to create the central state
  */
 export const createCentralState = async (
@@ -273,18 +277,22 @@ export const createCentralState = async (
   authority: PublicKey,
   mint: PublicKey,
   programId: PublicKey,
+  accessNftProgramSigner = ACCESS_NFT_PROGRAM_SIGNER,
 ) => {
   const [centralStateKey] = CentralState.getKey(programId);
+  const [protocolConfigKey] = ProtocolConfig.getKey(programId, centralStateKey);
 
   return new createCentralStateInstruction({
     dailyInflation: new BN.BN(dailyInflation),
     authority: authority.toBuffer(),
+    accessNftProgramSigner: accessNftProgramSigner.toBuffer(),
   }).getInstruction(
     programId,
     centralStateKey,
     SystemProgram.programId,
     authority,
-    mint
+    mint,
+    protocolConfigKey,
   );
 };
 
@@ -432,6 +440,8 @@ export const stake = async (
     TOKEN_PROGRAM_ID,
     poolVault,
     feesAta,
+    programId, // The fee override of the pool is skipped
+    tokenMint,
   );
 };
 
@@ -510,6 +520,7 @@ export const unstake = async (
     destinationAccount,
     TOKEN_PROGRAM_ID,
     stakePoolVault,
+    tokenMint,
   );
 };
 
@@ -745,6 +756,7 @@ export const unlockBondV2 = async (
     bond.pool,
     poolVault,
     TOKEN_PROGRAM_ID,
+    tokenMint,
   );
 };
 
@@ -851,17 +863,39 @@ export const migrateCentralStateV2 = (
   );
 };
 
+/**
+ * This function can be used to migrate a stake account, a bond V2 account or the central state to its latest layout
+ * @param feePayer The fee payer of the transaction, pays the rent of the additional space
+ * @param account The account to migrate
+ * @param programId The ACCESS program ID
+ * @returns ix The instruction to migrate the account
+ */
+export const migrateAccount = (
+  feePayer: PublicKey,
+  account: PublicKey,
+  programId = ACCESS_PROGRAM_ID,
+) => {
+  return new migrateAccountInstruction().getInstruction(
+    programId,
+    feePayer,
+    account,
+    SystemProgram.programId,
+  );
+};
+
 /**
  * This function can be used to freeze or unfreeze the program instructions
  * @param freezeMask The bit mask of the instructions to freeze (0 = freeze, 1 = unfreeze)
  * @param freezeAuthority The authority to freeze the instructions - either the freeze authority (0 mask needed) or the central state authority
  * @param programId The ACCESS program ID
+ * @param feePayer The fee payer of the migration of the central state, defaults to the authority
  * @returns ix The instruction to freeze the program instructions
  */
 export const adminProgramFreeze = (
   freezeAuthority: PublicKey,
   freezeMask: BN = new BN.BN(0),
   programId = ACCESS_PROGRAM_ID,
+  feePayer: PublicKey = freezeAuthority,
 ) => {
   const [centralStateKey] = CentralStateV2.getKey(programId);
   return new adminProgramFreezeInstruction({
@@ -870,6 +904,8 @@ export const adminProgramFreeze = (
     programId,
     centralStateKey,
     freezeAuthority,
+    feePayer,
+    SystemProgram.programId,
   );
 };
 
@@ -959,3 +995,34 @@ export const closeRoyaltyAccount = async (
     centralStateKey,
   );
 }
+
+/**
+ * This function can be used to create or update the protocol config of the central state
+ * Deployments created before the protocol config need it once for the ACCESS NFT program to claim rewards
+ * @param connection The Solana RPC connection
+ * @param feePayer The fee payer of the transaction
+ * @param accessNftProgramSigner The signer of the ACCESS NFT program
+ * @param programId The ACCESS program ID
+ * @returns ix The instruction to set the protocol config
+ */
+export const adminSetProtocolConfig = async (
+  connection: Connection,
+  feePayer: PublicKey,
+  accessNftProgramSigner = ACCESS_NFT_PROGRAM_SIGNER,
+  programId = ACCESS_PROGRAM_ID,
+) => {
+  const [centralStateKey] = CentralStateV2.getKey(programId);
+  const centralState = await CentralStateV2.retrieve(connection, centralStateKey);
+  const [protocolConfigKey] = ProtocolConfig.getKey(programId, centralStateKey);
+
+  return new adminSetProtocolConfigInstruction({
+    accessNftProgramSigner: accessNftProgramSigner.toBuffer(),
+  }).getInstruction(
+    programId,
+    centralState.authority,
+    centralStateKey,
+    protocolConfigKey,
+    feePayer,
+    SystemProgram.programId,
+  );
+}
@@ -127,6 +127,59 @@ export class migrateCentralStateV2Instruction implements TaggedInstruction {
   }
 }
 
+export class migrateAccountInstruction implements TaggedInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      migrateAccountInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+        ],
+      },
+    ],
+  ]);
+
+  constructor() {
+    this.tag = 45;
+  }
+
+  serialize(): Uint8Array {
+    return serialize(migrateAccountInstruction.schema, this);
+  }
+
+  getInstruction(
+    programId: PublicKey,
+    feePayer: PublicKey,
+    account: PublicKey,
+    systemProgram: PublicKey,
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: feePayer,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: account,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+
 export class closeStakePoolInstruction implements TaggedInstruction {
   tag: number;
   static schema: Schema = new Map([
@@ -669,7 +722,7 @@ export class claimPoolRewardsInstruction implements TaggedInstruction {
     keys.push({
       pubkey: centralState,
       isSigner: false,
-      isWritable: false,
+      isWritable: true,
     });
     keys.push({
       pubkey: mint,
@@ -796,6 +849,7 @@ export class claimRewardsInstruction {
     splTokenProgram: PublicKey,
     ownerRoyaltyAccount: PublicKey,
     royaltyAta: PublicKey | null,
+    protocolConfig: PublicKey | null = null,
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -822,7 +876,7 @@ export class claimRewardsInstruction {
     keys.push({
       pubkey: centralState,
       isSigner: false,
-      isWritable: false,
+      isWritable: true,
     });
     keys.push({
       pubkey: mint,
@@ -850,6 +904,20 @@ export class claimRewardsInstruction {
         isSigner: false,
         isWritable: true,
       });
+    } else if (!!protocolConfig) {
+      // The program ID skips the royalty ATA before the protocol config
+      keys.push({
+        pubkey: programId,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (!!protocolConfig) {
+      keys.push({
+        pubkey: protocolConfig,
+        isSigner: false,
+        isWritable: false,
+      });
     }
     return new TransactionInstruction({
       keys,
@@ -1035,6 +1103,8 @@ export class adminProgramFreezeInstruction implements TaggedInstruction {
     programId: PublicKey,
     centralState: PublicKey,
     authority: PublicKey,
+    feePayer: PublicKey,
+    systemProgram: PublicKey,
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -1048,6 +1118,16 @@ export class adminProgramFreezeInstruction implements TaggedInstruction {
       isSigner: true,
       isWritable: false,
     });
+    keys.push({
+      pubkey: feePayer,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
     return new TransactionInstruction({
       keys,
       programId,
@@ -1093,6 +1173,8 @@ export class stakeInstruction implements TaggedInstruction {
     splTokenProgram: PublicKey,
     vault: PublicKey,
     centralStateVault: PublicKey,
+    poolFeeOverride: PublicKey,
+    mint: PublicKey,
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -1136,6 +1218,16 @@ export class stakeInstruction implements TaggedInstruction {
       isSigner: false,
       isWritable: true,
     });
+    keys.push({
+      pubkey: poolFeeOverride,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: mint,
+      isSigner: false,
+      isWritable: false,
+    });
     return new TransactionInstruction({
       keys,
       programId,
@@ -1148,6 +1240,7 @@ export class createCentralStateInstruction implements TaggedInstruction {
   tag: number;
   dailyInflation: BN;
   authority: Uint8Array;
+  accessNftProgramSigner: Uint8Array;
   static schema: Schema = new Map([
     [
       createCentralStateInstruction,
@@ -1157,6 +1250,7 @@ export class createCentralStateInstruction implements TaggedInstruction {
           ["tag", "u8"],
           ["dailyInflation", "u64"],
           ["authority", [32]],
+          ["accessNftProgramSigner", [32]],
         ],
       },
     ],
@@ -1165,10 +1259,12 @@ export class createCentralStateInstruction implements TaggedInstruction {
   constructor(obj: {
     dailyInflation: BN;
     authority: Uint8Array;
+    accessNftProgramSigner: Uint8Array;
   }) {
     this.tag = 0;
     this.dailyInflation = obj.dailyInflation;
     this.authority = obj.authority;
+    this.accessNftProgramSigner = obj.accessNftProgramSigner;
   }
 
   serialize(): Uint8Array {
@@ -1181,6 +1277,7 @@ export class createCentralStateInstruction implements TaggedInstruction {
     systemProgram: PublicKey,
     feePayer: PublicKey,
     mint: PublicKey,
+    protocolConfig: PublicKey,
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -1204,6 +1301,11 @@ export class createCentralStateInstruction implements TaggedInstruction {
       isSigner: false,
       isWritable: false,
     });
+    keys.push({
+      pubkey: protocolConfig,
+      isSigner: false,
+      isWritable: true,
+    });
     return new TransactionInstruction({
       keys,
       programId,
@@ -1246,6 +1348,7 @@ export class claimBondV2RewardsInstruction implements TaggedInstruction {
     splTokenProgram: PublicKey,
     ownerRoyaltyAccount: PublicKey,
     royaltyAta: PublicKey | null,
+    protocolConfig: PublicKey | null = null,
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -1272,7 +1375,7 @@ export class claimBondV2RewardsInstruction implements TaggedInstruction {
     keys.push({
       pubkey: centralState,
       isSigner: false,
-      isWritable: false,
+      isWritable: true,
     });
     keys.push({
       pubkey: mint,
@@ -1300,6 +1403,20 @@ export class claimBondV2RewardsInstruction implements TaggedInstruction {
         isSigner: false,
         isWritable: true,
       });
+    } else if (!!protocolConfig) {
+      // The program ID skips the royalty ATA before the protocol config
+      keys.push({
+        pubkey: programId,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (!!protocolConfig) {
+      keys.push({
+        pubkey: protocolConfig,
+        isSigner: false,
+        isWritable: false,
+      });
     }
     return new TransactionInstruction({
       keys,
@@ -1600,6 +1717,7 @@ export class claimBondInstruction implements TaggedInstruction {
     poolVault: PublicKey,
     centralState: PublicKey,
     splTokenProgram: PublicKey,
+    quoteMint: PublicKey | null,
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -1648,6 +1766,13 @@ export class claimBondInstruction implements TaggedInstruction {
       isSigner: false,
       isWritable: false,
     });
+    if (!!quoteMint) {
+      keys.push({
+        pubkey: quoteMint,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
     return new TransactionInstruction({
       keys,
       programId,
@@ -1911,7 +2036,7 @@ export class claimBondRewardsInstruction implements TaggedInstruction {
     keys.push({
       pubkey: centralState,
       isSigner: false,
-      isWritable: false,
+      isWritable: true,
     });
     keys.push({
       pubkey: mint,
@@ -2075,6 +2200,7 @@ export class unstakeInstruction implements TaggedInstruction {
     destinationToken: PublicKey,
     splTokenProgram: PublicKey,
     vault: PublicKey,
+    mint: PublicKey,
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -2113,6 +2239,11 @@ export class unstakeInstruction implements TaggedInstruction {
       isSigner: false,
       isWritable: true,
     });
+    keys.push({
+      pubkey: mint,
+      isSigner: false,
+      isWritable: false,
+    });
     return new TransactionInstruction({
       keys,
       programId,
@@ -2280,6 +2411,7 @@ export class unlockBondV2Instruction implements TaggedInstruction {
     pool: PublicKey,
     poolVault: PublicKey,
     splTokenProgram: PublicKey,
+    mint: PublicKey,
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -2318,6 +2450,11 @@ export class unlockBondV2Instruction implements TaggedInstruction {
       isSigner: false,
       isWritable: false,
     });
+    keys.push({
+      pubkey: mint,
+      isSigner: false,
+      isWritable: false,
+    });
     return new TransactionInstruction({
       keys,
       programId,
@@ -2381,7 +2518,7 @@ export class adminMintInstruction implements TaggedInstruction {
     keys.push({
       pubkey: centralState,
       isSigner: false,
-      isWritable: false,
+      isWritable: true,
     });
     keys.push({
       pubkey: splTokenProgram,
@@ -2501,3 +2638,73 @@ export class closeRoyaltyAccountInstruction implements TaggedInstruction {
     });
   }
 }
+
+export class adminSetProtocolConfigInstruction implements TaggedInstruction {
+  tag: number;
+  accessNftProgramSigner: Uint8Array;
+  static schema: Schema = new Map([
+    [
+      adminSetProtocolConfigInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["accessNftProgramSigner", [32]],
+        ],
+      },
+    ],
+  ]);
+
+  constructor(obj: {
+    accessNftProgramSigner: Uint8Array;
+  }) {
+    this.tag = 38;
+    this.accessNftProgramSigner = obj.accessNftProgramSigner;
+  }
+
+  serialize(): Uint8Array {
+    return serialize(adminSetProtocolConfigInstruction.schema, this);
+  }
+
+  getInstruction(
+    programId: PublicKey,
+    authority: PublicKey,
+    centralState: PublicKey,
+    protocolConfig: PublicKey,
+    feePayer: PublicKey,
+    systemProgram: PublicKey,
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: authority,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: centralState,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: protocolConfig,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: feePayer,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
@@ -9,7 +9,8 @@ import {
   RoyaltyAccount,
   StakeAccount,
   StakePool,
-  Tag
+  Tag,
+  VERSION_FLAG
 } from "./state.js";
 import * as BN from "bn.js";
 import {
@@ -34,6 +35,56 @@ import {
   claimRewardsInstruction
 } from "./raw_instructions.js";
 
+// Base 58 encoding of a tag byte for the memcmp filters
+const encodeTag = (tag: number): string => {
+  const alphabet = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+  return tag < 58
+    ? alphabet[tag]
+    : alphabet[Math.floor(tag / 58)] + alphabet[tag % 58];
+};
+
+/**
+ * Finds the accounts of a versioned layout owned by `owner`. The owner follows the tag in the
+ * accounts created before the versioning and the tag and the version byte in the other ones,
+ * both layouts are queried
+ * @param connection The Solana RPC connection
+ * @param tag The tag of the accounts
+ * @param owner The owner of the accounts to retrieve
+ * @param programId The program ID
+ * @returns
+ */
+const getVersionedAccountsByOwner = async (
+  connection: Connection,
+  tag: Tag,
+  owner: PublicKey,
+  programId: PublicKey
+) => {
+  const query = (tagByte: number, ownerOffset: number) => {
+    const filters: MemcmpFilter[] = [
+      {
+        memcmp: {
+          offset: 0,
+          bytes: encodeTag(tagByte),
+        },
+      },
+      {
+        memcmp: {
+          offset: ownerOffset,
+          bytes: owner.toBase58(),
+        },
+      },
+    ];
+    return connection.getProgramAccounts(programId, {
+      filters,
+    });
+  };
+  const [unversioned, versioned] = await Promise.all([
+    query(tag, 1),
+    query(tag | VERSION_FLAG, 2),
+  ]);
+  return unversioned.concat(versioned);
+};
+
 /**
  * This function can be used to find all stake accounts of a user
  * @param connection The Solana RPC connection
@@ -46,23 +97,12 @@ export const getStakeAccounts = async (
   owner: PublicKey,
   programId: PublicKey
 ) => {
-  const filters: MemcmpFilter[] = [
-    {
-      memcmp: {
-        offset: 0,
-        bytes: "4",
-      },
-    },
-    {
-      memcmp: {
-        offset: 1,
-        bytes: owner.toBase58(),
-      },
-    },
-  ];
-  return await connection.getProgramAccounts(programId, {
-    filters,
-  });
+  return await getVersionedAccountsByOwner(
+    connection,
+    Tag.StakeAccount,
+    owner,
+    programId
+  );
 };
 
 /**
@@ -86,7 +126,8 @@ export const getStakePools = async (
     },
     {
       memcmp: {
-        offset: 1 + 1 + 2 + 4 + 8 + 8 + 8,
+        // tag, nonce, current day, reward index and flags, then 4 u64
+        offset: 1 + 1 + 2 + 4 + 8 + 8 + 8 + 8,
         bytes: owner.toBase58(),
       },
     },
@@ -140,23 +181,12 @@ export const getBondV2Accounts = async (
   owner: PublicKey,
   programId: PublicKey
 ) => {
-  const filters = [
-    {
-      memcmp: {
-        offset: 0,
-        bytes: "C", // 12 in base 58 - todo test
-      },
-    },
-    {
-      memcmp: {
-        offset: 1,
-        bytes: owner.toBase58(),
-      },
-    },
-  ];
-  return await connection.getProgramAccounts(programId, {
-    filters,
-  });
+  return await getVersionedAccountsByOwner(
+    connection,
+    Tag.BondV2Account,
+    owner,
+    programId
+  );
 };
 
 /**
@@ -347,9 +377,9 @@ export const getLockedAmountForPool = async (
   );
   if (allBondV2AccountsForUser != null && allBondV2AccountsForUser.length > 0) {
     allBondV2AccountsForUser.forEach((ba) => {
-      const b = BondAccount.deserialize(ba.account.data);
-      if (b.stakePool.toBase58() === poolPubkey.toBase58()) {
-        bondsAmountSum = bondsAmountSum.add(b.totalStaked);
+      const b = BondV2Account.deserialize(ba.account.data);
+      if (b.pool.toBase58() === poolPubkey.toBase58()) {
+        bondsAmountSum = bondsAmountSum.add(b.amount);
       }
     });
   }
@@ -747,17 +777,28 @@ export const fullUserRewardClaim = async (
   poolOffsets: Map<string, number> | undefined = undefined,
   tokenAccountFeepayer = feePayer,
 ): Promise<[TransactionInstruction[], TransactionInstruction[]]> => {
-  const filters: MemcmpFilter[] = [
-    {
-      memcmp: {
-        offset: 1,
-        bytes: user.toBase58(),
+  // The owner follows the tag, and the version byte of the versioned layouts
+  const getOwnerAccounts = async (ownerOffset: number, versioned: boolean) => {
+    const filters: MemcmpFilter[] = [
+      {
+        memcmp: {
+          offset: ownerOffset,
+          bytes: user.toBase58(),
+        },
       },
-    },
-  ];
-  const userOwnerAccounts = await connection.getProgramAccounts(programId, {
-    filters,
-  });
+    ];
+    const accounts = await connection.getProgramAccounts(programId, {
+      filters,
+    });
+    return accounts.filter(
+      e => ((e.account.data[0] & VERSION_FLAG) !== 0) === versioned
+    );
+  };
+  const [unversionedAccounts, versionedAccounts] = await Promise.all([
+    getOwnerAccounts(1, false),
+    getOwnerAccounts(2, true),
+  ]);
+  const userOwnerAccounts = unversionedAccounts.concat(versionedAccounts);
   const [centralStateKey] = CentralStateV2.getKey(programId);
   const centralState = await CentralStateV2.retrieve(
     connection,
@@ -778,7 +819,7 @@ export const fullUserRewardClaim = async (
 
   const claimIxs = userOwnerAccounts
     .map(account => {
-      switch (account.account.data[0]) {
+      switch (account.account.data[0] & ~VERSION_FLAG) {
         // stake account
         case Tag.StakeAccount:
           const stakeAccount = StakeAccount.deserialize(account.account.data);
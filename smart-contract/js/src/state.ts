@@ -1,4 +1,4 @@
-import { deserialize, Schema } from "borsh";
+import { deserialize, deserializeUnchecked, Schema } from "borsh";
 import * as BN from 'bn.js';
 import { Connection, PublicKey } from "@solana/web3.js";
 import { u64 } from "./u64.js";
@@ -9,6 +9,12 @@ export const DEFAULT_STAKER_MULTIPLIER = 50;
 /** Length of the circular buffer (stores data for calculating rewards for 274 days) */
 export const STAKE_BUFFER_LEN = 274;
 
+/** Length of the header of a stake pool, followed by its balances */
+export const STAKE_POOL_HEADER_LEN = 104;
+
+/** Length of a rewards tuple */
+export const REWARDS_TUPLE_LEN = 32;
+
 /** Maximum count of recipients of the fees */
 export const MAX_FEE_RECIPIENTS = 10;
 
@@ -41,8 +47,35 @@ export enum Tag {
   // V2 tags
   BondV2Account = 11,
   CentralStateV2 = 12,
+  RoyaltyAccount = 13,
+  PoolFeeOverride = 14,
+  FeeDistributionConfig = 15,
+  ProtocolConfig = 16,
+  BondV2Listing = 17,
+  BoostConfig = 18,
+  PoolBoost = 19,
+  GovernanceConfig = 20,
+  PoolControls = 21,
+  PoolAllowlistEntry = 22,
+  RoleTable = 23,
 }
 
+/**
+ * Set on the tag byte of the accounts followed by a version byte
+ */
+export const VERSION_FLAG = 0x80;
+
+/**
+ * Removes the version byte of an account with a versioned layout, the layouts of
+ * the stake accounts and bond V2 accounts only differ by it
+ */
+export const stripVersionHeader = (data: Buffer): Buffer => {
+  if ((data[0] & VERSION_FLAG) === 0) {
+    return data;
+  }
+  return Buffer.concat([Buffer.from([data[0] & ~VERSION_FLAG]), data.subarray(2)]);
+};
+
 /**
  * Rewards tuple
  */
@@ -71,6 +104,7 @@ export class StakePool {
   owner: PublicKey;
   vault: PublicKey;
 
+  /** The slots of the reward index of the pool, then its rewards buffer unless compacted */
   balances: RewardsTuple[];
 
   static schema: Schema = new Map<any, any>([
@@ -89,7 +123,6 @@ export class StakePool {
           ["stakersPart", "u64"],
           ["owner", [32]],
           ["vault", [32]],
-          ["balances", [RewardsTuple, STAKE_BUFFER_LEN]],
         ],
       },
     ],
@@ -116,8 +149,6 @@ export class StakePool {
     stakersPart: BN;
     owner: Uint8Array;
     vault: Uint8Array;
-
-    balances: RewardsTuple[];
   }) {
     this.tag = obj.tag as Tag;
     this.nonce = obj.nonce;
@@ -129,11 +160,26 @@ export class StakePool {
     this.stakersPart = obj.stakersPart;
     this.owner = new PublicKey(obj.owner);
     this.vault = new PublicKey(obj.vault);
-    this.balances = obj.balances;
+    this.balances = [];
   }
 
   static deserialize(data: Buffer) {
-    return deserialize(this.schema, StakePool, data);
+    // The pools created with the reward index have no rewards buffer
+    const pool: StakePool = deserializeUnchecked(this.schema, StakePool, data);
+    for (
+      let offset = STAKE_POOL_HEADER_LEN;
+      offset + REWARDS_TUPLE_LEN <= data.length;
+      offset += REWARDS_TUPLE_LEN
+    ) {
+      pool.balances.push(
+        deserialize(
+          this.schema,
+          RewardsTuple,
+          data.subarray(offset, offset + REWARDS_TUPLE_LEN)
+        )
+      );
+    }
+    return pool;
   }
 
   /**
@@ -209,7 +255,8 @@ export class StakeAccount {
   }
 
   static deserialize(data: Buffer) {
-    return deserialize(this.schema, StakeAccount, data);
+    // Accounts created since the reward index end with their reward checkpoint
+    return deserializeUnchecked(this.schema, StakeAccount, stripVersionHeader(data));
   }
 
   /**
@@ -354,16 +401,79 @@ export class CentralStateV2 {
   creationTime: BN;
   totalStaked: BN;
   totalStakedSnapshot: BN;
+  totalBoost: BN;
+  totalBoostSnapshot: BN;
   lastSnapshotOffset: BN;
   ixGate: BN;
   freezeAuthority: PublicKey;
   adminIxGate: BN;
   feeBasisPoints: number;
   lastFeeDistributionTime: BN;
+  exitsFrozenSince: BN;
+  exitsUnfrozenSince: BN;
+  emissionCap: BN;
+  stakerRewardsMinted: BN;
+  poolRewardsMinted: BN;
+  royaltiesMinted: BN;
+  adminMinted: BN;
+  bondsMinted: BN;
+  feesBurned: BN;
   private feeRecipientsCount: number; // this is needed due to Borsh encoding, see https://borsh.io/
   private recipients: FeeRecipient[];
 
   static schema: Schema = new Map<any, any>([
+    [
+      CentralStateV2,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["bumpSeed", "u8"],
+          ["dailyInflation", "u64"],
+          ["tokenMint", [32]],
+          ["authority", [32]],
+          ["creationTime", "u64"],
+          ["totalStaked", "u64"],
+          ["totalStakedSnapshot", "u64"],
+          ["totalBoost", "u64"],
+          ["totalBoostSnapshot", "u64"],
+          ["lastSnapshotOffset", "u64"],
+          ["ixGate", "u128"],
+          ["freezeAuthority", [32]],
+          ["adminIxGate", "u128"],
+          ["feeBasisPoints", "u16"],
+          ["lastFeeDistributionTime", "u64"],
+          ["exitsFrozenSince", "u64"],
+          ["exitsUnfrozenSince", "u64"],
+          ["emissionCap", "u64"],
+          ["stakerRewardsMinted", "u64"],
+          ["poolRewardsMinted", "u64"],
+          ["royaltiesMinted", "u64"],
+          ["adminMinted", "u64"],
+          ["bondsMinted", "u64"],
+          ["feesBurned", "u64"],
+          ["feeRecipientsCount", "u32"],
+          ["recipients", [FeeRecipient, MAX_FEE_RECIPIENTS]],
+        ],
+      },
+    ],
+    [
+      FeeRecipient,
+      {
+        kind: "struct",
+        fields: [
+          ["owner", [32]],
+          ["percentage", "u64"],
+        ],
+      },
+    ],
+  ]);
+
+  /**
+   * The layout of the central states that were not migrated to the version 1 layout, without
+   * the boosts, the freeze of the exits and the emission counters
+   */
+  static schemaV0: Schema = new Map<any, any>([
     [
       CentralStateV2,
       {
@@ -409,12 +519,23 @@ export class CentralStateV2 {
     creationTime: BN;
     totalStaked: BN;
     totalStakedSnapshot: BN;
+    totalBoost?: BN;
+    totalBoostSnapshot?: BN;
     lastSnapshotOffset: BN;
     ixGate: BN;
     freezeAuthority: Uint8Array;
     adminIxGate: BN;
     feeBasisPoints: number;
     lastFeeDistributionTime: BN;
+    exitsFrozenSince?: BN;
+    exitsUnfrozenSince?: BN;
+    emissionCap?: BN;
+    stakerRewardsMinted?: BN;
+    poolRewardsMinted?: BN;
+    royaltiesMinted?: BN;
+    adminMinted?: BN;
+    bondsMinted?: BN;
+    feesBurned?: BN;
     feeRecipientsCount: number;
     recipients: FeeRecipient[];
   }) {
@@ -426,12 +547,23 @@ export class CentralStateV2 {
     this.creationTime = obj.creationTime.fromTwos(64);
     this.totalStaked = obj.totalStaked;
     this.totalStakedSnapshot = obj.totalStakedSnapshot;
+    this.totalBoost = obj.totalBoost || new BN.BN(0);
+    this.totalBoostSnapshot = obj.totalBoostSnapshot || new BN.BN(0);
     this.lastSnapshotOffset = obj.lastSnapshotOffset;
     this.ixGate = obj.ixGate;
     this.freezeAuthority = new PublicKey(obj.freezeAuthority);
     this.adminIxGate = obj.adminIxGate;
     this.feeBasisPoints = obj.feeBasisPoints;
     this.lastFeeDistributionTime = obj.lastFeeDistributionTime.fromTwos(64);
+    this.exitsFrozenSince = (obj.exitsFrozenSince || new BN.BN(0)).fromTwos(64);
+    this.exitsUnfrozenSince = (obj.exitsUnfrozenSince || new BN.BN(0)).fromTwos(64);
+    this.emissionCap = obj.emissionCap || new BN.BN(0);
+    this.stakerRewardsMinted = obj.stakerRewardsMinted || new BN.BN(0);
+    this.poolRewardsMinted = obj.poolRewardsMinted || new BN.BN(0);
+    this.royaltiesMinted = obj.royaltiesMinted || new BN.BN(0);
+    this.adminMinted = obj.adminMinted || new BN.BN(0);
+    this.bondsMinted = obj.bondsMinted || new BN.BN(0);
+    this.feesBurned = obj.feesBurned || new BN.BN(0);
     this.feeRecipientsCount = obj.feeRecipientsCount;
     this.recipients = obj.recipients;
   }
@@ -446,7 +578,11 @@ export class CentralStateV2 {
   }
 
   static deserialize(data: Buffer) {
-    return deserialize(this.schema, CentralStateV2, data);
+    // The central states migrated to the version 1 layout start with a version byte
+    if ((data[0] & VERSION_FLAG) === 0) {
+      return deserializeUnchecked(this.schemaV0, CentralStateV2, data);
+    }
+    return deserializeUnchecked(this.schema, CentralStateV2, stripVersionHeader(data));
   }
 
   /**
@@ -660,7 +796,8 @@ export class BondV2Account {
   }
 
   static deserialize(data: Buffer) {
-    return deserialize(this.schema, BondV2Account, data);
+    // Accounts created since the reward index end with their reward checkpoint
+    return deserializeUnchecked(this.schema, BondV2Account, stripVersionHeader(data));
   }
 
   /**
@@ -784,6 +921,72 @@ export class RoyaltyAccount {
   }
 }
 
+/**
+ * The protocol config of the central state
+ */
+export class ProtocolConfig {
+  tag: Tag;
+  centralState: PublicKey;
+  accessNftProgramSigner: PublicKey;
+
+  static schema: Schema = new Map<any, any>([
+    [
+      ProtocolConfig,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["centralState", [32]],
+          ["accessNftProgramSigner", [32]],
+        ],
+      },
+    ],
+  ]);
+
+  constructor(obj: {
+    tag: number;
+    centralState: Uint8Array;
+    accessNftProgramSigner: Uint8Array;
+  }) {
+    this.tag = obj.tag;
+    this.centralState = new PublicKey(obj.centralState);
+    this.accessNftProgramSigner = new PublicKey(obj.accessNftProgramSigner);
+  }
+
+  static deserialize(data: Buffer) {
+    return deserialize(this.schema, ProtocolConfig, data);
+  }
+
+  /**
+   * This method can be used to retrieve the protocol config
+   * @param connection The Solana RPC connection
+   * @param key The protocol config key
+   * @returns
+   */
+  static async retrieve(connection: Connection, key: PublicKey) {
+    const accountInfo = await connection.getAccountInfo(key);
+    if (!accountInfo || !accountInfo.data) {
+      return null; // the config is only created by AdminSetProtocolConfig on older deployments
+    }
+    return this.deserialize(accountInfo.data);
+  }
+
+  /**
+   * This method can be used to derive the protocol config key
+   * @param programId The ACCESS program ID
+   * @param centralState The key of the central state
+   * @returns
+   */
+  static getKey(
+    programId: PublicKey,
+    centralState: PublicKey,
+  ) {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_config"), centralState.toBuffer()],
+      programId
+    );
+  }
+}
 
 /// mainnet ACCESS token mint and program id
 export const ACCESS_MINT = new PublicKey("5MAYDfq5yxtudAhtfyuMBuHZjgAbaS9tbEyEQYAhDS5y");
@@ -60,7 +60,8 @@ export const claimBond = async (
     tokenMint,
     stakePool.vault,
     centralStateKey,
-    TOKEN_PROGRAM_ID
+    TOKEN_PROGRAM_ID,
+    bond.totalQuoteAmount.isZero() ? null : bond.quoteMint,
   );
 };
 
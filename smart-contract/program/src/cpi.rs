@@ -3,6 +3,8 @@ use solana_program::{
     rent::Rent, system_instruction::create_account, sysvar::Sysvar,
 };
 
+use crate::utils::unpack_mint;
+
 #[allow(missing_docs)]
 pub struct Cpi {}
 
@@ -34,4 +36,110 @@ impl Cpi {
             &[signer_seeds],
         )
     }
+
+    /// Transfers tokens with `transfer_checked` with either token program.
+    /// The accounts of the transfer hook of the mint are looked up in `transfer_hook_accounts`
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer<'a>(
+        token_program: &AccountInfo<'a>,
+        source: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+        mint: &AccountInfo<'a>,
+        transfer_hook_accounts: &[AccountInfo<'a>],
+        amount: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let decimals = unpack_mint(mint)?.decimals;
+        spl_token_2022::onchain::invoke_transfer_checked(
+            token_program.key,
+            source.clone(),
+            mint.clone(),
+            destination.clone(),
+            authority.clone(),
+            transfer_hook_accounts,
+            amount,
+            decimals,
+            signer_seeds,
+        )
+    }
+
+    #[allow(missing_docs)]
+    pub fn mint_to<'a>(
+        token_program: &AccountInfo<'a>,
+        mint: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+        amount: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let mint_instruction = spl_token_2022::instruction::mint_to(
+            token_program.key,
+            mint.key,
+            destination.key,
+            authority.key,
+            &[],
+            amount,
+        )?;
+        invoke_signed(
+            &mint_instruction,
+            &[
+                token_program.clone(),
+                mint.clone(),
+                destination.clone(),
+                authority.clone(),
+            ],
+            signer_seeds,
+        )
+    }
+
+    #[allow(missing_docs)]
+    pub fn burn<'a>(
+        token_program: &AccountInfo<'a>,
+        account: &AccountInfo<'a>,
+        mint: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+        amount: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let burn_instruction = spl_token_2022::instruction::burn(
+            token_program.key,
+            account.key,
+            mint.key,
+            authority.key,
+            &[],
+            amount,
+        )?;
+        invoke_signed(
+            &burn_instruction,
+            &[
+                token_program.clone(),
+                account.clone(),
+                mint.clone(),
+                authority.clone(),
+            ],
+            signer_seeds,
+        )
+    }
+
+    #[allow(missing_docs)]
+    pub fn initialize_mint<'a>(
+        token_program: &AccountInfo<'a>,
+        mint: &AccountInfo<'a>,
+        mint_authority: &Pubkey,
+        decimals: u8,
+    ) -> ProgramResult {
+        let initialize_instruction = spl_token_2022::instruction::initialize_mint2(
+            token_program.key,
+            mint.key,
+            mint_authority,
+            None,
+            decimals,
+        )?;
+        invoke_signed(
+            &initialize_instruction,
+            &[token_program.clone(), mint.clone()],
+            &[],
+        )
+    }
 }
@@ -0,0 +1,340 @@
+//! Decoding of any account owned by the program
+//!
+//! [`ProtocolAccount::decode`] dispatches on the leading [`Tag`] byte, without its version flag, so that the type of an account
+//! does not have to be known beforehand. With the `json` feature the decoded accounts can be
+//! printed as JSON for explorers, the CLI and migration scripts.
+use std::mem::size_of;
+
+use borsh::BorshDeserialize;
+use num_traits::FromPrimitive;
+use solana_program::program_error::ProgramError;
+
+use crate::state::{
+    BondAccount, BondV2Account, BondV2Listing, BoostConfig, CentralState, CentralStateV2,
+    FeeDistributionConfig, GovernanceConfig, PoolAllowlistEntry, PoolBoost, PoolControls,
+    PoolFeeOverride, ProtocolConfig, RoleTable, RoyaltyAccount, StakeAccount, StakePoolHeader,
+    StakePoolHeaped, Tag,
+};
+use crate::versioning::{read_header, Versioned};
+
+#[allow(missing_docs)]
+/// An account of the program, frozen and inactive accounts decode to the variant of their type
+pub enum ProtocolAccount {
+    StakePool(StakePoolHeaped),
+    StakeAccount(StakeAccount),
+    BondAccount(BondAccount),
+    CentralState(CentralState),
+    CentralStateV2(CentralStateV2),
+    BondV2Account(BondV2Account),
+    RoyaltyAccount(RoyaltyAccount),
+    PoolFeeOverride(PoolFeeOverride),
+    FeeDistributionConfig(FeeDistributionConfig),
+    ProtocolConfig(ProtocolConfig),
+    BondV2Listing(BondV2Listing),
+    BoostConfig(BoostConfig),
+    PoolBoost(PoolBoost),
+    GovernanceConfig(GovernanceConfig),
+    PoolControls(PoolControls),
+    PoolAllowlistEntry(PoolAllowlistEntry),
+    RoleTable(RoleTable),
+    /// A closed account
+    Deleted,
+}
+
+fn deserialize<T: BorshDeserialize>(data: &[u8]) -> Result<T, ProgramError> {
+    // Some accounts are allocated with more space than their Borsh length
+    T::deserialize(&mut &data[..]).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+impl ProtocolAccount {
+    /// Decodes the data of an account owned by the program
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, _) = read_header(data)?;
+        let account = match tag {
+            Tag::Uninitialized => return Err(ProgramError::UninitializedAccount),
+            Tag::Deleted => ProtocolAccount::Deleted,
+            Tag::StakePool | Tag::InactiveStakePool | Tag::FrozenStakePool => {
+                if data.len() < size_of::<StakePoolHeader>() {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                ProtocolAccount::StakePool(StakePoolHeaped::from_buffer(data))
+            }
+            Tag::StakeAccount | Tag::FrozenStakeAccount => {
+                ProtocolAccount::StakeAccount(StakeAccount::load(data)?)
+            }
+            Tag::BondAccount | Tag::InactiveBondAccount | Tag::FrozenBondAccount => {
+                ProtocolAccount::BondAccount(deserialize(data)?)
+            }
+            Tag::CentralState => ProtocolAccount::CentralState(deserialize(data)?),
+            Tag::CentralStateV2 => ProtocolAccount::CentralStateV2(CentralStateV2::load(data)?),
+            Tag::BondV2Account => ProtocolAccount::BondV2Account(BondV2Account::load(data)?),
+            Tag::RoyaltyAccount => ProtocolAccount::RoyaltyAccount(deserialize(data)?),
+            Tag::PoolFeeOverride => ProtocolAccount::PoolFeeOverride(deserialize(data)?),
+            Tag::FeeDistributionConfig => {
+                ProtocolAccount::FeeDistributionConfig(deserialize(data)?)
+            }
+            Tag::ProtocolConfig => ProtocolAccount::ProtocolConfig(deserialize(data)?),
+            Tag::BondV2Listing => ProtocolAccount::BondV2Listing(deserialize(data)?),
+            Tag::BoostConfig => ProtocolAccount::BoostConfig(deserialize(data)?),
+            Tag::PoolBoost => ProtocolAccount::PoolBoost(deserialize(data)?),
+            Tag::GovernanceConfig => ProtocolAccount::GovernanceConfig(deserialize(data)?),
+            Tag::PoolControls => ProtocolAccount::PoolControls(deserialize(data)?),
+            Tag::PoolAllowlistEntry => ProtocolAccount::PoolAllowlistEntry(deserialize(data)?),
+            Tag::RoleTable => ProtocolAccount::RoleTable(deserialize(data)?),
+        };
+        Ok(account)
+    }
+
+    /// The tag of the account
+    pub fn tag(&self) -> Tag {
+        match self {
+            ProtocolAccount::StakePool(p) => {
+                Tag::from_u8(p.header.tag).unwrap_or(Tag::Uninitialized)
+            }
+            ProtocolAccount::StakeAccount(a) => a.tag,
+            ProtocolAccount::BondAccount(a) => a.tag,
+            ProtocolAccount::CentralState(a) => a.tag,
+            ProtocolAccount::CentralStateV2(a) => a.tag,
+            ProtocolAccount::BondV2Account(a) => a.tag,
+            ProtocolAccount::RoyaltyAccount(a) => a.tag,
+            ProtocolAccount::PoolFeeOverride(a) => a.tag,
+            ProtocolAccount::FeeDistributionConfig(a) => a.tag,
+            ProtocolAccount::ProtocolConfig(a) => a.tag,
+            ProtocolAccount::BondV2Listing(a) => a.tag,
+            ProtocolAccount::BoostConfig(a) => a.tag,
+            ProtocolAccount::PoolBoost(a) => a.tag,
+            ProtocolAccount::GovernanceConfig(a) => a.tag,
+            ProtocolAccount::PoolControls(a) => a.tag,
+            ProtocolAccount::PoolAllowlistEntry(a) => a.tag,
+            ProtocolAccount::RoleTable(a) => a.tag,
+            ProtocolAccount::Deleted => Tag::Deleted,
+        }
+    }
+
+    /// Returns true if the account was frozen by the central state authority
+    pub fn is_frozen(&self) -> bool {
+        matches!(
+            self.tag(),
+            Tag::FrozenStakePool | Tag::FrozenStakeAccount | Tag::FrozenBondAccount
+        )
+    }
+}
+
+#[cfg(feature = "json")]
+mod json {
+    use serde_json::{json, Value};
+    use solana_program::pubkey::Pubkey;
+
+    use super::ProtocolAccount;
+
+    impl ProtocolAccount {
+        /// The account as JSON, the public keys are base58 encoded and the `u128` values are strings
+        pub fn to_json(&self) -> Value {
+            let tag = format!("{:?}", self.tag());
+            match self {
+                ProtocolAccount::StakePool(p) => {
+                    let balances: Vec<Value> = p
+                        .rewards_buffer()
+                        .iter()
+                        .map(|b| {
+                            json!({
+                                "pool_reward": b.pool_reward.to_string(),
+                                "stakers_reward": b.stakers_reward.to_string(),
+                            })
+                        })
+                        .collect();
+                    json!({
+                        "tag": tag,
+                        "nonce": p.header.nonce,
+                        "current_day_idx": p.header.current_day_idx,
+                        "minimum_stake_amount": p.header.minimum_stake_amount,
+                        "total_staked": p.header.total_staked,
+                        "last_claimed_offset": p.header.last_claimed_offset,
+                        "stakers_part": p.header.stakers_part,
+                        "owner": Pubkey::new_from_array(p.header.owner).to_string(),
+                        "vault": Pubkey::new_from_array(p.header.vault).to_string(),
+                        "boost_enabled": p.header.uses_boost(),
+                        "has_controls": p.header.has_controls(),
+                        "balances": balances,
+                        "reward_index": p.reward_index().map(|i| json!({
+                            "start_day_idx": p.header.reward_index_start,
+                            "pool_reward_index": i.pool_reward_index.to_string(),
+                            "stakers_reward_index": i.stakers_reward_index.to_string(),
+                            "pool_claimed_index": i.pool_claimed_index.to_string(),
+                            "stakers_reward_index_start": i.stakers_reward_index_start.to_string(),
+                        })),
+                    })
+                }
+                ProtocolAccount::StakeAccount(a) => json!({
+                    "tag": tag,
+                    "owner": a.owner.to_string(),
+                    "stake_amount": a.stake_amount,
+                    "stake_pool": a.stake_pool.to_string(),
+                    "last_claimed_offset": a.last_claimed_offset,
+                    "pool_minimum_at_creation": a.pool_minimum_at_creation,
+                    "last_claimed_index": a.last_claimed_index.to_string(),
+                }),
+                ProtocolAccount::BondAccount(a) => json!({
+                    "tag": tag,
+                    "owner": a.owner.to_string(),
+                    "total_amount_sold": a.total_amount_sold,
+                    "total_staked": a.total_staked,
+                    "total_quote_amount": a.total_quote_amount,
+                    "quote_mint": a.quote_mint.to_string(),
+                    "seller_token_account": a.seller_token_account.to_string(),
+                    "unlock_start_date": a.unlock_start_date,
+                    "unlock_period": a.unlock_period,
+                    "unlock_amount": a.unlock_amount,
+                    "last_unlock_time": a.last_unlock_time,
+                    "total_unlocked_amount": a.total_unlocked_amount,
+                    "pool_minimum_at_creation": a.pool_minimum_at_creation,
+                    "stake_pool": a.stake_pool.to_string(),
+                    "last_claimed_offset": a.last_claimed_offset,
+                    "sellers": a.sellers.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+                }),
+                ProtocolAccount::CentralState(a) => json!({
+                    "tag": tag,
+                    "signer_nonce": a.signer_nonce,
+                    "daily_inflation": a.daily_inflation,
+                    "token_mint": a.token_mint.to_string(),
+                    "authority": a.authority.to_string(),
+                    "creation_time": a.creation_time,
+                    "total_staked": a.total_staked,
+                    "total_staked_snapshot": a.total_staked_snapshot,
+                    "last_snapshot_offset": a.last_snapshot_offset,
+                }),
+                ProtocolAccount::CentralStateV2(a) => {
+                    let recipients: Vec<Value> = a
+                        .recipients
+                        .iter()
+                        .map(|r| json!({"owner": r.owner.to_string(), "percentage": r.percentage}))
+                        .collect();
+                    json!({
+                        "tag": tag,
+                        "bump_seed": a.bump_seed,
+                        "daily_inflation": a.daily_inflation,
+                        "token_mint": a.token_mint.to_string(),
+                        "authority": a.authority.to_string(),
+                        "creation_time": a.creation_time,
+                        "total_staked": a.total_staked,
+                        "total_staked_snapshot": a.total_staked_snapshot,
+                        "total_boost": a.total_boost,
+                        "total_boost_snapshot": a.total_boost_snapshot,
+                        "last_snapshot_offset": a.last_snapshot_offset,
+                        "ix_gate": format!("{:#034x}", a.ix_gate),
+                        "freeze_authority": a.freeze_authority.to_string(),
+                        "admin_ix_gate": format!("{:#034x}", a.admin_ix_gate),
+                        "fee_basis_points": a.fee_basis_points,
+                        "last_fee_distribution_time": a.last_fee_distribution_time,
+                        "exits_frozen_since": a.exits_frozen_since,
+                        "exits_unfrozen_since": a.exits_unfrozen_since,
+                        "emission_cap": a.emission_cap,
+                        "staker_rewards_minted": a.staker_rewards_minted,
+                        "pool_rewards_minted": a.pool_rewards_minted,
+                        "royalties_minted": a.royalties_minted,
+                        "admin_minted": a.admin_minted,
+                        "bonds_minted": a.bonds_minted,
+                        "fees_burned": a.fees_burned,
+                        "recipients": recipients,
+                    })
+                }
+                ProtocolAccount::BondV2Account(a) => json!({
+                    "tag": tag,
+                    "owner": a.owner.to_string(),
+                    "amount": a.amount,
+                    "pool": a.pool.to_string(),
+                    "last_claimed_offset": a.last_claimed_offset,
+                    "pool_minimum_at_creation": a.pool_minimum_at_creation,
+                    "unlock_timestamp": a.unlock_timestamp,
+                    "last_claimed_index": a.last_claimed_index.to_string(),
+                    "boost": a.boost,
+                    "unclaimed_rewards": a.unclaimed_rewards,
+                }),
+                ProtocolAccount::RoyaltyAccount(a) => json!({
+                    "tag": tag,
+                    "rent_payer": a.rent_payer.to_string(),
+                    "royalty_payer": a.royalty_payer.to_string(),
+                    "recipient_ata": a.recipient_ata.to_string(),
+                    "expiration_date": a.expiration_date,
+                    "royalty_basis_points": a.royalty_basis_points,
+                }),
+                ProtocolAccount::PoolFeeOverride(a) => json!({
+                    "tag": tag,
+                    "stake_pool": a.stake_pool.to_string(),
+                    "fee_basis_points": a.fee_basis_points,
+                }),
+                ProtocolAccount::FeeDistributionConfig(a) => json!({
+                    "tag": tag,
+                    "central_state": a.central_state.to_string(),
+                    "keeper_reward_basis_points": a.keeper_reward_basis_points,
+                }),
+                ProtocolAccount::ProtocolConfig(a) => json!({
+                    "tag": tag,
+                    "central_state": a.central_state.to_string(),
+                    "access_nft_program_signer": a.access_nft_program_signer.to_string(),
+                }),
+                ProtocolAccount::BondV2Listing(a) => json!({
+                    "tag": tag,
+                    "seller": a.seller.to_string(),
+                    "escrow": a.escrow.to_string(),
+                    "pool": a.pool.to_string(),
+                    "unlock_timestamp": a.unlock_timestamp,
+                    "quote_mint": a.quote_mint.to_string(),
+                    "quote_destination": a.quote_destination.to_string(),
+                    "price": a.price,
+                }),
+                ProtocolAccount::BoostConfig(a) => json!({
+                    "tag": tag,
+                    "central_state": a.central_state.to_string(),
+                    "max_lock_duration": a.max_lock_duration,
+                    "max_lock_boost_basis_points": a.max_lock_boost_basis_points,
+                    "forever_boost_basis_points": a.forever_boost_basis_points,
+                }),
+                ProtocolAccount::PoolBoost(a) => json!({
+                    "tag": tag,
+                    "stake_pool": a.stake_pool.to_string(),
+                    "total_boost": a.total_boost,
+                }),
+                ProtocolAccount::GovernanceConfig(a) => json!({
+                    "tag": tag,
+                    "central_state": a.central_state.to_string(),
+                    "realm": a.realm.to_string(),
+                    "governing_token_mint": a.governing_token_mint.to_string(),
+                    "lock_weighting": a.lock_weighting,
+                }),
+                ProtocolAccount::PoolControls(a) => json!({
+                    "tag": tag,
+                    "stake_pool": a.stake_pool.to_string(),
+                    "max_total_staked": a.max_total_staked,
+                    "paused": a.paused,
+                    "allowlist_enabled": a.allowlist_enabled,
+                }),
+                ProtocolAccount::PoolAllowlistEntry(a) => json!({
+                    "tag": tag,
+                    "stake_pool": a.stake_pool.to_string(),
+                    "staker": a.staker.to_string(),
+                }),
+                ProtocolAccount::RoleTable(a) => {
+                    let roles: Vec<Value> = a
+                        .roles
+                        .iter()
+                        .map(|r| json!({"ix": r.ix, "authority": r.authority.to_string()}))
+                        .collect();
+                    json!({
+                        "tag": tag,
+                        "central_state": a.central_state.to_string(),
+                        "admin": a.admin.to_string(),
+                        "roles": roles,
+                    })
+                }
+                ProtocolAccount::Deleted => json!({ "tag": tag }),
+            }
+        }
+
+        /// The account as pretty printed JSON
+        pub fn to_json_pretty(&self) -> String {
+            // Serializing a `Value` cannot fail
+            serde_json::to_string_pretty(&self.to_json()).unwrap()
+        }
+    }
+}
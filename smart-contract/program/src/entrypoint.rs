@@ -227,6 +227,58 @@ impl PrintProgramError for AccessError {
             AccessError::AccessCnftAuthorityMustSign => {
                 msg!("Access cnft authority must sign")
             }
+            AccessError::MintNotProvided => {
+                msg!("The mint account must be provided")
+            }
+            AccessError::UnsupportedTokenExtension => {
+                msg!("Unsupported token extension")
+            }
+            AccessError::WrongCentralState => {
+                msg!("The account does not belong to the central state")
+            }
+            AccessError::RewardIndexAlreadyEnabled => {
+                msg!("Error: The stake pool already uses the reward index")
+            }
+            AccessError::RewardIndexNotEnabled => {
+                msg!("Error: The stake pool does not use the reward index")
+            }
+            AccessError::RewardsBufferStillClaimable => {
+                msg!("Error: The rewards buffer of the stake pool can still be claimed")
+            }
+            AccessError::RewardCheckpointNotAllocated => {
+                msg!("Error: The account has no room for its reward checkpoint, allocate it first")
+            }
+            AccessError::UnsupportedAccountVersion => {
+                msg!("Error: The account layout version is not supported")
+            }
+            AccessError::BondV2DestinationNotEmpty => {
+                msg!("Error: The destination bond V2 account is not empty")
+            }
+            AccessError::WrongQuoteMint => msg!("Error: Wrong quote mint"),
+            AccessError::ListingPriceMismatch => {
+                msg!("Error: The price does not match the listing")
+            }
+            AccessError::AccountMigrationRequired => {
+                msg!("Error: The account must be migrated to its latest layout")
+            }
+            AccessError::BoostNotRefreshed => {
+                msg!("Error: The boost of the bond must be refreshed")
+            }
+            AccessError::WrongReceiptMint => msg!("Error: Wrong receipt mint"),
+            AccessError::StakePoolPaused => {
+                msg!("Error: The stake pool does not accept new stakes")
+            }
+            AccessError::StakePoolCapExceeded => msg!("Error: The stake pool cap is exceeded"),
+            AccessError::StakerNotAllowlisted => {
+                msg!("Error: The staker is not allowlisted by the stake pool")
+            }
+            AccessError::EmergencyExitNotOpen => {
+                msg!("Error: The program has not been frozen for long enough")
+            }
+            AccessError::NotAnAdminInstruction => {
+                msg!("Error: The instruction is not an admin instruction")
+            }
+            AccessError::EmissionCapExceeded => msg!("Error: The emission cap is exceeded"),
         }
     }
 }
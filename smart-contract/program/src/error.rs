@@ -134,6 +134,46 @@ pub enum AccessError {
     WrongAccessCnftAuthority,
     #[error("Access cnft authority must sign")]
     AccessCnftAuthorityMustSign,
+    #[error("The mint account must be provided")]
+    MintNotProvided,
+    #[error("Unsupported token extension")]
+    UnsupportedTokenExtension,
+    #[error("The account does not belong to the central state")]
+    WrongCentralState,
+    #[error("The stake pool already uses the reward index")]
+    RewardIndexAlreadyEnabled,
+    #[error("The stake pool does not use the reward index")]
+    RewardIndexNotEnabled,
+    #[error("The rewards buffer of the stake pool can still be claimed")]
+    RewardsBufferStillClaimable,
+    #[error("The account has no room for its reward checkpoint")]
+    RewardCheckpointNotAllocated,
+    #[error("The account layout version is not supported")]
+    UnsupportedAccountVersion,
+    #[error("The destination bond V2 account is not empty")]
+    BondV2DestinationNotEmpty,
+    #[error("Wrong quote mint")]
+    WrongQuoteMint,
+    #[error("The price does not match the listing")]
+    ListingPriceMismatch,
+    #[error("The account must be migrated to its latest layout")]
+    AccountMigrationRequired,
+    #[error("The boost of the bond must be refreshed")]
+    BoostNotRefreshed,
+    #[error("Wrong receipt mint")]
+    WrongReceiptMint,
+    #[error("The stake pool does not accept new stakes")]
+    StakePoolPaused,
+    #[error("The stake pool cap is exceeded")]
+    StakePoolCapExceeded,
+    #[error("The staker is not allowlisted by the stake pool")]
+    StakerNotAllowlisted,
+    #[error("The program has not been frozen for long enough")]
+    EmergencyExitNotOpen,
+    #[error("The instruction is not an admin instruction")]
+    NotAnAdminInstruction,
+    #[error("The emission cap is exceeded")]
+    EmissionCapExceeded,
 }
 
 impl From<AccessError> for ProgramError {
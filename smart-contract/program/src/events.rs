@@ -0,0 +1,333 @@
+//! Events emitted by the processors
+//!
+//! Each event is Borsh encoded and logged with `sol_log_data`, which the runtime prints as a
+//! single `Program data: <base64>` line. Use [`Event::from_logs`] to decode the events of a
+//! transaction, only the lines logged by the program are decoded.
+use std::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{entrypoint::ProgramResult, log::sol_log_data, pubkey::Pubkey};
+
+use crate::state::FeeRecipient;
+
+/// The prefix of the log lines written by `sol_log_data`
+pub const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+#[allow(missing_docs)]
+// The order must be kept! Add the new events to the end
+pub enum Event {
+    Stake {
+        central_state: Pubkey,
+        stake_pool: Pubkey,
+        stake_account: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+        fee: u64,
+    },
+    Unstake {
+        central_state: Pubkey,
+        stake_pool: Pubkey,
+        stake_account: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+    },
+    /// `account` is the stake account, the bond account or the stake pool depending on `kind`
+    Claim {
+        central_state: Pubkey,
+        stake_pool: Pubkey,
+        kind: ClaimKind,
+        account: Pubkey,
+        owner: Pubkey,
+        destination: Pubkey,
+        reward: u64,
+    },
+    RoyaltyPaid {
+        central_state: Pubkey,
+        royalty_payer: Pubkey,
+        recipient_ata: Pubkey,
+        amount: u64,
+    },
+    /// The FP32 rewards written to the buffer of the pool for `day`
+    Crank {
+        central_state: Pubkey,
+        stake_pool: Pubkey,
+        day: u64,
+        pool_reward: u128,
+        stakers_reward: u128,
+        total_claimable: u128,
+    },
+    BondCreated {
+        central_state: Pubkey,
+        stake_pool: Pubkey,
+        bond: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+        unlock_timestamp: Option<i64>,
+    },
+    BondLocked {
+        central_state: Pubkey,
+        stake_pool: Pubkey,
+        bond: Pubkey,
+        amount: u64,
+        fee: u64,
+    },
+    BondUnlocked {
+        central_state: Pubkey,
+        stake_pool: Pubkey,
+        bond: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+    },
+    /// `amount` is split between the recipients, the keeper and the burn
+    FeesDistributed {
+        central_state: Pubkey,
+        amount: u64,
+        recipients: Vec<(Pubkey, u64)>,
+        keeper_reward: u64,
+        burned: u64,
+    },
+    Admin {
+        central_state: Pubkey,
+        authority: Pubkey,
+        change: AdminChange,
+    },
+    /// `bond` was closed and its position moved to `new_bond` of `new_owner`, a listing owns
+    /// the bonds it escrows
+    BondTransferred {
+        central_state: Pubkey,
+        stake_pool: Pubkey,
+        bond: Pubkey,
+        new_bond: Pubkey,
+        owner: Pubkey,
+        new_owner: Pubkey,
+        amount: u64,
+    },
+    BondListed {
+        central_state: Pubkey,
+        stake_pool: Pubkey,
+        listing: Pubkey,
+        seller: Pubkey,
+        quote_mint: Pubkey,
+        price: u64,
+    },
+    BondSold {
+        central_state: Pubkey,
+        stake_pool: Pubkey,
+        listing: Pubkey,
+        seller: Pubkey,
+        buyer: Pubkey,
+        quote_mint: Pubkey,
+        price: u64,
+    },
+    BondListingCancelled {
+        central_state: Pubkey,
+        stake_pool: Pubkey,
+        listing: Pubkey,
+        seller: Pubkey,
+    },
+    /// `settled_rewards` are the rewards earned with the previous boost, paid by the next claim
+    BondBoostRefreshed {
+        central_state: Pubkey,
+        stake_pool: Pubkey,
+        bond: Pubkey,
+        previous_boost: u64,
+        boost: u64,
+        settled_rewards: u64,
+    },
+    ReceiptMintCreated {
+        central_state: Pubkey,
+        stake_pool: Pubkey,
+        receipt_mint: Pubkey,
+    },
+    /// `reserve_amount` is the premium paid to the reserve when minting and the share of the
+    /// reserve paid out when redeeming
+    ReceiptMinted {
+        central_state: Pubkey,
+        stake_pool: Pubkey,
+        receipt_account: Pubkey,
+        amount: u64,
+        reserve_amount: u64,
+    },
+    ReceiptRedeemed {
+        central_state: Pubkey,
+        stake_pool: Pubkey,
+        receipt_account: Pubkey,
+        amount: u64,
+        reserve_amount: u64,
+    },
+    VoterWeightUpdated {
+        central_state: Pubkey,
+        voter: Pubkey,
+        voter_weight: u64,
+    },
+    MaxVoterWeightUpdated {
+        central_state: Pubkey,
+        max_voter_weight: u64,
+    },
+    /// Emitted by `admin_program_freeze` when it starts a new freeze of the unstakes or the unlocks
+    ProgramFreezeRecorded {
+        central_state: Pubkey,
+        frozen_since: i64,
+    },
+    /// `account` is the stake account or the bond V2 account
+    EmergencyWithdrawal {
+        central_state: Pubkey,
+        stake_pool: Pubkey,
+        account: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+    },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum ClaimKind {
+    StakeAccount,
+    StakePool,
+    Bond,
+    BondV2,
+    /// Rewards of the stake account of the receipt holders of a pool, minted to its reserve
+    Receipt,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+#[allow(missing_docs)]
+// The order must be kept! Add the new changes to the end
+pub enum AdminChange {
+    Inflation {
+        daily_inflation: u64,
+    },
+    PoolMinimum {
+        stake_pool: Pubkey,
+        minimum_stake_amount: u64,
+    },
+    PoolMultiplier {
+        stake_pool: Pubkey,
+        stakers_part: u64,
+    },
+    ProtocolFee {
+        fee_basis_points: u16,
+    },
+    FeeSplit {
+        recipients: Vec<FeeRecipient>,
+    },
+    Authority {
+        new_authority: Pubkey,
+    },
+    FreezeAuthority {
+        new_freeze_authority: Pubkey,
+    },
+    ProgramFreeze {
+        ix_gate: u128,
+    },
+    AccountFreeze {
+        account: Pubkey,
+        frozen: bool,
+    },
+    /// `ix` is the index of the renounced `ProgramInstruction`
+    Renounce {
+        ix: u8,
+    },
+    Mint {
+        destination: Pubkey,
+        amount: u64,
+    },
+    /// `fee_basis_points` is `None` when the override is removed
+    PoolFeeOverride {
+        stake_pool: Pubkey,
+        fee_basis_points: Option<u16>,
+    },
+    KeeperReward {
+        keeper_reward_basis_points: u16,
+    },
+    ProtocolConfig {
+        access_nft_program_signer: Pubkey,
+    },
+    RewardIndex {
+        stake_pool: Pubkey,
+        start_day_idx: u16,
+    },
+    BoostConfig {
+        max_lock_duration: i64,
+        max_lock_boost_basis_points: u16,
+        forever_boost_basis_points: u16,
+    },
+    GovernanceConfig {
+        realm: Pubkey,
+        governing_token_mint: Pubkey,
+        lock_weighting: bool,
+    },
+    PoolControls {
+        stake_pool: Pubkey,
+        max_total_staked: u64,
+        paused: bool,
+        allowlist_enabled: bool,
+    },
+    PoolAllowlistEntry {
+        stake_pool: Pubkey,
+        staker: Pubkey,
+        allowed: bool,
+    },
+    /// `authority` is the admin of the role table when the role is removed
+    AdminRole {
+        ix: u8,
+        authority: Pubkey,
+    },
+    /// `emission_cap` is 0 when the cap is removed
+    EmissionCap {
+        emission_cap: u64,
+    },
+}
+
+impl Event {
+    /// Logs the event as a single `Program data:` line
+    pub fn emit(&self) -> ProgramResult {
+        sol_log_data(&[&self.try_to_vec()?]);
+        Ok(())
+    }
+
+    // Decodes one `Program data:` log line, returns `None` for any other line
+    fn from_log(log: &str) -> Option<Self> {
+        use base64::Engine;
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(log.strip_prefix(PROGRAM_DATA_PREFIX)?)
+            .ok()?;
+        Self::try_from_slice(&data).ok()
+    }
+
+    /// Decodes the events logged by `program_id` in the logs of a transaction
+    ///
+    /// The `Program data:` lines are attributed to the program being executed, tracked with the
+    /// `invoke` and `success` / `failed` lines of the runtime. The lines logged by the other
+    /// programs of the transaction, including the ones invoked by `program_id`, are skipped.
+    pub fn from_logs<S: AsRef<str>>(program_id: &Pubkey, logs: &[S]) -> Vec<Self> {
+        let mut call_stack = Vec::new();
+        let mut events = Vec::new();
+        for log in logs.iter().map(|log| log.as_ref()) {
+            if log.starts_with(PROGRAM_DATA_PREFIX) {
+                if call_stack.last() == Some(program_id) {
+                    events.extend(Self::from_log(log));
+                }
+                continue;
+            }
+            let (key, status) = match log
+                .strip_prefix("Program ")
+                .and_then(|log| log.split_once(' '))
+            {
+                Some(line) => line,
+                None => continue,
+            };
+            let key = match Pubkey::from_str(key) {
+                Ok(key) => key,
+                Err(_) => continue,
+            };
+            if status.starts_with("invoke [") {
+                call_stack.push(key);
+            } else if status == "success" || status.starts_with("failed") {
+                call_stack.pop();
+            }
+        }
+        events
+    }
+}
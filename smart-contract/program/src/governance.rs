@@ -0,0 +1,145 @@
+//! Voter weight addin accounts of SPL Governance
+//!
+//! The layouts follow the addin interface of SPL Governance (`spl-governance-addin-api`): the
+//! accounts start with an 8 bytes discriminator instead of a [`Tag`][`crate::state::Tag`] and are
+//! decoded with [`VoterWeightRecord::from_account_info`] and [`MaxVoterWeightRecord::from_account_info`].
+//! The expiry of both records is the slot of their update, the realm only accepts them in the
+//! transaction updating them.
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo, clock::Slot, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey,
+};
+
+use crate::error::AccessError;
+
+/// The action a voter weight is computed for, `None` for any action
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum VoterWeightAction {
+    CastVote,
+    CommentProposal,
+    CreateGovernance,
+    CreateProposal,
+    SignOffProposal,
+}
+
+/// The voting power of a voter, the ACS staked in the stake accounts and locked in the bonds V2
+/// of the voter
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub struct VoterWeightRecord {
+    pub account_discriminator: [u8; 8],
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    /// The voter
+    pub governing_token_owner: Pubkey,
+    pub voter_weight: u64,
+    pub voter_weight_expiry: Option<Slot>,
+    pub weight_action: Option<VoterWeightAction>,
+    pub weight_action_target: Option<Pubkey>,
+    pub reserved: [u8; 8],
+}
+
+#[allow(missing_docs)]
+impl VoterWeightRecord {
+    /// `sha256("account:VoterWeightRecord")[..8]`
+    pub const ACCOUNT_DISCRIMINATOR: [u8; 8] = [46, 249, 155, 75, 153, 248, 116, 9];
+    pub const SEED: &'static [u8; 19] = b"voter_weight_record";
+    /// The size of the account, with all the options set
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 9 + 2 + 33 + 8;
+
+    pub fn create_key(central_state: &Pubkey, voter: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[
+            VoterWeightRecord::SEED,
+            &central_state.to_bytes(),
+            &voter.to_bytes(),
+        ];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn new(realm: Pubkey, governing_token_mint: Pubkey, voter: Pubkey) -> Self {
+        Self {
+            account_discriminator: VoterWeightRecord::ACCOUNT_DISCRIMINATOR,
+            realm,
+            governing_token_mint,
+            governing_token_owner: voter,
+            voter_weight: 0,
+            voter_weight_expiry: None,
+            weight_action: None,
+            weight_action_target: None,
+            reserved: [0; 8],
+        }
+    }
+
+    /// The options are variable sized, the account is cleared before writing the record
+    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
+        dst.fill(0);
+        self.serialize(&mut dst)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<VoterWeightRecord, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data.get(..8) != Some(&VoterWeightRecord::ACCOUNT_DISCRIMINATOR[..]) {
+            msg!("Invalid voter weight record");
+            return Err(AccessError::DataTypeMismatch.into());
+        }
+        let result = VoterWeightRecord::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
+
+/// The maximum voting power of the realm, the total staked in the central state
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub struct MaxVoterWeightRecord {
+    pub account_discriminator: [u8; 8],
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub max_voter_weight: u64,
+    pub max_voter_weight_expiry: Option<Slot>,
+    pub reserved: [u8; 8],
+}
+
+#[allow(missing_docs)]
+impl MaxVoterWeightRecord {
+    /// `sha256("account:MaxVoterWeightRecord")[..8]`
+    pub const ACCOUNT_DISCRIMINATOR: [u8; 8] = [157, 95, 242, 151, 16, 98, 26, 118];
+    pub const SEED: &'static [u8; 23] = b"max_voter_weight_record";
+    /// The size of the account, with all the options set
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 9 + 8;
+
+    pub fn create_key(central_state: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[MaxVoterWeightRecord::SEED, &central_state.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn new(realm: Pubkey, governing_token_mint: Pubkey) -> Self {
+        Self {
+            account_discriminator: MaxVoterWeightRecord::ACCOUNT_DISCRIMINATOR,
+            realm,
+            governing_token_mint,
+            max_voter_weight: 0,
+            max_voter_weight_expiry: None,
+            reserved: [0; 8],
+        }
+    }
+
+    /// The options are variable sized, the account is cleared before writing the record
+    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
+        dst.fill(0);
+        self.serialize(&mut dst)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<MaxVoterWeightRecord, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data.get(..8) != Some(&MaxVoterWeightRecord::ACCOUNT_DISCRIMINATOR[..]) {
+            msg!("Invalid max voter weight record");
+            return Err(AccessError::DataTypeMismatch.into());
+        }
+        let result = MaxVoterWeightRecord::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
@@ -5,19 +5,27 @@ use solana_program::{instruction::Instruction, pubkey::Pubkey};
 
 pub use crate::processor::{
     activate_stake_pool, add_to_bond_v2, admin_change_freeze_authority, admin_freeze, admin_mint,
-    admin_program_freeze, admin_renounce, admin_set_protocol_fee, admin_setup_fee_split,
-    change_central_state_authority, change_inflation, change_pool_minimum, change_pool_multiplier,
-    claim_bond, claim_bond_rewards, claim_bond_v2_rewards, claim_pool_rewards, claim_rewards,
-    close_royalty_account, close_stake_account, close_stake_pool, crank, create_bond,
-    create_bond_v2, create_central_state, create_royalty_account, create_stake_account,
-    create_stake_pool, distribute_fees, edit_metadata, migrate_central_state_v2, sign_bond, stake,
-    unlock_bond_tokens, unlock_bond_v2, unstake,
+    admin_program_freeze, admin_renounce, admin_set_boost_config, admin_set_emission_cap,
+    admin_set_governance_config, admin_set_keeper_reward, admin_set_pool_fee_override,
+    admin_set_protocol_config, admin_set_protocol_fee, admin_set_role, admin_setup_fee_split,
+    buy_bond_v2, cancel_bond_v2_listing, change_central_state_authority, change_inflation,
+    change_pool_controls, change_pool_minimum, change_pool_multiplier, claim_bond,
+    claim_bond_rewards, claim_bond_v2_rewards, claim_pool_rewards, claim_receipt_rewards,
+    claim_rewards, close_royalty_account, close_stake_account, close_stake_pool, compact_stake_pool,
+    crank, create_bond, create_bond_v2, create_central_state, create_receipt_mint,
+    create_royalty_account, create_stake_account, create_stake_pool, distribute_fees, edit_metadata,
+    emergency_withdraw, enable_reward_index, get_central_state_info, get_claimable_rewards,
+    get_pool_apr, get_stake_summary, list_bond_v2, migrate_account, migrate_central_state_v2,
+    refresh_bond_v2_boost, set_pool_allowlist_entry, sign_bond, stake, transfer_bond_v2,
+    unlock_bond_tokens, unlock_bond_v2, unstake, update_max_voter_weight_record,
+    update_voter_weight_record,
 };
 
 #[allow(missing_docs)]
-#[derive(BorshDeserialize, BorshSerialize, BorshSize, FromPrimitive, Copy, Clone)]
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, FromPrimitive, Copy, Clone, Debug)]
 pub enum ProgramInstruction {
     /// Create central state
+    /// The original central state is derived from the program id, tenants are derived from the program id and their mint
     ///
     /// | Index | Writable | Signer | Description                  |
     /// | -------------------------------------------------------- |
@@ -25,6 +33,7 @@ pub enum ProgramInstruction {
     /// | 1     | ❌        | ❌      | The system program account   |
     /// | 2     | ✅        | ✅      | The fee payer account        |
     /// | 3     | ❌        | ❌      | The mint of the ACCESS token |
+    /// | 4     | ✅        | ❌      | The protocol config account  |
     CreateCentralState,
     /// Create stake pool
     ///
@@ -46,38 +55,53 @@ pub enum ProgramInstruction {
     ActivateStakePool,
     /// Create stake account
     ///
-    /// | Index | Writable | Signer | Description                |
-    /// | ------------------------------------------------------ |
-    /// | 0     | ✅        | ❌      | The stake account          |
-    /// | 1     | ❌        | ❌      | The system program account |
-    /// | 2     | ❌        | ❌      | The stake pool account     |
-    /// | 3     | ✅        | ✅      | The fee payer account      |
-    /// | 4     | ❌        | ❌      | The central state account  |
+    /// | Index | Writable | Signer | Description                                                                          |
+    /// | ---------------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The stake account                                                                    |
+    /// | 1     | ❌        | ❌      | The system program account                                                           |
+    /// | 2     | ❌        | ❌      | The stake pool account                                                               |
+    /// | 3     | ✅        | ✅      | The fee payer account                                                                |
+    /// | 4     | ❌        | ❌      | The central state account                                                            |
+    /// | 5     | ❌        | ❌      | The pool controls account, required once the pool has controls                       |
+    /// | 6     | ❌        | ❌      | The allowlist entry of the owner, required once the allowlist of the pool is enabled |
     CreateStakeAccount,
     /// Stake
     ///
-    /// | Index | Writable | Signer | Description                                             |
-    /// | ----------------------------------------------------------------------------------- |
-    /// | 0     | ✅        | ❌      | The central state account                               |
-    /// | 1     | ✅        | ❌      | The destination stake account -  can be owned by anyone |
-    /// | 2     | ✅        | ❌      | The stake pool account                                  |
-    /// | 3     | ❌        | ✅      | The owner of the token account - staker                 |
-    /// | 4     | ✅        | ❌      | The source account of the stake tokens                  |
-    /// | 5     | ❌        | ❌      | The SPL token program account                           |
-    /// | 6     | ✅        | ❌      | The stake pool vault account                            |
-    /// | 7     | ✅        | ❌      | The central state ATA                                   |
+    /// | Index      | Writable | Signer | Description                                                                                                                |
+    /// | ----------------------------------------------------------------------------------------------------------------------------------------------------------- |
+    /// | 0          | ✅        | ❌      | The central state account                                                                                                  |
+    /// | 1          | ✅        | ❌      | The destination stake account -  can be owned by anyone                                                                    |
+    /// | 2          | ✅        | ❌      | The stake pool account                                                                                                     |
+    /// | 3          | ❌        | ✅      | The owner of the token account - staker                                                                                    |
+    /// | 4          | ✅        | ❌      | The source account of the stake tokens                                                                                     |
+    /// | 5          | ❌        | ❌      | The SPL token program account                                                                                              |
+    /// | 6          | ✅        | ❌      | The stake pool vault account                                                                                               |
+    /// | 7          | ✅        | ❌      | The central state ATA                                                                                                      |
+    /// | 8          | ❌        | ❌      | The optional fee override account of the stake pool                                                                        |
+    /// | 9          | ❌        | ❌      | The mint of the ACS token                                                                                                  |
+    /// | 10         | ❌        | ❌      | The pool controls account, required once the pool has controls                                                             |
+    /// | 11         | ❌        | ❌      | The allowlist entry of the stake account owner or of the receipt buyer, required once the allowlist of the pool is enabled |
+    /// | 12         | ✅        | ❌      | The optional receipt mint of the stake pool, to stake for the receipt holders                                              |
+    /// | 13         | ✅        | ❌      | The receipt token account receiving the minted receipts                                                                    |
+    /// | 14         | ✅        | ❌      | The receipt reserve, receives the premium of the accrued rewards paid on top of the stake                                  |
+    /// | 15..15 + M | ❌        | ❌      | (Optional) The accounts of the transfer hook of the mint, skipped optional accounts are set to the program id              |
     Stake,
     /// Unstake
     ///
-    /// | Index | Writable | Signer | Description                          |
-    /// | ---------------------------------------------------------------- |
-    /// | 0     | ✅        | ❌      | The central state account            |
-    /// | 1     | ✅        | ❌      | The stake account                    |
-    /// | 2     | ✅        | ❌      | The stake pool account               |
-    /// | 3     | ❌        | ✅      | The owner of the stake account       |
-    /// | 4     | ✅        | ❌      | The destination of the staked tokens |
-    /// | 5     | ❌        | ❌      | The SPL token program account        |
-    /// | 6     | ✅        | ❌      | The stake pool vault                 |
+    /// | Index      | Writable | Signer | Description                                                                                                   |
+    /// | ---------------------------------------------------------------------------------------------------------------------------------------------- |
+    /// | 0          | ✅        | ❌      | The central state account                                                                                     |
+    /// | 1          | ✅        | ❌      | The stake account                                                                                             |
+    /// | 2          | ✅        | ❌      | The stake pool account                                                                                        |
+    /// | 3          | ❌        | ✅      | The owner of the stake account                                                                                |
+    /// | 4          | ✅        | ❌      | The destination of the staked tokens                                                                          |
+    /// | 5          | ❌        | ❌      | The SPL token program account                                                                                 |
+    /// | 6          | ✅        | ❌      | The stake pool vault                                                                                          |
+    /// | 7          | ❌        | ❌      | The mint of the ACS token                                                                                     |
+    /// | 8          | ✅        | ❌      | The optional receipt mint of the stake pool, to redeem receipts                                               |
+    /// | 9          | ✅        | ❌      | The receipt token account of the owner, the receipts are burnt from it                                        |
+    /// | 10         | ✅        | ❌      | The receipt reserve, pays out the share of the accrued rewards                                                |
+    /// | 11..11 + M | ❌        | ❌      | (Optional) The accounts of the transfer hook of the mint, skipped optional accounts are set to the program id |
     Unstake,
     /// Claim rewards of a stake pool
     /// This instruction is used by stake pool owner for claiming their staking rewards
@@ -87,7 +111,7 @@ pub enum ProgramInstruction {
     /// | 0     | ✅        | ❌      | The stake pool account                                                  |
     /// | 1     | ❌        | ✅      | The stake pool owner account                                            |
     /// | 2     | ✅        | ❌      | The rewards destination                                                 |
-    /// | 3     | ❌        | ❌      | The central state account                                               |
+    /// | 3     | ✅        | ❌      | The central state account                                               |
     /// | 4     | ✅        | ❌      | The mint address of the ACCESS token                                    |
     /// | 5     | ❌        | ❌      | The SPL token program account                                           |
     /// | 6     | ❌        | ❌      | The owner's royalty split account to check if royalties need to be paid |
@@ -102,20 +126,22 @@ pub enum ProgramInstruction {
     /// | 1     | ✅        | ❌      | The stake account                                                       |
     /// | 2     | ❌        | ✅      | The owner of the Stake account                                          |
     /// | 3     | ✅        | ❌      | The rewards destination                                                 |
-    /// | 4     | ❌        | ❌      | The central state account                                               |
+    /// | 4     | ✅        | ❌      | The central state account                                               |
     /// | 5     | ✅        | ❌      | The mint address of the ACS token                                       |
     /// | 6     | ❌        | ❌      | The Access NFT program signer - to handle different royalty account     |
     /// | 7     | ❌        | ❌      | The SPL token program account                                           |
     /// | 8     | ❌        | ❌      | The owner's royalty split account to check if royalties need to be paid |
     /// | 9     | ✅        | ❌      | The royalty ATA account                                                 |
+    /// | 10    | ❌        | ❌      | The protocol config account, required when the Access NFT program signs |
     ClaimRewards,
     /// Permissionless crank to update the stake pool rewards
     /// This instructions updates the circular buffer with the pool balances multiplied by the current inflation
     ///
-    /// | Index | Writable | Signer | Description               |
-    /// | ----------------------------------------------------- |
-    /// | 0     | ✅        | ❌      | The stake pool account    |
-    /// | 1     | ✅        | ❌      | The central state account |
+    /// | Index | Writable | Signer | Description                                                      |
+    /// | -------------------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The stake pool account                                           |
+    /// | 1     | ✅        | ❌      | The central state account                                        |
+    /// | 2     | ❌        | ❌      | The pool boost account, required once the pool has boosted bonds |
     Crank,
     /// Close a stake pool
     /// This instruction can be used to close an empty stake pool and collect the lamports
@@ -138,11 +164,12 @@ pub enum ProgramInstruction {
     CloseStakeAccount,
     /// Change central state inflation
     ///
-    /// | Index | Writable | Signer | Description                          |
-    /// | ---------------------------------------------------------------- |
-    /// | 0     | ✅        | ❌      | The central state account            |
-    /// | 1     | ❌        | ✅      | The central state account authority  |
-    /// | 2     | ❌        | ❌      | The mint address of the ACCESS token |
+    /// | Index | Writable | Signer | Description                                                 |
+    /// | --------------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The central state account                                   |
+    /// | 1     | ❌        | ✅      | The central state account authority                         |
+    /// | 2     | ❌        | ❌      | The mint address of the ACCESS token                        |
+    /// | 3     | ❌        | ❌      | The role table account, required once the roles are enabled |
     ChangeInflation,
     /// Create a bond
     /// This instruction can be used by authorized sellers to create a bond
@@ -182,17 +209,18 @@ pub enum ProgramInstruction {
     /// Claim bond
     /// This instruction allows a buyer to claim a bond once it has been signed by enough DAO members.
     ///
-    /// | Index | Writable | Signer | Description                                      |
-    /// | ---------------------------------------------------------------------------- |
-    /// | 0     | ✅        | ❌      | The bond account                                 |
-    /// | 1     | ❌        | ✅      | The account of the bond buyer                    |
-    /// | 2     | ✅        | ❌      | The token account used to purchase the bond      |
-    /// | 3     | ✅        | ❌      | The token account where the sell proceed is sent |
-    /// | 4     | ✅        | ❌      | The stake pool account                           |
-    /// | 5     | ✅        | ❌      | The mint of the ACCESS token                     |
-    /// | 6     | ✅        | ❌      | The vault of the stake pool                      |
-    /// | 7     | ✅        | ❌      | The central state account                        |
-    /// | 8     | ❌        | ❌      | The SPL token program account                    |
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The bond account                                                       |
+    /// | 1     | ❌        | ✅      | The account of the bond buyer                                          |
+    /// | 2     | ✅        | ❌      | The token account used to purchase the bond                            |
+    /// | 3     | ✅        | ❌      | The token account where the sell proceed is sent                       |
+    /// | 4     | ✅        | ❌      | The stake pool account                                                 |
+    /// | 5     | ✅        | ❌      | The mint of the ACCESS token                                           |
+    /// | 6     | ✅        | ❌      | The vault of the stake pool                                            |
+    /// | 7     | ✅        | ❌      | The central state account                                              |
+    /// | 8     | ❌        | ❌      | The SPL token program account                                          |
+    /// | 9     | ❌        | ❌      | The mint of the quote token, required when the bond has a quote amount |
     ClaimBond,
     /// Claim bond rewards
     /// This Instruction allows bond owners to claim their staking rewards
@@ -203,7 +231,7 @@ pub enum ProgramInstruction {
     /// | 1     | ✅        | ❌      | The bond account                     |
     /// | 2     | ❌        | ✅      | The bond account owner               |
     /// | 3     | ✅        | ❌      | The rewards destination              |
-    /// | 4     | ❌        | ❌      | The central state account            |
+    /// | 4     | ✅        | ❌      | The central state account            |
     /// | 5     | ✅        | ❌      | The mint address of the ACCESS token |
     /// | 6     | ❌        | ❌      | The SPL token program account        |
     ClaimBondRewards,
@@ -218,22 +246,24 @@ pub enum ProgramInstruction {
     ChangePoolMinimum,
     /// Allows central state authority to mint ACCESS tokens
     ///
-    /// | Index | Writable | Signer | Description                   |
-    /// | --------------------------------------------------------- |
-    /// | 0     | ❌        | ✅      | The central state authority   |
-    /// | 1     | ✅        | ❌      | The ACCESS mint token         |
-    /// | 2     | ✅        | ❌      | The ACCESS token destination  |
-    /// | 3     | ❌        | ❌      | The central state account     |
-    /// | 4     | ❌        | ❌      | The SPL token program account |
+    /// | Index | Writable | Signer | Description                                                 |
+    /// | --------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The central state authority                                 |
+    /// | 1     | ✅        | ❌      | The ACCESS mint token                                       |
+    /// | 2     | ✅        | ❌      | The ACCESS token destination                                |
+    /// | 3     | ✅        | ❌      | The central state account                                   |
+    /// | 4     | ❌        | ❌      | The SPL token program account                               |
+    /// | 5     | ❌        | ❌      | The role table account, required once the roles are enabled |
     AdminMint,
     /// Freeze and unfreeze a program account
     /// This admin instruction can be dangereous 💀
     ///
-    /// | Index | Writable | Signer | Description                         |
-    /// | --------------------------------------------------------------- |
-    /// | 0     | ❌        | ✅      | The central state authority         |
-    /// | 1     | ✅        | ❌      | The account to freeze (or unfreeze) |
-    /// | 2     | ❌        | ❌      | The central state account           |
+    /// | Index | Writable | Signer | Description                                                 |
+    /// | --------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The central state authority                                 |
+    /// | 1     | ✅        | ❌      | The account to freeze (or unfreeze)                         |
+    /// | 2     | ❌        | ❌      | The central state account                                   |
+    /// | 3     | ❌        | ❌      | The role table account, required once the roles are enabled |
     AdminFreeze,
     ///
     /// | Index | Writable | Signer | Description                  |
@@ -244,19 +274,21 @@ pub enum ProgramInstruction {
     ChangePoolMultiplier,
     /// Change central state authority
     ///
-    /// | Index | Writable | Signer | Description                         |
-    /// | --------------------------------------------------------------- |
-    /// | 0     | ✅        | ❌      | The central state account           |
-    /// | 1     | ❌        | ✅      | The central state account authority |
+    /// | Index | Writable | Signer | Description                                                 |
+    /// | --------------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The central state account                                   |
+    /// | 1     | ❌        | ✅      | The central state account authority                         |
+    /// | 2     | ✅        | ❌      | The role table account, required once the roles are enabled |
     ChangeCentralStateAuthority,
     /// Edit metadata
     ///
-    /// | Index | Writable | Signer | Description                         |
-    /// | --------------------------------------------------------------- |
-    /// | 0     | ❌        | ❌      | The central state account           |
-    /// | 1     | ❌        | ✅      | The central state account authority |
-    /// | 2     | ✅        | ❌      | The metadata account                |
-    /// | 3     | ❌        | ❌      | The metadata program account        |
+    /// | Index | Writable | Signer | Description                                                 |
+    /// | --------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The central state account                                   |
+    /// | 1     | ❌        | ✅      | The central state account authority                         |
+    /// | 2     | ✅        | ❌      | The metadata account                                        |
+    /// | 3     | ❌        | ❌      | The metadata program account                                |
+    /// | 4     | ❌        | ❌      | The role table account, required once the roles are enabled |
     EditMetadata,
     /// Create a Bond V2
     ///
@@ -270,18 +302,24 @@ pub enum ProgramInstruction {
     CreateBondV2,
     /// Add more tokens to an existing Bond V2
     ///
-    /// | Index | Writable | Signer | Description                       |
-    /// | ------------------------------------------------------------- |
-    /// | 0     | ✅        | ✅      | The bond seller account           |
-    /// | 1     | ✅        | ❌      | From ATA                          |
-    /// | 2     | ✅        | ❌      | The bond account                  |
-    /// | 3     | ✅        | ❌      | Central state                     |
-    /// | 4     | ✅        | ❌      | The vault of the central state    |
-    /// | 5     | ✅        | ❌      | The pool account                  |
-    /// | 6     | ✅        | ❌      | The vault of the pool             |
-    /// | 7     | ✅        | ❌      | The mint address of the ACS token |
-    /// | 8     | ❌        | ❌      | The SPL token program account     |
-    /// | 9     | ❌        | ❌      | The system program account        |
+    /// | Index      | Writable | Signer | Description                                                                                                   |
+    /// | ---------------------------------------------------------------------------------------------------------------------------------------------- |
+    /// | 0          | ✅        | ✅      | The bond seller account                                                                                       |
+    /// | 1          | ✅        | ❌      | From ATA                                                                                                      |
+    /// | 2          | ✅        | ❌      | The bond account                                                                                              |
+    /// | 3          | ✅        | ❌      | Central state                                                                                                 |
+    /// | 4          | ✅        | ❌      | The vault of the central state                                                                                |
+    /// | 5          | ✅        | ❌      | The pool account                                                                                              |
+    /// | 6          | ✅        | ❌      | The vault of the pool                                                                                         |
+    /// | 7          | ✅        | ❌      | The mint address of the ACS token                                                                             |
+    /// | 8          | ❌        | ❌      | The SPL token program account                                                                                 |
+    /// | 9          | ❌        | ❌      | The system program account                                                                                    |
+    /// | 10         | ❌        | ❌      | The optional pool fee override                                                                                |
+    /// | 11         | ❌        | ❌      | The pool controls account, required once the pool has controls                                                |
+    /// | 12         | ❌        | ❌      | The allowlist entry of the bond owner, required once the allowlist of the pool is enabled                     |
+    /// | 13         | ❌        | ❌      | The boost config account, required for the boosted bonds V2                                                   |
+    /// | 14         | ✅        | ❌      | The pool boost account, required for the boosted bonds V2                                                     |
+    /// | 15..15 + M | ❌        | ❌      | (Optional) The accounts of the transfer hook of the mint, skipped optional accounts are set to the program id |
     AddToBondV2,
     /// Claim rewards of a bond V2  from the Access NFT Program
     ///
@@ -291,48 +329,59 @@ pub enum ProgramInstruction {
     /// | 1     | ✅        | ❌      | The Bond V2 account                                                     |
     /// | 2     | ❌        | ✅      | The owner of the Bond V2 account                                        |
     /// | 3     | ✅        | ❌      | The rewards destination                                                 |
-    /// | 4     | ❌        | ❌      | The central state account                                               |
+    /// | 4     | ✅        | ❌      | The central state account                                               |
     /// | 5     | ✅        | ❌      | The mint address of the ACS token                                       |
     /// | 6     | ❌        | ❌      | The Access NFT program signer - to handle different royalty account     |
     /// | 7     | ❌        | ❌      | The SPL token program account                                           |
     /// | 8     | ❌        | ❌      | The owner's royalty split account to check if royalties need to be paid |
     /// | 9     | ✅        | ❌      | The royalty ATA account                                                 |
+    /// | 10    | ❌        | ❌      | The protocol config account, required when the Access NFT program signs |
     ClaimBondV2Rewards,
     /// Unlock bond v2
     ///
-    /// | Index | Writable | Signer | Description                          |
-    /// | ---------------------------------------------------------------- |
-    /// | 0     | ✅        | ❌      | The central state account            |
-    /// | 1     | ✅        | ❌      | The bond account                     |
-    /// | 2     | ❌        | ✅      | The owner of the bond V2 account     |
-    /// | 3     | ✅        | ❌      | The destination of the locked tokens |
-    /// | 4     | ✅        | ❌      | The pool account                     |
-    /// | 5     | ✅        | ❌      | The pool vault                       |
-    /// | 6     | ❌        | ❌      | The SPL token program account        |
+    /// | Index    | Writable | Signer | Description                                                                                                   |
+    /// | -------------------------------------------------------------------------------------------------------------------------------------------- |
+    /// | 0        | ✅        | ❌      | The central state account                                                                                     |
+    /// | 1        | ✅        | ❌      | The bond account                                                                                              |
+    /// | 2        | ❌        | ✅      | The owner of the bond V2 account                                                                              |
+    /// | 3        | ✅        | ❌      | The destination of the locked tokens                                                                          |
+    /// | 4        | ✅        | ❌      | The pool account                                                                                              |
+    /// | 5        | ✅        | ❌      | The pool vault                                                                                                |
+    /// | 6        | ❌        | ❌      | The SPL token program account                                                                                 |
+    /// | 7        | ❌        | ❌      | The mint of the ACS token                                                                                     |
+    /// | 8        | ✅        | ❌      | The pool boost account, required for the boosted bonds V2                                                     |
+    /// | 9..9 + M | ❌        | ❌      | (Optional) The accounts of the transfer hook of the mint, skipped optional accounts are set to the program id |
     UnlockBondV2,
     /// Setup fee split
     ///
-    /// | Index | Writable | Signer | Description                 |
-    /// | ------------------------------------------------------- |
-    /// | 0     | ❌        | ✅      | The central state authority |
-    /// | 1     | ✅        | ❌      | The central state account   |
+    /// | Index | Writable | Signer | Description                                                 |
+    /// | --------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The central state authority                                 |
+    /// | 1     | ✅        | ❌      | The central state account                                   |
+    /// | 2     | ❌        | ❌      | The role table account, required once the roles are enabled |
     AdminSetupFeeSplit,
     /// Distribute fees to the recipients
     ///
-    /// | Index    | Writable | Signer | Description                                  |
-    /// | --------------------------------------------------------------------------- |
-    /// | 0        | ✅        | ❌      | The central state account                    |
-    /// | 1        | ✅        | ❌      | The central state ATA                        |
-    /// | 2        | ❌        | ❌      | The SPL token program account                |
-    /// | 3        | ✅        | ❌      | The mint address of the ACS token            |
-    /// | 4..4 + N | ✅        | ❌      | The token accounts to distribute the fees to |
+    /// The caller can optionally pass the fee distribution config and its own token account to receive the keeper reward
+    ///
+    /// | Index    | Writable | Signer | Description                                                                                                 |
+    /// | ------------------------------------------------------------------------------------------------------------------------------------------ |
+    /// | 0        | ✅        | ❌      | The central state account                                                                                   |
+    /// | 1        | ✅        | ❌      | The central state ATA                                                                                       |
+    /// | 2        | ❌        | ❌      | The SPL token program account                                                                               |
+    /// | 3        | ✅        | ❌      | The mint address of the ACS token                                                                           |
+    /// | 4..4 + N | ✅        | ❌      | The token accounts to distribute the fees to                                                                |
+    /// | 4 + N    | ✅        | ❌      | (Optional) The fee distribution config account                                                              |
+    /// | 5 + N    | ✅        | ❌      | (Optional) The keeper token account                                                                         |
+    /// | 6 + N..  | ❌        | ❌      | (Optional) The accounts of the transfer hook of the mint, skipped keeper accounts are set to the program id |
     DistributeFees,
     /// Admin set protocol fee
     ///
-    /// | Index | Writable | Signer | Description                 |
-    /// | ------------------------------------------------------- |
-    /// | 0     | ❌        | ✅      | The central state authority |
-    /// | 1     | ✅        | ❌      | The central state account   |
+    /// | Index | Writable | Signer | Description                                                 |
+    /// | --------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The central state authority                                 |
+    /// | 1     | ✅        | ❌      | The central state account                                   |
+    /// | 2     | ❌        | ❌      | The role table account, required once the roles are enabled |
     AdminSetProtocolFee,
     /// Migrate the central state to the v2 format
     ///
@@ -344,24 +393,29 @@ pub enum ProgramInstruction {
     MigrateCentralStateV2,
     /// Admin program freeze instruction.
     ///
-    /// | Index | Writable | Signer | Description                                             |
-    /// | ----------------------------------------------------------------------------------- |
-    /// | 0     | ✅        | ❌      | The central state account                               |
-    /// | 1     | ❌        | ✅      | The central state account authority or freeze authority |
+    /// | Index | Writable | Signer | Description                                                 |
+    /// | --------------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The central state account                                   |
+    /// | 1     | ❌        | ✅      | The central state account authority or freeze authority     |
+    /// | 2     | ✅        | ✅      | The fee payer account                                       |
+    /// | 3     | ❌        | ❌      | The system program account                                  |
+    /// | 4     | ❌        | ❌      | The role table account, required once the roles are enabled |
     AdminProgramFreeze,
     /// Admin renounce functionality
     ///
-    /// | Index | Writable | Signer | Description                         |
-    /// | --------------------------------------------------------------- |
-    /// | 0     | ✅        | ❌      | The central state account           |
-    /// | 1     | ❌        | ✅      | The central state account authority |
+    /// | Index | Writable | Signer | Description                                                 |
+    /// | --------------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The central state account                                   |
+    /// | 1     | ❌        | ✅      | The central state account authority                         |
+    /// | 2     | ❌        | ❌      | The role table account, required once the roles are enabled |
     AdminRenounce,
     /// Change freeze authority
     ///
-    /// | Index | Writable | Signer | Description                         |
-    /// | --------------------------------------------------------------- |
-    /// | 0     | ✅        | ❌      | The central state account           |
-    /// | 1     | ❌        | ✅      | The central state account authority |
+    /// | Index | Writable | Signer | Description                                                 |
+    /// | --------------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The central state account                                   |
+    /// | 1     | ❌        | ✅      | The central state account authority                         |
+    /// | 2     | ❌        | ❌      | The role table account, required once the roles are enabled |
     AdminChangeFreezeAuthority,
     /// Create royalty account
     ///
@@ -383,6 +437,310 @@ pub enum ProgramInstruction {
     /// | 2     | ✅        | ❌      | The account where the funds should be returned |
     /// | 3     | ❌        | ❌      | The central state account                      |
     CloseRoyaltyAccount,
+    /// Admin set pool fee override
+    /// This instruction can be used to set or remove a protocol fee override of a stake pool
+    ///
+    /// | Index | Writable | Signer | Description                                                 |
+    /// | --------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The central state authority                                 |
+    /// | 1     | ❌        | ❌      | The central state account                                   |
+    /// | 2     | ❌        | ❌      | The stake pool account                                      |
+    /// | 3     | ✅        | ❌      | The pool fee override account                               |
+    /// | 4     | ✅        | ✅      | The fee payer account                                       |
+    /// | 5     | ❌        | ❌      | The system program account                                  |
+    /// | 6     | ❌        | ❌      | The role table account, required once the roles are enabled |
+    AdminSetPoolFeeOverride,
+    /// Admin set keeper reward
+    /// This instruction can be used to set the share of the distributed fees paid to the caller of `distribute_fees`
+    ///
+    /// | Index | Writable | Signer | Description                                                 |
+    /// | --------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The central state authority                                 |
+    /// | 1     | ❌        | ❌      | The central state account                                   |
+    /// | 2     | ✅        | ❌      | The fee distribution config account                         |
+    /// | 3     | ✅        | ✅      | The fee payer account                                       |
+    /// | 4     | ❌        | ❌      | The system program account                                  |
+    /// | 5     | ❌        | ❌      | The role table account, required once the roles are enabled |
+    AdminSetKeeperReward,
+    /// Admin set protocol config
+    /// This instruction can be used to set the external keys trusted by the protocol
+    ///
+    /// | Index | Writable | Signer | Description                                                 |
+    /// | --------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The central state authority                                 |
+    /// | 1     | ❌        | ❌      | The central state account                                   |
+    /// | 2     | ✅        | ❌      | The protocol config account                                 |
+    /// | 3     | ✅        | ✅      | The fee payer account                                       |
+    /// | 4     | ❌        | ❌      | The system program account                                  |
+    /// | 5     | ❌        | ❌      | The role table account, required once the roles are enabled |
+    AdminSetProtocolConfig,
+    /// Get claimable rewards
+    /// Read-only, returns a `ClaimableRewardsView` with `set_return_data`
+    ///
+    /// | Index | Writable | Signer | Description                                             |
+    /// | ----------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The central state account                               |
+    /// | 1     | ❌        | ❌      | The stake pool account                                  |
+    /// | 2     | ❌        | ❌      | The stake account                                       |
+    /// | 3     | ❌        | ❌      | The optional royalty account of the stake account owner |
+    GetClaimableRewards,
+    /// Get pool APR
+    /// Read-only, returns a `PoolAprView` with `set_return_data`
+    ///
+    /// | Index | Writable | Signer | Description               |
+    /// | ----------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The central state account |
+    /// | 1     | ❌        | ❌      | The stake pool account    |
+    GetPoolApr,
+    /// Get stake summary
+    /// Read-only, returns a `StakeSummaryView` with `set_return_data`
+    ///
+    /// | Index    | Writable | Signer | Description                                   |
+    /// | ---------------------------------------------------------------------------- |
+    /// | 0        | ❌        | ❌      | The central state account                     |
+    /// | 1        | ❌        | ❌      | The stake pool account                        |
+    /// | 2        | ❌        | ❌      | The stake account of the owner                |
+    /// | 3..3 + N | ❌        | ❌      | The bond V2 accounts of the owner in the pool |
+    GetStakeSummary,
+    /// Get central state info
+    /// Read-only, returns a `CentralStateInfoView` with `set_return_data`
+    ///
+    /// | Index | Writable | Signer | Description               |
+    /// | ----------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The central state account |
+    GetCentralStateInfo,
+    /// Switch a stake pool from its rewards buffer to the cumulative reward index
+    ///
+    /// | Index | Writable | Signer | Description                 |
+    /// | ------------------------------------------------------- |
+    /// | 0     | ✅        | ✅      | The fee payer account       |
+    /// | 1     | ✅        | ❌      | The stake pool account      |
+    /// | 2     | ❌        | ✅      | The owner of the stake pool |
+    /// | 3     | ❌        | ❌      | The central state account   |
+    /// | 4     | ❌        | ❌      | The system program account  |
+    EnableRewardIndex,
+    /// Remove the rewards buffer of a stake pool using the reward index
+    ///
+    /// | Index | Writable | Signer | Description                                                          |
+    /// | ------------------------------------------------------------------------------------------------ |
+    /// | 0     | ✅        | ❌      | The stake pool account                                               |
+    /// | 1     | ✅        | ❌      | The owner of the stake pool, receives the rent of the rewards buffer |
+    /// | 2     | ❌        | ❌      | The central state account                                            |
+    CompactStakePool,
+    /// Rewrite a stake account, a bond V2 account or a central state with its latest layout
+    ///
+    /// | Index | Writable | Signer | Description                                                  |
+    /// | ---------------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ✅      | The fee payer account, pays the rent of the additional space |
+    /// | 1     | ✅        | ❌      | The account to migrate                                       |
+    /// | 2     | ❌        | ❌      | The system program account                                   |
+    MigrateAccount,
+    /// Transfer a bond V2 to the bond V2 account of a new owner, the rewards must be claimed first
+    ///
+    /// | Index | Writable | Signer | Description                                                         |
+    /// | ----------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The central state account                                           |
+    /// | 1     | ✅        | ❌      | The bond V2 account, closed by the transfer                         |
+    /// | 2     | ✅        | ❌      | The bond V2 account of the new owner                                |
+    /// | 3     | ✅        | ✅      | The owner of the bond, receives the rent of the closed bond account |
+    /// | 4     | ❌        | ❌      | The pool account                                                    |
+    /// | 5     | ✅        | ✅      | The fee payer account, pays the rent of the new bond account        |
+    /// | 6     | ❌        | ❌      | The system program account                                          |
+    TransferBondV2,
+    /// List a bond V2 for a price in a quote mint, the bond is escrowed by the listing
+    ///
+    /// | Index | Writable | Signer | Description                                                        |
+    /// | ---------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The central state account                                          |
+    /// | 1     | ✅        | ❌      | The bond V2 account of the seller, closed by the listing           |
+    /// | 2     | ✅        | ❌      | The listing account                                                |
+    /// | 3     | ✅        | ❌      | The bond V2 account of the listing escrowing the bond              |
+    /// | 4     | ✅        | ✅      | The owner of the bond                                              |
+    /// | 5     | ❌        | ❌      | The mint in which the price is paid                                |
+    /// | 6     | ❌        | ❌      | The token account of the seller receiving the price                |
+    /// | 7     | ❌        | ❌      | The pool account                                                   |
+    /// | 8     | ✅        | ✅      | The fee payer account, pays the rent of the listing and the escrow |
+    /// | 9     | ❌        | ❌      | The system program account                                         |
+    ListBondV2,
+    /// Cancel the listing of a bond V2 and give the escrowed bond back to the seller
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The central state account                                              |
+    /// | 1     | ✅        | ❌      | The listing account                                                    |
+    /// | 2     | ✅        | ❌      | The bond V2 account of the listing escrowing the bond                  |
+    /// | 3     | ✅        | ❌      | The bond V2 account of the seller                                      |
+    /// | 4     | ✅        | ✅      | The seller, receives the rent of the listing and the escrow            |
+    /// | 5     | ❌        | ❌      | The pool account                                                       |
+    /// | 6     | ✅        | ✅      | The fee payer account, pays the rent of the bond account of the seller |
+    /// | 7     | ❌        | ❌      | The system program account                                             |
+    CancelBondV2Listing,
+    /// Pay the price of a listing to the seller and receive the escrowed bond V2, the rewards earned
+    /// while listed are minted to the seller
+    ///
+    /// | Index      | Writable | Signer | Description                                                                           |
+    /// | ---------------------------------------------------------------------------------------------------------------------- |
+    /// | 0          | ✅        | ❌      | The central state account                                                             |
+    /// | 1          | ✅        | ❌      | The listing account                                                                   |
+    /// | 2          | ✅        | ❌      | The bond V2 account of the listing escrowing the bond                                 |
+    /// | 3          | ✅        | ❌      | The bond V2 account of the buyer                                                      |
+    /// | 4          | ❌        | ✅      | The buyer                                                                             |
+    /// | 5          | ✅        | ❌      | The seller, receives the rent of the listing and the escrow                           |
+    /// | 6          | ✅        | ❌      | The quote token account of the buyer                                                  |
+    /// | 7          | ✅        | ❌      | The quote token account of the seller                                                 |
+    /// | 8          | ❌        | ❌      | The quote mint                                                                        |
+    /// | 9          | ❌        | ❌      | The token program of the quote mint                                                   |
+    /// | 10         | ❌        | ❌      | The pool account                                                                      |
+    /// | 11         | ✅        | ✅      | The fee payer account, pays the rent of the bond account of the buyer                 |
+    /// | 12         | ❌        | ❌      | The system program account                                                            |
+    /// | 13         | ✅        | ❌      | The token mint of the central state                                                   |
+    /// | 14         | ✅        | ❌      | The token account of the seller receiving the rewards earned by the bond while listed |
+    /// | 15         | ❌        | ❌      | The token program of the central state mint                                           |
+    /// | 16..16 + M | ❌        | ❌      | The accounts of the transfer hook of the quote mint                                   |
+    BuyBondV2,
+    /// Set the boost curve of the bonds
+    ///
+    /// | Index | Writable | Signer | Description                                                 |
+    /// | --------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The central state authority                                 |
+    /// | 1     | ❌        | ❌      | The central state account                                   |
+    /// | 2     | ✅        | ❌      | The boost config account                                    |
+    /// | 3     | ✅        | ✅      | The fee payer account                                       |
+    /// | 4     | ❌        | ❌      | The system program account                                  |
+    /// | 5     | ❌        | ❌      | The role table account, required once the roles are enabled |
+    AdminSetBoostConfig,
+    /// Settle the rewards of a bond V2 and refresh its boost from its remaining lock time
+    ///
+    /// | Index | Writable | Signer | Description                                                                  |
+    /// | -------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The central state account                                                    |
+    /// | 1     | ✅        | ❌      | The bond V2 account                                                          |
+    /// | 2     | ✅        | ❌      | The pool account                                                             |
+    /// | 3     | ✅        | ❌      | The pool boost account                                                       |
+    /// | 4     | ❌        | ❌      | The boost config account                                                     |
+    /// | 5     | ✅        | ✅      | The fee payer account, pays the rent of the pool boost and of the migrations |
+    /// | 6     | ❌        | ❌      | The system program account                                                   |
+    RefreshBondV2Boost,
+    /// Create the receipt mint of a stake pool
+    ///
+    /// | Index | Writable | Signer | Description                                                       |
+    /// | --------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The central state account                                         |
+    /// | 1     | ❌        | ❌      | The stake pool account                                            |
+    /// | 2     | ❌        | ✅      | The owner of the stake pool                                       |
+    /// | 3     | ✅        | ❌      | The receipt mint account                                          |
+    /// | 4     | ✅        | ❌      | The stake account of the receipt holders                          |
+    /// | 5     | ❌        | ❌      | The receipt reserve, the ATA of the receipt mint for the ACS mint |
+    /// | 6     | ❌        | ❌      | The mint of the ACS token                                         |
+    /// | 7     | ❌        | ❌      | The SPL token program account                                     |
+    /// | 8     | ✅        | ✅      | The fee payer account                                             |
+    /// | 9     | ❌        | ❌      | The system program account                                        |
+    CreateReceiptMint,
+    /// Claim the rewards of the stake account of the receipt holders to the receipt reserve
+    ///
+    /// | Index | Writable | Signer | Description                              |
+    /// | -------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The central state account                |
+    /// | 1     | ✅        | ❌      | The stake pool account                   |
+    /// | 2     | ✅        | ❌      | The stake account of the receipt holders |
+    /// | 3     | ❌        | ❌      | The receipt mint account                 |
+    /// | 4     | ✅        | ❌      | The receipt reserve                      |
+    /// | 5     | ✅        | ❌      | The mint address of the ACS token        |
+    /// | 6     | ❌        | ❌      | The SPL token program account            |
+    ClaimReceiptRewards,
+    /// Set the SPL Governance realm of the voter weight records
+    ///
+    /// | Index | Writable | Signer | Description                                                 |
+    /// | --------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The central state authority                                 |
+    /// | 1     | ❌        | ❌      | The central state account                                   |
+    /// | 2     | ✅        | ❌      | The governance config account                               |
+    /// | 3     | ✅        | ✅      | The fee payer account                                       |
+    /// | 4     | ❌        | ❌      | The system program account                                  |
+    /// | 5     | ❌        | ❌      | The role table account, required once the roles are enabled |
+    AdminSetGovernanceConfig,
+    /// Update the voter weight record of a voter from their stake accounts, bonds V2 and receipts
+    ///
+    /// | Index | Writable | Signer | Description                                                                                                                            |
+    /// | ------------------------------------------------------------------------------------------------------------------------------------------------------------------ |
+    /// | 0     | ❌        | ❌      | The central state account                                                                                                              |
+    /// | 1     | ❌        | ❌      | The governance config account                                                                                                          |
+    /// | 2     | ❌        | ❌      | The boost config account, read with the lock weighting only                                                                            |
+    /// | 3     | ✅        | ❌      | The voter weight record account                                                                                                        |
+    /// | 4     | ❌        | ✅      | The voter                                                                                                                              |
+    /// | 5     | ✅        | ✅      | The fee payer account                                                                                                                  |
+    /// | 6     | ❌        | ❌      | The system program account                                                                                                             |
+    /// | 7...  | ❌        | ❌      | The stake pools of the voter, each followed by the stake account, the bonds V2 and the receipt token accounts of the voter in the pool |
+    UpdateVoterWeightRecord,
+    /// Update the max voter weight record of the central state
+    ///
+    /// | Index | Writable | Signer | Description                         |
+    /// | --------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The central state account           |
+    /// | 1     | ❌        | ❌      | The governance config account       |
+    /// | 2     | ✅        | ❌      | The max voter weight record account |
+    /// | 3     | ✅        | ✅      | The fee payer account               |
+    /// | 4     | ❌        | ❌      | The system program account          |
+    UpdateMaxVoterWeightRecord,
+    /// Change the cap, the pause and the allowlist of a pool
+    ///
+    /// | Index | Writable | Signer | Description                  |
+    /// | -------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The stake pool account       |
+    /// | 1     | ❌        | ✅      | The stake pool owner account |
+    /// | 2     | ❌        | ❌      | The central state account    |
+    /// | 3     | ✅        | ❌      | The pool controls account    |
+    /// | 4     | ✅        | ✅      | The fee payer account        |
+    /// | 5     | ❌        | ❌      | The system program account   |
+    ChangePoolControls,
+    /// Add or remove a staker of the allowlist of a pool
+    ///
+    /// | Index | Writable | Signer | Description                  |
+    /// | -------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The stake pool account       |
+    /// | 1     | ✅        | ✅      | The stake pool owner account |
+    /// | 2     | ❌        | ❌      | The central state account    |
+    /// | 3     | ✅        | ❌      | The allowlist entry account  |
+    /// | 4     | ✅        | ✅      | The fee payer account        |
+    /// | 5     | ❌        | ❌      | The system program account   |
+    SetPoolAllowlistEntry,
+    /// Withdraw the principal of a stake account or of a bond V2 after a maximum freeze
+    ///
+    /// | Index      | Writable | Signer | Description                                                                                                   |
+    /// | ---------------------------------------------------------------------------------------------------------------------------------------------- |
+    /// | 0          | ✅        | ❌      | The central state account                                                                                     |
+    /// | 1          | ✅        | ❌      | The stake pool account                                                                                        |
+    /// | 2          | ✅        | ❌      | The stake account or the bond V2 account                                                                      |
+    /// | 3          | ❌        | ✅      | The owner of the position or the receipt holder                                                               |
+    /// | 4          | ✅        | ❌      | The destination of the principal                                                                              |
+    /// | 5          | ✅        | ❌      | The stake pool vault                                                                                          |
+    /// | 6          | ❌        | ❌      | The SPL token program account                                                                                 |
+    /// | 7          | ❌        | ❌      | The mint of the ACS token                                                                                     |
+    /// | 8          | ✅        | ❌      | The pool boost account, required for the boosted bonds V2                                                     |
+    /// | 9          | ✅        | ❌      | The optional receipt mint of the stake pool, to withdraw the stake of the receipt holder                      |
+    /// | 10         | ✅        | ❌      | The receipt token account of the holder, all its receipts are burnt                                           |
+    /// | 11         | ✅        | ❌      | The receipt reserve, pays out the share of the accrued rewards                                                |
+    /// | 12..12 + M | ❌        | ❌      | (Optional) The accounts of the transfer hook of the mint, skipped optional accounts are set to the program id |
+    EmergencyWithdraw,
+    /// Delegate an admin instruction to its own authority, enabling the roles on the first call
+    ///
+    /// | Index | Writable | Signer | Description                       |
+    /// | ------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The current authority of the role |
+    /// | 1     | ✅        | ❌      | The central state account         |
+    /// | 2     | ✅        | ❌      | The role table account            |
+    /// | 3     | ✅        | ✅      | The fee payer account             |
+    /// | 4     | ❌        | ❌      | The system program account        |
+    AdminSetRole,
+    /// Admin set the emission cap, the central state must have been migrated to its latest layout
+    ///
+    /// | Index | Writable | Signer | Description                                                 |
+    /// | --------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The central state authority                                 |
+    /// | 1     | ✅        | ❌      | The central state account                                   |
+    /// | 2     | ❌        | ❌      | The mint of the ACS token                                   |
+    /// | 3     | ❌        | ❌      | The role table account, required once the roles are enabled |
+    AdminSetEmissionCap,
 }
 
 #[allow(missing_docs)]
@@ -810,3 +1168,253 @@ pub fn close_royalty_account(
         params,
     )
 }
+
+#[allow(missing_docs)]
+pub fn admin_set_pool_fee_override(
+    program_id: Pubkey,
+    accounts: admin_set_pool_fee_override::Accounts<Pubkey>,
+    params: admin_set_pool_fee_override::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        program_id,
+        ProgramInstruction::AdminSetPoolFeeOverride as u8,
+        params,
+    )
+}
+
+#[allow(missing_docs)]
+pub fn admin_set_keeper_reward(
+    program_id: Pubkey,
+    accounts: admin_set_keeper_reward::Accounts<Pubkey>,
+    params: admin_set_keeper_reward::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        program_id,
+        ProgramInstruction::AdminSetKeeperReward as u8,
+        params,
+    )
+}
+
+#[allow(missing_docs)]
+pub fn admin_set_protocol_config(
+    program_id: Pubkey,
+    accounts: admin_set_protocol_config::Accounts<Pubkey>,
+    params: admin_set_protocol_config::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        program_id,
+        ProgramInstruction::AdminSetProtocolConfig as u8,
+        params,
+    )
+}
+
+#[allow(missing_docs)]
+pub fn get_claimable_rewards(
+    program_id: Pubkey,
+    accounts: get_claimable_rewards::Accounts<Pubkey>,
+    params: get_claimable_rewards::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::GetClaimableRewards as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn get_pool_apr(
+    program_id: Pubkey,
+    accounts: get_pool_apr::Accounts<Pubkey>,
+    params: get_pool_apr::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::GetPoolApr as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn get_stake_summary(
+    program_id: Pubkey,
+    accounts: get_stake_summary::Accounts<Pubkey>,
+    params: get_stake_summary::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::GetStakeSummary as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn get_central_state_info(
+    program_id: Pubkey,
+    accounts: get_central_state_info::Accounts<Pubkey>,
+    params: get_central_state_info::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::GetCentralStateInfo as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn enable_reward_index(
+    program_id: Pubkey,
+    accounts: enable_reward_index::Accounts<Pubkey>,
+    params: enable_reward_index::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::EnableRewardIndex as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn compact_stake_pool(
+    program_id: Pubkey,
+    accounts: compact_stake_pool::Accounts<Pubkey>,
+    params: compact_stake_pool::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::CompactStakePool as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn migrate_account(
+    program_id: Pubkey,
+    accounts: migrate_account::Accounts<Pubkey>,
+    params: migrate_account::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::MigrateAccount as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn transfer_bond_v2(
+    program_id: Pubkey,
+    accounts: transfer_bond_v2::Accounts<Pubkey>,
+    params: transfer_bond_v2::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::TransferBondV2 as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn list_bond_v2(
+    program_id: Pubkey,
+    accounts: list_bond_v2::Accounts<Pubkey>,
+    params: list_bond_v2::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::ListBondV2 as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn cancel_bond_v2_listing(
+    program_id: Pubkey,
+    accounts: cancel_bond_v2_listing::Accounts<Pubkey>,
+    params: cancel_bond_v2_listing::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::CancelBondV2Listing as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn buy_bond_v2(
+    program_id: Pubkey,
+    accounts: buy_bond_v2::Accounts<Pubkey>,
+    params: buy_bond_v2::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::BuyBondV2 as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn admin_set_boost_config(
+    program_id: Pubkey,
+    accounts: admin_set_boost_config::Accounts<Pubkey>,
+    params: admin_set_boost_config::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::AdminSetBoostConfig as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn refresh_bond_v2_boost(
+    program_id: Pubkey,
+    accounts: refresh_bond_v2_boost::Accounts<Pubkey>,
+    params: refresh_bond_v2_boost::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::RefreshBondV2Boost as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn create_receipt_mint(
+    program_id: Pubkey,
+    accounts: create_receipt_mint::Accounts<Pubkey>,
+    params: create_receipt_mint::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::CreateReceiptMint as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn claim_receipt_rewards(
+    program_id: Pubkey,
+    accounts: claim_receipt_rewards::Accounts<Pubkey>,
+    params: claim_receipt_rewards::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::ClaimReceiptRewards as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn admin_set_governance_config(
+    program_id: Pubkey,
+    accounts: admin_set_governance_config::Accounts<Pubkey>,
+    params: admin_set_governance_config::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::AdminSetGovernanceConfig as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn update_voter_weight_record(
+    program_id: Pubkey,
+    accounts: update_voter_weight_record::Accounts<Pubkey>,
+    params: update_voter_weight_record::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::UpdateVoterWeightRecord as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn update_max_voter_weight_record(
+    program_id: Pubkey,
+    accounts: update_max_voter_weight_record::Accounts<Pubkey>,
+    params: update_max_voter_weight_record::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        program_id,
+        ProgramInstruction::UpdateMaxVoterWeightRecord as u8,
+        params,
+    )
+}
+
+#[allow(missing_docs)]
+pub fn change_pool_controls(
+    program_id: Pubkey,
+    accounts: change_pool_controls::Accounts<Pubkey>,
+    params: change_pool_controls::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::ChangePoolControls as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn set_pool_allowlist_entry(
+    program_id: Pubkey,
+    accounts: set_pool_allowlist_entry::Accounts<Pubkey>,
+    params: set_pool_allowlist_entry::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::SetPoolAllowlistEntry as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn emergency_withdraw(
+    program_id: Pubkey,
+    accounts: emergency_withdraw::Accounts<Pubkey>,
+    params: emergency_withdraw::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::EmergencyWithdraw as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn admin_set_role(
+    program_id: Pubkey,
+    accounts: admin_set_role::Accounts<Pubkey>,
+    params: admin_set_role::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::AdminSetRole as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn admin_set_emission_cap(
+    program_id: Pubkey,
+    accounts: admin_set_emission_cap::Accounts<Pubkey>,
+    params: admin_set_emission_cap::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::AdminSetEmissionCap as u8, params)
+}
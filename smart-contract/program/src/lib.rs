@@ -49,6 +49,21 @@ pub mod error;
 pub mod instruction;
 /// Describes the different data structres that the program uses to encode state
 pub mod state;
+/// Reward math shared by the processors and the off-chain clients
+pub mod rewards;
+/// Typed events logged by the processors and their decoder
+pub mod events;
+/// Tag based decoding of the program accounts
+pub mod decoder;
+/// Results of the read-only view instructions
+pub mod views;
+/// Versioned account layouts and their migration
+pub mod versioning;
+/// Voter weight records of the SPL Governance addin interface
+pub mod governance;
+/// In-process harness to test integrations without a validator
+#[cfg(feature = "testing")]
+pub mod testing;
 
 #[doc(hidden)]
 pub(crate) mod processor;
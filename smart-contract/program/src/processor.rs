@@ -43,6 +43,32 @@ pub mod admin_renounce;
 pub mod admin_change_freeze_authority;
 pub mod create_royalty_account;
 pub mod close_royalty_account;
+pub mod admin_set_pool_fee_override;
+pub mod admin_set_keeper_reward;
+pub mod admin_set_protocol_config;
+pub mod get_claimable_rewards;
+pub mod get_pool_apr;
+pub mod get_stake_summary;
+pub mod get_central_state_info;
+pub mod enable_reward_index;
+pub mod compact_stake_pool;
+pub mod migrate_account;
+pub mod transfer_bond_v2;
+pub mod list_bond_v2;
+pub mod cancel_bond_v2_listing;
+pub mod buy_bond_v2;
+pub mod admin_set_boost_config;
+pub mod refresh_bond_v2_boost;
+pub mod create_receipt_mint;
+pub mod claim_receipt_rewards;
+pub mod admin_set_governance_config;
+pub mod update_voter_weight_record;
+pub mod update_max_voter_weight_record;
+pub mod change_pool_controls;
+pub mod set_pool_allowlist_entry;
+pub mod emergency_withdraw;
+pub mod admin_set_role;
+pub mod admin_set_emission_cap;
 
 pub struct Processor {}
 
@@ -276,6 +302,168 @@ impl Processor {
                 msg!("Instruction: Close royalty account");
                 close_royalty_account::process_close_royalty_account(program_id, accounts)?;
             }
+            ProgramInstruction::AdminSetPoolFeeOverride => {
+                msg!("Instruction: Admin set pool fee override");
+                let params = admin_set_pool_fee_override::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                admin_set_pool_fee_override::process_admin_set_pool_fee_override(
+                    program_id, accounts, params,
+                )?;
+            }
+            ProgramInstruction::AdminSetKeeperReward => {
+                msg!("Instruction: Admin set keeper reward");
+                let params = admin_set_keeper_reward::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                admin_set_keeper_reward::process_admin_set_keeper_reward(
+                    program_id, accounts, params,
+                )?;
+            }
+            ProgramInstruction::AdminSetProtocolConfig => {
+                msg!("Instruction: Admin set protocol config");
+                let params = admin_set_protocol_config::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                admin_set_protocol_config::process_admin_set_protocol_config(
+                    program_id, accounts, params,
+                )?;
+            }
+            ProgramInstruction::GetClaimableRewards => {
+                msg!("Instruction: Get claimable rewards");
+                let params = get_claimable_rewards::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                get_claimable_rewards::process_get_claimable_rewards(program_id, accounts, params)?;
+            }
+            ProgramInstruction::GetPoolApr => {
+                msg!("Instruction: Get pool APR");
+                let params = get_pool_apr::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                get_pool_apr::process_get_pool_apr(program_id, accounts, params)?;
+            }
+            ProgramInstruction::GetStakeSummary => {
+                msg!("Instruction: Get stake summary");
+                let params = get_stake_summary::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                get_stake_summary::process_get_stake_summary(program_id, accounts, params)?;
+            }
+            ProgramInstruction::GetCentralStateInfo => {
+                msg!("Instruction: Get central state info");
+                let params = get_central_state_info::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                get_central_state_info::process_get_central_state_info(program_id, accounts, params)?;
+            }
+            ProgramInstruction::EnableRewardIndex => {
+                msg!("Instruction: Enable reward index");
+                let params = enable_reward_index::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                enable_reward_index::process_enable_reward_index(program_id, accounts, params)?;
+            }
+            ProgramInstruction::CompactStakePool => {
+                msg!("Instruction: Compact stake pool");
+                let params = compact_stake_pool::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                compact_stake_pool::process_compact_stake_pool(program_id, accounts, params)?;
+            }
+            ProgramInstruction::MigrateAccount => {
+                msg!("Instruction: Migrate account");
+                let params = migrate_account::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                migrate_account::process_migrate_account(program_id, accounts, params)?;
+            }
+            ProgramInstruction::TransferBondV2 => {
+                msg!("Instruction: Transfer bond V2");
+                let params = transfer_bond_v2::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                transfer_bond_v2::process_transfer_bond_v2(program_id, accounts, params)?;
+            }
+            ProgramInstruction::ListBondV2 => {
+                msg!("Instruction: List bond V2");
+                let params = list_bond_v2::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                list_bond_v2::process_list_bond_v2(program_id, accounts, params)?;
+            }
+            ProgramInstruction::CancelBondV2Listing => {
+                msg!("Instruction: Cancel bond V2 listing");
+                let params = cancel_bond_v2_listing::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                cancel_bond_v2_listing::process_cancel_bond_v2_listing(program_id, accounts, params)?;
+            }
+            ProgramInstruction::BuyBondV2 => {
+                msg!("Instruction: Buy bond V2");
+                let params = buy_bond_v2::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                buy_bond_v2::process_buy_bond_v2(program_id, accounts, params)?;
+            }
+            ProgramInstruction::AdminSetBoostConfig => {
+                msg!("Instruction: Admin set boost config");
+                let params = admin_set_boost_config::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                admin_set_boost_config::process_admin_set_boost_config(program_id, accounts, params)?;
+            }
+            ProgramInstruction::RefreshBondV2Boost => {
+                msg!("Instruction: Refresh bond V2 boost");
+                let params = refresh_bond_v2_boost::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                refresh_bond_v2_boost::process_refresh_bond_v2_boost(program_id, accounts, params)?;
+            }
+            ProgramInstruction::CreateReceiptMint => {
+                msg!("Instruction: Create receipt mint");
+                let params = create_receipt_mint::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                create_receipt_mint::process_create_receipt_mint(program_id, accounts, params)?;
+            }
+            ProgramInstruction::ClaimReceiptRewards => {
+                msg!("Instruction: Claim receipt rewards");
+                let params = claim_receipt_rewards::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                claim_receipt_rewards::process_claim_receipt_rewards(program_id, accounts, params)?;
+            }
+            ProgramInstruction::AdminSetGovernanceConfig => {
+                msg!("Instruction: Admin set governance config");
+                let params = admin_set_governance_config::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                admin_set_governance_config::process_admin_set_governance_config(program_id, accounts, params)?;
+            }
+            ProgramInstruction::UpdateVoterWeightRecord => {
+                msg!("Instruction: Update voter weight record");
+                let params = update_voter_weight_record::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                update_voter_weight_record::process_update_voter_weight_record(program_id, accounts, params)?;
+            }
+            ProgramInstruction::UpdateMaxVoterWeightRecord => {
+                msg!("Instruction: Update max voter weight record");
+                let params = update_max_voter_weight_record::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                update_max_voter_weight_record::process_update_max_voter_weight_record(program_id, accounts, params)?;
+            }
+            ProgramInstruction::ChangePoolControls => {
+                msg!("Instruction: Change pool controls");
+                let params = change_pool_controls::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                change_pool_controls::process_change_pool_controls(program_id, accounts, params)?;
+            }
+            ProgramInstruction::SetPoolAllowlistEntry => {
+                msg!("Instruction: Set pool allowlist entry");
+                let params = set_pool_allowlist_entry::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                set_pool_allowlist_entry::process_set_pool_allowlist_entry(program_id, accounts, params)?;
+            }
+            ProgramInstruction::EmergencyWithdraw => {
+                msg!("Instruction: Emergency withdraw");
+                let params = emergency_withdraw::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                emergency_withdraw::process_emergency_withdraw(program_id, accounts, params)?;
+            }
+            ProgramInstruction::AdminSetRole => {
+                msg!("Instruction: Admin set role");
+                let params = admin_set_role::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                admin_set_role::process_admin_set_role(program_id, accounts, params)?;
+            }
+            ProgramInstruction::AdminSetEmissionCap => {
+                msg!("Instruction: Admin set emission cap");
+                let params = admin_set_emission_cap::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                admin_set_emission_cap::process_admin_set_emission_cap(program_id, accounts, params)?;
+            }
         }
 
         Ok(())
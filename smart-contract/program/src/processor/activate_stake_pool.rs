@@ -11,7 +11,7 @@ use solana_program::{
 use crate::error::AccessError;
 use crate::instruction::ProgramInstruction::ActivateStakePool;
 use crate::state::{CentralStateV2, StakePool, Tag};
-use crate::utils::{check_account_owner};
+use crate::utils::{check_account_owner, check_pool_central_state};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 pub struct Params {}
@@ -51,6 +51,13 @@ pub fn process_activate_stake_pool(program_id: &Pubkey, accounts: &[AccountInfo]
     let mut stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::InactiveStakePool])?;
     let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&ActivateStakePool)?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
 
     if stake_pool.header.tag != Tag::InactiveStakePool as u8 {
         return Err(AccessError::ActiveStakePoolNotAllowed.into());
@@ -62,6 +69,10 @@ pub fn process_activate_stake_pool(program_id: &Pubkey, accounts: &[AccountInfo]
         return Err(AccessError::Overflow.into());
     }
     stake_pool.header.current_day_idx = central_state.last_snapshot_offset as u16;
+    // The reward index of a pool created with it starts on its first day
+    if stake_pool.header.uses_reward_index() {
+        stake_pool.header.reward_index_start = stake_pool.header.current_day_idx;
+    }
 
     Ok(())
 }
@@ -6,21 +6,25 @@ use solana_program::{
     clock::Clock,
     entrypoint::ProgramResult,
     msg,
-    program::invoke,
     program_error::ProgramError,
     pubkey::Pubkey,
     system_program,
 };
-use solana_program::program_pack::Pack;
 use solana_program::sysvar::Sysvar;
-use spl_token::instruction::transfer;
-use spl_token::state::Account;
 
+use crate::cpi::Cpi;
 use crate::error::AccessError;
+use crate::events::Event;
 use crate::instruction::ProgramInstruction::AddToBondV2;
-use crate::state::{BondV2Account, CentralStateV2, StakePool};
+use crate::rewards::bond_boost;
+use crate::state::{BondV2Account, BoostConfig, CentralStateV2, PoolBoost, StakePool};
 use crate::state::Tag;
-use crate::utils::{assert_valid_fee, check_account_key, check_account_owner, check_signer};
+use crate::utils::{
+    assert_valid_fee, check_account_key, check_account_owner, check_signer, check_token_program,
+    retrieve_pool_fee_override, unpack_token_account, check_pool_central_state, check_pool_controls,
+    next_optional_account_info,
+};
+use crate::versioning::migrate_if_needed;
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 /// The required parameters for the `add_to_bond_v2` instruction
@@ -69,6 +73,26 @@ pub struct Accounts<'a, T> {
 
     /// The system program account
     pub system_program: &'a T,
+
+    /// The optional fee override account of the pool
+    pub pool_fee_override: Option<&'a T>,
+
+    /// The pool controls account, required once the pool has controls
+    pub pool_controls: Option<&'a T>,
+
+    /// The allowlist entry of the bond owner, required once the allowlist of the pool is enabled
+    pub allowlist_entry: Option<&'a T>,
+
+    /// The boost config account, required for the boosted bonds V2
+    pub boost_config: Option<&'a T>,
+
+    /// The pool boost account, required for the boosted bonds V2
+    #[cons(writable)]
+    pub pool_boost: Option<&'a T>,
+
+    /// The accounts of the transfer hook of the mint, after the optional accounts which can be
+    /// skipped with the program id
+    pub transfer_hook_accounts: &'a [T],
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -88,6 +112,12 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             mint: next_account_info(accounts_iter)?,
             spl_token_program: next_account_info(accounts_iter)?,
             system_program: next_account_info(accounts_iter)?,
+            pool_fee_override: next_optional_account_info(accounts_iter, program_id),
+            pool_controls: next_optional_account_info(accounts_iter, program_id),
+            allowlist_entry: next_optional_account_info(accounts_iter, program_id),
+            boost_config: next_optional_account_info(accounts_iter, program_id),
+            pool_boost: next_optional_account_info(accounts_iter, program_id),
+            transfer_hook_accounts: accounts_iter.as_slice(),
         };
 
         // Check keys
@@ -96,15 +126,15 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             &system_program::ID,
             AccessError::WrongSystemProgram,
         )?;
-        check_account_key(
-            accounts.spl_token_program,
-            &spl_token::ID,
-            AccessError::WrongSplTokenProgramId,
-        )?;
+        check_token_program(accounts.spl_token_program)?;
 
         // Check ownership
         check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
-        check_account_owner(accounts.central_state_vault, &spl_token::ID, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.central_state_vault,
+            accounts.spl_token_program.key,
+            AccessError::WrongOwner,
+        )?;
         check_account_owner(
             accounts.bond_v2_account,
             program_id,
@@ -117,12 +147,12 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         )?;
         check_account_owner(
             accounts.from_ata,
-            &spl_token::ID,
+            accounts.spl_token_program.key,
             AccessError::WrongTokenAccountOwner,
         )?;
         check_account_owner(
             accounts.pool_vault,
-            &spl_token::ID,
+            accounts.spl_token_program.key,
             AccessError::WrongTokenAccountOwner,
         )?;
 
@@ -147,7 +177,16 @@ pub fn process_add_to_bond_v2(
     let mut bond = BondV2Account::from_account_info(accounts.bond_v2_account)?;
     let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&AddToBondV2)?;
+    check_pool_central_state(
+        accounts.pool.key,
+        &pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
     assert_valid_fee(accounts.central_state_vault, accounts.central_state.key)?;
+    let pool_fee_override =
+        retrieve_pool_fee_override(accounts.pool_fee_override, accounts.pool.key, program_id)?;
 
     // if we were previously under the amount gets reset to the pool's one
     if bond.amount < bond.pool_minimum_at_creation {
@@ -176,6 +215,7 @@ pub fn process_add_to_bond_v2(
 
     if bond.amount == 0 {
         bond.last_claimed_offset = central_state.get_current_offset()?;
+        bond.last_claimed_index = pool.stakers_reward_index();
     }
 
     check_account_key(
@@ -184,7 +224,7 @@ pub fn process_add_to_bond_v2(
         AccessError::WrongMint,
     )?;
 
-    let from_ata = Account::unpack(&accounts.from_ata.data.borrow())?;
+    let from_ata = unpack_token_account(accounts.from_ata)?;
     if from_ata.mint != central_state.token_mint {
         msg!("Invalid ACCESS mint");
         #[cfg(not(feature = "no-mint-check"))]
@@ -201,6 +241,16 @@ pub fn process_add_to_bond_v2(
         return Err(AccessError::InvalidAmount.into());
     }
 
+    check_pool_controls(
+        &pool.header,
+        accounts.pool.key,
+        accounts.pool_controls,
+        accounts.allowlist_entry,
+        &bond.owner,
+        amount,
+        program_id,
+    )?;
+
     if bond.amount > 0 && bond.last_claimed_offset < pool.header.current_day_idx as u64 {
         msg!("Cannot add to a bond that has unclaimed rewards");
         return Err(AccessError::UnclaimedRewards.into());
@@ -213,70 +263,73 @@ pub fn process_add_to_bond_v2(
     }
 
     // Transfer the tokens to pool vault (or burn for forever bonds)
-    if bond.unlock_timestamp.is_some() {
-        let transfer_instruction = transfer(
-            &spl_token::ID,
-            accounts.from_ata.key,
-            accounts.pool_vault.key,
-            accounts.from.key,
-            &[],
+    let fee = if bond.unlock_timestamp.is_some() {
+        Cpi::transfer(
+            accounts.spl_token_program,
+            accounts.from_ata,
+            accounts.pool_vault,
+            accounts.from,
+            accounts.mint,
+            accounts.transfer_hook_accounts,
             amount,
-        )?;
-        invoke(
-            &transfer_instruction,
-            &[
-                accounts.spl_token_program.clone(),
-                accounts.from_ata.clone(),
-                accounts.pool_vault.clone(),
-                accounts.from.clone(),
-            ],
+            &[],
         )?;
 
         // Transfer fees
-        let fee_amount = central_state.calculate_fee(amount)?;
+        let fee_amount = central_state.calculate_fee(amount, pool_fee_override.as_ref())?;
         msg!("Transfer fees: {}", fee_amount);
-        let transfer_fees = transfer(
-            &spl_token::ID,
-            accounts.from_ata.key,
-            accounts.central_state_vault.key,
-            accounts.from.key,
-            &[],
+        Cpi::transfer(
+            accounts.spl_token_program,
+            accounts.from_ata,
+            accounts.central_state_vault,
+            accounts.from,
+            accounts.mint,
+            accounts.transfer_hook_accounts,
             fee_amount,
+            &[],
         )?;
-        invoke(
-            &transfer_fees,
-            &[
-                accounts.spl_token_program.clone(),
-                accounts.from_ata.clone(),
-                accounts.central_state_vault.clone(),
-                accounts.from.clone(),
-            ],
-        )?;
+        fee_amount
     } else {
-        let burn_instruction = spl_token::instruction::burn(
-            &spl_token::ID,
-            accounts.from_ata.key,
-            accounts.mint.key,
-            accounts.from.key,
-            &[accounts.from.key],
+        Cpi::burn(
+            accounts.spl_token_program,
+            accounts.from_ata,
+            accounts.mint,
+            accounts.from,
             amount,
+            &[],
         )?;
-        invoke(
-            &burn_instruction,
-            &[
-                accounts.from_ata.clone(),
-                accounts.mint.clone(),
-                accounts.from.clone(),
-                accounts.from.clone(),
-            ],
-        )?;
-    }
+        0
+    };
 
     // Update all the appropriate states
     bond.amount = bond
         .amount
         .checked_add(amount)
         .ok_or(AccessError::Overflow)?;
+
+    // The boost of a boosted bond follows its new amount, its rewards are already claimed
+    let previous_boost = bond.boost;
+    if previous_boost != 0 {
+        let (boost_config_info, pool_boost_info) = accounts
+            .boost_config
+            .zip(accounts.pool_boost)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let boost_config =
+            BoostConfig::get_checked(boost_config_info, accounts.central_state.key, program_id)?;
+        let mut pool_boost =
+            PoolBoost::get_checked(pool_boost_info, accounts.pool.key, program_id)?;
+        let boost = bond_boost(bond.amount, bond.unlock_timestamp, current_time, &boost_config)?;
+        pool_boost.update(previous_boost, boost)?;
+        pool_boost.save(&mut pool_boost_info.data.borrow_mut())?;
+        // The payer of the tokens tops up the rent of the central state if it has the old layout
+        migrate_if_needed::<CentralStateV2>(
+            accounts.central_state,
+            accounts.from,
+            accounts.system_program,
+        )?;
+        central_state.update_boost(previous_boost, boost)?;
+        bond.boost = boost;
+    }
     bond.save(&mut accounts.bond_v2_account.data.borrow_mut())?;
     pool.header.deposit(amount)?;
     central_state.total_staked = central_state
@@ -285,5 +338,14 @@ pub fn process_add_to_bond_v2(
         .ok_or(AccessError::Overflow)?;
     central_state.save(&mut accounts.central_state.data.borrow_mut())?;
 
+    Event::BondLocked {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.pool.key,
+        bond: *accounts.bond_v2_account.key,
+        amount,
+        fee,
+    }
+    .emit()?;
+
     Ok(())
 }
@@ -8,10 +8,11 @@ use solana_program::{
 };
 
 use crate::{error::AccessError};
+use crate::events::{AdminChange, Event};
 use bonfida_utils::{BorshSize, InstructionsAccount};
 use crate::instruction::ProgramInstruction::AdminChangeFreezeAuthority;
 
-use crate::utils::{check_account_key, check_account_owner, check_signer};
+use crate::utils::{check_account_owner, check_admin_authority, check_signer};
 use crate::state:: CentralStateV2;
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
@@ -31,6 +32,9 @@ pub struct Accounts<'a, T> {
     /// The central state account authority
     #[cons(signer)]
     pub authority: &'a T,
+
+    /// The role table account, required once the roles are enabled
+    pub role_table: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -42,6 +46,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         let accounts = Accounts {
             central_state: next_account_info(accounts_iter)?,
             authority: next_account_info(accounts_iter)?,
+            role_table: next_account_info(accounts_iter).ok(),
         };
 
         // Check ownership
@@ -67,14 +72,26 @@ pub fn process_admin_change_freeze_authority(
     let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&AdminChangeFreezeAuthority)?;
 
-    check_account_key(
+    check_admin_authority(
         accounts.authority,
-        &central_state.authority,
-        AccessError::WrongCentralStateAuthority,
+        accounts.role_table,
+        accounts.central_state.key,
+        &central_state,
+        &AdminChangeFreezeAuthority,
+        program_id,
     )?;
 
     central_state.freeze_authority = params.new_freeze_authority;
     central_state.save(&mut accounts.central_state.data.borrow_mut())?;
 
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.authority.key,
+        change: AdminChange::FreezeAuthority {
+            new_freeze_authority: params.new_freeze_authority,
+        },
+    }
+    .emit()?;
+
     Ok(())
 }
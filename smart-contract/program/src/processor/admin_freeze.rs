@@ -2,7 +2,6 @@
 //! This admin instruction can be dangereous 💀
 use bonfida_utils::{BorshSize, InstructionsAccount};
 use borsh::{BorshDeserialize, BorshSerialize};
-use num_traits::FromPrimitive;
 use solana_program::{
     account_info::{AccountInfo, next_account_info},
     entrypoint::ProgramResult,
@@ -11,9 +10,13 @@ use solana_program::{
 };
 
 use crate::error::AccessError;
+use crate::events::{AdminChange, Event};
 use crate::instruction::ProgramInstruction::AdminFreeze;
 use crate::state::{CentralStateV2, Tag, V1_INSTRUCTIONS_ALLOWED};
-use crate::utils::{check_account_key, check_account_owner, check_signer};
+use crate::utils::{
+    assert_original_central_state, check_account_owner, check_admin_authority, check_signer,
+};
+use crate::versioning::{read_header, set_tag};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 pub struct Params {}
@@ -30,6 +33,9 @@ pub struct Accounts<'a, T> {
 
     /// The central state account
     pub central_state: &'a T,
+
+    /// The role table account, required once the roles are enabled
+    pub role_table: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -42,6 +48,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             authority: next_account_info(accounts_iter)?,
             account_to_freeze: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
+            role_table: next_account_info(accounts_iter).ok(),
         };
 
         // Check ownership
@@ -71,19 +78,37 @@ pub fn process_admin_freeze(program_id: &Pubkey, accounts: &[AccountInfo]) -> Pr
 
     let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&AdminFreeze)?;
+    assert_original_central_state(accounts.central_state.key, &central_state, program_id)?;
 
-    check_account_key(
+    check_admin_authority(
         accounts.authority,
-        &central_state.authority,
-        AccessError::WrongCentralStateAuthority,
+        accounts.role_table,
+        accounts.central_state.key,
+        &central_state,
+        &AdminFreeze,
+        program_id,
     )?;
 
     let mut data = accounts.account_to_freeze.data.borrow_mut();
 
-    let current_tag = Tag::from_u8(data[0]).ok_or(ProgramError::InvalidAccountData)?;
+    let (current_tag, _) = read_header(&data)?;
     let new_tag = Tag::opposite(&current_tag)?;
 
-    data[0] = new_tag as u8;
+    let frozen = matches!(
+        new_tag,
+        Tag::FrozenStakePool | Tag::FrozenStakeAccount | Tag::FrozenBondAccount
+    );
+    set_tag(&mut data, new_tag)?;
+
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.authority.key,
+        change: AdminChange::AccountFreeze {
+            account: *accounts.account_to_freeze.key,
+            frozen,
+        },
+    }
+    .emit()?;
 
     Ok(())
 }
@@ -4,15 +4,19 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{AccountInfo, next_account_info},
     entrypoint::ProgramResult,
-    program::invoke_signed,
     program_error::ProgramError,
     pubkey::Pubkey,
 };
 
+use crate::cpi::Cpi;
 use crate::error::AccessError;
+use crate::events::{AdminChange, Event};
 use crate::instruction::ProgramInstruction::AdminMint;
-use crate::state::{CentralStateV2, V1_INSTRUCTIONS_ALLOWED};
-use crate::utils::{check_account_key, check_account_owner, check_signer};
+use crate::state::{CentralStateV2, Emission, V1_INSTRUCTIONS_ALLOWED};
+use crate::utils::{
+    check_account_key, check_account_owner, check_admin_authority, check_signer,
+    check_token_program, unpack_mint,
+};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 pub struct Params {
@@ -35,10 +39,14 @@ pub struct Accounts<'a, T> {
     pub access_token_destination: &'a T,
 
     /// The central state account
+    #[cons(writable)]
     pub central_state: &'a T,
 
     /// The SPL token program account
     pub spl_token_program: &'a T,
+
+    /// The role table account, required once the roles are enabled
+    pub role_table: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -53,14 +61,11 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             access_token_destination: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
             spl_token_program: next_account_info(accounts_iter)?,
+            role_table: next_account_info(accounts_iter).ok(),
         };
 
         // Check keys
-        check_account_key(
-            accounts.spl_token_program,
-            &spl_token::ID,
-            AccessError::WrongSplTokenProgramId,
-        )?;
+        check_token_program(accounts.spl_token_program)?;
 
         // Check ownership
         check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
@@ -85,7 +90,7 @@ pub fn process_admin_mint(
     }
 
     let accounts = Accounts::parse(accounts, program_id)?;
-    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&AdminMint)?;
 
     check_account_key(
@@ -93,31 +98,38 @@ pub fn process_admin_mint(
         &central_state.token_mint,
         AccessError::WrongMint,
     )?;
-    check_account_key(
+    check_admin_authority(
         accounts.authority,
-        &central_state.authority,
-        AccessError::WrongCentralStateAuthority,
+        accounts.role_table,
+        accounts.central_state.key,
+        &central_state,
+        &AdminMint,
+        program_id,
     )?;
 
+    central_state.check_emission_cap(unpack_mint(accounts.mint)?.supply, params.amount)?;
+    central_state.record_emission(Emission::AdminMint, params.amount)?;
+    central_state.save(&mut accounts.central_state.data.borrow_mut())?;
+
     // Transfer tokens
-    let mint_ix = spl_token::instruction::mint_to(
-        &spl_token::ID,
-        accounts.mint.key,
-        accounts.access_token_destination.key,
-        accounts.central_state.key,
-        &[],
+    Cpi::mint_to(
+        accounts.spl_token_program,
+        accounts.mint,
+        accounts.access_token_destination,
+        accounts.central_state,
         params.amount,
+        &[&central_state.signer_seeds(accounts.central_state.key, program_id)],
     )?;
-    invoke_signed(
-        &mint_ix,
-        &[
-            accounts.spl_token_program.clone(),
-            accounts.central_state.clone(),
-            accounts.mint.clone(),
-            accounts.access_token_destination.clone(),
-        ],
-        &[&[&program_id.to_bytes(), &[central_state.bump_seed]]],
-    )?;
+
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.authority.key,
+        change: AdminChange::Mint {
+            destination: *accounts.access_token_destination.key,
+            amount: params.amount,
+        },
+    }
+    .emit()?;
 
     Ok(())
 }
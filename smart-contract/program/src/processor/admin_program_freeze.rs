@@ -1,12 +1,17 @@
 //! Admin program freeze instruction.
+//! The time the unstakes or the unlocks of the bonds V2 were frozen is recorded in the central state,
+//! the emergency withdrawal opens `MAX_FREEZE_DURATION` seconds after it.
+//! A central state with the previous layout is migrated first, the fee payer tops up its rent.
 use bonfida_utils::{BorshSize, InstructionsAccount};
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::{account_info::{AccountInfo, next_account_info}, entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey};
+use solana_program::{account_info::{AccountInfo, next_account_info}, clock::Clock, entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey, system_program, sysvar::Sysvar};
 
 use crate::error::AccessError;
+use crate::events::{AdminChange, Event};
 use crate::instruction::ProgramInstruction;
 use crate::state::CentralStateV2;
-use crate::utils::{check_account_owner, check_signer};
+use crate::utils::{check_account_key, check_account_owner, check_admin_authority, check_signer};
+use crate::versioning::migrate_if_needed;
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 /// The required parameters for the `admin_program_freeze` instruction
@@ -25,6 +30,16 @@ pub struct Accounts<'a, T> {
     /// The central state account authority or freeze authority
     #[cons(signer)]
     pub authority: &'a T,
+
+    /// The fee payer account, pays the rent of the migration of the central state
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The role table account, required once the roles are enabled
+    pub role_table: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -36,8 +51,18 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         let accounts = Accounts {
             central_state: next_account_info(accounts_iter)?,
             authority: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            role_table: next_account_info(accounts_iter).ok(),
         };
 
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
         // Check ownership
         check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
 
@@ -62,13 +87,41 @@ pub fn process_admin_program_freeze(
     let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&ProgramInstruction::AdminProgramFreeze)?;
 
-    // Only central state authority can unfreeze, the freeze authority is only allowed to freeze everything
-    if accounts.authority.key != &central_state.authority && (accounts.authority.key != &central_state.freeze_authority || ix_gate > 0) {
-        return Err(AccessError::WrongCentralStateAuthority.into());
+    // Only the authority of the role can unfreeze, the freeze authority is only allowed to freeze everything
+    if accounts.authority.key != &central_state.freeze_authority || ix_gate > 0 {
+        check_admin_authority(
+            accounts.authority,
+            accounts.role_table,
+            accounts.central_state.key,
+            &central_state,
+            &ProgramInstruction::AdminProgramFreeze,
+            program_id,
+        )?;
     }
 
+    migrate_if_needed::<CentralStateV2>(
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+    )?;
     central_state.ix_gate = ix_gate;
+    let frozen_since = central_state.exits_frozen_since;
+    central_state.record_exits_freeze(Clock::get()?.unix_timestamp);
     central_state.save(&mut accounts.central_state.data.borrow_mut())?;
 
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.authority.key,
+        change: AdminChange::ProgramFreeze { ix_gate },
+    }
+    .emit()?;
+    if central_state.exits_frozen_since != frozen_since {
+        Event::ProgramFreezeRecorded {
+            central_state: *accounts.central_state.key,
+            frozen_since: central_state.exits_frozen_since,
+        }
+        .emit()?;
+    }
+
     Ok(())
 }
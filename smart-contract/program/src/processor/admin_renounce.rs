@@ -6,10 +6,14 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{account_info::{AccountInfo, next_account_info}, entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey};
 
 use crate::error::AccessError;
+use crate::events::{AdminChange, Event};
 use crate::instruction::ProgramInstruction;
 use crate::instruction::ProgramInstruction::AdminRenounce;
 use crate::state::CentralStateV2;
-use crate::utils::{check_account_key, check_account_owner, check_signer, get_freeze_mask, is_admin_renouncable_instruction};
+use crate::utils::{
+    check_account_owner, check_admin_authority, check_signer, get_freeze_mask,
+    is_admin_renouncable_instruction,
+};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 /// The required parameters for the `admin_renounce` instruction
@@ -28,6 +32,9 @@ pub struct Accounts<'a, T> {
     /// The central state account authority
     #[cons(signer)]
     pub authority: &'a T,
+
+    /// The role table account, required once the roles are enabled
+    pub role_table: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -39,6 +46,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         let accounts = Accounts {
             central_state: next_account_info(accounts_iter)?,
             authority: next_account_info(accounts_iter)?,
+            role_table: next_account_info(accounts_iter).ok(),
         };
 
         // Check ownership
@@ -64,16 +72,20 @@ pub fn process_admin_renounce(
 
     let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&AdminRenounce)?;
-    check_account_key(
-        accounts.authority,
-        &central_state.authority,
-        AccessError::WrongCentralStateAuthority,
-    )?;
-
     if !is_admin_renouncable_instruction(&ix) {
         return Err(AccessError::InvalidRenounceParams.into());
     }
 
+    // Each role is renounced by its own authority
+    check_admin_authority(
+        accounts.authority,
+        accounts.role_table,
+        accounts.central_state.key,
+        &central_state,
+        &ix,
+        program_id,
+    )?;
+
     let renounce_mask = get_freeze_mask(vec![ix]);
     let admin_ix_gate = central_state.admin_ix_gate & renounce_mask;
 
@@ -84,5 +96,12 @@ pub fn process_admin_renounce(
     central_state.admin_ix_gate = admin_ix_gate;
     central_state.save(&mut accounts.central_state.data.borrow_mut())?;
 
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.authority.key,
+        change: AdminChange::Renounce { ix: ix as u8 },
+    }
+    .emit()?;
+
     Ok(())
 }
@@ -0,0 +1,177 @@
+//! Admin set boost config
+//! This instruction allows the central state authority to set the boost curve of the bonds
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::cpi::Cpi;
+use crate::error::AccessError;
+use crate::events::{AdminChange, Event};
+use crate::instruction::ProgramInstruction::AdminSetBoostConfig;
+use crate::state::{BoostConfig, CentralStateV2, MAX_BOOST_BASIS_POINTS};
+use crate::utils::{check_account_key, check_account_owner, check_admin_authority, check_signer};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `admin_set_boost_config` instruction
+pub struct Params {
+    /// The remaining lock duration in seconds earning the maximum boost
+    pub max_lock_duration: i64,
+    /// Boost in basis points of the amount of a bond locked for at least `max_lock_duration`
+    pub max_lock_boost_basis_points: u16,
+    /// Boost in basis points of the amount of a forever bond
+    pub forever_boost_basis_points: u16,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `admin_set_boost_config` instruction
+pub struct Accounts<'a, T> {
+    /// The central state authority
+    #[cons(signer)]
+    pub authority: &'a T,
+
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The boost config account
+    #[cons(writable)]
+    pub boost_config: &'a T,
+
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The role table account, required once the roles are enabled
+    pub role_table: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            authority: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            boost_config: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            role_table: next_account_info(accounts_iter).ok(),
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+
+        // Check signer
+        check_signer(
+            accounts.authority,
+            AccessError::CentralStateAuthorityMustSign,
+        )?;
+        check_signer(accounts.fee_payer, AccessError::OwnerMustSign)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_admin_set_boost_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let Params {
+        max_lock_duration,
+        max_lock_boost_basis_points,
+        forever_boost_basis_points,
+    } = params;
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&AdminSetBoostConfig)?;
+
+    check_admin_authority(
+        accounts.authority,
+        accounts.role_table,
+        accounts.central_state.key,
+        &central_state,
+        &AdminSetBoostConfig,
+        program_id,
+    )?;
+
+    if max_lock_duration <= 0 {
+        msg!("The maximum lock duration must be positive");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if max_lock_boost_basis_points > MAX_BOOST_BASIS_POINTS
+        || forever_boost_basis_points > MAX_BOOST_BASIS_POINTS
+    {
+        msg!(
+            "The boosts cannot exceed {} basis points",
+            MAX_BOOST_BASIS_POINTS
+        );
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (derived_key, bump_seed) = BoostConfig::create_key(accounts.central_state.key, program_id);
+    check_account_key(
+        accounts.boost_config,
+        &derived_key,
+        AccessError::AccountNotDeterministic,
+    )?;
+
+    let config = BoostConfig::new(
+        *accounts.central_state.key,
+        max_lock_duration,
+        max_lock_boost_basis_points,
+        forever_boost_basis_points,
+    );
+    if accounts.boost_config.data_is_empty() {
+        Cpi::create_account(
+            program_id,
+            accounts.system_program,
+            accounts.fee_payer,
+            accounts.boost_config,
+            &[
+                BoostConfig::SEED,
+                &accounts.central_state.key.to_bytes(),
+                &[bump_seed],
+            ],
+            config.borsh_len(),
+        )?;
+    } else {
+        check_account_owner(accounts.boost_config, program_id, AccessError::WrongOwner)?;
+        // Makes sure the existing account is a boost config
+        BoostConfig::from_account_info(accounts.boost_config)?;
+    }
+
+    config.save(&mut accounts.boost_config.data.borrow_mut())?;
+
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.authority.key,
+        change: AdminChange::BoostConfig {
+            max_lock_duration,
+            max_lock_boost_basis_points,
+            forever_boost_basis_points,
+        },
+    }
+    .emit()?;
+
+    Ok(())
+}
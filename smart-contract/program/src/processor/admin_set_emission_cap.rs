@@ -0,0 +1,114 @@
+//! Admin set the emission cap
+//! The cap bounds the supply of the mint, the claims and the admin mints exceeding it fail
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::error::AccessError;
+use crate::events::{AdminChange, Event};
+use crate::instruction::ProgramInstruction::AdminSetEmissionCap;
+use crate::state::CentralStateV2;
+use crate::utils::{
+    check_account_key, check_account_owner, check_admin_authority, check_signer, unpack_mint,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `admin_set_emission_cap` instruction
+pub struct Params {
+    /// The maximum of the supply of the mint, 0 removes the cap
+    pub emission_cap: u64,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `admin_set_emission_cap` instruction
+pub struct Accounts<'a, T> {
+    /// The central state authority
+    #[cons(signer)]
+    pub authority: &'a T,
+
+    /// The central state account
+    #[cons(writable)]
+    pub central_state: &'a T,
+
+    /// The mint of the ACS token
+    pub mint: &'a T,
+
+    /// The role table account, required once the roles are enabled
+    pub role_table: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            authority: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            mint: next_account_info(accounts_iter)?,
+            role_table: next_account_info(accounts_iter).ok(),
+        };
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+
+        // Check signer
+        check_signer(
+            accounts.authority,
+            AccessError::CentralStateAuthorityMustSign,
+        )?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_admin_set_emission_cap(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let Params { emission_cap } = params;
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&AdminSetEmissionCap)?;
+
+    check_admin_authority(
+        accounts.authority,
+        accounts.role_table,
+        accounts.central_state.key,
+        &central_state,
+        &AdminSetEmissionCap,
+        program_id,
+    )?;
+
+    check_account_key(accounts.mint, &central_state.token_mint, AccessError::WrongMint)?;
+    let supply = unpack_mint(accounts.mint)?.supply;
+    if emission_cap != 0 && emission_cap < supply {
+        msg!("The emission cap {} is below the supply {}", emission_cap, supply);
+        return Err(AccessError::EmissionCapExceeded.into());
+    }
+    if emission_cap == central_state.emission_cap {
+        return Err(AccessError::NoOp.into());
+    }
+
+    // Fails with `AccountMigrationRequired` if the central state has no emission counters yet
+    central_state.emission_cap = emission_cap;
+    central_state.save(&mut accounts.central_state.data.borrow_mut())?;
+
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.authority.key,
+        change: AdminChange::EmissionCap { emission_cap },
+    }
+    .emit()?;
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+//! Admin set keeper reward
+//! This instruction allows the central state authority to set the share of each fee distribution paid to its caller
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::cpi::Cpi;
+use crate::error::AccessError;
+use crate::events::{AdminChange, Event};
+use crate::instruction::ProgramInstruction::AdminSetKeeperReward;
+use crate::state::{CentralStateV2, FeeDistributionConfig, MAX_KEEPER_REWARD_BASIS_POINTS};
+use crate::utils::{check_account_key, check_account_owner, check_admin_authority, check_signer};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `admin_set_keeper_reward` instruction
+pub struct Params {
+    // The keeper reward basis points
+    pub keeper_reward_basis_points: u16,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `admin_set_keeper_reward` instruction
+pub struct Accounts<'a, T> {
+    /// The central state authority
+    #[cons(signer)]
+    pub authority: &'a T,
+
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The fee distribution config account
+    #[cons(writable)]
+    pub fee_distribution_config: &'a T,
+
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The role table account, required once the roles are enabled
+    pub role_table: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            authority: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            fee_distribution_config: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            role_table: next_account_info(accounts_iter).ok(),
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+
+        // Check signer
+        check_signer(
+            accounts.authority,
+            AccessError::CentralStateAuthorityMustSign,
+        )?;
+        check_signer(accounts.fee_payer, AccessError::OwnerMustSign)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_admin_set_keeper_reward(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let Params {
+        keeper_reward_basis_points,
+    } = params;
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&AdminSetKeeperReward)?;
+
+    check_admin_authority(
+        accounts.authority,
+        accounts.role_table,
+        accounts.central_state.key,
+        &central_state,
+        &AdminSetKeeperReward,
+        program_id,
+    )?;
+
+    if keeper_reward_basis_points > MAX_KEEPER_REWARD_BASIS_POINTS {
+        msg!(
+            "The keeper reward cannot exceed {} basis points",
+            MAX_KEEPER_REWARD_BASIS_POINTS
+        );
+        return Err(AccessError::InvalidAmount.into());
+    }
+
+    let (derived_key, bump_seed) =
+        FeeDistributionConfig::create_key(accounts.central_state.key, program_id);
+    check_account_key(
+        accounts.fee_distribution_config,
+        &derived_key,
+        AccessError::AccountNotDeterministic,
+    )?;
+
+    let config = FeeDistributionConfig::new(*accounts.central_state.key, keeper_reward_basis_points);
+    if accounts.fee_distribution_config.data_is_empty() {
+        Cpi::create_account(
+            program_id,
+            accounts.system_program,
+            accounts.fee_payer,
+            accounts.fee_distribution_config,
+            &[
+                FeeDistributionConfig::SEED,
+                &accounts.central_state.key.to_bytes(),
+                &[bump_seed],
+            ],
+            config.borsh_len(),
+        )?;
+    } else {
+        check_account_owner(
+            accounts.fee_distribution_config,
+            program_id,
+            AccessError::WrongOwner,
+        )?;
+        // Makes sure the existing account is a fee distribution config
+        FeeDistributionConfig::from_account_info(accounts.fee_distribution_config)?;
+    }
+
+    config.save(&mut accounts.fee_distribution_config.data.borrow_mut())?;
+
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.authority.key,
+        change: AdminChange::KeeperReward {
+            keeper_reward_basis_points,
+        },
+    }
+    .emit()?;
+
+    Ok(())
+}
@@ -0,0 +1,203 @@
+//! Admin set pool fee override
+//! This instruction allows the central state authority to set (or remove) a protocol fee override for a specific stake pool
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::error::AccessError;
+use crate::events::{AdminChange, Event};
+use crate::instruction::ProgramInstruction::AdminSetPoolFeeOverride;
+use crate::state::{CentralStateV2, PoolFeeOverride, StakePool, Tag};
+use crate::utils::{
+    check_account_key, check_account_owner, check_admin_authority, check_pool_central_state,
+    check_signer,
+};
+use crate::cpi::Cpi;
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `admin_set_pool_fee_override` instruction
+pub struct Params {
+    // The overriding protocol fee basis points, `None` removes the override
+    pub fee_basis_points: Option<u16>,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `admin_set_pool_fee_override` instruction
+pub struct Accounts<'a, T> {
+    /// The central state authority
+    #[cons(signer)]
+    pub authority: &'a T,
+
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The stake pool account
+    pub stake_pool: &'a T,
+
+    /// The pool fee override account
+    #[cons(writable)]
+    pub pool_fee_override: &'a T,
+
+    /// The fee payer account (receives the lamports when the override is removed)
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The role table account, required once the roles are enabled
+    pub role_table: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            authority: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            stake_pool: next_account_info(accounts_iter)?,
+            pool_fee_override: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            role_table: next_account_info(accounts_iter).ok(),
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.stake_pool,
+            program_id,
+            AccessError::WrongStakePoolAccountOwner,
+        )?;
+
+        // Check signer
+        check_signer(
+            accounts.authority,
+            AccessError::CentralStateAuthorityMustSign,
+        )?;
+        check_signer(accounts.fee_payer, AccessError::OwnerMustSign)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_admin_set_pool_fee_override(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let Params { fee_basis_points } = params;
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&AdminSetPoolFeeOverride)?;
+
+    check_admin_authority(
+        accounts.authority,
+        accounts.role_table,
+        accounts.central_state.key,
+        &central_state,
+        &AdminSetPoolFeeOverride,
+        program_id,
+    )?;
+
+    // Only checks that the account is a stake pool of the central state
+    let stake_pool = StakePool::get_checked(
+        accounts.stake_pool,
+        vec![Tag::StakePool, Tag::InactiveStakePool],
+    )?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+
+    let (derived_key, bump_seed) = PoolFeeOverride::create_key(accounts.stake_pool.key, program_id);
+    check_account_key(
+        accounts.pool_fee_override,
+        &derived_key,
+        AccessError::AccountNotDeterministic,
+    )?;
+
+    match fee_basis_points {
+        Some(fee_basis_points) => {
+            if fee_basis_points > 10000 {
+                return Err(AccessError::InvalidAmount.into());
+            }
+            let pool_fee_override = PoolFeeOverride::new(*accounts.stake_pool.key, fee_basis_points);
+            if accounts.pool_fee_override.data_is_empty() {
+                Cpi::create_account(
+                    program_id,
+                    accounts.system_program,
+                    accounts.fee_payer,
+                    accounts.pool_fee_override,
+                    &[
+                        PoolFeeOverride::SEED,
+                        &accounts.stake_pool.key.to_bytes(),
+                        &[bump_seed],
+                    ],
+                    pool_fee_override.borsh_len(),
+                )?;
+            } else {
+                check_account_owner(
+                    accounts.pool_fee_override,
+                    program_id,
+                    AccessError::WrongOwner,
+                )?;
+                // Makes sure the existing account is a fee override
+                PoolFeeOverride::from_account_info(accounts.pool_fee_override)?;
+            }
+            msg!("Setting the pool fee override to {}", fee_basis_points);
+            pool_fee_override.save(&mut accounts.pool_fee_override.data.borrow_mut())?;
+        }
+        None => {
+            check_account_owner(
+                accounts.pool_fee_override,
+                program_id,
+                AccessError::WrongOwner,
+            )?;
+            let mut pool_fee_override =
+                PoolFeeOverride::from_account_info(accounts.pool_fee_override)?;
+            msg!("Removing the pool fee override");
+            pool_fee_override.close();
+            pool_fee_override.save(&mut accounts.pool_fee_override.data.borrow_mut())?;
+
+            let mut account_lamports = accounts.pool_fee_override.lamports.borrow_mut();
+            let mut destination_lamports = accounts.fee_payer.lamports.borrow_mut();
+
+            **destination_lamports += **account_lamports;
+            **account_lamports = 0;
+        }
+    }
+
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.authority.key,
+        change: AdminChange::PoolFeeOverride {
+            stake_pool: *accounts.stake_pool.key,
+            fee_basis_points,
+        },
+    }
+    .emit()?;
+
+    Ok(())
+}
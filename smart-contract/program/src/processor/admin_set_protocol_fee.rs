@@ -4,9 +4,10 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{account_info::{AccountInfo, next_account_info}, entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey};
 
 use crate::error::AccessError;
+use crate::events::{AdminChange, Event};
 use crate::instruction::ProgramInstruction::AdminSetProtocolFee;
 use crate::state::CentralStateV2;
-use crate::utils::{check_account_key, check_account_owner, check_signer};
+use crate::utils::{check_account_owner, check_admin_authority, check_signer};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 /// The required parameters for the `admin_set_protocol_fee` instruction
@@ -25,6 +26,9 @@ pub struct Accounts<'a, T> {
     /// The central state account
     #[cons(writable)]
     pub central_state: &'a T,
+
+    /// The role table account, required once the roles are enabled
+    pub role_table: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -36,6 +40,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         let accounts = Accounts {
             authority: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
+            role_table: next_account_info(accounts_iter).ok(),
         };
 
         // Check ownership
@@ -62,10 +67,13 @@ pub fn process_admin_set_protocol_fee(
     let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&AdminSetProtocolFee)?;
 
-    check_account_key(
+    check_admin_authority(
         accounts.authority,
-        &central_state.authority,
-        AccessError::WrongCentralStateAuthority,
+        accounts.role_table,
+        accounts.central_state.key,
+        &central_state,
+        &AdminSetProtocolFee,
+        program_id,
     )?;
 
     // Even though the protocol fee is added to the transaction (so we could do more than 100%),
@@ -77,5 +85,14 @@ pub fn process_admin_set_protocol_fee(
     central_state.fee_basis_points = protocol_fee_basis_points;
     central_state.save(&mut accounts.central_state.data.borrow_mut())?;
 
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.authority.key,
+        change: AdminChange::ProtocolFee {
+            fee_basis_points: protocol_fee_basis_points,
+        },
+    }
+    .emit()?;
+
     Ok(())
 }
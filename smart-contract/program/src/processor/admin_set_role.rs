@@ -0,0 +1,176 @@
+//! Admin set role
+//! This instruction delegates an admin instruction to its own authority. The first call enables the
+//! roles: the role table becomes the authority of the central state and the previous authority
+//! becomes the admin of the table, keeping the admin instructions without a role.
+//! A role is transferred or removed by its current authority and renounced with `AdminRenounce`.
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::cpi::Cpi;
+use crate::error::AccessError;
+use crate::events::{AdminChange, Event};
+use crate::instruction::ProgramInstruction;
+use crate::instruction::ProgramInstruction::AdminSetRole;
+use crate::state::{CentralStateV2, RoleTable};
+use crate::utils::{
+    check_account_key, check_account_owner, check_signer, get_freeze_mask,
+    is_admin_renouncable_instruction,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `admin_set_role` instruction
+pub struct Params {
+    /// The admin instruction of the role
+    pub ix: ProgramInstruction,
+    /// The new authority of the role, the admin of the role table removes the role
+    pub authority: Pubkey,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `admin_set_role` instruction
+pub struct Accounts<'a, T> {
+    /// The current authority of the role
+    #[cons(signer)]
+    pub authority: &'a T,
+
+    /// The central state account
+    #[cons(writable)]
+    pub central_state: &'a T,
+
+    /// The role table account
+    #[cons(writable)]
+    pub role_table: &'a T,
+
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            authority: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            role_table: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+
+        // Check signer
+        check_signer(
+            accounts.authority,
+            AccessError::CentralStateAuthorityMustSign,
+        )?;
+        check_signer(accounts.fee_payer, AccessError::OwnerMustSign)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_admin_set_role(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let Params { ix, authority } = params;
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&AdminSetRole)?;
+
+    if !is_admin_renouncable_instruction(&ix) {
+        return Err(AccessError::NotAnAdminInstruction.into());
+    }
+    if central_state.admin_ix_gate & !get_freeze_mask(vec![ix]) == 0 {
+        msg!("The role has been renounced");
+        return Err(AccessError::AlreadyRenounced.into());
+    }
+
+    let (derived_key, bump_seed) = RoleTable::create_key(accounts.central_state.key, program_id);
+    check_account_key(
+        accounts.role_table,
+        &derived_key,
+        AccessError::AccountNotDeterministic,
+    )?;
+
+    let mut role_table = if central_state.authority == derived_key {
+        RoleTable::get_checked(accounts.role_table, accounts.central_state.key, program_id)?
+    } else {
+        // Enables the roles, the role table becomes the authority of the central state
+        check_account_key(
+            accounts.authority,
+            &central_state.authority,
+            AccessError::WrongCentralStateAuthority,
+        )?;
+        if accounts.role_table.data_is_empty() {
+            Cpi::create_account(
+                program_id,
+                accounts.system_program,
+                accounts.fee_payer,
+                accounts.role_table,
+                &[
+                    RoleTable::SEED,
+                    &accounts.central_state.key.to_bytes(),
+                    &[bump_seed],
+                ],
+                RoleTable::LEN,
+            )?;
+        } else {
+            check_account_owner(accounts.role_table, program_id, AccessError::WrongOwner)?;
+            // Makes sure the existing account is a role table
+            RoleTable::from_account_info(accounts.role_table)?;
+        }
+        let role_table = RoleTable::new(*accounts.central_state.key, central_state.authority);
+        central_state.authority = derived_key;
+        central_state.save(&mut accounts.central_state.data.borrow_mut())?;
+        role_table
+    };
+
+    check_account_key(
+        accounts.authority,
+        &role_table.authority(&ix),
+        AccessError::WrongCentralStateAuthority,
+    )?;
+    if role_table.authority(&ix) == authority {
+        return Err(AccessError::NoOp.into());
+    }
+    role_table.set_role(&ix, authority)?;
+    role_table.save(&mut accounts.role_table.data.borrow_mut())?;
+
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.authority.key,
+        change: AdminChange::AdminRole {
+            ix: ix as u8,
+            authority,
+        },
+    }
+    .emit()?;
+
+    Ok(())
+}
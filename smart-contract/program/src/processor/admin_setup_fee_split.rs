@@ -13,12 +13,13 @@ use solana_program::clock::Clock;
 use solana_program::sysvar::Sysvar;
 
 use crate::error::AccessError;
+use crate::events::{AdminChange, Event};
 use crate::instruction::ProgramInstruction::AdminSetupFeeSplit;
 use crate::state::{
     FeeRecipient, MAX_FEE_RECIPIENTS, MAX_FEE_SPLIT_SETUP_DELAY,
 };
 use crate::state::CentralStateV2;
-use crate::utils::{check_account_key, check_account_owner, check_signer};
+use crate::utils::{check_account_owner, check_admin_authority, check_signer};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 /// The required parameters for the `admin_setup_fee_split` instruction
@@ -36,6 +37,9 @@ pub struct Accounts<'a, T> {
     /// The central state account
     #[cons(writable)]
     pub central_state: &'a T,
+
+    /// The role table account, required once the roles are enabled
+    pub role_table: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -47,6 +51,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         let accounts = Accounts {
             authority: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
+            role_table: next_account_info(accounts_iter).ok(),
         };
 
         // Check ownership
@@ -72,10 +77,13 @@ pub fn process_admin_setup_fee_split(
     let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&AdminSetupFeeSplit)?;
 
-    check_account_key(
+    check_admin_authority(
         accounts.authority,
-        &central_state.authority,
-        AccessError::WrongCentralStateAuthority,
+        accounts.role_table,
+        accounts.central_state.key,
+        &central_state,
+        &AdminSetupFeeSplit,
+        program_id,
     )?;
 
     // Check if right number of recipients
@@ -112,5 +120,15 @@ pub fn process_admin_setup_fee_split(
 
     // replace the recipients
     central_state.save(&mut accounts.central_state.data.borrow_mut())?;
+
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.authority.key,
+        change: AdminChange::FeeSplit {
+            recipients: central_state.recipients,
+        },
+    }
+    .emit()?;
+
     Ok(())
 }
@@ -0,0 +1,311 @@
+//! Buy a listed bond V2
+//! The buyer pays the price of the listing to the seller and receives the escrowed bond, the
+//! rewards earned by the bond while listed are minted to the seller
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::cpi::Cpi;
+use crate::error::AccessError;
+use crate::events::{ClaimKind, Event};
+use crate::instruction::ProgramInstruction::BuyBondV2;
+use crate::processor::transfer_bond_v2::move_bond_v2;
+use crate::rewards::bond_v2_reward;
+use crate::state::{BondV2Account, BondV2Listing, CentralStateV2, Emission, StakePool, Tag};
+use crate::utils::{
+    calc_reward_fp32, check_account_key, check_account_owner, check_pool_central_state,
+    check_signer, check_token_program, unpack_mint, unpack_token_account,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `buy_bond_v2` instruction
+pub struct Params {
+    /// The price of the listing, protects the buyer from a change of the listing
+    pub price: u64,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `buy_bond_v2` instruction
+pub struct Accounts<'a, T> {
+    /// The central state account
+    #[cons(writable)]
+    pub central_state: &'a T,
+
+    /// The listing account
+    #[cons(writable)]
+    pub listing: &'a T,
+
+    /// The bond V2 account of the listing escrowing the bond
+    #[cons(writable)]
+    pub escrow: &'a T,
+
+    /// The bond V2 account of the buyer
+    #[cons(writable)]
+    pub buyer_bond_v2_account: &'a T,
+
+    /// The buyer
+    #[cons(signer)]
+    pub buyer: &'a T,
+
+    /// The seller, receives the rent of the listing and the escrow
+    #[cons(writable)]
+    pub seller: &'a T,
+
+    /// The quote token account of the buyer
+    #[cons(writable)]
+    pub quote_source: &'a T,
+
+    /// The quote token account of the seller
+    #[cons(writable)]
+    pub quote_destination: &'a T,
+
+    /// The quote mint
+    pub quote_mint: &'a T,
+
+    /// The token program of the quote mint
+    pub spl_token_program: &'a T,
+
+    /// The pool account
+    pub pool: &'a T,
+
+    /// The fee payer account, pays the rent of the bond account of the buyer
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The token mint of the central state
+    #[cons(writable)]
+    pub mint: &'a T,
+
+    /// The token account of the seller receiving the rewards earned by the bond while listed
+    #[cons(writable)]
+    pub seller_rewards_destination: &'a T,
+
+    /// The token program of the central state mint
+    pub rewards_token_program: &'a T,
+
+    /// The accounts of the transfer hook of the quote mint
+    pub transfer_hook_accounts: &'a [T],
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            central_state: next_account_info(accounts_iter)?,
+            listing: next_account_info(accounts_iter)?,
+            escrow: next_account_info(accounts_iter)?,
+            buyer_bond_v2_account: next_account_info(accounts_iter)?,
+            buyer: next_account_info(accounts_iter)?,
+            seller: next_account_info(accounts_iter)?,
+            quote_source: next_account_info(accounts_iter)?,
+            quote_destination: next_account_info(accounts_iter)?,
+            quote_mint: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            pool: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            mint: next_account_info(accounts_iter)?,
+            seller_rewards_destination: next_account_info(accounts_iter)?,
+            rewards_token_program: next_account_info(accounts_iter)?,
+            transfer_hook_accounts: accounts_iter.as_slice(),
+        };
+
+        // Check keys
+        check_token_program(accounts.spl_token_program)?;
+        check_token_program(accounts.rewards_token_program)?;
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+        check_account_owner(accounts.listing, program_id, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.escrow,
+            program_id,
+            AccessError::WrongBondAccountOwner,
+        )?;
+        check_account_owner(
+            accounts.pool,
+            program_id,
+            AccessError::WrongStakePoolAccountOwner,
+        )?;
+        check_account_owner(
+            accounts.quote_mint,
+            accounts.spl_token_program.key,
+            AccessError::WrongOwner,
+        )?;
+        check_account_owner(
+            accounts.mint,
+            accounts.rewards_token_program.key,
+            AccessError::WrongOwner,
+        )?;
+        check_account_owner(
+            accounts.seller_rewards_destination,
+            accounts.rewards_token_program.key,
+            AccessError::WrongTokenAccountOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.buyer, AccessError::BuyerMustSign)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_buy_bond_v2(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let Params { price } = params;
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&BuyBondV2)?;
+    let stake_pool = StakePool::get_checked(accounts.pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+    let mut listing = BondV2Listing::from_account_info(accounts.listing)?;
+    let mut bond = BondV2Account::from_account_info(accounts.escrow)?;
+
+    check_account_key(accounts.seller, &listing.seller, AccessError::WrongOwner)?;
+    check_account_key(
+        accounts.escrow,
+        &listing.escrow,
+        AccessError::WrongBondAccountOwner,
+    )?;
+    check_account_key(accounts.listing, &bond.owner, AccessError::WrongOwner)?;
+    check_account_key(accounts.pool, &bond.pool, AccessError::WrongStakePool)?;
+    check_account_key(
+        accounts.quote_mint,
+        &listing.quote_mint,
+        AccessError::WrongQuoteMint,
+    )?;
+    check_account_key(
+        accounts.quote_destination,
+        &listing.quote_destination,
+        AccessError::WrongQuoteDestination,
+    )?;
+    if price != listing.price {
+        return Err(AccessError::ListingPriceMismatch.into());
+    }
+    check_account_key(
+        accounts.mint,
+        &central_state.token_mint,
+        AccessError::WrongMint,
+    )?;
+    let seller_rewards_destination = unpack_token_account(accounts.seller_rewards_destination)?;
+    if seller_rewards_destination.mint != central_state.token_mint {
+        return Err(AccessError::WrongMint.into());
+    }
+    if seller_rewards_destination.owner != listing.seller {
+        return Err(AccessError::WrongTokenAccountOwner.into());
+    }
+
+    // The rewards earned while listed belong to the seller (checks if the pool is cranked as well)
+    let reward_fp32 = calc_reward_fp32(
+        central_state.last_snapshot_offset,
+        bond.last_claimed_offset,
+        Some(bond.last_claimed_index),
+        &stake_pool,
+        true,
+        true,
+    )?;
+    let reward = bond_v2_reward(reward_fp32, &bond)?;
+    bond.last_claimed_offset = central_state.last_snapshot_offset;
+    bond.last_claimed_index = stake_pool.stakers_reward_index();
+    bond.unclaimed_rewards = 0;
+
+    if reward > 0 {
+        msg!("Settling the rewards of the listing {}", reward);
+        central_state.check_emission_cap(unpack_mint(accounts.mint)?.supply, reward)?;
+        central_state.record_emission(Emission::StakerRewards, reward)?;
+        Cpi::mint_to(
+            accounts.rewards_token_program,
+            accounts.mint,
+            accounts.seller_rewards_destination,
+            accounts.central_state,
+            reward,
+            &[&central_state.signer_seeds(accounts.central_state.key, program_id)],
+        )?;
+        central_state.save(&mut accounts.central_state.data.borrow_mut())?;
+        Event::Claim {
+            central_state: *accounts.central_state.key,
+            stake_pool: *accounts.pool.key,
+            kind: ClaimKind::BondV2,
+            account: *accounts.escrow.key,
+            owner: listing.seller,
+            destination: *accounts.seller_rewards_destination.key,
+            reward,
+        }
+        .emit()?;
+    }
+
+    // Pay the seller
+    Cpi::transfer(
+        accounts.spl_token_program,
+        accounts.quote_source,
+        accounts.quote_destination,
+        accounts.buyer,
+        accounts.quote_mint,
+        accounts.transfer_hook_accounts,
+        price,
+        &[],
+    )?;
+
+    move_bond_v2(
+        program_id,
+        accounts.central_state.key,
+        bond,
+        accounts.escrow,
+        accounts.buyer_bond_v2_account,
+        *accounts.buyer.key,
+        accounts.fee_payer,
+        accounts.seller,
+        accounts.system_program,
+    )?;
+
+    // Close the listing
+    listing.close();
+    listing.save(&mut accounts.listing.data.borrow_mut())?;
+
+    let mut listing_lamports = accounts.listing.lamports.borrow_mut();
+    let mut seller_lamports = accounts.seller.lamports.borrow_mut();
+
+    **seller_lamports += **listing_lamports;
+    **listing_lamports = 0;
+
+    Event::BondSold {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.pool.key,
+        listing: *accounts.listing.key,
+        seller: listing.seller,
+        buyer: *accounts.buyer.key,
+        quote_mint: listing.quote_mint,
+        price,
+    }
+    .emit()?;
+
+    Ok(())
+}
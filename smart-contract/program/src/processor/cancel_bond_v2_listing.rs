@@ -0,0 +1,164 @@
+//! Cancel the listing of a bond V2
+//! The escrowed bond goes back to the bond V2 account of the seller
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::error::AccessError;
+use crate::events::Event;
+use crate::instruction::ProgramInstruction::CancelBondV2Listing;
+use crate::processor::transfer_bond_v2::move_bond_v2;
+use crate::state::{BondV2Account, BondV2Listing, CentralStateV2, StakePool, Tag};
+use crate::utils::{
+    check_account_key, check_account_owner, check_pool_central_state, check_signer,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `cancel_bond_v2_listing` instruction
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `cancel_bond_v2_listing` instruction
+pub struct Accounts<'a, T> {
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The listing account
+    #[cons(writable)]
+    pub listing: &'a T,
+
+    /// The bond V2 account of the listing escrowing the bond
+    #[cons(writable)]
+    pub escrow: &'a T,
+
+    /// The bond V2 account of the seller
+    #[cons(writable)]
+    pub bond_v2_account: &'a T,
+
+    /// The seller, receives the rent of the listing and the escrow
+    #[cons(writable, signer)]
+    pub seller: &'a T,
+
+    /// The pool account
+    pub pool: &'a T,
+
+    /// The fee payer account, pays the rent of the bond account of the seller
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            central_state: next_account_info(accounts_iter)?,
+            listing: next_account_info(accounts_iter)?,
+            escrow: next_account_info(accounts_iter)?,
+            bond_v2_account: next_account_info(accounts_iter)?,
+            seller: next_account_info(accounts_iter)?,
+            pool: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+        check_account_owner(accounts.listing, program_id, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.escrow,
+            program_id,
+            AccessError::WrongBondAccountOwner,
+        )?;
+        check_account_owner(
+            accounts.pool,
+            program_id,
+            AccessError::WrongStakePoolAccountOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.seller, AccessError::OwnerMustSign)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_cancel_bond_v2_listing(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&CancelBondV2Listing)?;
+    let stake_pool = StakePool::get_checked(accounts.pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+    let mut listing = BondV2Listing::from_account_info(accounts.listing)?;
+    let bond = BondV2Account::from_account_info(accounts.escrow)?;
+
+    check_account_key(accounts.seller, &listing.seller, AccessError::WrongOwner)?;
+    check_account_key(
+        accounts.escrow,
+        &listing.escrow,
+        AccessError::WrongBondAccountOwner,
+    )?;
+    check_account_key(accounts.listing, &bond.owner, AccessError::WrongOwner)?;
+    check_account_key(accounts.pool, &bond.pool, AccessError::WrongStakePool)?;
+
+    move_bond_v2(
+        program_id,
+        accounts.central_state.key,
+        bond,
+        accounts.escrow,
+        accounts.bond_v2_account,
+        listing.seller,
+        accounts.fee_payer,
+        accounts.seller,
+        accounts.system_program,
+    )?;
+
+    // Close the listing
+    listing.close();
+    listing.save(&mut accounts.listing.data.borrow_mut())?;
+
+    let mut listing_lamports = accounts.listing.lamports.borrow_mut();
+    let mut seller_lamports = accounts.seller.lamports.borrow_mut();
+
+    **seller_lamports += **listing_lamports;
+    **listing_lamports = 0;
+
+    Event::BondListingCancelled {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.pool.key,
+        listing: *accounts.listing.key,
+        seller: listing.seller,
+    }
+    .emit()?;
+
+    Ok(())
+}
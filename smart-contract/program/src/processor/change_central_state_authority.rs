@@ -8,11 +8,12 @@ use solana_program::{
 };
 
 use crate::{error::AccessError};
+use crate::events::{AdminChange, Event};
 use bonfida_utils::{BorshSize, InstructionsAccount};
 use crate::instruction::ProgramInstruction::ChangeCentralStateAuthority;
 
-use crate::utils::{check_account_key, check_account_owner, check_signer};
-use crate::state:: CentralStateV2;
+use crate::utils::{check_account_owner, check_admin_authority, check_signer};
+use crate::state::{CentralStateV2, RoleTable};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 /// The required parameters for the `change_central_state_authority` instruction
@@ -31,6 +32,10 @@ pub struct Accounts<'a, T> {
     /// The central state account authority
     #[cons(signer)]
     pub authority: &'a T,
+
+    /// The role table account, required once the roles are enabled
+    #[cons(writable)]
+    pub role_table: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -42,6 +47,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         let accounts = Accounts {
             central_state: next_account_info(accounts_iter)?,
             authority: next_account_info(accounts_iter)?,
+            role_table: next_account_info(accounts_iter).ok(),
         };
 
         // Check ownership
@@ -67,14 +73,37 @@ pub fn process_change_central_state_auth(
     let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&ChangeCentralStateAuthority)?;
 
-    check_account_key(
+    check_admin_authority(
         accounts.authority,
-        &central_state.authority,
-        AccessError::WrongCentralStateAuthority,
+        accounts.role_table,
+        accounts.central_state.key,
+        &central_state,
+        &ChangeCentralStateAuthority,
+        program_id,
     )?;
 
-    central_state.authority = params.new_authority;
-    central_state.save(&mut accounts.central_state.data.borrow_mut())?;
+    // With the roles enabled the role table stays the central state authority, its admin changes
+    match accounts.role_table {
+        Some(role_table) if role_table.key == &central_state.authority => {
+            let mut table =
+                RoleTable::get_checked(role_table, accounts.central_state.key, program_id)?;
+            table.admin = params.new_authority;
+            table.save(&mut role_table.data.borrow_mut())?;
+        }
+        _ => {
+            central_state.authority = params.new_authority;
+            central_state.save(&mut accounts.central_state.data.borrow_mut())?;
+        }
+    }
+
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.authority.key,
+        change: AdminChange::Authority {
+            new_authority: params.new_authority,
+        },
+    }
+    .emit()?;
 
     Ok(())
 }
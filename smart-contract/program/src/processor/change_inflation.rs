@@ -2,11 +2,14 @@
 use bonfida_utils::{BorshSize, InstructionsAccount};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{account_info::{AccountInfo, next_account_info}, entrypoint::ProgramResult, msg, program_error::ProgramError, pubkey::Pubkey};
-use solana_program::program_pack::Pack;
 
 use crate::{error::AccessError};
+use crate::events::{AdminChange, Event};
 use crate::instruction::ProgramInstruction::ChangeInflation;
-use crate::utils::{check_account_key, check_account_owner, check_signer};
+use crate::utils::{
+    check_account_key, check_account_owner, check_admin_authority, check_signer,
+    check_token_program_owner, unpack_mint,
+};
 use crate::state:: CentralStateV2;
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
@@ -29,6 +32,9 @@ pub struct Accounts<'a, T> {
 
     /// The mint address of the ACCESS token
     pub mint: &'a T,
+
+    /// The role table account, required once the roles are enabled
+    pub role_table: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -41,11 +47,12 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             central_state: next_account_info(accounts_iter)?,
             authority: next_account_info(accounts_iter)?,
             mint: next_account_info(accounts_iter)?,
+            role_table: next_account_info(accounts_iter).ok(),
         };
 
         // Check ownership
         check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
-        check_account_owner(accounts.mint, &spl_token::ID, AccessError::WrongOwner)?;
+        check_token_program_owner(accounts.mint, AccessError::WrongOwner)?;
 
         // Check signer
         check_signer(
@@ -80,7 +87,7 @@ pub fn process_change_inflation(
         return   Err(AccessError::PoolMustBeCranked.into());
     }
 
-    let token_mint = spl_token::state::Mint::unpack_from_slice(&accounts.mint.data.clone().borrow_mut())?;
+    let token_mint = unpack_mint(accounts.mint)?;
 
     let supply = token_mint.supply;
     let annual_inflation = params.daily_inflation * 365;
@@ -89,14 +96,26 @@ pub fn process_change_inflation(
         return Err(AccessError::InvalidAmount.into());
     }
 
-    check_account_key(
+    check_admin_authority(
         accounts.authority,
-        &central_state.authority,
-        AccessError::WrongCentralStateAuthority,
+        accounts.role_table,
+        accounts.central_state.key,
+        &central_state,
+        &ChangeInflation,
+        program_id,
     )?;
 
     central_state.daily_inflation = params.daily_inflation;
     central_state.save(&mut accounts.central_state.data.borrow_mut())?;
 
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.authority.key,
+        change: AdminChange::Inflation {
+            daily_inflation: params.daily_inflation,
+        },
+    }
+    .emit()?;
+
     Ok(())
 }
@@ -0,0 +1,180 @@
+//! Change the controls of a pool
+//! This instruction allows a pool owner to cap the total staked of its pool, to pause the new
+//! stakes and to restrict them to the stakers of its allowlist. The stakers can always unstake
+//! and claim
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::cpi::Cpi;
+use crate::error::AccessError;
+use crate::events::{AdminChange, Event};
+use crate::instruction::ProgramInstruction::ChangePoolControls;
+use crate::state::{CentralStateV2, PoolControls, StakePool, StakePoolHeader, Tag};
+use crate::utils::{
+    check_account_key, check_account_owner, check_pool_central_state, check_signer,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `change_pool_controls` instruction
+pub struct Params {
+    /// The maximum total staked of the pool, 0 for no cap
+    pub max_total_staked: u64,
+    /// Rejects the new stakes
+    pub paused: bool,
+    /// Only accepts the new stakes of the stakers with an allowlist entry
+    pub allowlist_enabled: bool,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `change_pool_controls` instruction
+pub struct Accounts<'a, T> {
+    /// The stake pool account
+    #[cons(writable)]
+    pub stake_pool: &'a T,
+
+    /// The stake pool owner account
+    #[cons(signer)]
+    pub stake_pool_owner: &'a T,
+
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The pool controls account
+    #[cons(writable)]
+    pub pool_controls: &'a T,
+
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            stake_pool: next_account_info(accounts_iter)?,
+            stake_pool_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            pool_controls: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.stake_pool,
+            program_id,
+            AccessError::WrongStakePoolAccountOwner,
+        )?;
+
+        // Check signers
+        check_signer(
+            accounts.stake_pool_owner,
+            AccessError::StakePoolOwnerMustSign,
+        )?;
+        check_signer(accounts.fee_payer, AccessError::OwnerMustSign)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_change_pool_controls(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let Params {
+        max_total_staked,
+        paused,
+        allowlist_enabled,
+    } = params;
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&ChangePoolControls)?;
+
+    let mut stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+    check_account_key(
+        accounts.stake_pool_owner,
+        &Pubkey::from(stake_pool.header.owner),
+        AccessError::StakeAccountOwnerMismatch,
+    )?;
+
+    let (derived_key, bump_seed) = PoolControls::create_key(accounts.stake_pool.key, program_id);
+    check_account_key(
+        accounts.pool_controls,
+        &derived_key,
+        AccessError::AccountNotDeterministic,
+    )?;
+
+    let pool_controls = PoolControls::new(
+        *accounts.stake_pool.key,
+        max_total_staked,
+        paused,
+        allowlist_enabled,
+    );
+    if accounts.pool_controls.data_is_empty() {
+        Cpi::create_account(
+            program_id,
+            accounts.system_program,
+            accounts.fee_payer,
+            accounts.pool_controls,
+            &[
+                PoolControls::SEED,
+                &accounts.stake_pool.key.to_bytes(),
+                &[bump_seed],
+            ],
+            pool_controls.borsh_len(),
+        )?;
+    } else {
+        check_account_owner(accounts.pool_controls, program_id, AccessError::WrongOwner)?;
+        // Makes sure the existing account is a pool controls account
+        PoolControls::from_account_info(accounts.pool_controls)?;
+    }
+
+    pool_controls.save(&mut accounts.pool_controls.data.borrow_mut())?;
+    // The new stakes of the pool require its pool controls from now on
+    stake_pool.header.flags |= StakePoolHeader::CONTROLS_FLAG;
+
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.stake_pool_owner.key,
+        change: AdminChange::PoolControls {
+            stake_pool: *accounts.stake_pool.key,
+            max_total_staked,
+            paused,
+            allowlist_enabled,
+        },
+    }
+    .emit()?;
+
+    Ok(())
+}
@@ -9,11 +9,12 @@ use solana_program::{
 };
 
 use crate::state::StakePool;
+use crate::events::{AdminChange, Event};
 use crate::{error::AccessError, state::Tag};
 use bonfida_utils::{BorshSize, InstructionsAccount};
 use crate::instruction::ProgramInstruction::ChangePoolMinimum;
 
-use crate::utils::{check_account_key, check_account_owner, check_signer};
+use crate::utils::{check_account_key, check_account_owner, check_signer, check_pool_central_state};
 use crate::state:: CentralStateV2;
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
@@ -80,6 +81,13 @@ pub fn process_change_pool_minimum(
     central_state.assert_instruction_allowed(&ChangePoolMinimum)?;
 
     let mut stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
 
     check_account_key(
         accounts.stake_pool_owner,
@@ -89,5 +97,15 @@ pub fn process_change_pool_minimum(
 
     stake_pool.header.minimum_stake_amount = new_minimum;
 
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.stake_pool_owner.key,
+        change: AdminChange::PoolMinimum {
+            stake_pool: *accounts.stake_pool.key,
+            minimum_stake_amount: new_minimum,
+        },
+    }
+    .emit()?;
+
     Ok(())
 }
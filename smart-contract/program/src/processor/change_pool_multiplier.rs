@@ -9,12 +9,13 @@ use solana_program::{
     pubkey::Pubkey,
 };
 use crate::state:: CentralStateV2;
+use crate::events::{AdminChange, Event};
 use crate::state::StakePool;
 use crate::{error::AccessError, state::Tag};
 use bonfida_utils::{BorshSize, InstructionsAccount};
 use crate::instruction::ProgramInstruction::ChangePoolMultiplier;
 
-use crate::utils::{check_account_key, check_account_owner, check_signer};
+use crate::utils::{check_account_key, check_account_owner, check_signer, check_pool_central_state};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 /// The required parameters for the `change_pool_multiplier` instruction
@@ -80,6 +81,13 @@ pub fn process_change_pool_multiplier(
     central_state.assert_instruction_allowed(&ChangePoolMultiplier)?;
 
     let mut stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
 
     if new_multiplier > 100 {
         msg!("The pool multiplier is a percentage and needs to be smaller than 100 and greater than 0");
@@ -94,5 +102,15 @@ pub fn process_change_pool_multiplier(
 
     stake_pool.header.stakers_part = new_multiplier;
 
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.stake_pool_owner.key,
+        change: AdminChange::PoolMultiplier {
+            stake_pool: *accounts.stake_pool.key,
+            stakers_part: new_multiplier,
+        },
+    }
+    .emit()?;
+
     Ok(())
 }
@@ -5,19 +5,20 @@ use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     msg,
-    program::{invoke, invoke_signed},
     program_error::ProgramError,
     pubkey::Pubkey,
 };
 
 use crate::state::{BondAccount, StakePool, BOND_SIGNER_THRESHOLD, V1_INSTRUCTIONS_ALLOWED};
-use crate::{error::AccessError, state::Tag};
+use crate::{cpi::Cpi, error::AccessError, state::Tag};
 use bonfida_utils::{BorshSize, InstructionsAccount};
-use spl_token;
 use crate::instruction::ProgramInstruction::ClaimBond;
 
-use crate::utils::{assert_bond_derivation, check_account_key, check_account_owner, check_signer};
-use crate::state:: CentralStateV2;
+use crate::utils::{
+    assert_bond_derivation, check_account_key, check_account_owner, check_signer,
+    check_token_program, check_pool_central_state, unpack_mint,
+};
+use crate::state::{CentralStateV2, Emission};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 /// The required parameters for the `claim_bond` instruction
@@ -60,6 +61,9 @@ pub struct Accounts<'a, T> {
 
     /// The SPL token program account
     pub spl_token_program: &'a T,
+
+    /// The mint of the quote token, required when the bond has a quote amount
+    pub quote_mint: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -78,14 +82,11 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             pool_vault: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
             spl_token_program: next_account_info(accounts_iter)?,
+            quote_mint: next_account_info(accounts_iter).ok(),
         };
 
         // Check keys
-        check_account_key(
-            accounts.spl_token_program,
-            &spl_token::id(),
-            AccessError::WrongSplTokenProgramId,
-        )?;
+        check_token_program(accounts.spl_token_program)?;
 
         // Check ownership
         check_account_owner(accounts.bond_account, program_id, AccessError::WrongOwner)?;
@@ -110,6 +111,13 @@ pub fn process_claim_bond(
     let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&ClaimBond)?;
     let mut stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
 
     check_account_key(
         accounts.stake_pool,
@@ -152,23 +160,18 @@ pub fn process_claim_bond(
         msg!("Checking buyer signature");
         // Check signer
         check_signer(accounts.buyer, AccessError::BuyerMustSign)?;
+        let quote_mint = accounts.quote_mint.ok_or(AccessError::MintNotProvided)?;
+        check_account_key(quote_mint, &bond.quote_mint, AccessError::WrongMint)?;
         // Transfer tokens
-        let transfer_ix = spl_token::instruction::transfer(
-            &spl_token::ID,
-            accounts.quote_token_source.key,
-            accounts.quote_token_destination.key,
-            accounts.buyer.key,
+        Cpi::transfer(
+            accounts.spl_token_program,
+            accounts.quote_token_source,
+            accounts.quote_token_destination,
+            accounts.buyer,
+            quote_mint,
             &[],
             bond.total_quote_amount,
-        )?;
-        invoke(
-            &transfer_ix,
-            &[
-                accounts.spl_token_program.clone(),
-                accounts.quote_token_destination.clone(),
-                accounts.quote_token_source.clone(),
-                accounts.buyer.clone(),
-            ],
+            &[],
         )?;
     }
 
@@ -177,25 +180,20 @@ pub fn process_claim_bond(
 
     bond.save(&mut accounts.bond_account.data.borrow_mut())?;
 
-    // Mint ACCESS tokens into the pool vault
-    let mint_ix = spl_token::instruction::mint_to(
-        &spl_token::ID,
-        accounts.access_mint.key,
-        accounts.pool_vault.key,
-        accounts.central_state.key,
-        &[],
+    central_state.check_emission_cap(
+        unpack_mint(accounts.access_mint)?.supply,
         bond.total_amount_sold,
     )?;
+    central_state.record_emission(Emission::BondSale, bond.total_amount_sold)?;
 
-    invoke_signed(
-        &mint_ix,
-        &[
-            accounts.spl_token_program.clone(),
-            accounts.access_mint.clone(),
-            accounts.pool_vault.clone(),
-            accounts.central_state.clone(),
-        ],
-        &[&[&program_id.to_bytes(), &[central_state.bump_seed]]],
+    // Mint ACCESS tokens into the pool vault
+    Cpi::mint_to(
+        accounts.spl_token_program,
+        accounts.access_mint,
+        accounts.pool_vault,
+        accounts.central_state,
+        bond.total_amount_sold,
+        &[&central_state.signer_seeds(accounts.central_state.key, program_id)],
     )?;
 
     stake_pool.header.deposit(bond.total_amount_sold)?;
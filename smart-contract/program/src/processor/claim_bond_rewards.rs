@@ -1,28 +1,27 @@
 //! Claim bond rewards
 //! This Instruction allows bond owners to claim their staking rewards
-use std::convert::TryInto;
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     msg,
-    program::invoke_signed,
     program_error::ProgramError,
-    program_pack::Pack,
     pubkey::Pubkey,
 };
 
+use crate::rewards::staker_reward;
+use crate::events::{ClaimKind, Event};
 use crate::state::{BondAccount, StakePool};
-use crate::{error::AccessError, state::Tag};
+use crate::{cpi::Cpi, error::AccessError, state::Tag};
 use bonfida_utils::{BorshSize, InstructionsAccount};
-use spl_token::{instruction::mint_to, state::Account};
 use crate::instruction::ProgramInstruction::{ClaimBondRewards};
 
 use crate::utils::{
     assert_no_close_or_delegate, calc_reward_fp32, check_account_key, check_account_owner,
-    check_signer,
+    check_signer, check_token_program, unpack_token_account, check_pool_central_state,
+    unpack_mint,
 };
-use crate::state:: CentralStateV2;
+use crate::state::{CentralStateV2, Emission};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 /// The required parameters for the `claim_bond_rewards` instruction
@@ -48,6 +47,7 @@ pub struct Accounts<'a, T> {
     pub rewards_destination: &'a T,
 
     /// The central state account
+    #[cons(writable)]
     pub central_state: &'a T,
 
     /// The mint address of the ACCESS token
@@ -75,11 +75,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         };
 
         // Check keys
-        check_account_key(
-            accounts.spl_token_program,
-            &spl_token::ID,
-            AccessError::WrongSplTokenProgramId,
-        )?;
+        check_token_program(accounts.spl_token_program)?;
 
         // Check ownership
         check_account_owner(
@@ -94,11 +90,15 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         )?;
         check_account_owner(
             accounts.rewards_destination,
-            &spl_token::ID,
+            accounts.spl_token_program.key,
             AccessError::WrongOwner,
         )?;
         check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
-        check_account_owner(accounts.mint, &spl_token::ID, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.mint,
+            accounts.spl_token_program.key,
+            AccessError::WrongOwner,
+        )?;
 
         Ok(accounts)
     }
@@ -116,12 +116,19 @@ pub fn process_claim_bond_rewards(
 
     let accounts = Accounts::parse(accounts, program_id)?;
 
-    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&ClaimBondRewards)?;
     let stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
     let mut bond = BondAccount::from_account_info(accounts.bond_account, false)?;
 
-    let destination_token_acc = Account::unpack(&accounts.rewards_destination.data.borrow())?;
+    let destination_token_acc = unpack_token_account(accounts.rewards_destination)?;
     if destination_token_acc.mint != central_state.token_mint {
         msg!("Invalid ACCESS mint");
         #[cfg(not(feature = "no-mint-check"))]
@@ -153,46 +160,49 @@ pub fn process_claim_bond_rewards(
     )?;
 
     // Calculate the rewards (checks if the pool is cranked as well)
+    // V1 bonds have no reward checkpoint, in a pool using the reward index they only earn the
+    // days of its rewards buffer and the claim still moves their last claimed offset for the unlock
     let reward = calc_reward_fp32(
         central_state.last_snapshot_offset,
         bond.last_claimed_offset,
+        None,
         &stake_pool,
         true,
-        false,
-    )?
+        stake_pool.header.uses_reward_index(),
+    )?;
     // Multiply by the staker shares of the total pool
-    .checked_mul(bond.total_staked as u128)
-    .map(|r| ((r >> 31) + 1) >> 1)
-        .ok_or(AccessError::Overflow)?
-        .try_into()
-        .map_err(|_|AccessError::Overflow)?;
+    let reward = staker_reward(reward, bond.total_staked)?;
 
     msg!("Claiming bond rewards {}", reward);
     msg!("Total staked {}", bond.total_staked);
+    central_state.check_emission_cap(unpack_mint(accounts.mint)?.supply, reward)?;
+    central_state.record_emission(Emission::StakerRewards, reward)?;
 
     // Transfer rewards
-    let transfer_ix = mint_to(
-        &spl_token::ID,
-        accounts.mint.key,
-        accounts.rewards_destination.key,
-        accounts.central_state.key,
-        &[],
+    Cpi::mint_to(
+        accounts.spl_token_program,
+        accounts.mint,
+        accounts.rewards_destination,
+        accounts.central_state,
         reward,
-    )?;
-    invoke_signed(
-        &transfer_ix,
-        &[
-            accounts.spl_token_program.clone(),
-            accounts.mint.clone(),
-            accounts.central_state.clone(),
-            accounts.rewards_destination.clone(),
-        ],
-        &[&[&program_id.to_bytes(), &[central_state.bump_seed]]],
+        &[&central_state.signer_seeds(accounts.central_state.key, program_id)],
     )?;
 
     // Update states
     bond.last_claimed_offset = central_state.last_snapshot_offset;
     bond.save(&mut accounts.bond_account.data.borrow_mut())?;
+    central_state.save(&mut accounts.central_state.data.borrow_mut())?;
+
+    Event::Claim {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.stake_pool.key,
+        kind: ClaimKind::Bond,
+        account: *accounts.bond_account.key,
+        owner: bond.owner,
+        destination: *accounts.rewards_destination.key,
+        reward,
+    }
+    .emit()?;
 
     Ok(())
 }
@@ -2,25 +2,25 @@
 //! This instruction is used by stake pool owner for claiming their staking rewards
 use bonfida_utils::{BorshSize, InstructionsAccount};
 use borsh::{BorshDeserialize, BorshSerialize};
-use std::convert::TryInto;
 use solana_program::{
     account_info::{AccountInfo, next_account_info},
     entrypoint::ProgramResult,
     msg,
     program_error::ProgramError,
-    program_pack::Pack,
     pubkey::Pubkey,
 };
-use solana_program::program::invoke_signed;
-use spl_token::{instruction::mint_to, state::Account};
 
+use crate::cpi::Cpi;
 use crate::error::AccessError;
+use crate::events::{ClaimKind, Event};
 use crate::instruction::ProgramInstruction::ClaimPoolRewards;
+use crate::rewards::{pool_reward, split_royalty, ClaimableRewards};
 use crate::state::{StakePool,RoyaltyAccount, Tag};
-use crate::state::CentralStateV2;
+use crate::state::{CentralStateV2, Emission};
 use crate::utils::{
     assert_no_close_or_delegate, calc_reward_fp32, check_account_key, check_account_owner,
-    retrieve_royalty_account, check_signer,
+    retrieve_royalty_account, check_signer, check_token_program, unpack_token_account, check_pool_central_state,
+    unpack_mint,
 };
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
@@ -43,6 +43,7 @@ pub struct Accounts<'a, T> {
     pub rewards_destination: &'a T,
 
     /// The central state account
+    #[cons(writable)]
     pub central_state: &'a T,
 
     /// The mint address of the ACCESS token
@@ -78,11 +79,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         };
 
         // Check keys
-        check_account_key(
-            accounts.spl_token_program,
-            &spl_token::ID,
-            AccessError::WrongSplTokenProgramId,
-        )?;
+        check_token_program(accounts.spl_token_program)?;
 
         // Check ownership
         check_account_owner(
@@ -92,11 +89,15 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         )?;
         check_account_owner(
             accounts.rewards_destination,
-            &spl_token::ID,
+            accounts.spl_token_program.key,
             AccessError::WrongOwner,
         )?;
         check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
-        check_account_owner(accounts.mint, &spl_token::ID, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.mint,
+            accounts.spl_token_program.key,
+            AccessError::WrongOwner,
+        )?;
 
         Ok(accounts)
     }
@@ -109,11 +110,20 @@ pub fn process_claim_pool_rewards(
 ) -> ProgramResult {
     let accounts = Accounts::parse(accounts, program_id)?;
 
-    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&ClaimPoolRewards)?;
     let mut stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
 
-    let (derived_key, _) = RoyaltyAccount::create_key(accounts.owner.key, program_id);
+    let scope = central_state.scope(accounts.central_state.key, program_id);
+    let (derived_key, _) =
+        RoyaltyAccount::create_scoped_key(accounts.owner.key, scope.as_ref(), program_id);
     check_account_key(
         accounts.owner_royalty_account,
         &derived_key,
@@ -125,7 +135,7 @@ pub fn process_claim_pool_rewards(
         accounts.royalty_ata,
     )?;
 
-    let destination_token_acc = Account::unpack(&accounts.rewards_destination.data.borrow())?;
+    let destination_token_acc = unpack_token_account(accounts.rewards_destination)?;
 
     if destination_token_acc.mint != central_state.token_mint {
         msg!("Invalid ACCESS mint");
@@ -157,68 +167,74 @@ pub fn process_claim_pool_rewards(
     let reward = calc_reward_fp32(
         central_state.last_snapshot_offset,
         stake_pool.header.last_claimed_offset,
+        None,
         &stake_pool,
         false,
         false,
     )?;
 
-    let mut reward = (((reward >> 31) + 1) >> 1)
-        .try_into()
-        .map_err(|_|AccessError::Overflow)?;
+    let reward = pool_reward(reward)?;
 
     // split the rewards if there is a royalty account
-    let mut royalty_amount = 0;
-    if let Some(royalty_account) = royalty_account_data {
-        royalty_amount = royalty_account.calculate_royalty_amount(reward)?;
-        reward = reward.checked_sub(royalty_amount).ok_or(AccessError::Overflow)?;
-    }
+    let ClaimableRewards {
+        reward,
+        royalty: royalty_amount,
+    } = split_royalty(reward, royalty_account_data.as_ref())?;
 
     msg!("Claiming pool rewards {}, royalties {}", reward, royalty_amount);
+    let minted = reward.checked_add(royalty_amount).ok_or(AccessError::Overflow)?;
+    central_state.check_emission_cap(unpack_mint(accounts.mint)?.supply, minted)?;
+    central_state.record_emission(Emission::PoolRewards, reward)?;
+    central_state.record_emission(Emission::Royalties, royalty_amount)?;
 
     // Mint rewards
-    let mint_rewards_ix = mint_to(
-        &spl_token::ID,
-        accounts.mint.key,
-        accounts.rewards_destination.key,
-        accounts.central_state.key,
-        &[],
+    Cpi::mint_to(
+        accounts.spl_token_program,
+        accounts.mint,
+        accounts.rewards_destination,
+        accounts.central_state,
         reward,
-    )?;
-    invoke_signed(
-        &mint_rewards_ix,
-        &[
-            accounts.spl_token_program.clone(),
-            accounts.central_state.clone(),
-            accounts.mint.clone(),
-            accounts.rewards_destination.clone(),
-        ],
-        &[&[&program_id.to_bytes(), &[central_state.bump_seed]]],
+        &[&central_state.signer_seeds(accounts.central_state.key, program_id)],
     )?;
 
     // Mint royalties
     if royalty_amount > 0 {
-        let mint_royalty_ix = mint_to(
-            &spl_token::ID,
-            accounts.mint.key,
-            accounts.royalty_ata.unwrap().key,
-            accounts.central_state.key,
-            &[],
+        Cpi::mint_to(
+            accounts.spl_token_program,
+            accounts.mint,
+            accounts.royalty_ata.unwrap(),
+            accounts.central_state,
             royalty_amount,
-        )?;
-        invoke_signed(
-            &mint_royalty_ix,
-            &[
-                accounts.spl_token_program.clone(),
-                accounts.mint.clone(),
-                accounts.central_state.clone(),
-                accounts.royalty_ata.unwrap().clone(),
-            ],
-            &[&[&program_id.to_bytes(), &[central_state.bump_seed]]],
+            &[&central_state.signer_seeds(accounts.central_state.key, program_id)],
         )?;
     }
 
     // Update stake pool state
     stake_pool.header.last_claimed_offset = central_state.last_snapshot_offset;
+    if let Some(index) = stake_pool.reward_index_mut() {
+        index.pool_claimed_index = index.pool_reward_index;
+    }
+    central_state.save(&mut accounts.central_state.data.borrow_mut())?;
+
+    Event::Claim {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.stake_pool.key,
+        kind: ClaimKind::StakePool,
+        account: *accounts.stake_pool.key,
+        owner: *accounts.owner.key,
+        destination: *accounts.rewards_destination.key,
+        reward,
+    }
+    .emit()?;
+    if royalty_amount > 0 {
+        Event::RoyaltyPaid {
+            central_state: *accounts.central_state.key,
+            royalty_payer: *accounts.owner.key,
+            recipient_ata: *accounts.royalty_ata.unwrap().key,
+            amount: royalty_amount,
+        }
+        .emit()?;
+    }
 
     Ok(())
 }
@@ -0,0 +1,183 @@
+//! Claim the rewards of the stake account of the receipt holders of a pool
+//! This instruction is permissionless, the rewards are minted to the receipt reserve and paid out
+//! to the receipt holders when they redeem their receipts
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::cpi::Cpi;
+use crate::error::AccessError;
+use crate::events::{ClaimKind, Event};
+use crate::instruction::ProgramInstruction::ClaimReceiptRewards;
+use crate::rewards::staker_reward;
+use crate::state::{CentralStateV2, Emission, StakeAccount, StakePool, Tag};
+use crate::utils::{
+    calc_reward_fp32, check_account_key, check_account_owner, check_pool_central_state,
+    check_receipt_accounts, check_token_program, unpack_mint,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `claim_receipt_rewards` instruction
+pub struct Params {
+    // Should be false by default
+    pub allow_zero_rewards: bool,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `claim_receipt_rewards` instruction
+pub struct Accounts<'a, T> {
+    /// The central state account
+    #[cons(writable)]
+    pub central_state: &'a T,
+
+    /// The stake pool account
+    #[cons(writable)]
+    pub stake_pool: &'a T,
+
+    /// The stake account of the receipt holders
+    #[cons(writable)]
+    pub receipt_stake_account: &'a T,
+
+    /// The receipt mint account
+    pub receipt_mint: &'a T,
+
+    /// The receipt reserve
+    #[cons(writable)]
+    pub receipt_reserve: &'a T,
+
+    /// The mint address of the ACS token
+    #[cons(writable)]
+    pub mint: &'a T,
+
+    /// The SPL token program account
+    pub spl_token_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            central_state: next_account_info(accounts_iter)?,
+            stake_pool: next_account_info(accounts_iter)?,
+            receipt_stake_account: next_account_info(accounts_iter)?,
+            receipt_mint: next_account_info(accounts_iter)?,
+            receipt_reserve: next_account_info(accounts_iter)?,
+            mint: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_token_program(accounts.spl_token_program)?;
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.stake_pool,
+            program_id,
+            AccessError::WrongStakePoolAccountOwner,
+        )?;
+        check_account_owner(
+            accounts.receipt_stake_account,
+            program_id,
+            AccessError::WrongStakeAccountOwner,
+        )?;
+        check_account_owner(
+            accounts.receipt_mint,
+            accounts.spl_token_program.key,
+            AccessError::WrongReceiptMint,
+        )?;
+        check_account_owner(
+            accounts.mint,
+            accounts.spl_token_program.key,
+            AccessError::WrongOwner,
+        )?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_claim_receipt_rewards(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&ClaimReceiptRewards)?;
+    let stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+    check_account_key(
+        accounts.mint,
+        &central_state.token_mint,
+        AccessError::WrongMint,
+    )?;
+    check_receipt_accounts(
+        accounts.stake_pool.key,
+        &central_state.token_mint,
+        accounts.spl_token_program.key,
+        accounts.receipt_mint,
+        accounts.receipt_reserve,
+        accounts.receipt_stake_account,
+        program_id,
+    )?;
+    let mut stake_account = StakeAccount::from_account_info(accounts.receipt_stake_account)?;
+
+    // Calculate the rewards (checks if the pool is cranked as well)
+    let reward_fp32 = calc_reward_fp32(
+        central_state.last_snapshot_offset,
+        stake_account.last_claimed_offset,
+        Some(stake_account.last_claimed_index),
+        &stake_pool,
+        true,
+        params.allow_zero_rewards,
+    )?;
+    let reward = staker_reward(reward_fp32, stake_account.stake_amount)?;
+
+    msg!("Claiming receipt rewards {}", reward);
+    central_state.check_emission_cap(unpack_mint(accounts.mint)?.supply, reward)?;
+    central_state.record_emission(Emission::StakerRewards, reward)?;
+
+    Cpi::mint_to(
+        accounts.spl_token_program,
+        accounts.mint,
+        accounts.receipt_reserve,
+        accounts.central_state,
+        reward,
+        &[&central_state.signer_seeds(accounts.central_state.key, program_id)],
+    )?;
+
+    // Update states
+    stake_account.last_claimed_offset = central_state.last_snapshot_offset;
+    stake_account.last_claimed_index = stake_pool.stakers_reward_index();
+    stake_account.save(&mut accounts.receipt_stake_account.data.borrow_mut())?;
+    central_state.save(&mut accounts.central_state.data.borrow_mut())?;
+
+    Event::Claim {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.stake_pool.key,
+        kind: ClaimKind::Receipt,
+        account: *accounts.receipt_stake_account.key,
+        owner: stake_account.owner,
+        destination: *accounts.receipt_reserve.key,
+        reward,
+    }
+    .emit()?;
+
+    Ok(())
+}
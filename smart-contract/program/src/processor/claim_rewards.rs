@@ -1,6 +1,5 @@
 //! Claim rewards of a stake account
 //! This instruction can be used by stakers to claim their staking rewards
-use std::convert::TryInto;
 
 use bonfida_utils::{BorshSize, InstructionsAccount};
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -9,19 +8,20 @@ use solana_program::{
     entrypoint::ProgramResult,
     msg,
     program_error::ProgramError,
-    program_pack::Pack,
     pubkey::Pubkey,
 };
-use solana_program::program::invoke_signed;
-use spl_token::{instruction::mint_to, state::Account};
 
+use crate::cpi::Cpi;
 use crate::error::AccessError;
+use crate::events::{ClaimKind, Event};
 use crate::instruction::ProgramInstruction::ClaimRewards;
-use crate::state::{ACCESS_NFT_PROGRAM_SIGNER, StakeAccount, StakePool, Tag};
-use crate::state::{CentralStateV2, RoyaltyAccount};
+use crate::rewards::{split_royalty, staker_reward, ClaimableRewards};
+use crate::state::{StakeAccount, StakePool, Tag};
+use crate::state::{CentralStateV2, Emission, ProtocolConfig, RoyaltyAccount};
 use crate::utils::{
-    calc_reward_fp32, check_account_key, check_account_owner,
-    check_signer, retrieve_royalty_account,
+    calc_reward_fp32, check_account_key, check_account_owner, check_signer,
+    retrieve_royalty_account, check_token_program, unpack_token_account, check_pool_central_state,
+    next_optional_account_info, unpack_mint,
 };
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
@@ -51,6 +51,7 @@ pub struct Accounts<'a, T> {
     pub rewards_destination: &'a T,
 
     /// The central state account
+    #[cons(writable)]
     pub central_state: &'a T,
 
     /// The mint address of the ACS token
@@ -69,6 +70,9 @@ pub struct Accounts<'a, T> {
     /// The royalty ATA account
     #[cons(writable)]
     pub royalty_ata: Option<&'a T>,
+
+    /// The protocol config account, required when the Access NFT program signs
+    pub protocol_config: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -87,20 +91,12 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             access_nft_signer: next_account_info(accounts_iter)?,
             spl_token_program: next_account_info(accounts_iter)?,
             owner_royalty_account: next_account_info(accounts_iter)?,
-            royalty_ata: next_account_info(accounts_iter).ok(),
+            royalty_ata: next_optional_account_info(accounts_iter, program_id),
+            protocol_config: next_account_info(accounts_iter).ok(),
         };
 
         // Check keys
-        check_account_key(
-            accounts.spl_token_program,
-            &spl_token::ID,
-            AccessError::WrongSplTokenProgramId,
-        )?;
-        check_account_key(
-            accounts.access_nft_signer,
-            &ACCESS_NFT_PROGRAM_SIGNER,
-            AccessError::WrongAccessCnftAuthority,
-        )?;
+        check_token_program(accounts.spl_token_program)?;
 
         // Check ownership
         check_account_owner(
@@ -115,11 +111,15 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         )?;
         check_account_owner(
             accounts.rewards_destination,
-            &spl_token::ID,
+            accounts.spl_token_program.key,
             AccessError::WrongOwner,
         )?;
         check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
-        check_account_owner(accounts.mint, &spl_token::ID, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.mint,
+            accounts.spl_token_program.key,
+            AccessError::WrongOwner,
+        )?;
 
         // Check signers
         check_signer(accounts.owner, AccessError::StakeAccountOwnerMustSign)?;
@@ -135,12 +135,35 @@ pub fn process_claim_rewards(
 ) -> ProgramResult {
     let accounts = Accounts::parse(accounts, program_id)?;
 
-    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&ClaimRewards)?;
+
+    // The signer of the Access NFT program is only known from the protocol config
+    if accounts.access_nft_signer.is_signer {
+        let protocol_config = ProtocolConfig::get_checked(
+            accounts
+                .protocol_config
+                .ok_or(ProgramError::NotEnoughAccountKeys)?,
+            accounts.central_state.key,
+            program_id,
+        )?;
+        check_account_key(
+            accounts.access_nft_signer,
+            &protocol_config.access_nft_program_signer,
+            AccessError::WrongAccessCnftAuthority,
+        )?;
+    }
     let stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
     let mut stake_account = StakeAccount::from_account_info(accounts.stake_account)?;
 
-    let destination_token_acc = Account::unpack(&accounts.rewards_destination.data.borrow())?;
+    let destination_token_acc = unpack_token_account(accounts.rewards_destination)?;
 
     if destination_token_acc.mint != central_state.token_mint {
         msg!("Invalid ACCESS mint");
@@ -152,7 +175,9 @@ pub fn process_claim_rewards(
 
     // We only allow custom royalty account and unchecked destination account when this is a CPI call from the NFT program.
     if !accounts.access_nft_signer.is_signer {
-        let (derived_key, _) = RoyaltyAccount::create_key(accounts.owner.key, program_id);
+        let scope = central_state.scope(accounts.central_state.key, program_id);
+        let (derived_key, _) =
+            RoyaltyAccount::create_scoped_key(accounts.owner.key, scope.as_ref(), program_id);
         check_account_key(
             accounts.owner_royalty_account,
             &derived_key,
@@ -182,74 +207,76 @@ pub fn process_claim_rewards(
     )?;
 
     // Calculate the rewards (checks if the pool is cranked as well)
-    let mut reward = calc_reward_fp32(
+    let reward_fp32 = calc_reward_fp32(
         central_state.last_snapshot_offset,
         stake_account.last_claimed_offset,
+        Some(stake_account.last_claimed_index),
         &stake_pool,
         true,
         params.allow_zero_rewards,
-    )?
-        // Multiply by the staker shares of the total pool
-        .checked_mul(stake_account.stake_amount as u128)
-        .map(|r| ((r >> 31) + 1) >> 1)
-        .ok_or(AccessError::Overflow)?
-        .try_into()
-        .map_err(|_| AccessError::Overflow)?;
+    )?;
+    // Multiply by the staker shares of the total pool
+    let reward = staker_reward(reward_fp32, stake_account.stake_amount)?;
 
     // split the rewards if there is a royalty account
-    let mut royalty_amount = 0;
-    if let Some(royalty_account) = royalty_account_data {
-        royalty_amount = royalty_account.calculate_royalty_amount(reward)?;
-        reward = reward.checked_sub(royalty_amount).ok_or(AccessError::Overflow)?;
-    }
+    let ClaimableRewards {
+        reward,
+        royalty: royalty_amount,
+    } = split_royalty(reward, royalty_account_data.as_ref())?;
 
     msg!("Claiming rewards {}, royalties {}", reward, royalty_amount);
+    let minted = reward.checked_add(royalty_amount).ok_or(AccessError::Overflow)?;
+    central_state.check_emission_cap(unpack_mint(accounts.mint)?.supply, minted)?;
+    central_state.record_emission(Emission::StakerRewards, reward)?;
+    central_state.record_emission(Emission::Royalties, royalty_amount)?;
 
     // Mint rewards
-    let mint_rewards_ix = mint_to(
-        &spl_token::ID,
-        accounts.mint.key,
-        accounts.rewards_destination.key,
-        accounts.central_state.key,
-        &[],
+    Cpi::mint_to(
+        accounts.spl_token_program,
+        accounts.mint,
+        accounts.rewards_destination,
+        accounts.central_state,
         reward,
-    )?;
-    invoke_signed(
-        &mint_rewards_ix,
-        &[
-            accounts.spl_token_program.clone(),
-            accounts.mint.clone(),
-            accounts.central_state.clone(),
-            accounts.rewards_destination.clone(),
-        ],
-        &[&[&program_id.to_bytes(), &[central_state.bump_seed]]],
+        &[&central_state.signer_seeds(accounts.central_state.key, program_id)],
     )?;
 
     // Mint royalties
     if royalty_amount > 0 {
-        let mint_royalty_ix = mint_to(
-            &spl_token::ID,
-            accounts.mint.key,
-            accounts.royalty_ata.unwrap().key,
-            accounts.central_state.key,
-            &[],
+        Cpi::mint_to(
+            accounts.spl_token_program,
+            accounts.mint,
+            accounts.royalty_ata.unwrap(),
+            accounts.central_state,
             royalty_amount,
-        )?;
-        invoke_signed(
-            &mint_royalty_ix,
-            &[
-                accounts.spl_token_program.clone(),
-                accounts.mint.clone(),
-                accounts.central_state.clone(),
-                accounts.royalty_ata.unwrap().clone(),
-            ],
-            &[&[&program_id.to_bytes(), &[central_state.bump_seed]]],
+            &[&central_state.signer_seeds(accounts.central_state.key, program_id)],
         )?;
     }
 
     // Update states
     stake_account.last_claimed_offset = central_state.last_snapshot_offset;
+    stake_account.last_claimed_index = stake_pool.stakers_reward_index();
     stake_account.save(&mut accounts.stake_account.data.borrow_mut())?;
+    central_state.save(&mut accounts.central_state.data.borrow_mut())?;
+
+    Event::Claim {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.stake_pool.key,
+        kind: ClaimKind::StakeAccount,
+        account: *accounts.stake_account.key,
+        owner: stake_account.owner,
+        destination: *accounts.rewards_destination.key,
+        reward,
+    }
+    .emit()?;
+    if royalty_amount > 0 {
+        Event::RoyaltyPaid {
+            central_state: *accounts.central_state.key,
+            royalty_payer: *accounts.owner.key,
+            recipient_ata: *accounts.royalty_ata.unwrap().key,
+            amount: royalty_amount,
+        }
+        .emit()?;
+    }
 
     Ok(())
 }
@@ -73,6 +73,15 @@ pub fn process_close_royalty_account(program_id: &Pubkey, accounts: &[AccountInf
     central_state.assert_instruction_allowed(&CloseStakeAccount)?;
     let mut royalty_account = RoyaltyAccount::from_account_info(accounts.royalty_account)?;
 
+    let scope = central_state.scope(accounts.central_state.key, program_id);
+    let (derived_key, _) =
+        RoyaltyAccount::create_scoped_key(accounts.royalty_payer.key, scope.as_ref(), program_id);
+    check_account_key(
+        accounts.royalty_account,
+        &derived_key,
+        AccessError::WrongCentralState,
+    )?;
+
     check_account_key(
         accounts.royalty_payer,
         &royalty_account.royalty_payer,
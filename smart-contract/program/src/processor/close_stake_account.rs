@@ -8,9 +8,7 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
-use crate::utils::{
-    assert_empty_stake_account, check_account_key, check_account_owner, check_signer,
-};
+use crate::utils::{assert_original_central_state, assert_empty_stake_account, check_account_key, check_account_owner, check_signer};
 use bonfida_utils::BorshSize;
 use bonfida_utils::InstructionsAccount;
 
@@ -70,6 +68,7 @@ pub fn process_close_stake_account(program_id: &Pubkey, accounts: &[AccountInfo]
 
     let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&CloseStakeAccount)?;
+    assert_original_central_state(accounts.central_state.key, &central_state, program_id)?;
     let mut stake_account = StakeAccount::from_account_info(accounts.stake_account)?;
 
     check_account_key(
@@ -2,7 +2,10 @@
 //! This instruction can be used to close an empty stake pool and collect the lamports
 use crate::{
     state::Tag,
-    utils::{assert_empty_stake_pool, check_account_key, check_account_owner, check_signer},
+    utils::{
+        assert_empty_stake_pool, check_account_key, check_account_owner, check_pool_central_state,
+        check_signer, check_token_program_owner, unpack_token_account,
+    },
 };
 use bonfida_utils::{BorshSize, InstructionsAccount};
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -11,10 +14,8 @@ use solana_program::{
     entrypoint::ProgramResult,
     msg,
     program_error::ProgramError,
-    program_pack::Pack,
     pubkey::Pubkey,
 };
-use spl_token::state::Account;
 use crate::state:: CentralStateV2;
 
 use crate::error::AccessError;
@@ -65,7 +66,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             program_id,
             AccessError::WrongOwner,
         )?;
-        check_account_owner(accounts.pool_vault, &spl_token::ID, AccessError::WrongOwner)?;
+        check_token_program_owner(accounts.pool_vault, AccessError::WrongOwner)?;
 
         // Check signer
         check_signer(accounts.owner, AccessError::StakePoolOwnerMustSign)?;
@@ -91,6 +92,13 @@ pub fn process_close_stake_pool(
         accounts.stake_pool_account,
         vec![Tag::InactiveStakePool, Tag::StakePool],
     )?;
+    check_pool_central_state(
+        accounts.stake_pool_account.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
 
     check_account_key(
         accounts.owner,
@@ -103,7 +111,7 @@ pub fn process_close_stake_pool(
         AccessError::StakePoolVaultMismatch,
     )?;
 
-    let vault = Account::unpack_from_slice(&accounts.pool_vault.data.borrow_mut())?;
+    let vault = unpack_token_account(accounts.pool_vault)?;
 
     if vault.amount != 0 {
         msg!("Vault isn't empty, there are remaining unstake requests");
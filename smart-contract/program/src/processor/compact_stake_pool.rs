@@ -0,0 +1,129 @@
+//! Remove the rewards buffer of a stake pool using the reward index once the days before the
+//! switch can't be claimed anymore, the rent is refunded to the pool owner
+use std::mem::size_of;
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::error::AccessError;
+use crate::instruction::ProgramInstruction::CompactStakePool;
+use crate::state::{
+    CentralStateV2, RewardIndex, StakePool, StakePoolHeader, Tag, STAKE_BUFFER_LEN,
+};
+use crate::utils::{check_account_key, check_account_owner, check_pool_central_state};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `compact_stake_pool` instruction
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `compact_stake_pool` instruction
+pub struct Accounts<'a, T> {
+    /// The stake pool account
+    #[cons(writable)]
+    pub stake_pool: &'a T,
+
+    /// The owner of the stake pool, receives the rent of the rewards buffer
+    #[cons(writable)]
+    pub owner: &'a T,
+
+    /// The central state account
+    pub central_state: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            stake_pool: next_account_info(accounts_iter)?,
+            owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+        };
+
+        // Check ownership
+        check_account_owner(
+            accounts.stake_pool,
+            program_id,
+            AccessError::WrongStakePoolAccountOwner,
+        )?;
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_compact_stake_pool(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&CompactStakePool)?;
+
+    {
+        let stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+        check_pool_central_state(
+            accounts.stake_pool.key,
+            &stake_pool.header,
+            accounts.central_state.key,
+            &central_state,
+            program_id,
+        )?;
+        check_account_key(
+            accounts.owner,
+            &Pubkey::from(stake_pool.header.owner),
+            AccessError::WrongStakePoolOwner,
+        )?;
+
+        if !stake_pool.header.uses_reward_index() {
+            return Err(AccessError::RewardIndexNotEnabled.into());
+        }
+        if stake_pool.rewards_buffer().is_empty() {
+            msg!("The stake pool is already compacted");
+            return Err(AccessError::NoOp.into());
+        }
+
+        // A claim only goes back STAKE_BUFFER_LEN days
+        let claim_window_passed = central_state.last_snapshot_offset
+            >= (stake_pool.header.reward_index_start as u64) + STAKE_BUFFER_LEN;
+        let buffer_empty = stake_pool
+            .rewards_buffer()
+            .iter()
+            .all(|r| r.pool_reward == 0 && r.stakers_reward == 0);
+        if !claim_window_passed && !buffer_empty {
+            return Err(AccessError::RewardsBufferStillClaimable.into());
+        }
+    }
+
+    let new_data_len = size_of::<StakePoolHeader>() + size_of::<RewardIndex>();
+    accounts.stake_pool.realloc(new_data_len, false)?;
+
+    let refund = accounts
+        .stake_pool
+        .lamports()
+        .checked_sub(Rent::get()?.minimum_balance(new_data_len))
+        .ok_or(AccessError::Overflow)?;
+    let mut stake_pool_lamports = accounts.stake_pool.lamports.borrow_mut();
+    let mut owner_lamports = accounts.owner.lamports.borrow_mut();
+
+    **owner_lamports += refund;
+    **stake_pool_lamports -= refund;
+
+    msg!("Stake pool compacted, {} lamports refunded", refund);
+
+    Ok(())
+}
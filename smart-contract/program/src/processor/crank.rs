@@ -10,12 +10,13 @@ use solana_program::{
     program_error::ProgramError,
     pubkey::Pubkey,
 };
-use spl_math::precise_number::PreciseNumber;
 
 use crate::error::AccessError;
+use crate::events::Event;
 use crate::instruction::ProgramInstruction::Crank;
-use crate::state::{RewardsTuple, StakePool, Tag};
-use crate::utils::check_account_owner;
+use crate::rewards::{calc_daily_rewards, max_daily_claimable, DailyRewards};
+use crate::state::{PoolBoost, StakePool, Tag};
+use crate::utils::{check_account_owner, check_pool_central_state};
 use crate::state:: CentralStateV2;
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
@@ -32,6 +33,9 @@ pub struct Accounts<'a, T> {
     /// The central state account
     #[cons(writable)]
     pub central_state: &'a T,
+
+    /// The pool boost account, required once the pool has boosted bonds
+    pub pool_boost: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -43,6 +47,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         let accounts = Accounts {
             stake_pool: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
+            pool_boost: next_account_info(accounts_iter).ok(),
         };
 
         // Check ownership
@@ -67,11 +72,19 @@ pub fn process_crank(
     let mut stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
     let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&Crank)?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
 
     let current_offset = central_state.get_current_offset()?;
     // check if we need to do a system wide snapshot
     if central_state.last_snapshot_offset < current_offset {
         central_state.total_staked_snapshot = central_state.total_staked;
+        central_state.total_boost_snapshot = central_state.total_boost;
         central_state.last_snapshot_offset = current_offset;
         central_state.save(&mut accounts.central_state.data.borrow_mut())?;
     }
@@ -80,6 +93,22 @@ pub fn process_crank(
         #[cfg(not(any(feature = "days-to-sec-10s", feature = "days-to-sec-15m")))]
         return Err(AccessError::NoOp.into());
     }
+    // The boosts of the bonds add to the weight of the pool
+    let mut pool_total_staked = stake_pool.header.total_staked;
+    if stake_pool.header.uses_boost() {
+        let pool_boost = PoolBoost::get_checked(
+            accounts
+                .pool_boost
+                .ok_or(ProgramError::NotEnoughAccountKeys)?,
+            accounts.stake_pool.key,
+            program_id,
+        )?;
+        msg!("Total boost in pool {}", pool_boost.total_boost);
+        pool_total_staked = pool_total_staked
+            .checked_add(pool_boost.total_boost)
+            .ok_or(AccessError::Overflow)?;
+    }
+
     msg!("Total staked in pool {}", stake_pool.header.total_staked);
     msg!("Daily inflation {}", central_state.daily_inflation);
     msg!("Total staked {}", central_state.total_staked);
@@ -87,86 +116,35 @@ pub fn process_crank(
         "Total staked snapshot {}",
         central_state.total_staked_snapshot
     );
+    msg!("Total boost snapshot {}", central_state.total_boost_snapshot);
+
+    let DailyRewards {
+        rewards,
+        total_claimable,
+    } = calc_daily_rewards(
+        central_state.daily_inflation,
+        pool_total_staked,
+        stake_pool.header.stakers_part,
+        central_state.total_weight_snapshot()?,
+    )?;
 
-    // get the pool staked amount at the time of last system snapshot
-    let total_staked_snapshot = stake_pool.header.total_staked as u128;
-
-    let mut stakers_reward = 0;
-    if total_staked_snapshot != 0 {
-        // Stakers rewards per ACS staked
-        stakers_reward = ((central_state.daily_inflation as u128) << 32)
-            .checked_mul(stake_pool.header.stakers_part as u128)
-            .ok_or(AccessError::Overflow)?
-            .checked_div(100u128)
-            .ok_or(AccessError::Overflow)?
-            .checked_div(central_state.total_staked_snapshot as u128)
-            .unwrap_or(0);
-    };
-
-    msg!("Stakers reward {}", stakers_reward);
-
-    let precise_total_staked_snapshot = PreciseNumber::new(
-        total_staked_snapshot
-            .checked_shl(32)
-            .ok_or(AccessError::Overflow)?,
-    )
-    .ok_or(AccessError::Overflow)?;
-    let precise_daily_inflation =
-        PreciseNumber::new(central_state.daily_inflation as u128).ok_or(AccessError::Overflow)?;
-    let precise_system_staked_snapshot =
-        PreciseNumber::new(central_state.total_staked_snapshot as u128)
-            .ok_or(AccessError::Overflow)?;
+    msg!("Stakers reward {}", rewards.stakers_reward);
+    msg!("Pool reward {}", rewards.pool_reward);
+    msg!("Total claimable rewards {}", total_claimable);
 
-    // Total pool reward
-    let precise_pool_reward = (precise_total_staked_snapshot)
-        .checked_mul(&precise_daily_inflation)
-        .ok_or(AccessError::Overflow)?
-        .checked_mul(
-            &PreciseNumber::new(
-                100u64
-                    .checked_sub(stake_pool.header.stakers_part)
-                    .ok_or(AccessError::Overflow)? as u128,
-            )
-            .ok_or(AccessError::Overflow)?,
-        )
-        .ok_or(AccessError::Overflow)?
-        .checked_div(&PreciseNumber::new(100u128).ok_or(AccessError::Overflow)?)
-        .ok_or(AccessError::Overflow)?
-        .checked_div(&precise_system_staked_snapshot)
-        .unwrap_or(PreciseNumber::new(0).ok_or(AccessError::Overflow)?);
-
-    let pool_reward = precise_pool_reward
-        .to_imprecise()
-        .ok_or(AccessError::Overflow)?;
-
-    msg!("Pool reward {}", pool_reward);
-
-    let total_claimable_rewards = (((pool_reward >> 31) + 1) >> 1)
-        .checked_add(
-            ((stakers_reward
-                .checked_mul(total_staked_snapshot)
-                .ok_or(AccessError::Overflow)?
-                >> 31)
-                + 1)
-                >> 1,
-        )
-        .ok_or(AccessError::Overflow)?;
-
-    msg!("Total claimable rewards {}", total_claimable_rewards);
-
-    assert!(
-        total_claimable_rewards
-            <= (central_state.daily_inflation as u128)
-                .checked_add(1_000_000)
-                .ok_or(AccessError::Overflow)?
-    );
+    assert!(total_claimable <= max_daily_claimable(central_state.daily_inflation)?);
+
+    stake_pool.add_daily_rewards(current_offset, rewards)?;
+
+    Event::Crank {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.stake_pool.key,
+        day: current_offset,
+        pool_reward: rewards.pool_reward,
+        stakers_reward: rewards.stakers_reward,
+        total_claimable,
+    }
+    .emit()?;
 
-    stake_pool.push_balances_buff(
-        current_offset,
-        RewardsTuple {
-            pool_reward,
-            stakers_reward,
-        },
-    )?;
     Ok(())
 }
@@ -11,10 +11,11 @@ use solana_program::{
 use crate::state:: CentralStateV2;
 
 use crate::error::AccessError;
+use crate::events::Event;
 use crate::state::{BondAccount, StakePool, BOND_SIGNER_THRESHOLD, V1_INSTRUCTIONS_ALLOWED};
 #[cfg(not(feature = "no-bond-signer"))]
 use crate::utils::assert_authorized_seller;
-use crate::utils::{assert_uninitialized, check_account_key, check_account_owner, check_signer};
+use crate::utils::{assert_uninitialized, check_account_key, check_account_owner, check_signer, check_pool_central_state};
 use crate::{cpi::Cpi, state::Tag};
 use bonfida_utils::{BorshSize, InstructionsAccount};
 use crate::instruction::ProgramInstruction::CreateBond;
@@ -117,6 +118,13 @@ pub fn process_create_bond(
         BondAccount::create_key(&params.buyer, params.total_amount_sold, program_id);
 
     let stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
 
     check_account_key(
         accounts.bond_account,
@@ -166,5 +174,15 @@ pub fn process_create_bond(
 
     bond.save(&mut accounts.bond_account.data.borrow_mut())?;
 
+    Event::BondCreated {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.stake_pool.key,
+        bond: *accounts.bond_account.key,
+        owner: params.buyer,
+        amount: params.total_amount_sold,
+        unlock_timestamp: Some(params.unlock_start_date),
+    }
+    .emit()?;
+
     Ok(())
 }
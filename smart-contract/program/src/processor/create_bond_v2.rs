@@ -17,11 +17,12 @@ use solana_program::{
 
 use crate::{cpi::Cpi, state::Tag};
 use crate::error::AccessError;
+use crate::events::Event;
 use crate::instruction::ProgramInstruction::CreateBondV2;
 use crate::state::{BondV2Account, StakePool};
 use crate::state::CentralStateV2;
 use crate::utils::{
-    check_account_key, check_account_owner,
+    check_account_key, check_account_owner, check_pool_central_state,
 };
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
@@ -107,6 +108,13 @@ pub fn process_create_bond_v2(
     let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&CreateBondV2)?;
     let pool = StakePool::get_checked(accounts.pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.pool.key,
+        &pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
 
     let (derived_key, bump_seed) =
         BondV2Account::create_key(&owner, accounts.pool.key, unlock_timestamp, program_id);
@@ -149,5 +157,16 @@ pub fn process_create_bond_v2(
     )?;
 
     bond.save(&mut accounts.bond_v2_account.data.borrow_mut())?;
+
+    Event::BondCreated {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.pool.key,
+        bond: *accounts.bond_v2_account.key,
+        owner,
+        amount: 0,
+        unlock_timestamp,
+    }
+    .emit()?;
+
     Ok(())
 }
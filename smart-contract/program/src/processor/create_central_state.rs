@@ -6,14 +6,18 @@ use solana_program::{
     entrypoint::ProgramResult,
     msg,
     program_error::ProgramError,
+    program_option::COption,
     pubkey::Pubkey,
     system_program,
 };
 
-use crate::state::CentralState;
+use crate::state::{CentralState, CentralStateV2, ProtocolConfig};
 use crate::{cpi::Cpi, error::AccessError};
 
-use crate::utils::{check_account_key, check_account_owner};
+use crate::utils::{
+    assert_supported_mint, check_account_key, check_account_owner, check_token_program_owner,
+    unpack_mint,
+};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 /// The required parameters for the `create_central_state` instruction
@@ -22,6 +26,8 @@ pub struct Params {
     pub daily_inflation: u64,
     // Authority
     pub authority: Pubkey,
+    // The signer of the ACCESS NFT program
+    pub access_nft_program_signer: Pubkey,
 }
 
 #[derive(InstructionsAccount)]
@@ -40,6 +46,10 @@ pub struct Accounts<'a, T> {
 
     /// The mint of the ACCESS token
     pub mint: &'a T,
+
+    /// The protocol config account
+    #[cons(writable)]
+    pub protocol_config: &'a T,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -50,6 +60,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             system_program: next_account_info(accounts_iter)?,
             fee_payer: next_account_info(accounts_iter)?,
             mint: next_account_info(accounts_iter)?,
+            protocol_config: next_account_info(accounts_iter)?,
         };
 
         // Check keys
@@ -65,6 +76,12 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             &system_program::ID,
             AccessError::WrongOwner,
         )?;
+        check_account_owner(
+            accounts.protocol_config,
+            &system_program::ID,
+            AccessError::WrongOwner,
+        )?;
+        check_token_program_owner(accounts.mint, AccessError::WrongOwner)?;
 
         Ok(accounts)
     }
@@ -76,13 +93,33 @@ pub fn process_create_central_state(
     params: Params,
 ) -> ProgramResult {
     let accounts = Accounts::parse(accounts)?;
-    let (derived_state_key, nonce) = CentralState::find_key(program_id);
-
-    check_account_key(
-        accounts.central_state,
-        &derived_state_key,
-        AccessError::AccountNotDeterministic,
-    )?;
+    assert_supported_mint(accounts.mint)?;
+    let (original_state_key, original_nonce) = CentralState::find_key(program_id);
+    let program_id_seed = program_id.to_bytes();
+    let mint_seed = accounts.mint.key.to_bytes();
+
+    // Any other central state than the original one is a tenant keyed by its mint
+    let (nonce, mut signer_seeds): (u8, Vec<&[u8]>) =
+        if accounts.central_state.key == &original_state_key {
+            (original_nonce, vec![&program_id_seed])
+        } else {
+            let (tenant_state_key, tenant_nonce) =
+                CentralStateV2::find_tenant_key(accounts.mint.key, program_id);
+            check_account_key(
+                accounts.central_state,
+                &tenant_state_key,
+                AccessError::AccountNotDeterministic,
+            )?;
+            // The mint authority has to be handed over first so that nobody else can claim the mint
+            let mint = unpack_mint(accounts.mint)?;
+            if mint.mint_authority != COption::Some(tenant_state_key) {
+                msg!("The mint authority must be the tenant central state");
+                return Err(AccessError::WrongMint.into());
+            }
+            (tenant_nonce, vec![&program_id_seed, &mint_seed])
+        };
+    let nonce_seed = [nonce];
+    signer_seeds.push(&nonce_seed);
 
     let state = CentralState::new(
         nonce,
@@ -98,11 +135,40 @@ pub fn process_create_central_state(
         accounts.system_program,
         accounts.fee_payer,
         accounts.central_state,
-        &[&program_id.to_bytes(), &[nonce]],
+        &signer_seeds,
         state.borsh_len(),
     )?;
 
     state.save(&mut accounts.central_state.data.borrow_mut())?;
 
+    let (derived_config_key, config_nonce) =
+        ProtocolConfig::create_key(accounts.central_state.key, program_id);
+    check_account_key(
+        accounts.protocol_config,
+        &derived_config_key,
+        AccessError::AccountNotDeterministic,
+    )?;
+
+    let config = ProtocolConfig::new(
+        *accounts.central_state.key,
+        params.access_nft_program_signer,
+    );
+
+    msg!("+ Creating protocol config");
+    Cpi::create_account(
+        program_id,
+        accounts.system_program,
+        accounts.fee_payer,
+        accounts.protocol_config,
+        &[
+            ProtocolConfig::SEED,
+            &accounts.central_state.key.to_bytes(),
+            &[config_nonce],
+        ],
+        config.borsh_len(),
+    )?;
+
+    config.save(&mut accounts.protocol_config.data.borrow_mut())?;
+
     Ok(())
 }
@@ -0,0 +1,222 @@
+//! Create the receipt mint of a stake pool
+//! The receipts are minted 1:1 when staking through the stake account of the receipt holders and
+//! burnt when unstaking from it, the rewards of that stake account accrue in the receipt reserve
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_program,
+};
+use spl_token_2022::state::Mint;
+
+use crate::cpi::Cpi;
+use crate::error::AccessError;
+use crate::events::Event;
+use crate::instruction::ProgramInstruction::CreateReceiptMint;
+use crate::state::{CentralStateV2, StakeAccount, StakePool, StakePoolHeader, Tag};
+use crate::utils::{
+    assert_valid_vault, check_account_key, check_account_owner, check_pool_central_state,
+    check_receipt_accounts, check_signer, check_token_program, unpack_mint,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `create_receipt_mint` instruction
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `create_receipt_mint` instruction
+pub struct Accounts<'a, T> {
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The stake pool account
+    pub stake_pool: &'a T,
+
+    /// The owner of the stake pool
+    #[cons(signer)]
+    pub pool_owner: &'a T,
+
+    /// The receipt mint account
+    #[cons(writable)]
+    pub receipt_mint: &'a T,
+
+    /// The stake account of the receipt holders
+    #[cons(writable)]
+    pub receipt_stake_account: &'a T,
+
+    /// The receipt reserve, the ATA of the receipt mint for the ACS mint
+    pub receipt_reserve: &'a T,
+
+    /// The mint of the ACS token
+    pub mint: &'a T,
+
+    /// The SPL token program account
+    pub spl_token_program: &'a T,
+
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            central_state: next_account_info(accounts_iter)?,
+            stake_pool: next_account_info(accounts_iter)?,
+            pool_owner: next_account_info(accounts_iter)?,
+            receipt_mint: next_account_info(accounts_iter)?,
+            receipt_stake_account: next_account_info(accounts_iter)?,
+            receipt_reserve: next_account_info(accounts_iter)?,
+            mint: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_token_program(accounts.spl_token_program)?;
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.stake_pool,
+            program_id,
+            AccessError::WrongStakePoolAccountOwner,
+        )?;
+        check_account_owner(
+            accounts.receipt_mint,
+            &system_program::ID,
+            AccessError::WrongOwner,
+        )?;
+
+        // Check signers
+        check_signer(accounts.pool_owner, AccessError::StakePoolOwnerMustSign)?;
+        check_signer(accounts.fee_payer, AccessError::OwnerMustSign)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_create_receipt_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&CreateReceiptMint)?;
+    let stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+    check_account_key(
+        accounts.pool_owner,
+        &Pubkey::from(stake_pool.header.owner),
+        AccessError::WrongStakePoolOwner,
+    )?;
+    check_account_key(
+        accounts.mint,
+        &central_state.token_mint,
+        AccessError::WrongMint,
+    )?;
+    let receipt_mint_nonce = check_receipt_accounts(
+        accounts.stake_pool.key,
+        &central_state.token_mint,
+        accounts.spl_token_program.key,
+        accounts.receipt_mint,
+        accounts.receipt_reserve,
+        accounts.receipt_stake_account,
+        program_id,
+    )?;
+    assert_valid_vault(
+        accounts.receipt_reserve,
+        accounts.receipt_mint.key,
+        &central_state.token_mint,
+    )?;
+
+    // The receipts have the decimals of the ACS token so that they are minted 1:1
+    let decimals = unpack_mint(accounts.mint)?.decimals;
+    Cpi::create_account(
+        accounts.spl_token_program.key,
+        accounts.system_program,
+        accounts.fee_payer,
+        accounts.receipt_mint,
+        &[
+            StakePoolHeader::RECEIPT_MINT_SEED,
+            &accounts.stake_pool.key.to_bytes(),
+            &[receipt_mint_nonce],
+        ],
+        Mint::LEN,
+    )?;
+    Cpi::initialize_mint(
+        accounts.spl_token_program,
+        accounts.receipt_mint,
+        accounts.central_state.key,
+        decimals,
+    )?;
+
+    // The stake account of the receipt holders has no minimum, any amount of receipts can be redeemed
+    let stake_account = if accounts.receipt_stake_account.data_is_empty() {
+        let (_, stake_nonce) = StakeAccount::find_key(
+            accounts.receipt_mint.key,
+            accounts.stake_pool.key,
+            program_id,
+        );
+        let stake_account =
+            StakeAccount::new(*accounts.receipt_mint.key, *accounts.stake_pool.key, 0);
+        Cpi::create_account(
+            program_id,
+            accounts.system_program,
+            accounts.fee_payer,
+            accounts.receipt_stake_account,
+            &[
+                StakeAccount::SEED,
+                &accounts.receipt_mint.key.to_bytes(),
+                &accounts.stake_pool.key.to_bytes(),
+                &[stake_nonce],
+            ],
+            stake_account.borsh_len(),
+        )?;
+        stake_account
+    } else {
+        check_account_owner(
+            accounts.receipt_stake_account,
+            program_id,
+            AccessError::WrongStakeAccountOwner,
+        )?;
+        let mut stake_account = StakeAccount::from_account_info(accounts.receipt_stake_account)?;
+        stake_account.pool_minimum_at_creation = 0;
+        stake_account
+    };
+    stake_account.save(&mut accounts.receipt_stake_account.data.borrow_mut())?;
+
+    Event::ReceiptMintCreated {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.stake_pool.key,
+        receipt_mint: *accounts.receipt_mint.key,
+    }
+    .emit()?;
+
+    Ok(())
+}
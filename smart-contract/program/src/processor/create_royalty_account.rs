@@ -97,8 +97,10 @@ pub fn process_create_royalty_account(
     let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&CreateRoyaltyAccount)?;
 
-    let (derived_royalty_key, bump_seed) = RoyaltyAccount::create_key(
+    let scope = central_state.scope(accounts.central_state.key, program_id);
+    let (derived_royalty_key, bump_seed) = RoyaltyAccount::create_scoped_key(
         accounts.royalty_payer.key,
+        scope.as_ref(),
         program_id,
     );
 
@@ -121,11 +123,7 @@ pub fn process_create_royalty_account(
         accounts.system_program,
         accounts.fee_payer,
         accounts.royalty_account,
-        &[
-            RoyaltyAccount::SEED,
-            &accounts.royalty_payer.key.to_bytes(),
-            &[bump_seed],
-        ],
+        &RoyaltyAccount::seeds(accounts.royalty_payer.key, scope.as_ref(), &[bump_seed]),
         royalty_account.borsh_len(),
     )?;
 
@@ -14,7 +14,9 @@ use crate::{cpi::Cpi, error::AccessError};
 use bonfida_utils::{BorshSize, InstructionsAccount};
 use crate::instruction::ProgramInstruction::CreateStakeAccount;
 
-use crate::utils::{check_account_key, check_account_owner};
+use crate::utils::{
+    check_account_key, check_account_owner, check_pool_central_state, check_pool_controls,
+};
 use crate::state:: CentralStateV2;
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 /// The required parameters for the `create_stake_account` instruction
@@ -44,6 +46,12 @@ pub struct Accounts<'a, T> {
 
     /// The central state account
     pub central_state: &'a T,
+
+    /// The pool controls account, required once the pool has controls
+    pub pool_controls: Option<&'a T>,
+
+    /// The allowlist entry of the owner, required once the allowlist of the pool is enabled
+    pub allowlist_entry: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -58,6 +66,8 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             stake_pool: next_account_info(accounts_iter)?,
             fee_payer: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
+            pool_controls: next_account_info(accounts_iter).ok(),
+            allowlist_entry: next_account_info(accounts_iter).ok(),
         };
 
         // Check keys
@@ -90,6 +100,22 @@ pub fn process_create_stake_account(
     let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&CreateStakeAccount)?;
     let stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+    check_pool_controls(
+        &stake_pool.header,
+        accounts.stake_pool.key,
+        accounts.pool_controls,
+        accounts.allowlist_entry,
+        &params.owner,
+        0,
+        program_id,
+    )?;
 
     let derived_stake_key = StakeAccount::create_key(
         &params.nonce,
@@ -1,4 +1,5 @@
 //! Create stake pool
+//! New pools start with the reward index, only the pools created before it keep a rewards buffer
 use std::mem::size_of;
 
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -13,13 +14,13 @@ use solana_program::{
 use crate::{
     cpi::Cpi,
     error::AccessError,
-    state::{RewardsTuple, StakePoolHeader, Tag, STAKE_BUFFER_LEN},
+    state::{RewardIndex, StakePoolHeader, Tag},
 };
 use crate::{state::StakePool, utils::assert_valid_vault};
 use bonfida_utils::{BorshSize, InstructionsAccount};
 use crate::instruction::ProgramInstruction::CreateStakePool;
 
-use crate::utils::{check_account_key, check_account_owner};
+use crate::utils::{check_account_key, check_account_owner, check_token_program_owner};
 use crate::state:: CentralStateV2;
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
@@ -83,11 +84,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             &system_program::ID,
             AccessError::WrongOwner,
         )?;
-        check_account_owner(
-            accounts.vault,
-            &spl_token::ID,
-            AccessError::WrongTokenAccountOwner,
-        )?;
+        check_token_program_owner(accounts.vault, AccessError::WrongTokenAccountOwner)?;
 
         Ok(accounts)
     }
@@ -102,7 +99,9 @@ pub fn process_create_stake_pool(
     let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&CreateStakePool)?;
 
-    let (derived_stake_key, nonce) = StakePool::find_key(&accounts.owner.key, program_id);
+    let scope = central_state.scope(accounts.central_state.key, program_id);
+    let (derived_stake_key, nonce) =
+        StakePool::find_scoped_key(accounts.owner.key, scope.as_ref(), program_id);
 
     check_account_key(
         accounts.stake_pool_account,
@@ -110,22 +109,23 @@ pub fn process_create_stake_pool(
         AccessError::AccountNotDeterministic,
     )?;
 
-    assert_valid_vault(accounts.vault, &derived_stake_key)?;
+    assert_valid_vault(accounts.vault, &derived_stake_key, &central_state.token_mint)?;
 
-    let stake_pool_header = StakePoolHeader::new(
+    let mut stake_pool_header = StakePoolHeader::new(
         *accounts.owner.key,
         nonce,
         *accounts.vault.key,
         params.minimum_stake_amount,
     )?;
+    stake_pool_header.reward_index_enabled = 1;
 
     Cpi::create_account(
         program_id,
         accounts.system_program,
         accounts.fee_payer,
         accounts.stake_pool_account,
-        &[StakePoolHeader::SEED, &accounts.owner.key.to_bytes(), &[nonce]],
-        stake_pool_header.borsh_len() + size_of::<RewardsTuple>() * STAKE_BUFFER_LEN as usize,
+        &StakePoolHeader::seeds(accounts.owner.key, scope.as_ref(), &[nonce]),
+        stake_pool_header.borsh_len() + size_of::<RewardIndex>(),
     )?;
 
     let mut stake_pool =
@@ -5,22 +5,24 @@ use solana_program::{
     account_info::{AccountInfo, next_account_info},
     entrypoint::ProgramResult,
     msg,
-    program::invoke_signed,
     program_error::ProgramError,
-    program_pack::Pack,
     pubkey::Pubkey,
 };
 use solana_program::clock::Clock;
 use solana_program::sysvar::Sysvar;
-use spl_token::state::Account;
 
 use crate::{
     state::MIN_DISTRIBUTE_AMOUNT,
-    utils::{assert_valid_vault, check_account_key, check_account_owner},
+    utils::{
+        assert_valid_vault, check_account_key, check_account_owner, check_token_program,
+        unpack_token_account,
+    },
 };
+use crate::cpi::Cpi;
 use crate::error::AccessError;
+use crate::events::Event;
 use crate::instruction::ProgramInstruction::DistributeFees;
-use crate::state::CentralStateV2;
+use crate::state::{CentralStateV2, FeeDistributionConfig};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 /// The required parameters for the `distribute_fees` instruction
@@ -44,7 +46,10 @@ pub struct Accounts<'a, T> {
     #[cons(writable)]
     pub mint: &'a T,
 
-    /// The token accounts to distribute the fees to
+    /// The token accounts to distribute the fees to, optionally followed by
+    /// the fee distribution config and the keeper token account, then by
+    /// the accounts of the transfer hook of the mint. The keeper accounts
+    /// are skipped with the program id before the transfer hook accounts
     #[cons(writable)]
     pub token_accounts: &'a [T],
 }
@@ -64,11 +69,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         };
 
         // Check keys
-        check_account_key(
-            accounts.spl_token_program,
-            &spl_token::ID,
-            AccessError::WrongSplTokenProgramId,
-        )?;
+        check_token_program(accounts.spl_token_program)?;
 
         // Check ownership
         check_account_owner(
@@ -78,26 +79,14 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         )?;
         check_account_owner(
             accounts.central_state_vault,
-            &spl_token::ID,
+            accounts.spl_token_program.key,
             AccessError::WrongTokenAccountOwner,
         )?;
         check_account_owner(
             accounts.mint,
-            &spl_token::ID,
+            accounts.spl_token_program.key,
             AccessError::WrongOwner,
         )?;
-        msg!("Recipient count: {}", accounts.token_accounts.len());
-        for (i, token_account) in accounts.token_accounts.iter().enumerate() {
-            let res = check_account_owner(
-                token_account,
-                &spl_token::ID,
-                AccessError::WrongTokenAccountOwner,
-            );
-            if res.is_err() {
-                msg!("Token account {} owner invalid", i);
-                res?;
-            }
-        }
 
         // Check signer
         Ok(accounts)
@@ -113,7 +102,11 @@ pub fn process_distribute_fees(
 
     let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&DistributeFees)?;
-    assert_valid_vault(accounts.central_state_vault, accounts.central_state.key)?;
+    assert_valid_vault(
+        accounts.central_state_vault,
+        accounts.central_state.key,
+        &central_state.token_mint,
+    )?;
 
     check_account_key(
         accounts.mint,
@@ -121,17 +114,29 @@ pub fn process_distribute_fees(
         AccessError::WrongMint,
     )?;
 
-    // check recipient count
-    if accounts.token_accounts.len() != central_state.recipients.len() {
-        msg!("Invalid count of the token accounts");
-        return Err(AccessError::InvalidTokenAccount.into());
-    }
+    // check recipient count, the keeper accounts are optional and skipped with the program id when
+    // followed by the accounts of the transfer hook
+    let recipient_count = central_state.recipients.len();
+    let (recipient_accounts, keeper_accounts, transfer_hook_accounts) =
+        match accounts.token_accounts.len() {
+            n if n == recipient_count => (accounts.token_accounts, None, &[][..]),
+            n if n >= recipient_count + 2 => {
+                let (recipient_accounts, remaining_accounts) =
+                    accounts.token_accounts.split_at(recipient_count);
+                let (keeper_accounts, transfer_hook_accounts) = remaining_accounts.split_at(2);
+                let keeper_accounts = Some(keeper_accounts).filter(|k| k[0].key != program_id);
+                (recipient_accounts, keeper_accounts, transfer_hook_accounts)
+            }
+            _ => {
+                msg!("Invalid count of the token accounts");
+                return Err(AccessError::InvalidTokenAccount.into());
+            }
+        };
 
     // Distribute
-    let central_state_vault = Account::unpack(&accounts.central_state_vault.data.borrow())?;
+    let central_state_vault = unpack_token_account(accounts.central_state_vault)?;
     let total_balance = central_state_vault.amount;
     msg!("Balance to distribute: {}", total_balance);
-    let mut remaining_balance = total_balance;
 
     // This covers us against someone calling it too often and thereby burning too many excess tokens
     if total_balance < MIN_DISTRIBUTE_AMOUNT {
@@ -139,17 +144,79 @@ pub fn process_distribute_fees(
         return Err(AccessError::InvalidAmount.into());
     }
 
-    for (i, (token_account, recipient)) in accounts
-        .token_accounts
+    // Pay the keeper reward
+    let mut keeper_reward = 0;
+    if let Some(keeper_accounts) = keeper_accounts {
+        let fee_distribution_config = &keeper_accounts[0];
+        let keeper_token_account = &keeper_accounts[1];
+        let (derived_key, _) =
+            FeeDistributionConfig::create_key(accounts.central_state.key, program_id);
+        check_account_key(
+            fee_distribution_config,
+            &derived_key,
+            AccessError::AccountNotDeterministic,
+        )?;
+        check_account_owner(fee_distribution_config, program_id, AccessError::WrongOwner)?;
+        check_account_owner(
+            keeper_token_account,
+            accounts.spl_token_program.key,
+            AccessError::WrongOwner,
+        )?;
+        if keeper_token_account.key == accounts.central_state_vault.key {
+            msg!("The keeper token account cannot be the central state vault");
+            return Err(AccessError::InvalidTokenAccount.into());
+        }
+        if unpack_token_account(keeper_token_account)?.mint != central_state.token_mint {
+            msg!("Invalid keeper token account mint");
+            return Err(AccessError::WrongMint.into());
+        }
+
+        keeper_reward = FeeDistributionConfig::from_account_info(fee_distribution_config)?
+            .calculate_keeper_reward(total_balance)?;
+        if keeper_reward > 0 {
+            Cpi::transfer(
+                accounts.spl_token_program,
+                accounts.central_state_vault,
+                keeper_token_account,
+                accounts.central_state,
+                accounts.mint,
+                transfer_hook_accounts,
+                keeper_reward,
+                &[&central_state.signer_seeds(accounts.central_state.key, program_id)],
+            )?;
+            msg!("Paid keeper reward {}", keeper_reward);
+        }
+    }
+
+    let distributed_balance = total_balance
+        .checked_sub(keeper_reward)
+        .ok_or(AccessError::Overflow)?;
+    let mut remaining_balance = distributed_balance;
+    let mut paid_recipients = Vec::with_capacity(recipient_accounts.len());
+
+    msg!("Recipient count: {}", recipient_accounts.len());
+    for (i, token_account) in recipient_accounts.iter().enumerate() {
+        let res = check_account_owner(
+            token_account,
+            accounts.spl_token_program.key,
+            AccessError::WrongTokenAccountOwner,
+        );
+        if res.is_err() {
+            msg!("Token account {} owner invalid", i);
+            res?;
+        }
+    }
+
+    for (i, (token_account, recipient)) in recipient_accounts
         .iter()
         .zip(central_state.recipients.iter())
         .enumerate()
     {
-        if Account::unpack(&token_account.data.borrow())?.owner != recipient.owner {
+        if unpack_token_account(token_account)?.owner != recipient.owner {
             msg!("Invalid ordering of the token accounts at index {}", i);
             return Err(AccessError::InvalidTokenAccount.into());
         }
-        let amount = total_balance
+        let amount = distributed_balance
             .checked_mul(recipient.percentage)
             .ok_or(AccessError::Overflow)?
             .checked_div(100)
@@ -158,55 +225,48 @@ pub fn process_distribute_fees(
             msg!("Skipping zero amount for recipient with index {}", i);
             continue;
         }
-        let ix = spl_token::instruction::transfer(
-            &spl_token::ID,
-            accounts.central_state_vault.key,
-            token_account.key,
-            accounts.central_state.key,
-            &[],
+        Cpi::transfer(
+            accounts.spl_token_program,
+            accounts.central_state_vault,
+            token_account,
+            accounts.central_state,
+            accounts.mint,
+            transfer_hook_accounts,
             amount,
-        )
-            .unwrap();
-        invoke_signed(
-            &ix,
-            &[
-                accounts.spl_token_program.clone(),
-                accounts.central_state_vault.clone(),
-                token_account.clone(),
-                accounts.central_state.clone(),
-            ],
-            &[&[&program_id.to_bytes(), &[central_state.bump_seed]]],
+            &[&central_state.signer_seeds(accounts.central_state.key, program_id)],
         )?;
         remaining_balance = remaining_balance
             .checked_sub(amount)
             .ok_or(AccessError::Overflow)?;
+        paid_recipients.push((recipient.owner, amount));
     }
 
     // There is almost always something to burn due to rounding down.
     // However, this will be an insignificantly small amount (<= 1 ^ -5 ACS) if the sum of the percentages is 100.
     if remaining_balance > 0 {
-        let burn_instruction = spl_token::instruction::burn(
-            &spl_token::ID,
-            accounts.central_state_vault.key,
-            accounts.mint.key,
-            accounts.central_state.key,
-            &[],
+        Cpi::burn(
+            accounts.spl_token_program,
+            accounts.central_state_vault,
+            accounts.mint,
+            accounts.central_state,
             remaining_balance,
-        )?;
-        invoke_signed(
-            &burn_instruction,
-            &[
-                accounts.central_state_vault.clone(),
-                accounts.mint.clone(),
-                accounts.central_state.clone(),
-                accounts.central_state.clone(),
-            ],
-            &[&[&program_id.to_bytes(), &[central_state.bump_seed]]],
+            &[&central_state.signer_seeds(accounts.central_state.key, program_id)],
         )?;
         msg!("Burned {} tokens", remaining_balance);
     }
+    central_state.record_burned_fees(remaining_balance)?;
 
     central_state.last_fee_distribution_time = Clock::get()?.unix_timestamp;
     central_state.save(&mut accounts.central_state.data.borrow_mut())?;
+
+    Event::FeesDistributed {
+        central_state: *accounts.central_state.key,
+        amount: total_balance,
+        recipients: paid_recipients,
+        keeper_reward,
+        burned: remaining_balance,
+    }
+    .emit()?;
+
     Ok(())
 }
@@ -15,7 +15,7 @@ use solana_program::{
 use crate::{error::AccessError};
 use crate::instruction::ProgramInstruction::EditMetadata;
 use crate::state::V1_INSTRUCTIONS_ALLOWED;
-use crate::utils::{check_account_key, check_account_owner, check_signer};
+use crate::utils::{check_account_key, check_account_owner, check_admin_authority, check_signer};
 use crate::state:: CentralStateV2;
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
@@ -45,6 +45,9 @@ pub struct Accounts<'a, T> {
 
     /// The metadata program account
     pub metadata_program: &'a T,
+
+    /// The role table account, required once the roles are enabled
+    pub role_table: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -58,6 +61,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             authority: next_account_info(accounts_iter)?,
             metadata: next_account_info(accounts_iter)?,
             metadata_program: next_account_info(accounts_iter)?,
+            role_table: next_account_info(accounts_iter).ok(),
         };
 
         // Check keys
@@ -100,10 +104,13 @@ pub fn process_edit_metadata(
     central_state.assert_instruction_allowed(&EditMetadata)?;
     let (metadata_key, _) = find_metadata_account(&central_state.token_mint);
 
-    check_account_key(
+    check_admin_authority(
         accounts.authority,
-        &central_state.authority,
-        AccessError::WrongCentralStateAuthority,
+        accounts.role_table,
+        accounts.central_state.key,
+        &central_state,
+        &EditMetadata,
+        program_id,
     )?;
     check_account_key(
         accounts.metadata,
@@ -133,7 +140,7 @@ pub fn process_edit_metadata(
     invoke_signed(
         &ix,
         &[accounts.metadata.clone(), accounts.central_state.clone()],
-        &[&[&program_id.to_bytes(), &[central_state.bump_seed]]],
+        &[&central_state.signer_seeds(accounts.central_state.key, program_id)],
     )?;
 
     Ok(())
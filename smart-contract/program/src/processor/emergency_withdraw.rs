@@ -0,0 +1,368 @@
+//! Emergency withdraw
+//! Once the unstakes or the unlocks of the bonds V2 have been frozen for `MAX_FREEZE_DURATION`
+//! seconds, this instruction returns the principal of a stake account or of a bond V2 to its owner.
+//! It is not gated by the ix gate and skips the reward accounting, the unclaimed rewards are lost.
+//! The frozen stake pools and stake accounts are also covered.
+//! The receipt holders burn their receipts to withdraw the stake backing them and their share of
+//! the receipt reserve.
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use crate::cpi::Cpi;
+use crate::error::AccessError;
+use crate::events::Event;
+use crate::rewards::receipt_reserve_share;
+use crate::state::{
+    BondV2Account, CentralStateV2, PoolBoost, StakeAccount, StakePool, StakePoolHeader, Tag,
+};
+use crate::utils::{
+    check_account_key, check_account_owner, check_pool_central_state, check_receipt_accounts,
+    check_signer, check_token_program, next_optional_account_info, unpack_mint,
+    unpack_token_account,
+};
+use crate::versioning::{read_header, Versioned};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `emergency_withdraw` instruction
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `emergency_withdraw` instruction
+pub struct Accounts<'a, T> {
+    /// The central state account
+    #[cons(writable)]
+    pub central_state: &'a T,
+
+    /// The stake pool account
+    #[cons(writable)]
+    pub stake_pool: &'a T,
+
+    /// The stake account or the bond V2 account
+    #[cons(writable)]
+    pub position: &'a T,
+
+    /// The owner of the position or the receipt holder
+    #[cons(signer)]
+    pub owner: &'a T,
+
+    /// The destination of the principal
+    #[cons(writable)]
+    pub destination_token: &'a T,
+
+    /// The stake pool vault
+    #[cons(writable)]
+    pub vault: &'a T,
+
+    /// The SPL token program account
+    pub spl_token_program: &'a T,
+
+    /// The mint of the ACS token
+    pub mint: &'a T,
+
+    /// The pool boost account, required for the boosted bonds V2
+    #[cons(writable)]
+    pub pool_boost: Option<&'a T>,
+
+    /// The optional receipt mint of the stake pool, to withdraw the stake of the receipt holder
+    #[cons(writable)]
+    pub receipt_mint: Option<&'a T>,
+
+    /// The receipt token account of the holder, all its receipts are burnt
+    #[cons(writable)]
+    pub receipt_account: Option<&'a T>,
+
+    /// The receipt reserve, pays out the share of the accrued rewards
+    #[cons(writable)]
+    pub receipt_reserve: Option<&'a T>,
+
+    /// The accounts of the transfer hook of the mint, after the optional accounts which can be
+    /// skipped with the program id
+    pub transfer_hook_accounts: &'a [T],
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            central_state: next_account_info(accounts_iter)?,
+            stake_pool: next_account_info(accounts_iter)?,
+            position: next_account_info(accounts_iter)?,
+            owner: next_account_info(accounts_iter)?,
+            destination_token: next_account_info(accounts_iter)?,
+            vault: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            mint: next_account_info(accounts_iter)?,
+            pool_boost: next_optional_account_info(accounts_iter, program_id),
+            receipt_mint: next_optional_account_info(accounts_iter, program_id),
+            receipt_account: next_optional_account_info(accounts_iter, program_id),
+            receipt_reserve: next_optional_account_info(accounts_iter, program_id),
+            transfer_hook_accounts: accounts_iter.as_slice(),
+        };
+
+        // Check keys
+        check_token_program(accounts.spl_token_program)?;
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.stake_pool,
+            program_id,
+            AccessError::WrongStakePoolAccountOwner,
+        )?;
+        check_account_owner(
+            accounts.position,
+            program_id,
+            AccessError::WrongStakeAccountOwner,
+        )?;
+        check_account_owner(
+            accounts.destination_token,
+            accounts.spl_token_program.key,
+            AccessError::WrongTokenAccountOwner,
+        )?;
+        check_account_owner(
+            accounts.vault,
+            accounts.spl_token_program.key,
+            AccessError::WrongTokenAccountOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.owner, AccessError::StakeAccountOwnerMustSign)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_emergency_withdraw(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    // Not gated by the ix gate, the authority freezing the exits must not be able to block it
+    let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    let now = Clock::get()?.unix_timestamp;
+    if !central_state.emergency_exit_open(now) {
+        return Err(AccessError::EmergencyExitNotOpen.into());
+    }
+
+    let mut stake_pool = StakePool::get_checked(
+        accounts.stake_pool,
+        vec![Tag::StakePool, Tag::FrozenStakePool],
+    )?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+    check_account_key(accounts.mint, &central_state.token_mint, AccessError::WrongMint)?;
+
+    let destination_token_acc = unpack_token_account(accounts.destination_token)?;
+    if destination_token_acc.mint != central_state.token_mint {
+        msg!("Invalid ACCESS mint");
+        #[cfg(not(feature = "no-mint-check"))]
+        return Err(AccessError::WrongMint.into());
+    }
+    check_account_key(
+        accounts.vault,
+        &Pubkey::from(stake_pool.header.vault),
+        AccessError::StakePoolVaultMismatch,
+    )?;
+
+    // The boost of a bond V2 is removed from the weights of the pool and of the system
+    let (tag, _) = read_header(&accounts.position.data.borrow())?;
+    let mut receipts = None;
+    let (amount, removed_boost) = match tag {
+        Tag::StakeAccount | Tag::FrozenStakeAccount => {
+            let mut stake_account = StakeAccount::load(&accounts.position.data.borrow())?;
+            check_account_key(
+                accounts.stake_pool,
+                &stake_account.stake_pool,
+                AccessError::StakePoolMismatch,
+            )?;
+            // The receipt holders withdraw from the stake account owned by the receipt mint
+            let amount = match (
+                accounts.receipt_mint,
+                accounts.receipt_account,
+                accounts.receipt_reserve,
+            ) {
+                (Some(receipt_mint), Some(receipt_account), Some(receipt_reserve)) => {
+                    let receipt_mint_nonce = check_receipt_accounts(
+                        accounts.stake_pool.key,
+                        &central_state.token_mint,
+                        accounts.spl_token_program.key,
+                        receipt_mint,
+                        receipt_reserve,
+                        accounts.position,
+                        program_id,
+                    )?;
+                    receipts = Some((
+                        receipt_mint,
+                        receipt_account,
+                        receipt_reserve,
+                        receipt_mint_nonce,
+                    ));
+                    unpack_token_account(receipt_account)?.amount
+                }
+                (None, _, _) => {
+                    check_account_key(
+                        accounts.owner,
+                        &stake_account.owner,
+                        AccessError::StakeAccountOwnerMismatch,
+                    )?;
+                    stake_account.stake_amount
+                }
+                _ => return Err(ProgramError::NotEnoughAccountKeys),
+            };
+            stake_account.withdraw(amount)?;
+            stake_account.save(&mut accounts.position.data.borrow_mut())?;
+            (amount, 0)
+        }
+        Tag::BondV2Account => {
+            let mut bond = BondV2Account::from_account_info(accounts.position)?;
+            check_account_key(
+                accounts.owner,
+                &bond.owner,
+                AccessError::StakeAccountOwnerMismatch,
+            )?;
+            check_account_key(
+                accounts.stake_pool,
+                &bond.pool,
+                AccessError::StakePoolMismatch,
+            )?;
+            match bond.unlock_timestamp {
+                None => {
+                    msg!("Cannot withdraw from a Forever bond");
+                    return Err(ProgramError::InvalidArgument);
+                }
+                // The emergency withdrawal does not shorten the lock of the bond
+                Some(unlock_timestamp) if now < unlock_timestamp => {
+                    msg!("The bond tokens have not started unlocking yet");
+                    return Err(ProgramError::InvalidArgument);
+                }
+                Some(_) => {}
+            }
+            let removed_boost = bond.boost;
+            if removed_boost != 0 {
+                let pool_boost_info = accounts
+                    .pool_boost
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                let mut pool_boost =
+                    PoolBoost::get_checked(pool_boost_info, accounts.stake_pool.key, program_id)?;
+                pool_boost.update(removed_boost, 0)?;
+                pool_boost.save(&mut pool_boost_info.data.borrow_mut())?;
+                bond.boost = 0;
+            }
+            let amount = bond.amount;
+            bond.withdraw(amount)?;
+            bond.save(&mut accounts.position.data.borrow_mut())?;
+            (amount, removed_boost)
+        }
+        _ => {
+            msg!("The position must be a stake account or a bond V2 account");
+            return Err(AccessError::DataTypeMismatch.into());
+        }
+    };
+    if amount == 0 {
+        msg!("Nothing to withdraw");
+        return Err(ProgramError::InvalidArgument);
+    }
+    stake_pool.header.withdraw(amount)?;
+
+    // Transfer tokens
+    let pool_owner = Pubkey::new_from_array(stake_pool.header.owner);
+    let pool_nonce = [stake_pool.header.nonce];
+    let scope = central_state.scope(accounts.central_state.key, program_id);
+    let signer_seeds = StakePoolHeader::seeds(&pool_owner, scope.as_ref(), &pool_nonce);
+
+    drop(stake_pool);
+
+    Cpi::transfer(
+        accounts.spl_token_program,
+        accounts.vault,
+        accounts.destination_token,
+        accounts.stake_pool,
+        accounts.mint,
+        accounts.transfer_hook_accounts,
+        amount,
+        &[&signer_seeds],
+    )?;
+
+    // Burn the receipts and pay out their share of the reserve
+    let mut receipt_share = 0;
+    if let Some((receipt_mint, receipt_account, receipt_reserve, receipt_mint_nonce)) = receipts {
+        receipt_share = receipt_reserve_share(
+            amount,
+            unpack_token_account(receipt_reserve)?.amount,
+            unpack_mint(receipt_mint)?.supply,
+            false,
+        )?;
+        Cpi::burn(
+            accounts.spl_token_program,
+            receipt_account,
+            receipt_mint,
+            accounts.owner,
+            amount,
+            &[],
+        )?;
+        if receipt_share > 0 {
+            Cpi::transfer(
+                accounts.spl_token_program,
+                receipt_reserve,
+                accounts.destination_token,
+                receipt_mint,
+                accounts.mint,
+                accounts.transfer_hook_accounts,
+                receipt_share,
+                &[&[
+                    StakePoolHeader::RECEIPT_MINT_SEED,
+                    &accounts.stake_pool.key.to_bytes(),
+                    &[receipt_mint_nonce],
+                ]],
+            )?;
+        }
+    }
+
+    // Update central state
+    central_state.total_staked = central_state
+        .total_staked
+        .checked_sub(amount)
+        .ok_or(AccessError::Overflow)?;
+    central_state.update_boost(removed_boost, 0)?;
+    central_state.save(&mut accounts.central_state.data.borrow_mut())?;
+
+    Event::EmergencyWithdrawal {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.stake_pool.key,
+        account: *accounts.position.key,
+        owner: *accounts.owner.key,
+        amount,
+    }
+    .emit()?;
+    if let Some((_, receipt_account, _, _)) = receipts {
+        Event::ReceiptRedeemed {
+            central_state: *accounts.central_state.key,
+            stake_pool: *accounts.stake_pool.key,
+            receipt_account: *receipt_account.key,
+            amount,
+            reserve_amount: receipt_share,
+        }
+        .emit()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+//! Switch a stake pool from its rewards buffer to the cumulative reward index
+//! The rewards buffer is kept after the index until the pool is compacted so that the stakers
+//! can still claim the days before the switch
+use std::mem::size_of;
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::error::AccessError;
+use crate::events::{AdminChange, Event};
+use crate::instruction::ProgramInstruction::EnableRewardIndex;
+use crate::rewards::initial_reward_index;
+use crate::state::{CentralStateV2, RewardIndex, StakePool, StakePoolHeader, Tag};
+use crate::utils::{
+    check_account_key, check_account_owner, check_pool_central_state, check_signer, grow_account,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `enable_reward_index` instruction
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `enable_reward_index` instruction
+pub struct Accounts<'a, T> {
+    /// The fee payer account, pays the rent of the reward index
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The stake pool account
+    #[cons(writable)]
+    pub stake_pool: &'a T,
+
+    /// The owner of the stake pool
+    #[cons(signer)]
+    pub owner: &'a T,
+
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            fee_payer: next_account_info(accounts_iter)?,
+            stake_pool: next_account_info(accounts_iter)?,
+            owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
+        // Check ownership
+        check_account_owner(
+            accounts.stake_pool,
+            program_id,
+            AccessError::WrongStakePoolAccountOwner,
+        )?;
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+
+        // Check signer
+        check_signer(accounts.owner, AccessError::StakePoolOwnerMustSign)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_enable_reward_index(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&EnableRewardIndex)?;
+
+    let (reward_index, start_day_idx) = {
+        let stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+        check_pool_central_state(
+            accounts.stake_pool.key,
+            &stake_pool.header,
+            accounts.central_state.key,
+            &central_state,
+            program_id,
+        )?;
+        check_account_key(
+            accounts.owner,
+            &Pubkey::from(stake_pool.header.owner),
+            AccessError::WrongStakePoolOwner,
+        )?;
+
+        if stake_pool.header.uses_reward_index() {
+            return Err(AccessError::RewardIndexAlreadyEnabled.into());
+        }
+
+        // The rewards of the current day must be in the buffer before the switch
+        if (stake_pool.header.current_day_idx as u64) < central_state.get_current_offset()? {
+            msg!("Pool must be cranked before enabling the reward index");
+            return Err(AccessError::PoolMustBeCranked.into());
+        }
+
+        (
+            initial_reward_index(&stake_pool)?,
+            stake_pool.header.current_day_idx,
+        )
+    };
+
+    // The reward index is inserted between the header and the rewards buffer
+    let buffer_start = size_of::<StakePoolHeader>();
+    let buffer_end = accounts.stake_pool.data_len();
+    grow_account(
+        accounts.stake_pool,
+        buffer_end + size_of::<RewardIndex>(),
+        accounts.fee_payer,
+        accounts.system_program,
+    )?;
+    accounts.stake_pool.data.borrow_mut().copy_within(
+        buffer_start..buffer_end,
+        buffer_start + size_of::<RewardIndex>(),
+    );
+
+    let mut stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    stake_pool.header.reward_index_enabled = 1;
+    stake_pool.header.reward_index_start = start_day_idx;
+    *stake_pool
+        .reward_index_mut()
+        .ok_or(AccessError::RewardIndexNotEnabled)? = reward_index;
+
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.owner.key,
+        change: AdminChange::RewardIndex {
+            stake_pool: *accounts.stake_pool.key,
+            start_day_idx,
+        },
+    }
+    .emit()?;
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+//! Read-only view of the central state
+//! This instruction changes no state, it returns a `CentralStateInfoView` with `set_return_data`
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::error::AccessError;
+use crate::instruction::ProgramInstruction::GetCentralStateInfo;
+use crate::state::CentralStateV2;
+use crate::utils::check_account_owner;
+use crate::views::{self, CentralStateInfoView};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `get_central_state_info` instruction
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `get_central_state_info` instruction
+pub struct Accounts<'a, T> {
+    /// The central state account
+    pub central_state: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            central_state: next_account_info(accounts_iter)?,
+        };
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_get_central_state_info(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&GetCentralStateInfo)?;
+
+    views::set(&CentralStateInfoView {
+        token_mint: central_state.token_mint,
+        authority: central_state.authority,
+        daily_inflation: central_state.daily_inflation,
+        total_staked: central_state.total_staked,
+        total_staked_snapshot: central_state.total_staked_snapshot,
+        last_snapshot_offset: central_state.last_snapshot_offset,
+        current_offset: central_state.get_current_offset()?,
+        creation_time: central_state.creation_time,
+        fee_basis_points: central_state.fee_basis_points,
+        ix_gate: central_state.ix_gate,
+    })
+}
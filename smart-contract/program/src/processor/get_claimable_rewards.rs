@@ -0,0 +1,133 @@
+//! Read-only view of the rewards claimable by a stake account
+//! This instruction changes no state, it returns a `ClaimableRewardsView` with `set_return_data`
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use crate::error::AccessError;
+use crate::instruction::ProgramInstruction::GetClaimableRewards;
+use crate::rewards::{calc_reward_fp32, is_royalty_active, split_royalty, staker_reward};
+use crate::state::{CentralStateV2, RoyaltyAccount, StakeAccount, StakePool, Tag};
+use crate::utils::{check_account_key, check_account_owner, check_pool_central_state};
+use crate::views::{self, claimable_offset, ClaimableRewardsView};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `get_claimable_rewards` instruction
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `get_claimable_rewards` instruction
+pub struct Accounts<'a, T> {
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The stake pool account
+    pub stake_pool: &'a T,
+
+    /// The stake account
+    pub stake_account: &'a T,
+
+    /// The royalty account of the stake account owner, the royalties are not deducted without it
+    pub owner_royalty_account: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            central_state: next_account_info(accounts_iter)?,
+            stake_pool: next_account_info(accounts_iter)?,
+            stake_account: next_account_info(accounts_iter)?,
+            owner_royalty_account: next_account_info(accounts_iter).ok(),
+        };
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.stake_pool,
+            program_id,
+            AccessError::WrongStakeAccountOwner,
+        )?;
+        check_account_owner(
+            accounts.stake_account,
+            program_id,
+            AccessError::WrongStakeAccountOwner,
+        )?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_get_claimable_rewards(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&GetClaimableRewards)?;
+    let stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+    let stake_account = StakeAccount::from_account_info(accounts.stake_account)?;
+    check_account_key(
+        accounts.stake_pool,
+        &stake_account.stake_pool,
+        AccessError::WrongStakePool,
+    )?;
+
+    let mut royalty_account = None;
+    if let Some(owner_royalty_account) = accounts.owner_royalty_account {
+        let scope = central_state.scope(accounts.central_state.key, program_id);
+        let (derived_key, _) =
+            RoyaltyAccount::create_scoped_key(&stake_account.owner, scope.as_ref(), program_id);
+        check_account_key(
+            owner_royalty_account,
+            &derived_key,
+            AccessError::AccountNotDeterministic,
+        )?;
+        if !owner_royalty_account.data_is_empty() {
+            royalty_account = Some(RoyaltyAccount::from_account_info(owner_royalty_account)?);
+        }
+    }
+    let now = Clock::get()?.unix_timestamp;
+    let royalty_account = royalty_account.filter(|r| is_royalty_active(r, now));
+
+    let offset = claimable_offset(&central_state, &stake_pool.header);
+    let reward_fp32 = calc_reward_fp32(
+        offset,
+        stake_account.last_claimed_offset,
+        Some(stake_account.last_claimed_index),
+        &stake_pool,
+        true,
+        true,
+    )?;
+    let rewards = split_royalty(
+        staker_reward(reward_fp32, stake_account.stake_amount)?,
+        royalty_account.as_ref(),
+    )?;
+
+    views::set(&ClaimableRewardsView {
+        reward: rewards.reward,
+        royalty: rewards.royalty,
+        offset,
+        needs_crank: (stake_pool.header.current_day_idx as u64)
+            < central_state.get_current_offset()?,
+    })
+}
@@ -0,0 +1,109 @@
+//! Read-only view of the staker APR of a pool
+//! This instruction changes no state, it returns a `PoolAprView` with `set_return_data`.
+//! The rewards are projected from the current inflation and amounts staked.
+use std::convert::TryInto;
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::error::AccessError;
+use crate::instruction::ProgramInstruction::GetPoolApr;
+use crate::rewards::{calc_daily_rewards, pool_reward};
+use crate::state::{CentralStateV2, StakePool, Tag};
+use crate::utils::{check_account_owner, check_pool_central_state};
+use crate::views::{self, PoolAprView};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `get_pool_apr` instruction
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `get_pool_apr` instruction
+pub struct Accounts<'a, T> {
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The stake pool account
+    pub stake_pool: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            central_state: next_account_info(accounts_iter)?,
+            stake_pool: next_account_info(accounts_iter)?,
+        };
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.stake_pool,
+            program_id,
+            AccessError::WrongStakeAccountOwner,
+        )?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_get_pool_apr(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&GetPoolApr)?;
+    let stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+
+    // The first crank of a day takes a new snapshot of the total staked and boosted
+    let total_weight_snapshot =
+        if central_state.last_snapshot_offset < central_state.get_current_offset()? {
+            central_state
+                .total_staked
+                .checked_add(central_state.total_boost)
+                .ok_or(AccessError::Overflow)?
+        } else {
+            central_state.total_weight_snapshot()?
+        };
+    let daily = calc_daily_rewards(
+        central_state.daily_inflation,
+        stake_pool.header.total_staked,
+        stake_pool.header.stakers_part,
+        total_weight_snapshot,
+    )?;
+
+    let stakers_apr_basis_points = daily
+        .rewards
+        .stakers_reward
+        .checked_mul(365 * 10_000)
+        .ok_or(AccessError::Overflow)?
+        >> 32;
+
+    views::set(&PoolAprView {
+        stakers_apr_basis_points: stakers_apr_basis_points
+            .try_into()
+            .map_err(|_| AccessError::Overflow)?,
+        stakers_daily_reward_fp32: daily.rewards.stakers_reward,
+        pool_daily_reward: pool_reward(daily.rewards.pool_reward)?,
+        total_staked: stake_pool.header.total_staked,
+    })
+}
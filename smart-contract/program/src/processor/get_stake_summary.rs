@@ -0,0 +1,165 @@
+//! Read-only view of the stake of an owner in a pool
+//! This instruction changes no state, it returns a `StakeSummaryView` with `set_return_data`.
+//! The effective stake adds the amounts locked in the given bond V2 accounts to the stake account.
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::error::AccessError;
+use crate::instruction::ProgramInstruction::GetStakeSummary;
+use crate::rewards::{calc_reward_fp32, staker_reward};
+use crate::state::{BondV2Account, CentralStateV2, StakeAccount, StakePool, Tag};
+use crate::utils::{check_account_key, check_account_owner, check_pool_central_state};
+use crate::views::{self, claimable_offset, StakeSummaryView};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `get_stake_summary` instruction
+pub struct Params {
+    /// The owner of the stake
+    pub owner: Pubkey,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `get_stake_summary` instruction
+pub struct Accounts<'a, T> {
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The stake pool account
+    pub stake_pool: &'a T,
+
+    /// The stake account of the owner, it does not need to exist
+    pub stake_account: &'a T,
+
+    /// The bond V2 accounts of the owner in the pool
+    pub bond_v2_accounts: &'a [T],
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            central_state: next_account_info(accounts_iter)?,
+            stake_pool: next_account_info(accounts_iter)?,
+            stake_account: next_account_info(accounts_iter)?,
+            bond_v2_accounts: accounts_iter.as_slice(),
+        };
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.stake_pool,
+            program_id,
+            AccessError::WrongStakeAccountOwner,
+        )?;
+        for bond_v2_account in accounts.bond_v2_accounts {
+            check_account_owner(bond_v2_account, program_id, AccessError::WrongOwner)?;
+        }
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_get_stake_summary(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    let Params { owner } = params;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&GetStakeSummary)?;
+    let stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+    let offset = claimable_offset(&central_state, &stake_pool.header);
+
+    let (stake_account_key, _) =
+        StakeAccount::find_key(&owner, accounts.stake_pool.key, program_id);
+    check_account_key(
+        accounts.stake_account,
+        &stake_account_key,
+        AccessError::AccountNotDeterministic,
+    )?;
+
+    let mut staked_amount = 0;
+    let mut claimable_rewards = 0u64;
+    if !accounts.stake_account.data_is_empty() {
+        check_account_owner(
+            accounts.stake_account,
+            program_id,
+            AccessError::WrongStakeAccountOwner,
+        )?;
+        let stake_account = StakeAccount::from_account_info(accounts.stake_account)?;
+        let reward_fp32 = calc_reward_fp32(
+            offset,
+            stake_account.last_claimed_offset,
+            Some(stake_account.last_claimed_index),
+            &stake_pool,
+            true,
+            true,
+        )?;
+        staked_amount = stake_account.stake_amount;
+        claimable_rewards = staker_reward(reward_fp32, stake_account.stake_amount)?;
+    }
+
+    let mut bonds_v2_amount = 0u64;
+    for (i, account) in accounts.bond_v2_accounts.iter().enumerate() {
+        let bond = BondV2Account::from_account_info(account)?;
+        if bond.owner != owner || &bond.pool != accounts.stake_pool.key {
+            msg!("Invalid bond V2 account at index {}", i);
+            return Err(AccessError::WrongStakePool.into());
+        }
+        // A bond must not be counted twice
+        if accounts.bond_v2_accounts[..i]
+            .iter()
+            .any(|a| a.key == account.key)
+        {
+            msg!("Duplicate bond V2 account at index {}", i);
+            return Err(ProgramError::InvalidArgument);
+        }
+        let reward_fp32 = calc_reward_fp32(
+            offset,
+            bond.last_claimed_offset,
+            Some(bond.last_claimed_index),
+            &stake_pool,
+            true,
+            true,
+        )?;
+        bonds_v2_amount = bonds_v2_amount
+            .checked_add(bond.amount)
+            .ok_or(AccessError::Overflow)?;
+        claimable_rewards = claimable_rewards
+            .checked_add(staker_reward(reward_fp32, bond.amount)?)
+            .ok_or(AccessError::Overflow)?;
+    }
+
+    let effective_stake = staked_amount
+        .checked_add(bonds_v2_amount)
+        .ok_or(AccessError::Overflow)?;
+    views::set(&StakeSummaryView {
+        owner,
+        stake_pool: *accounts.stake_pool.key,
+        staked_amount,
+        bonds_v2_amount,
+        effective_stake,
+        minimum_stake_amount: stake_pool.header.minimum_stake_amount,
+        meets_minimum: effective_stake >= stake_pool.header.minimum_stake_amount,
+        claimable_rewards,
+    })
+}
@@ -0,0 +1,216 @@
+//! List a bond V2 for sale
+//! The bond is escrowed in a bond V2 account owned by the listing until it is bought or the
+//! listing is cancelled. The rewards of the escrowed bond go to its next owner
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::cpi::Cpi;
+use crate::error::AccessError;
+use crate::events::Event;
+use crate::instruction::ProgramInstruction::ListBondV2;
+use crate::processor::transfer_bond_v2::move_bond_v2;
+use crate::state::{BondV2Account, BondV2Listing, CentralStateV2, StakePool, Tag};
+use crate::utils::{
+    assert_bond_v2_rewards_claimed, check_account_key, check_account_owner,
+    check_pool_central_state, check_signer, check_token_program_owner, unpack_token_account,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `list_bond_v2` instruction
+pub struct Params {
+    /// The price of the bond in quote tokens
+    pub price: u64,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `list_bond_v2` instruction
+pub struct Accounts<'a, T> {
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The bond V2 account of the seller, closed by the listing
+    #[cons(writable)]
+    pub bond_v2_account: &'a T,
+
+    /// The listing account
+    #[cons(writable)]
+    pub listing: &'a T,
+
+    /// The bond V2 account of the listing escrowing the bond
+    #[cons(writable)]
+    pub escrow: &'a T,
+
+    /// The owner of the bond
+    #[cons(writable, signer)]
+    pub seller: &'a T,
+
+    /// The mint in which the price is paid
+    pub quote_mint: &'a T,
+
+    /// The token account of the seller receiving the price
+    pub quote_destination: &'a T,
+
+    /// The pool account
+    pub pool: &'a T,
+
+    /// The fee payer account, pays the rent of the listing and the escrow
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            central_state: next_account_info(accounts_iter)?,
+            bond_v2_account: next_account_info(accounts_iter)?,
+            listing: next_account_info(accounts_iter)?,
+            escrow: next_account_info(accounts_iter)?,
+            seller: next_account_info(accounts_iter)?,
+            quote_mint: next_account_info(accounts_iter)?,
+            quote_destination: next_account_info(accounts_iter)?,
+            pool: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.bond_v2_account,
+            program_id,
+            AccessError::WrongBondAccountOwner,
+        )?;
+        check_account_owner(
+            accounts.listing,
+            &system_program::ID,
+            AccessError::WrongOwner,
+        )?;
+        check_account_owner(
+            accounts.pool,
+            program_id,
+            AccessError::WrongStakePoolAccountOwner,
+        )?;
+        check_token_program_owner(accounts.quote_mint, AccessError::WrongOwner)?;
+        check_token_program_owner(
+            accounts.quote_destination,
+            AccessError::WrongTokenAccountOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.seller, AccessError::OwnerMustSign)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_list_bond_v2(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let Params { price } = params;
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&ListBondV2)?;
+    let stake_pool = StakePool::get_checked(accounts.pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+    let bond = BondV2Account::from_account_info(accounts.bond_v2_account)?;
+
+    check_account_key(accounts.seller, &bond.owner, AccessError::WrongOwner)?;
+    check_account_key(accounts.pool, &bond.pool, AccessError::WrongStakePool)?;
+    assert_bond_v2_rewards_claimed(&bond, &stake_pool.header, &central_state)?;
+
+    if unpack_token_account(accounts.quote_destination)?.mint != *accounts.quote_mint.key {
+        return Err(AccessError::WrongQuoteMint.into());
+    }
+    if price == 0 {
+        msg!("The price of a listing cannot be zero");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (derived_key, bump_seed) =
+        BondV2Listing::create_key(accounts.bond_v2_account.key, program_id);
+    check_account_key(
+        accounts.listing,
+        &derived_key,
+        AccessError::AccountNotDeterministic,
+    )?;
+
+    let listing = BondV2Listing::new(
+        *accounts.seller.key,
+        *accounts.escrow.key,
+        bond.pool,
+        bond.unlock_timestamp,
+        *accounts.quote_mint.key,
+        *accounts.quote_destination.key,
+        price,
+    );
+
+    let seeds: &[&[u8]] = &[
+        BondV2Listing::SEED,
+        &accounts.bond_v2_account.key.to_bytes(),
+        &[bump_seed],
+    ];
+    Cpi::create_account(
+        program_id,
+        accounts.system_program,
+        accounts.fee_payer,
+        accounts.listing,
+        seeds,
+        listing.borsh_len(),
+    )?;
+    listing.save(&mut accounts.listing.data.borrow_mut())?;
+
+    move_bond_v2(
+        program_id,
+        accounts.central_state.key,
+        bond,
+        accounts.bond_v2_account,
+        accounts.escrow,
+        *accounts.listing.key,
+        accounts.fee_payer,
+        accounts.seller,
+        accounts.system_program,
+    )?;
+
+    Event::BondListed {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.pool.key,
+        listing: *accounts.listing.key,
+        seller: *accounts.seller.key,
+        quote_mint: *accounts.quote_mint.key,
+        price,
+    }
+    .emit()?;
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//! Rewrite an account with the latest layout of its type
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::error::AccessError;
+use crate::state::{BondV2Account, CentralStateV2, StakeAccount, Tag};
+use crate::utils::{check_account_key, check_account_owner};
+use crate::versioning::{migrate, read_header};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `migrate_account` instruction
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `migrate_account` instruction
+pub struct Accounts<'a, T> {
+    /// The fee payer account, pays the rent of the additional space
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The account to migrate
+    #[cons(writable)]
+    pub account: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            fee_payer: next_account_info(accounts_iter)?,
+            account: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.account, program_id, AccessError::WrongOwner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_migrate_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    migrate_account(&accounts)
+}
+
+/// Migrates the account to the latest layout of its tag
+pub(crate) fn migrate_account(accounts: &Accounts<AccountInfo>) -> ProgramResult {
+    let (tag, _) = read_header(&accounts.account.data.borrow())?;
+    match tag {
+        Tag::StakeAccount | Tag::FrozenStakeAccount => migrate::<StakeAccount>(
+            accounts.account,
+            accounts.fee_payer,
+            accounts.system_program,
+        ),
+        Tag::BondV2Account => migrate::<BondV2Account>(
+            accounts.account,
+            accounts.fee_payer,
+            accounts.system_program,
+        ),
+        Tag::CentralStateV2 => migrate::<CentralStateV2>(
+            accounts.account,
+            accounts.fee_payer,
+            accounts.system_program,
+        ),
+        Tag::Uninitialized | Tag::Deleted => Err(AccessError::DataTypeMismatch.into()),
+        _ => {
+            msg!("The account type has no newer layout");
+            Err(AccessError::NoOp.into())
+        }
+    }
+}
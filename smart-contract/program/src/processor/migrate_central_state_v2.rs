@@ -100,8 +100,10 @@ pub fn process_migrate_central_state_v2(
     )?;
     accounts.central_state.realloc(new_data_len, false)?;
 
-    // Save new data
-    state_v2.save(&mut accounts.central_state.data.borrow_mut())?;
+    // Save new data, with the latest layout since the account still has the V1 tag
+    state_v2
+        .serialize(&mut &mut accounts.central_state.data.borrow_mut()[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
 
     Ok(())
 }
@@ -0,0 +1,224 @@
+//! Permissionless instruction to refresh the boost of a bond V2
+//! The rewards earned with the previous boost are settled in the bond and paid by its next claim,
+//! the new boost follows the remaining lock time of the bond and the boost config
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use crate::cpi::Cpi;
+use crate::error::AccessError;
+use crate::events::Event;
+use crate::instruction::ProgramInstruction::RefreshBondV2Boost;
+use crate::rewards::{bond_boost, staker_reward};
+use crate::state::{
+    BondV2Account, BoostConfig, CentralStateV2, PoolBoost, StakePool, StakePoolHeader, Tag,
+};
+use crate::utils::{
+    calc_reward_fp32, check_account_key, check_account_owner, check_pool_central_state,
+    check_signer,
+};
+use crate::versioning::migrate_if_needed;
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `refresh_bond_v2_boost` instruction
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `refresh_bond_v2_boost` instruction
+pub struct Accounts<'a, T> {
+    /// The central state account
+    #[cons(writable)]
+    pub central_state: &'a T,
+
+    /// The bond V2 account
+    #[cons(writable)]
+    pub bond_v2_account: &'a T,
+
+    /// The pool account
+    #[cons(writable)]
+    pub pool: &'a T,
+
+    /// The pool boost account
+    #[cons(writable)]
+    pub pool_boost: &'a T,
+
+    /// The boost config account
+    pub boost_config: &'a T,
+
+    /// The fee payer account, pays the rent of the pool boost and of the migrations
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            central_state: next_account_info(accounts_iter)?,
+            bond_v2_account: next_account_info(accounts_iter)?,
+            pool: next_account_info(accounts_iter)?,
+            pool_boost: next_account_info(accounts_iter)?,
+            boost_config: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.bond_v2_account,
+            program_id,
+            AccessError::WrongBondAccountOwner,
+        )?;
+        check_account_owner(
+            accounts.pool,
+            program_id,
+            AccessError::WrongStakePoolAccountOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.fee_payer, AccessError::OwnerMustSign)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_refresh_bond_v2_boost(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&RefreshBondV2Boost)?;
+    let mut stake_pool = StakePool::get_checked(accounts.pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+    let boost_config = BoostConfig::get_checked(
+        accounts.boost_config,
+        accounts.central_state.key,
+        program_id,
+    )?;
+    let mut bond = BondV2Account::from_account_info(accounts.bond_v2_account)?;
+
+    check_account_key(accounts.pool, &bond.pool, AccessError::WrongStakePool)?;
+    if (stake_pool.header.current_day_idx as u64) < central_state.get_current_offset()? {
+        return Err(AccessError::PoolMustBeCranked.into());
+    }
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let boost = bond_boost(
+        bond.amount,
+        bond.unlock_timestamp,
+        current_time,
+        &boost_config,
+    )?;
+    if boost == bond.boost {
+        msg!("The boost of the bond is up to date");
+        return Err(AccessError::NoOp.into());
+    }
+
+    // The bond account and the central state need their latest layout to hold the boosts
+    migrate_if_needed::<BondV2Account>(
+        accounts.bond_v2_account,
+        accounts.fee_payer,
+        accounts.system_program,
+    )?;
+    migrate_if_needed::<CentralStateV2>(
+        accounts.central_state,
+        accounts.fee_payer,
+        accounts.system_program,
+    )?;
+
+    let (derived_key, bump_seed) = PoolBoost::create_key(accounts.pool.key, program_id);
+    check_account_key(
+        accounts.pool_boost,
+        &derived_key,
+        AccessError::AccountNotDeterministic,
+    )?;
+    let mut pool_boost = if accounts.pool_boost.data_is_empty() {
+        let pool_boost = PoolBoost::new(*accounts.pool.key);
+        Cpi::create_account(
+            program_id,
+            accounts.system_program,
+            accounts.fee_payer,
+            accounts.pool_boost,
+            &[PoolBoost::SEED, &accounts.pool.key.to_bytes(), &[bump_seed]],
+            pool_boost.borsh_len(),
+        )?;
+        pool_boost
+    } else {
+        PoolBoost::get_checked(accounts.pool_boost, accounts.pool.key, program_id)?
+    };
+
+    // Settle the rewards earned with the previous boost
+    let reward_fp32 = calc_reward_fp32(
+        central_state.last_snapshot_offset,
+        bond.last_claimed_offset,
+        Some(bond.last_claimed_index),
+        &stake_pool,
+        true,
+        true,
+    )?;
+    let settled_rewards = staker_reward(reward_fp32, bond.weight()?)?;
+    bond.unclaimed_rewards = bond
+        .unclaimed_rewards
+        .checked_add(settled_rewards)
+        .ok_or(AccessError::Overflow)?;
+    bond.last_claimed_offset = central_state.last_snapshot_offset;
+    bond.last_claimed_index = stake_pool.stakers_reward_index();
+
+    // Update the weights of the pool and of the system
+    let previous_boost = bond.boost;
+    pool_boost.update(previous_boost, boost)?;
+    central_state.update_boost(previous_boost, boost)?;
+    stake_pool.header.flags |= StakePoolHeader::BOOST_FLAG;
+    bond.boost = boost;
+
+    msg!("Boost refreshed from {} to {}", previous_boost, boost);
+
+    // Save states
+    bond.save(&mut accounts.bond_v2_account.data.borrow_mut())?;
+    pool_boost.save(&mut accounts.pool_boost.data.borrow_mut())?;
+    central_state.save(&mut accounts.central_state.data.borrow_mut())?;
+
+    Event::BondBoostRefreshed {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.pool.key,
+        bond: *accounts.bond_v2_account.key,
+        previous_boost,
+        boost,
+        settled_rewards,
+    }
+    .emit()?;
+
+    Ok(())
+}
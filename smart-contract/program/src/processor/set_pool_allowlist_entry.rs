@@ -0,0 +1,181 @@
+//! Set a pool allowlist entry
+//! This instruction allows a pool owner to add a staker to the allowlist of its pool, or to remove
+//! it and get the rent of the entry back. The allowlist is only checked once enabled by the pool
+//! controls, for the new stakes
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::cpi::Cpi;
+use crate::error::AccessError;
+use crate::events::{AdminChange, Event};
+use crate::instruction::ProgramInstruction::SetPoolAllowlistEntry;
+use crate::state::{CentralStateV2, PoolAllowlistEntry, StakePool, Tag};
+use crate::utils::{
+    check_account_key, check_account_owner, check_pool_central_state, check_signer,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `set_pool_allowlist_entry` instruction
+pub struct Params {
+    /// The staker, the owner of the stake account or of the bond V2
+    pub staker: Pubkey,
+    /// Adds the staker to the allowlist if `true`, removes it otherwise
+    pub allowed: bool,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `set_pool_allowlist_entry` instruction
+pub struct Accounts<'a, T> {
+    /// The stake pool account
+    pub stake_pool: &'a T,
+
+    /// The stake pool owner account
+    #[cons(writable, signer)]
+    pub stake_pool_owner: &'a T,
+
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The allowlist entry account
+    #[cons(writable)]
+    pub allowlist_entry: &'a T,
+
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            stake_pool: next_account_info(accounts_iter)?,
+            stake_pool_owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            allowlist_entry: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.stake_pool,
+            program_id,
+            AccessError::WrongStakePoolAccountOwner,
+        )?;
+
+        // Check signers
+        check_signer(
+            accounts.stake_pool_owner,
+            AccessError::StakePoolOwnerMustSign,
+        )?;
+        check_signer(accounts.fee_payer, AccessError::OwnerMustSign)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_set_pool_allowlist_entry(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let Params { staker, allowed } = params;
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&SetPoolAllowlistEntry)?;
+
+    let stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+    check_account_key(
+        accounts.stake_pool_owner,
+        &Pubkey::from(stake_pool.header.owner),
+        AccessError::StakeAccountOwnerMismatch,
+    )?;
+
+    let (derived_key, bump_seed) =
+        PoolAllowlistEntry::create_key(accounts.stake_pool.key, &staker, program_id);
+    check_account_key(
+        accounts.allowlist_entry,
+        &derived_key,
+        AccessError::AccountNotDeterministic,
+    )?;
+
+    match (allowed, accounts.allowlist_entry.data_is_empty()) {
+        (true, true) => {
+            let entry = PoolAllowlistEntry::new(*accounts.stake_pool.key, staker);
+            Cpi::create_account(
+                program_id,
+                accounts.system_program,
+                accounts.fee_payer,
+                accounts.allowlist_entry,
+                &[
+                    PoolAllowlistEntry::SEED,
+                    &accounts.stake_pool.key.to_bytes(),
+                    &staker.to_bytes(),
+                    &[bump_seed],
+                ],
+                entry.borsh_len(),
+            )?;
+            entry.save(&mut accounts.allowlist_entry.data.borrow_mut())?;
+        }
+        (false, false) => {
+            check_account_owner(
+                accounts.allowlist_entry,
+                program_id,
+                AccessError::WrongOwner,
+            )?;
+            let mut entry = PoolAllowlistEntry::from_account_info(accounts.allowlist_entry)?;
+            entry.close();
+            entry.save(&mut accounts.allowlist_entry.data.borrow_mut())?;
+
+            let mut entry_lamports = accounts.allowlist_entry.lamports.borrow_mut();
+            let mut owner_lamports = accounts.stake_pool_owner.lamports.borrow_mut();
+
+            **owner_lamports += **entry_lamports;
+            **entry_lamports = 0;
+        }
+        _ => return Err(AccessError::NoOp.into()),
+    }
+
+    Event::Admin {
+        central_state: *accounts.central_state.key,
+        authority: *accounts.stake_pool_owner.key,
+        change: AdminChange::PoolAllowlistEntry {
+            stake_pool: *accounts.stake_pool.key,
+            staker,
+            allowed,
+        },
+    }
+    .emit()?;
+
+    Ok(())
+}
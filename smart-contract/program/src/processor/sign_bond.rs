@@ -13,7 +13,7 @@ use crate::state:: CentralStateV2;
 use crate::error::AccessError;
 use crate::instruction::ProgramInstruction::SignBond;
 use crate::state::{BOND_SIGNER_THRESHOLD, BondAccount, V1_INSTRUCTIONS_ALLOWED};
-use crate::utils::{assert_authorized_seller, check_account_owner, check_signer};
+use crate::utils::{assert_original_central_state, assert_authorized_seller, check_account_owner, check_signer};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 /// The required parameters for the `sign_bond` instruction
@@ -67,6 +67,7 @@ pub fn process_sign_bond(
 
     let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&SignBond)?;
+    assert_original_central_state(accounts.central_state.key, &central_state, program_id)?;
     let mut bond = BondAccount::from_account_info(accounts.bond_account, true)?;
     assert_authorized_seller(accounts.seller, params.seller_index as usize)?;
 
@@ -5,22 +5,25 @@ use solana_program::{
     account_info::{AccountInfo, next_account_info},
     entrypoint::ProgramResult,
     msg,
-    program::invoke,
     program_error::ProgramError,
     pubkey::Pubkey,
 };
-use solana_program::program_pack::Pack;
-use spl_token::instruction::transfer;
-use spl_token::state::Account;
 use crate::state:: CentralStateV2;
 
 use crate::{
     state::Tag,
-    utils::{assert_valid_fee, check_account_key, check_account_owner, check_signer},
+    utils::{
+        assert_valid_fee, check_account_key, check_account_owner, check_pool_central_state,
+        check_pool_controls, check_receipt_accounts, check_signer, check_token_program, retrieve_pool_fee_override,
+        next_optional_account_info, unpack_mint, unpack_token_account,
+    },
 };
+use crate::cpi::Cpi;
 use crate::error::AccessError;
+use crate::events::Event;
 use crate::instruction::ProgramInstruction::Stake;
-use crate::state::{StakeAccount, StakePool};
+use crate::rewards::receipt_reserve_share;
+use crate::state::{StakeAccount, StakePool, StakePoolHeader};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 /// The required parameters for the `stake` instruction
@@ -62,6 +65,34 @@ pub struct Accounts<'a, T> {
     /// The central state ATA
     #[cons(writable)]
     pub central_state_vault: &'a T,
+
+    /// The optional fee override account of the stake pool
+    pub pool_fee_override: Option<&'a T>,
+
+    /// The mint of the ACS token
+    pub mint: &'a T,
+
+    /// The pool controls account, required once the pool has controls
+    pub pool_controls: Option<&'a T>,
+
+    /// The allowlist entry of the stake account owner or of the receipt buyer, required once the allowlist of the pool is enabled
+    pub allowlist_entry: Option<&'a T>,
+
+    /// The optional receipt mint of the stake pool, to stake for the receipt holders
+    #[cons(writable)]
+    pub receipt_mint: Option<&'a T>,
+
+    /// The receipt token account receiving the minted receipts
+    #[cons(writable)]
+    pub receipt_account: Option<&'a T>,
+
+    /// The receipt reserve, receives the premium of the accrued rewards paid on top of the stake
+    #[cons(writable)]
+    pub receipt_reserve: Option<&'a T>,
+
+    /// The accounts of the transfer hook of the mint, after the optional accounts which can be
+    /// skipped with the program id
+    pub transfer_hook_accounts: &'a [T],
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -79,14 +110,18 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             spl_token_program: next_account_info(accounts_iter)?,
             vault: next_account_info(accounts_iter)?,
             central_state_vault: next_account_info(accounts_iter)?,
+            pool_fee_override: next_optional_account_info(accounts_iter, program_id),
+            mint: next_account_info(accounts_iter)?,
+            pool_controls: next_optional_account_info(accounts_iter, program_id),
+            allowlist_entry: next_optional_account_info(accounts_iter, program_id),
+            receipt_mint: next_optional_account_info(accounts_iter, program_id),
+            receipt_account: next_optional_account_info(accounts_iter, program_id),
+            receipt_reserve: next_optional_account_info(accounts_iter, program_id),
+            transfer_hook_accounts: accounts_iter.as_slice(),
         };
 
         // Check keys
-        check_account_key(
-            accounts.spl_token_program,
-            &spl_token::ID,
-            AccessError::WrongSplTokenProgramId,
-        )?;
+        check_token_program(accounts.spl_token_program)?;
 
         // Check ownership
         check_account_owner(
@@ -96,7 +131,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         )?;
         check_account_owner(
             accounts.central_state_vault,
-            &spl_token::ID,
+            accounts.spl_token_program.key,
             AccessError::WrongOwner,
         )?;
         check_account_owner(
@@ -111,12 +146,12 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         )?;
         check_account_owner(
             accounts.source_token,
-            &spl_token::ID,
+            accounts.spl_token_program.key,
             AccessError::WrongTokenAccountOwner,
         )?;
         check_account_owner(
             accounts.vault,
-            &spl_token::ID,
+            accounts.spl_token_program.key,
             AccessError::WrongTokenAccountOwner,
         )?;
 
@@ -139,8 +174,16 @@ pub fn process_stake(
     let mut stake_account = StakeAccount::from_account_info(accounts.stake_account)?;
     let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&Stake)?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+    check_account_key(accounts.mint, &central_state.token_mint, AccessError::WrongMint)?;
 
-    let source_token_acc = Account::unpack(&accounts.source_token.data.borrow())?;
+    let source_token_acc = unpack_token_account(accounts.source_token)?;
     if source_token_acc.mint != central_state.token_mint {
         msg!("Invalid ACCESS mint");
         #[cfg(not(feature = "no-mint-check"))]
@@ -155,6 +198,34 @@ pub fn process_stake(
         &stake_account.stake_pool,
         AccessError::StakePoolMismatch,
     )?;
+    let receipts = match (
+        accounts.receipt_mint,
+        accounts.receipt_account,
+        accounts.receipt_reserve,
+    ) {
+        (Some(receipt_mint), Some(receipt_account), Some(receipt_reserve)) => {
+            check_receipt_accounts(
+                accounts.stake_pool.key,
+                &central_state.token_mint,
+                accounts.spl_token_program.key,
+                receipt_mint,
+                receipt_reserve,
+                accounts.stake_account,
+                program_id,
+            )?;
+            Some((receipt_mint, receipt_account, receipt_reserve))
+        }
+        (None, _, _) => {
+            // The stake of the receipt holders must always be backed by receipts
+            let (receipt_mint_key, _) =
+                StakePoolHeader::find_receipt_mint(accounts.stake_pool.key, program_id);
+            if stake_account.owner == receipt_mint_key {
+                return Err(AccessError::WrongReceiptMint.into());
+            }
+            None
+        }
+        _ => return Err(ProgramError::NotEnoughAccountKeys),
+    };
     check_account_key(
         accounts.vault,
         &Pubkey::from(stake_pool.header.vault),
@@ -167,11 +238,32 @@ pub fn process_stake(
     }
 
     assert_valid_fee(accounts.central_state_vault, accounts.central_state.key)?;
+    let pool_fee_override = retrieve_pool_fee_override(
+        accounts.pool_fee_override,
+        accounts.stake_pool.key,
+        program_id,
+    )?;
 
     if amount == 0 {
         return Err(AccessError::CannotStakeZero.into());
     }
 
+    // The stake of the receipt holders is owned by the receipt mint, the allowlist applies to the
+    // staker buying the receipts
+    let staker = match receipts {
+        Some(_) => accounts.token_owner.key,
+        None => &stake_account.owner,
+    };
+    check_pool_controls(
+        &stake_pool.header,
+        accounts.stake_pool.key,
+        accounts.pool_controls,
+        accounts.allowlist_entry,
+        staker,
+        amount,
+        program_id,
+    )?;
+
     if stake_account.stake_amount > 0
         && stake_account.last_claimed_offset < stake_pool.header.current_day_idx as u64
     {
@@ -189,44 +281,32 @@ pub fn process_stake(
 
     if stake_account.stake_amount == 0 {
         stake_account.last_claimed_offset = central_state.get_current_offset()?;
+        stake_account.last_claimed_index = stake_pool.stakers_reward_index();
     }
 
     // Transfer tokens
-    let transfer_instruction = transfer(
-        &spl_token::ID,
-        accounts.source_token.key,
-        accounts.vault.key,
-        accounts.token_owner.key,
-        &[],
+    Cpi::transfer(
+        accounts.spl_token_program,
+        accounts.source_token,
+        accounts.vault,
+        accounts.token_owner,
+        accounts.mint,
+        accounts.transfer_hook_accounts,
         amount,
-    )?;
-    invoke(
-        &transfer_instruction,
-        &[
-            accounts.spl_token_program.clone(),
-            accounts.source_token.clone(),
-            accounts.vault.clone(),
-            accounts.token_owner.clone(),
-        ],
+        &[],
     )?;
 
     // Transfer fees
-    let transfer_fees = transfer(
-        &spl_token::ID,
-        accounts.source_token.key,
-        accounts.central_state_vault.key,
-        accounts.token_owner.key,
+    let fee = central_state.calculate_fee(amount, pool_fee_override.as_ref())?;
+    Cpi::transfer(
+        accounts.spl_token_program,
+        accounts.source_token,
+        accounts.central_state_vault,
+        accounts.token_owner,
+        accounts.mint,
+        accounts.transfer_hook_accounts,
+        fee,
         &[],
-        central_state.calculate_fee(amount)?,
-    )?;
-    invoke(
-        &transfer_fees,
-        &[
-            accounts.spl_token_program.clone(),
-            accounts.source_token.clone(),
-            accounts.central_state_vault.clone(),
-            accounts.token_owner.clone(),
-        ],
     )?;
 
     if stake_account
@@ -245,6 +325,37 @@ pub fn process_stake(
         return Err(ProgramError::InvalidArgument);
     }
 
+    // Mint the receipts, the staker pays the share of the reserve its receipts will be entitled to
+    let mut receipt_premium = 0;
+    if let Some((receipt_mint, receipt_account, receipt_reserve)) = receipts {
+        receipt_premium = receipt_reserve_share(
+            amount,
+            unpack_token_account(receipt_reserve)?.amount,
+            unpack_mint(receipt_mint)?.supply,
+            true,
+        )?;
+        if receipt_premium > 0 {
+            Cpi::transfer(
+                accounts.spl_token_program,
+                accounts.source_token,
+                receipt_reserve,
+                accounts.token_owner,
+                accounts.mint,
+                accounts.transfer_hook_accounts,
+                receipt_premium,
+                &[],
+            )?;
+        }
+        Cpi::mint_to(
+            accounts.spl_token_program,
+            receipt_mint,
+            receipt_account,
+            accounts.central_state,
+            amount,
+            &[&central_state.signer_seeds(accounts.central_state.key, program_id)],
+        )?;
+    }
+
     // Update stake account
     stake_account.deposit(amount)?;
     stake_pool.header.deposit(amount)?;
@@ -259,5 +370,25 @@ pub fn process_stake(
     stake_account.save(&mut accounts.stake_account.data.borrow_mut())?;
     central_state.save(&mut accounts.central_state.data.borrow_mut())?;
 
+    Event::Stake {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.stake_pool.key,
+        stake_account: *accounts.stake_account.key,
+        owner: stake_account.owner,
+        amount,
+        fee,
+    }
+    .emit()?;
+    if let Some((_, receipt_account, _)) = receipts {
+        Event::ReceiptMinted {
+            central_state: *accounts.central_state.key,
+            stake_pool: *accounts.stake_pool.key,
+            receipt_account: *receipt_account.key,
+            amount,
+            reserve_amount: receipt_premium,
+        }
+        .emit()?;
+    }
+
     Ok(())
 }
@@ -0,0 +1,215 @@
+//! Transfer a bond V2 to a new owner
+//! The position is moved to the bond V2 account of the new owner with the same unlock timestamp
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::cpi::Cpi;
+use crate::error::AccessError;
+use crate::events::Event;
+use crate::instruction::ProgramInstruction::TransferBondV2;
+use crate::state::{BondV2Account, CentralStateV2, StakePool, Tag};
+use crate::utils::{
+    assert_bond_v2_rewards_claimed, check_account_key, check_account_owner,
+    check_pool_central_state, check_signer,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `transfer_bond_v2` instruction
+pub struct Params {
+    /// The new owner of the bond
+    pub new_owner: Pubkey,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `transfer_bond_v2` instruction
+pub struct Accounts<'a, T> {
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The bond V2 account, closed by the transfer
+    #[cons(writable)]
+    pub bond_v2_account: &'a T,
+
+    /// The bond V2 account of the new owner
+    #[cons(writable)]
+    pub new_bond_v2_account: &'a T,
+
+    /// The owner of the bond, receives the rent of the closed bond account
+    #[cons(writable, signer)]
+    pub owner: &'a T,
+
+    /// The pool account
+    pub pool: &'a T,
+
+    /// The fee payer account, pays the rent of the new bond account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            central_state: next_account_info(accounts_iter)?,
+            bond_v2_account: next_account_info(accounts_iter)?,
+            new_bond_v2_account: next_account_info(accounts_iter)?,
+            owner: next_account_info(accounts_iter)?,
+            pool: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+        check_account_owner(
+            accounts.bond_v2_account,
+            program_id,
+            AccessError::WrongBondAccountOwner,
+        )?;
+        check_account_owner(
+            accounts.pool,
+            program_id,
+            AccessError::WrongStakePoolAccountOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.owner, AccessError::OwnerMustSign)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_transfer_bond_v2(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let Params { new_owner } = params;
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&TransferBondV2)?;
+    let stake_pool = StakePool::get_checked(accounts.pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+    let bond = BondV2Account::from_account_info(accounts.bond_v2_account)?;
+
+    check_account_key(accounts.owner, &bond.owner, AccessError::WrongOwner)?;
+    check_account_key(accounts.pool, &bond.pool, AccessError::WrongStakePool)?;
+    assert_bond_v2_rewards_claimed(&bond, &stake_pool.header, &central_state)?;
+
+    move_bond_v2(
+        program_id,
+        accounts.central_state.key,
+        bond,
+        accounts.bond_v2_account,
+        accounts.new_bond_v2_account,
+        new_owner,
+        accounts.fee_payer,
+        accounts.owner,
+        accounts.system_program,
+    )
+}
+
+/// Moves a bond from `source` to the bond V2 account of `new_owner` with the same pool and unlock
+/// timestamp, the bond account of `new_owner` is created if needed and can only be reused when
+/// empty. `source` is closed and its rent goes to `rent_destination`
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn move_bond_v2<'a>(
+    program_id: &Pubkey,
+    central_state: &Pubkey,
+    mut bond: BondV2Account,
+    source: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    new_owner: Pubkey,
+    fee_payer: &AccountInfo<'a>,
+    rent_destination: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    let (derived_key, bump_seed) =
+        BondV2Account::create_key(&new_owner, &bond.pool, bond.unlock_timestamp, program_id);
+    check_account_key(
+        destination,
+        &derived_key,
+        AccessError::AccountNotDeterministic,
+    )?;
+    if destination.key == source.key {
+        msg!("The bond already belongs to the new owner");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if destination.owner == &system_program::ID {
+        let seeds: &[&[u8]] = &[
+            BondV2Account::SEED,
+            &new_owner.to_bytes(),
+            &bond.pool.to_bytes(),
+            &bond.unlock_timestamp.unwrap_or(0).to_le_bytes(),
+            &[bump_seed],
+        ];
+        Cpi::create_account(
+            program_id,
+            system_program,
+            fee_payer,
+            destination,
+            seeds,
+            bond.borsh_len(),
+        )?;
+    } else {
+        check_account_owner(destination, program_id, AccessError::WrongBondAccountOwner)?;
+        if BondV2Account::from_account_info(destination)?.amount != 0 {
+            return Err(AccessError::BondV2DestinationNotEmpty.into());
+        }
+    }
+
+    let owner = bond.owner;
+    bond.owner = new_owner;
+    bond.save(&mut destination.data.borrow_mut())?;
+
+    // Close the source account
+    bond.owner = owner;
+    bond.close();
+    bond.save(&mut source.data.borrow_mut())?;
+
+    let mut source_lamports = source.lamports.borrow_mut();
+    let mut destination_lamports = rent_destination.lamports.borrow_mut();
+
+    **destination_lamports += **source_lamports;
+    **source_lamports = 0;
+
+    Event::BondTransferred {
+        central_state: *central_state,
+        stake_pool: bond.pool,
+        bond: *source.key,
+        new_bond: *destination.key,
+        owner,
+        new_owner,
+        amount: bond.amount,
+    }
+    .emit()
+}
@@ -6,20 +6,21 @@ use solana_program::{
     clock::Clock,
     entrypoint::ProgramResult,
     msg,
-    program::invoke_signed,
     program_error::ProgramError,
     pubkey::Pubkey,
     sysvar::Sysvar,
 };
 
 use crate::state::{BondAccount, StakePool, StakePoolHeader};
-use crate::{error::AccessError, state::Tag};
+use crate::events::Event;
+use crate::{cpi::Cpi, error::AccessError, state::Tag};
 use bonfida_utils::{BorshSize, InstructionsAccount};
-use solana_program::program_pack::Pack;
-use spl_token::state::Account;
 use crate::instruction::ProgramInstruction::UnlockBondTokens;
 
-use crate::utils::{assert_bond_derivation, check_account_key, check_account_owner, check_signer};
+use crate::utils::{
+    assert_bond_derivation, check_account_key, check_account_owner, check_signer,
+    check_token_program, unpack_token_account, check_pool_central_state,
+};
 use crate::state:: CentralStateV2;
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
@@ -78,11 +79,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         };
 
         // Check keys
-        check_account_key(
-            accounts.spl_token_program,
-            &spl_token::ID,
-            AccessError::WrongSplTokenProgramId,
-        )?;
+        check_token_program(accounts.spl_token_program)?;
 
         // Check ownership
         check_account_owner(accounts.bond_account, program_id, AccessError::WrongOwner)?;
@@ -106,9 +103,16 @@ pub fn process_unlock_bond_tokens(
     central_state.assert_instruction_allowed(&UnlockBondTokens)?;
     let mut bond = BondAccount::from_account_info(accounts.bond_account, false)?;
     let mut stake_pool = StakePool::get_checked(accounts.stake_pool, vec![Tag::StakePool])?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
     let current_time = Clock::get()?.unix_timestamp;
 
-    let destination_token_acc = Account::unpack(&accounts.access_token_destination.data.borrow())?;
+    let destination_token_acc = unpack_token_account(accounts.access_token_destination)?;
     if destination_token_acc.mint != central_state.token_mint {
         msg!("Invalid ACCESS mint");
         #[cfg(not(feature = "no-mint-check"))]
@@ -173,33 +177,23 @@ pub fn process_unlock_bond_tokens(
     // Update the stake pool
     stake_pool.header.withdraw(unlock_amount)?;
 
-    let signer_seeds: &[&[u8]] = &[
-        StakePoolHeader::SEED,
-        &stake_pool.header.owner.clone(),
-        &[stake_pool.header.nonce],
-    ];
+    let pool_owner = Pubkey::new_from_array(stake_pool.header.owner);
+    let pool_nonce = [stake_pool.header.nonce];
+    let scope = central_state.scope(accounts.central_state.key, program_id);
+    let signer_seeds = StakePoolHeader::seeds(&pool_owner, scope.as_ref(), &pool_nonce);
 
     drop(stake_pool);
 
     // Transfer tokens
-    let transfer_ix = spl_token::instruction::transfer(
-        &spl_token::ID,
-        accounts.pool_vault.key,
-        accounts.access_token_destination.key,
-        accounts.stake_pool.key,
+    Cpi::transfer(
+        accounts.spl_token_program,
+        accounts.pool_vault,
+        accounts.access_token_destination,
+        accounts.stake_pool,
+        accounts.mint,
         &[],
         unlock_amount,
-    )?;
-
-    invoke_signed(
-        &transfer_ix,
-        &[
-            accounts.spl_token_program.clone(),
-            accounts.pool_vault.clone(),
-            accounts.access_token_destination.clone(),
-            accounts.stake_pool.clone(),
-        ],
-        &[signer_seeds],
+        &[&signer_seeds],
     )?;
 
     // Update bond state
@@ -231,5 +225,14 @@ pub fn process_unlock_bond_tokens(
         .ok_or(AccessError::Overflow)?;
     central_state.save(&mut accounts.central_state.data.borrow_mut())?;
 
+    Event::BondUnlocked {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.stake_pool.key,
+        bond: *accounts.bond_account.key,
+        owner: bond.owner,
+        amount: unlock_amount,
+    }
+    .emit()?;
+
     Ok(())
 }
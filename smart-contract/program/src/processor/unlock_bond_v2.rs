@@ -1,13 +1,14 @@
 //! Unlock bond v2
 use crate::{
     state::{Tag},
-    utils::{check_account_key, check_account_owner, check_signer},
+    utils::{
+        check_account_key, check_account_owner, check_pool_central_state, check_signer,
+        check_token_program, next_optional_account_info, unpack_token_account,
+    },
 };
 use bonfida_utils::{BorshSize, InstructionsAccount};
 use borsh::{BorshDeserialize, BorshSerialize};
 
-use solana_program::program::invoke_signed;
-use solana_program::program_pack::Pack;
 use solana_program::sysvar::Sysvar;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
@@ -17,12 +18,11 @@ use solana_program::{
     program_error::ProgramError,
     pubkey::Pubkey,
 };
-use spl_token::instruction::transfer;
-use spl_token::state::Account;
-
+use crate::cpi::Cpi;
 use crate::error::AccessError;
+use crate::events::Event;
 use crate::instruction::ProgramInstruction::UnlockBondV2;
-use crate::state::{BondV2Account, StakePool, StakePoolHeader};
+use crate::state::{BondV2Account, PoolBoost, StakePool, StakePoolHeader};
 use crate::state:: CentralStateV2;
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
@@ -58,6 +58,17 @@ pub struct Accounts<'a, T> {
 
     /// The SPL token program account
     pub spl_token_program: &'a T,
+
+    /// The mint of the ACS token
+    pub mint: &'a T,
+
+    /// The pool boost account, required for the boosted bonds V2
+    #[cons(writable)]
+    pub pool_boost: Option<&'a T>,
+
+    /// The accounts of the transfer hook of the mint, after the optional accounts which can be
+    /// skipped with the program id
+    pub transfer_hook_accounts: &'a [T],
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -74,14 +85,13 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             pool: next_account_info(accounts_iter)?,
             pool_vault: next_account_info(accounts_iter)?,
             spl_token_program: next_account_info(accounts_iter)?,
+            mint: next_account_info(accounts_iter)?,
+            pool_boost: next_optional_account_info(accounts_iter, program_id),
+            transfer_hook_accounts: accounts_iter.as_slice(),
         };
 
         // Check keys
-        check_account_key(
-            accounts.spl_token_program,
-            &spl_token::ID,
-            AccessError::WrongSplTokenProgramId,
-        )?;
+        check_token_program(accounts.spl_token_program)?;
 
         // Check ownership
         check_account_owner(
@@ -101,12 +111,12 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         )?;
         check_account_owner(
             accounts.destination_account,
-            &spl_token::ID,
+            accounts.spl_token_program.key,
             AccessError::WrongTokenAccountOwner,
         )?;
         check_account_owner(
             accounts.pool_vault,
-            &spl_token::ID,
+            accounts.spl_token_program.key,
             AccessError::WrongTokenAccountOwner,
         )?;
 
@@ -128,8 +138,16 @@ pub fn process_unlock_bond_v2(
     let mut bond_v2_account = BondV2Account::from_account_info(accounts.bond_v2_account)?;
     let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&UnlockBondV2)?;
+    check_pool_central_state(
+        accounts.pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+    check_account_key(accounts.mint, &central_state.token_mint, AccessError::WrongMint)?;
 
-    let destination_token_acc = Account::unpack(&accounts.destination_account.data.borrow())?;
+    let destination_token_acc = unpack_token_account(accounts.destination_account)?;
     if destination_token_acc.mint != central_state.token_mint {
         msg!("Invalid ACCESS mint");
         #[cfg(not(feature = "no-mint-check"))]
@@ -175,6 +193,19 @@ pub fn process_unlock_bond_v2(
         return Err(ProgramError::InvalidArgument);
     }
 
+    // The rewards of the boost are claimed, remove it from the weights of the pool and the system
+    let removed_boost = bond_v2_account.boost;
+    if removed_boost != 0 {
+        let pool_boost_info = accounts
+            .pool_boost
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let mut pool_boost =
+            PoolBoost::get_checked(pool_boost_info, accounts.pool.key, program_id)?;
+        pool_boost.update(removed_boost, 0)?;
+        pool_boost.save(&mut pool_boost_info.data.borrow_mut())?;
+        bond_v2_account.boost = 0;
+    }
+
     let amount = bond_v2_account.amount;
     msg!("Unlocking {} tokens", amount);
     if amount == 0 {
@@ -187,31 +218,22 @@ pub fn process_unlock_bond_v2(
     stake_pool.header.withdraw(amount)?;
 
     // Transfer tokens
-    let signer_seeds: &[&[u8]] = &[
-        StakePoolHeader::SEED,
-        &stake_pool.header.owner.clone(),
-        &[stake_pool.header.nonce],
-    ];
-    let transfer_instruction = transfer(
-        &spl_token::ID,
-        accounts.pool_vault.key,
-        accounts.destination_account.key,
-        accounts.pool.key,
-        &[],
-        amount,
-    )?;
+    let pool_owner = Pubkey::new_from_array(stake_pool.header.owner);
+    let pool_nonce = [stake_pool.header.nonce];
+    let scope = central_state.scope(accounts.central_state.key, program_id);
+    let signer_seeds = StakePoolHeader::seeds(&pool_owner, scope.as_ref(), &pool_nonce);
 
     drop(stake_pool);
 
-    invoke_signed(
-        &transfer_instruction,
-        &[
-            accounts.spl_token_program.clone(),
-            accounts.pool_vault.clone(),
-            accounts.destination_account.clone(),
-            accounts.pool.clone(),
-        ],
-        &[signer_seeds],
+    Cpi::transfer(
+        accounts.spl_token_program,
+        accounts.pool_vault,
+        accounts.destination_account,
+        accounts.pool,
+        accounts.mint,
+        accounts.transfer_hook_accounts,
+        amount,
+        &[&signer_seeds],
     )?;
 
     // Save states
@@ -222,7 +244,17 @@ pub fn process_unlock_bond_v2(
         .total_staked
         .checked_sub(amount)
         .ok_or(AccessError::Overflow)?;
+    central_state.update_boost(removed_boost, 0)?;
     central_state.save(&mut accounts.central_state.data.borrow_mut())?;
 
+    Event::BondUnlocked {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.pool.key,
+        bond: *accounts.bond_v2_account.key,
+        owner: bond_v2_account.owner,
+        amount,
+    }
+    .emit()?;
+
     Ok(())
 }
@@ -1,13 +1,15 @@
 //! Unstake
 use crate::{
     state::{Tag},
-    utils::{check_account_key, check_account_owner, check_signer},
+    utils::{
+        check_account_key, check_account_owner, check_pool_central_state, check_receipt_accounts,
+        check_signer, check_token_program, next_optional_account_info, unpack_mint,
+        unpack_token_account,
+    },
 };
 use bonfida_utils::{BorshSize, InstructionsAccount};
 use borsh::{BorshDeserialize, BorshSerialize};
 
-use solana_program::program::invoke_signed;
-use solana_program::program_pack::Pack;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
@@ -15,11 +17,11 @@ use solana_program::{
     pubkey::Pubkey,
     msg,
 };
-use spl_token::instruction::transfer;
-use spl_token::state::Account;
-
+use crate::cpi::Cpi;
 use crate::error::AccessError;
+use crate::events::Event;
 use crate::instruction::ProgramInstruction::Unstake;
+use crate::rewards::receipt_reserve_share;
 use crate::state::{StakeAccount, StakePool, StakePoolHeader};
 use crate::state:: CentralStateV2;
 
@@ -59,6 +61,25 @@ pub struct Accounts<'a, T> {
     /// The stake pool vault
     #[cons(writable)]
     pub vault: &'a T,
+
+    /// The mint of the ACS token
+    pub mint: &'a T,
+
+    /// The optional receipt mint of the stake pool, to redeem receipts
+    #[cons(writable)]
+    pub receipt_mint: Option<&'a T>,
+
+    /// The receipt token account of the owner, the receipts are burnt from it
+    #[cons(writable)]
+    pub receipt_account: Option<&'a T>,
+
+    /// The receipt reserve, pays out the share of the accrued rewards
+    #[cons(writable)]
+    pub receipt_reserve: Option<&'a T>,
+
+    /// The accounts of the transfer hook of the mint, after the optional accounts which can be
+    /// skipped with the program id
+    pub transfer_hook_accounts: &'a [T],
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -75,14 +96,15 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             destination_token: next_account_info(accounts_iter)?,
             spl_token_program: next_account_info(accounts_iter)?,
             vault: next_account_info(accounts_iter)?,
+            mint: next_account_info(accounts_iter)?,
+            receipt_mint: next_optional_account_info(accounts_iter, program_id),
+            receipt_account: next_optional_account_info(accounts_iter, program_id),
+            receipt_reserve: next_optional_account_info(accounts_iter, program_id),
+            transfer_hook_accounts: accounts_iter.as_slice(),
         };
 
         // Check keys
-        check_account_key(
-            accounts.spl_token_program,
-            &spl_token::ID,
-            AccessError::WrongSplTokenProgramId,
-        )?;
+        check_token_program(accounts.spl_token_program)?;
 
         // Check ownership
         check_account_owner(
@@ -102,12 +124,12 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         )?;
         check_account_owner(
             accounts.destination_token,
-            &spl_token::ID,
+            accounts.spl_token_program.key,
             AccessError::WrongTokenAccountOwner,
         )?;
         check_account_owner(
             accounts.vault,
-            &spl_token::ID,
+            accounts.spl_token_program.key,
             AccessError::WrongTokenAccountOwner,
         )?;
 
@@ -130,8 +152,16 @@ pub fn process_unstake(
     let mut stake_account = StakeAccount::from_account_info(accounts.stake_account)?;
     let mut central_state = CentralStateV2::from_account_info(accounts.central_state)?;
     central_state.assert_instruction_allowed(&Unstake)?;
+    check_pool_central_state(
+        accounts.stake_pool.key,
+        &stake_pool.header,
+        accounts.central_state.key,
+        &central_state,
+        program_id,
+    )?;
+    check_account_key(accounts.mint, &central_state.token_mint, AccessError::WrongMint)?;
 
-    let destination_token_acc = Account::unpack(&accounts.destination_token.data.borrow())?;
+    let destination_token_acc = unpack_token_account(accounts.destination_token)?;
     if destination_token_acc.mint != central_state.token_mint {
         msg!("Invalid ACCESS mint");
         #[cfg(not(feature = "no-mint-check"))]
@@ -144,11 +174,34 @@ pub fn process_unstake(
     if (stake_pool.header.current_day_idx as u64) < central_state.get_current_offset()? {
         return Err(AccessError::PoolMustBeCranked.into());
     }
-    check_account_key(
-        accounts.owner,
-        &stake_account.owner,
-        AccessError::StakeAccountOwnerMismatch,
-    )?;
+    // The receipt holders unstake from the stake account owned by the receipt mint
+    let receipts = match (
+        accounts.receipt_mint,
+        accounts.receipt_account,
+        accounts.receipt_reserve,
+    ) {
+        (Some(receipt_mint), Some(receipt_account), Some(receipt_reserve)) => {
+            let receipt_mint_nonce = check_receipt_accounts(
+                accounts.stake_pool.key,
+                &central_state.token_mint,
+                accounts.spl_token_program.key,
+                receipt_mint,
+                receipt_reserve,
+                accounts.stake_account,
+                program_id,
+            )?;
+            Some((receipt_mint, receipt_account, receipt_reserve, receipt_mint_nonce))
+        }
+        (None, _, _) => {
+            check_account_key(
+                accounts.owner,
+                &stake_account.owner,
+                AccessError::StakeAccountOwnerMismatch,
+            )?;
+            None
+        }
+        _ => return Err(ProgramError::NotEnoughAccountKeys),
+    };
     check_account_key(
         accounts.stake_pool,
         &stake_account.stake_pool,
@@ -180,33 +233,59 @@ pub fn process_unstake(
     stake_pool.header.withdraw(amount)?;
 
     // Transfer tokens
-    let signer_seeds: &[&[u8]] = &[
-        StakePoolHeader::SEED,
-        &stake_pool.header.owner.clone(),
-        &[stake_pool.header.nonce],
-    ];
-    let transfer_instruction = transfer(
-        &spl_token::ID,
-        accounts.vault.key,
-        accounts.destination_token.key,
-        accounts.stake_pool.key,
-        &[],
-        amount,
-    )?;
+    let pool_owner = Pubkey::new_from_array(stake_pool.header.owner);
+    let pool_nonce = [stake_pool.header.nonce];
+    let scope = central_state.scope(accounts.central_state.key, program_id);
+    let signer_seeds = StakePoolHeader::seeds(&pool_owner, scope.as_ref(), &pool_nonce);
 
     drop(stake_pool);
 
-    invoke_signed(
-        &transfer_instruction,
-        &[
-            accounts.spl_token_program.clone(),
-            accounts.vault.clone(),
-            accounts.destination_token.clone(),
-            accounts.stake_pool.clone(),
-        ],
-        &[signer_seeds],
+    Cpi::transfer(
+        accounts.spl_token_program,
+        accounts.vault,
+        accounts.destination_token,
+        accounts.stake_pool,
+        accounts.mint,
+        accounts.transfer_hook_accounts,
+        amount,
+        &[&signer_seeds],
     )?;
 
+    // Burn the receipts and pay out their share of the reserve
+    let mut receipt_share = 0;
+    if let Some((receipt_mint, receipt_account, receipt_reserve, receipt_mint_nonce)) = receipts {
+        receipt_share = receipt_reserve_share(
+            amount,
+            unpack_token_account(receipt_reserve)?.amount,
+            unpack_mint(receipt_mint)?.supply,
+            false,
+        )?;
+        Cpi::burn(
+            accounts.spl_token_program,
+            receipt_account,
+            receipt_mint,
+            accounts.owner,
+            amount,
+            &[],
+        )?;
+        if receipt_share > 0 {
+            Cpi::transfer(
+                accounts.spl_token_program,
+                receipt_reserve,
+                accounts.destination_token,
+                receipt_mint,
+                accounts.mint,
+                accounts.transfer_hook_accounts,
+                receipt_share,
+                &[&[
+                    StakePoolHeader::RECEIPT_MINT_SEED,
+                    &accounts.stake_pool.key.to_bytes(),
+                    &[receipt_mint_nonce],
+                ]],
+            )?;
+        }
+    }
+
     // Save states
     stake_account.save(&mut accounts.stake_account.data.borrow_mut())?;
 
@@ -217,5 +296,24 @@ pub fn process_unstake(
         .ok_or(AccessError::Overflow)?;
     central_state.save(&mut accounts.central_state.data.borrow_mut())?;
 
+    Event::Unstake {
+        central_state: *accounts.central_state.key,
+        stake_pool: *accounts.stake_pool.key,
+        stake_account: *accounts.stake_account.key,
+        owner: stake_account.owner,
+        amount,
+    }
+    .emit()?;
+    if let Some((_, receipt_account, _, _)) = receipts {
+        Event::ReceiptRedeemed {
+            central_state: *accounts.central_state.key,
+            stake_pool: *accounts.stake_pool.key,
+            receipt_account: *receipt_account.key,
+            amount,
+            reserve_amount: receipt_share,
+        }
+        .emit()?;
+    }
+
     Ok(())
 }
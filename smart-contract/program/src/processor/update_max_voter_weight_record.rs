@@ -0,0 +1,148 @@
+//! Update the max voter weight record of the central state
+//! This instruction is permissionless, the max voter weight is the total staked in the central state
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use crate::cpi::Cpi;
+use crate::error::AccessError;
+use crate::events::Event;
+use crate::governance::MaxVoterWeightRecord;
+use crate::instruction::ProgramInstruction::UpdateMaxVoterWeightRecord;
+use crate::state::{CentralStateV2, GovernanceConfig};
+use crate::utils::{check_account_key, check_account_owner, check_signer};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `update_max_voter_weight_record` instruction
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `update_max_voter_weight_record` instruction
+pub struct Accounts<'a, T> {
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The governance config account
+    pub governance_config: &'a T,
+
+    /// The max voter weight record account
+    #[cons(writable)]
+    pub max_voter_weight_record: &'a T,
+
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            central_state: next_account_info(accounts_iter)?,
+            governance_config: next_account_info(accounts_iter)?,
+            max_voter_weight_record: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer, AccessError::OwnerMustSign)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_update_max_voter_weight_record(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&UpdateMaxVoterWeightRecord)?;
+    let governance_config = GovernanceConfig::get_checked(
+        accounts.governance_config,
+        accounts.central_state.key,
+        program_id,
+    )?;
+
+    let (derived_key, bump_seed) =
+        MaxVoterWeightRecord::create_key(accounts.central_state.key, program_id);
+    check_account_key(
+        accounts.max_voter_weight_record,
+        &derived_key,
+        AccessError::AccountNotDeterministic,
+    )?;
+    if accounts.max_voter_weight_record.data_is_empty() {
+        Cpi::create_account(
+            program_id,
+            accounts.system_program,
+            accounts.fee_payer,
+            accounts.max_voter_weight_record,
+            &[
+                MaxVoterWeightRecord::SEED,
+                &accounts.central_state.key.to_bytes(),
+                &[bump_seed],
+            ],
+            MaxVoterWeightRecord::LEN,
+        )?;
+    } else {
+        check_account_owner(
+            accounts.max_voter_weight_record,
+            program_id,
+            AccessError::WrongOwner,
+        )?;
+        // Makes sure the existing account is a max voter weight record
+        MaxVoterWeightRecord::from_account_info(accounts.max_voter_weight_record)?;
+    }
+
+    // The boosts of the bonds V2 only add to the voter weights with the lock weighting
+    let max_voter_weight = if governance_config.lock_weighting {
+        central_state
+            .total_staked
+            .checked_add(central_state.total_boost)
+            .ok_or(AccessError::Overflow)?
+    } else {
+        central_state.total_staked
+    };
+    let mut record = MaxVoterWeightRecord::new(
+        governance_config.realm,
+        governance_config.governing_token_mint,
+    );
+    record.max_voter_weight = max_voter_weight;
+    record.max_voter_weight_expiry = Some(Clock::get()?.slot);
+    record.save(&mut accounts.max_voter_weight_record.data.borrow_mut())?;
+
+    Event::MaxVoterWeightUpdated {
+        central_state: *accounts.central_state.key,
+        max_voter_weight,
+    }
+    .emit()?;
+
+    Ok(())
+}
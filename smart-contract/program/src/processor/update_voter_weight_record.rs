@@ -0,0 +1,288 @@
+//! Update the voter weight record of a voter
+//! Voters call this instruction in the transaction of their governance action, the voter weight is
+//! the ACS staked in the given stake accounts and locked in the given bonds V2 of the voter, and
+//! the receipts held by the voter in the given receipt token accounts.
+//! With the lock weighting of the governance config the bonds V2 add their boost, capped by the
+//! boost of their remaining lock time.
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use crate::cpi::Cpi;
+use crate::error::AccessError;
+use crate::events::Event;
+use crate::governance::{VoterWeightAction, VoterWeightRecord};
+use crate::instruction::ProgramInstruction::UpdateVoterWeightRecord;
+use crate::rewards::bond_boost;
+use crate::state::{
+    BondV2Account, BoostConfig, CentralStateV2, GovernanceConfig, StakeAccount, StakePool,
+    StakePoolHeader, Tag,
+};
+use crate::utils::{
+    check_account_key, check_account_owner, check_pool_central_state, check_signer,
+    is_token_program, unpack_token_account,
+};
+use crate::versioning::read_header;
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+/// The required parameters for the `update_voter_weight_record` instruction
+pub struct Params {
+    /// The governance action the voter weight is used for
+    pub weight_action: Option<VoterWeightAction>,
+    /// The target of the governance action, e.g. the proposal
+    pub weight_action_target: Option<Pubkey>,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `update_voter_weight_record` instruction
+pub struct Accounts<'a, T> {
+    /// The central state account
+    pub central_state: &'a T,
+
+    /// The governance config account
+    pub governance_config: &'a T,
+
+    /// The boost config account, read with the lock weighting only
+    pub boost_config: &'a T,
+
+    /// The voter weight record account
+    #[cons(writable)]
+    pub voter_weight_record: &'a T,
+
+    /// The voter
+    #[cons(signer)]
+    pub voter: &'a T,
+
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The stake pools of the voter, each followed by the stake account, the bonds V2 and the receipt token accounts of the voter in the pool
+    pub voter_accounts: &'a [T],
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            central_state: next_account_info(accounts_iter)?,
+            governance_config: next_account_info(accounts_iter)?,
+            boost_config: next_account_info(accounts_iter)?,
+            voter_weight_record: next_account_info(accounts_iter)?,
+            voter: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            voter_accounts: accounts_iter.as_slice(),
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            AccessError::WrongSystemProgram,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.central_state, program_id, AccessError::WrongOwner)?;
+        for account in accounts
+            .voter_accounts
+            .iter()
+            .filter(|a| !is_token_program(a.owner))
+        {
+            check_account_owner(account, program_id, AccessError::WrongOwner)?;
+        }
+
+        // Check signers
+        check_signer(accounts.voter, AccessError::OwnerMustSign)?;
+        check_signer(accounts.fee_payer, AccessError::OwnerMustSign)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_update_voter_weight_record(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let Params {
+        weight_action,
+        weight_action_target,
+    } = params;
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let central_state = CentralStateV2::from_account_info(accounts.central_state)?;
+    central_state.assert_instruction_allowed(&UpdateVoterWeightRecord)?;
+    let governance_config = GovernanceConfig::get_checked(
+        accounts.governance_config,
+        accounts.central_state.key,
+        program_id,
+    )?;
+    let boost_config = if governance_config.lock_weighting {
+        Some(BoostConfig::get_checked(
+            accounts.boost_config,
+            accounts.central_state.key,
+            program_id,
+        )?)
+    } else {
+        None
+    };
+    let now = Clock::get()?;
+
+    // The stake accounts, the bonds V2 and the receipts must follow the pool they belong to
+    let mut current_pool: Option<&Pubkey> = None;
+    let mut voter_weight = 0u64;
+    for (i, account) in accounts.voter_accounts.iter().enumerate() {
+        // An account must not be counted twice
+        if accounts.voter_accounts[..i]
+            .iter()
+            .any(|a| a.key == account.key)
+        {
+            msg!("Duplicate voter account at index {}", i);
+            return Err(ProgramError::InvalidArgument);
+        }
+        // The receipts count for the stake of the receipt holders, owned by the receipt mint
+        if is_token_program(account.owner) {
+            let receipt_account = unpack_token_account(account)?;
+            check_account_key(
+                accounts.voter,
+                &receipt_account.owner,
+                AccessError::WrongOwner,
+            )?;
+            let receipt_mint = current_pool
+                .map(|pool| StakePoolHeader::find_receipt_mint(pool, program_id).0);
+            if receipt_mint != Some(receipt_account.mint) {
+                msg!("The receipt account at index {} must follow its pool", i);
+                return Err(AccessError::WrongReceiptMint.into());
+            }
+            voter_weight = voter_weight
+                .checked_add(receipt_account.amount)
+                .ok_or(AccessError::Overflow)?;
+            continue;
+        }
+        let (tag, _) = read_header(&account.data.borrow())?;
+        let weight = match tag {
+            Tag::StakePool => {
+                let stake_pool = StakePool::get_checked(account, vec![Tag::StakePool])?;
+                check_pool_central_state(
+                    account.key,
+                    &stake_pool.header,
+                    accounts.central_state.key,
+                    &central_state,
+                    program_id,
+                )?;
+                current_pool = Some(account.key);
+                0
+            }
+            Tag::StakeAccount => {
+                let stake_account = StakeAccount::from_account_info(account)?;
+                check_account_key(
+                    accounts.voter,
+                    &stake_account.owner,
+                    AccessError::StakeAccountOwnerMismatch,
+                )?;
+                if current_pool != Some(&stake_account.stake_pool) {
+                    msg!("The stake account at index {} must follow its pool", i);
+                    return Err(AccessError::StakePoolMismatch.into());
+                }
+                stake_account.stake_amount
+            }
+            Tag::BondV2Account => {
+                let bond = BondV2Account::from_account_info(account)?;
+                check_account_key(accounts.voter, &bond.owner, AccessError::WrongOwner)?;
+                if current_pool != Some(&bond.pool) {
+                    msg!("The bond V2 account at index {} must follow its pool", i);
+                    return Err(AccessError::StakePoolMismatch.into());
+                }
+                match &boost_config {
+                    // A boost that was not refreshed since the lock decayed does not count
+                    Some(boost_config) => {
+                        let boost = bond_boost(
+                            bond.amount,
+                            bond.unlock_timestamp,
+                            now.unix_timestamp,
+                            boost_config,
+                        )?;
+                        bond.amount
+                            .checked_add(std::cmp::min(boost, bond.boost))
+                            .ok_or(AccessError::Overflow)?
+                    }
+                    None => bond.amount,
+                }
+            }
+            _ => {
+                msg!("Invalid voter account at index {}", i);
+                return Err(AccessError::DataTypeMismatch.into());
+            }
+        };
+        voter_weight = voter_weight
+            .checked_add(weight)
+            .ok_or(AccessError::Overflow)?;
+    }
+
+    let (derived_key, bump_seed) =
+        VoterWeightRecord::create_key(accounts.central_state.key, accounts.voter.key, program_id);
+    check_account_key(
+        accounts.voter_weight_record,
+        &derived_key,
+        AccessError::AccountNotDeterministic,
+    )?;
+    if accounts.voter_weight_record.data_is_empty() {
+        Cpi::create_account(
+            program_id,
+            accounts.system_program,
+            accounts.fee_payer,
+            accounts.voter_weight_record,
+            &[
+                VoterWeightRecord::SEED,
+                &accounts.central_state.key.to_bytes(),
+                &accounts.voter.key.to_bytes(),
+                &[bump_seed],
+            ],
+            VoterWeightRecord::LEN,
+        )?;
+    } else {
+        check_account_owner(
+            accounts.voter_weight_record,
+            program_id,
+            AccessError::WrongOwner,
+        )?;
+        // Makes sure the existing account is a voter weight record
+        VoterWeightRecord::from_account_info(accounts.voter_weight_record)?;
+    }
+
+    let mut record = VoterWeightRecord::new(
+        governance_config.realm,
+        governance_config.governing_token_mint,
+        *accounts.voter.key,
+    );
+    record.voter_weight = voter_weight;
+    record.voter_weight_expiry = Some(now.slot);
+    record.weight_action = weight_action;
+    record.weight_action_target = weight_action_target;
+    record.save(&mut accounts.voter_weight_record.data.borrow_mut())?;
+
+    Event::VoterWeightUpdated {
+        central_state: *accounts.central_state.key,
+        voter: *accounts.voter.key,
+        voter_weight,
+    }
+    .emit()?;
+
+    Ok(())
+}
@@ -0,0 +1,509 @@
+//! The functions are pure, they only read decoded accounts and the current time is passed
+//! explicitly. They are not `no_std`: they use the state types and the errors of the program and
+//! `spl_math`, like the processors. Given the same accounts they return the exact amounts minted
+//! by a crank or a claim, or the error the program returns for it.
+use core::convert::TryInto;
+use core::ops::Deref;
+
+use spl_math::precise_number::PreciseNumber;
+
+use crate::error::AccessError;
+use crate::state::{
+    BondV2Account, BoostConfig, CentralStateV2, RewardIndex, RewardsTuple, RoyaltyAccount,
+    StakeAccount, StakePool, StakePoolHeader, STAKE_BUFFER_LEN,
+};
+
+/// The amounts minted by a claim
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ClaimableRewards {
+    /// Minted to the rewards destination
+    pub reward: u64,
+    /// Minted to the royalty recipient
+    pub royalty: u64,
+}
+
+/// The rewards of one day of a pool, written to its buffer by a crank
+#[derive(Debug, Clone, Copy)]
+pub struct DailyRewards {
+    /// The FP32 pool owner reward and stakers reward per staked token
+    pub rewards: RewardsTuple,
+    /// The tokens claimable by the owner and all the stakers of the pool for that day
+    pub total_claimable: u128,
+}
+
+/// Computes the rewards of a pool for the current day
+///
+/// * `pool_total_staked` The amount staked in the pool and the boosts of its bonds
+/// * `stakers_part` The % of the pool rewards going to the stakers
+/// * `system_total_staked_snapshot` The staked weight of all the pools at the last snapshot
+pub fn calc_daily_rewards(
+    daily_inflation: u64,
+    pool_total_staked: u64,
+    stakers_part: u64,
+    system_total_staked_snapshot: u64,
+) -> Result<DailyRewards, AccessError> {
+    let total_staked_snapshot = pool_total_staked as u128;
+
+    let mut stakers_reward = 0;
+    if total_staked_snapshot != 0 {
+        // Stakers rewards per ACS staked
+        stakers_reward = ((daily_inflation as u128) << 32)
+            .checked_mul(stakers_part as u128)
+            .ok_or(AccessError::Overflow)?
+            .checked_div(100u128)
+            .ok_or(AccessError::Overflow)?
+            .checked_div(system_total_staked_snapshot as u128)
+            .unwrap_or(0);
+    };
+
+    let precise_total_staked_snapshot = PreciseNumber::new(
+        total_staked_snapshot
+            .checked_shl(32)
+            .ok_or(AccessError::Overflow)?,
+    )
+    .ok_or(AccessError::Overflow)?;
+    let precise_daily_inflation =
+        PreciseNumber::new(daily_inflation as u128).ok_or(AccessError::Overflow)?;
+    let precise_system_staked_snapshot =
+        PreciseNumber::new(system_total_staked_snapshot as u128).ok_or(AccessError::Overflow)?;
+
+    // Total pool reward
+    let precise_pool_reward = (precise_total_staked_snapshot)
+        .checked_mul(&precise_daily_inflation)
+        .ok_or(AccessError::Overflow)?
+        .checked_mul(
+            &PreciseNumber::new(
+                100u64
+                    .checked_sub(stakers_part)
+                    .ok_or(AccessError::Overflow)? as u128,
+            )
+            .ok_or(AccessError::Overflow)?,
+        )
+        .ok_or(AccessError::Overflow)?
+        .checked_div(&PreciseNumber::new(100u128).ok_or(AccessError::Overflow)?)
+        .ok_or(AccessError::Overflow)?
+        .checked_div(&precise_system_staked_snapshot)
+        .unwrap_or(PreciseNumber::new(0).ok_or(AccessError::Overflow)?);
+
+    let pool_reward = precise_pool_reward
+        .to_imprecise()
+        .ok_or(AccessError::Overflow)?;
+
+    let total_claimable = round_fp32(pool_reward)
+        .checked_add(round_fp32(
+            stakers_reward
+                .checked_mul(total_staked_snapshot)
+                .ok_or(AccessError::Overflow)?,
+        ))
+        .ok_or(AccessError::Overflow)?;
+
+    Ok(DailyRewards {
+        rewards: RewardsTuple {
+            pool_reward,
+            stakers_reward,
+        },
+        total_claimable,
+    })
+}
+
+/// The upper bound of the tokens claimable for one day of a pool, checked by every crank
+pub fn max_daily_claimable(daily_inflation: u64) -> Result<u128, AccessError> {
+    (daily_inflation as u128)
+        .checked_add(1_000_000)
+        .ok_or(AccessError::Overflow)
+}
+
+/// Cumulate the claimable rewards from the last claimed day to the present.
+/// Result is in FP32 format.
+///
+/// * `last_claimed_index` The reward checkpoint of a stake or bond V2 account, `None` for the
+///   pool owner and for the V1 bonds which only earn from the rewards buffer
+/// * `staker` Compute the reward for a staker or a pool owner
+pub fn calc_reward_fp32<H, B>(
+    current_offset: u64,
+    last_claimed_offset: u64,
+    last_claimed_index: Option<u128>,
+    stake_pool: &StakePool<H, B>,
+    staker: bool,
+    allow_zero_rewards: bool,
+) -> Result<u128, AccessError>
+where
+    H: Deref<Target = StakePoolHeader>,
+    B: Deref<Target = [RewardsTuple]>,
+{
+    let nb_days_to_claim = core::cmp::min(
+        current_offset.saturating_sub(last_claimed_offset),
+        STAKE_BUFFER_LEN,
+    );
+    if nb_days_to_claim == 0 {
+        if !allow_zero_rewards {
+            return Err(AccessError::NoOp);
+        }
+        return Ok(0);
+    }
+
+    if current_offset > stake_pool.header.current_day_idx as u64 {
+        #[cfg(not(any(feature = "days-to-sec-10s", feature = "days-to-sec-15m")))]
+        return Err(AccessError::PoolMustBeCranked);
+    }
+
+    let reward = match (stake_pool.reward_index(), staker, last_claimed_index) {
+        (None, _, _) => sum_rewards_buffer(
+            stake_pool.rewards_buffer(),
+            stake_pool.header.current_day_idx as u64,
+            nb_days_to_claim,
+            staker,
+        )?,
+        (Some(index), false, _) => index
+            .pool_reward_index
+            .checked_sub(index.pool_claimed_index)
+            .ok_or(AccessError::Overflow)?,
+        (Some(index), true, Some(last_claimed_index)) => index
+            .stakers_reward_index
+            .checked_sub(reward_checkpoint(
+                current_offset,
+                last_claimed_offset,
+                last_claimed_index,
+                stake_pool,
+            )?)
+            .ok_or(AccessError::Overflow)?,
+        (Some(_), true, None) => {
+            legacy_stakers_reward(current_offset, last_claimed_offset, stake_pool)?
+        }
+    };
+
+    if reward == 0 && !allow_zero_rewards {
+        return Err(AccessError::NoOp);
+    }
+
+    Ok(reward)
+}
+
+/// Sum of the rewards of the `nb_days` days of the buffer ending at `end_day_idx`
+fn sum_rewards_buffer(
+    buffer: &[RewardsTuple],
+    end_day_idx: u64,
+    nb_days: u64,
+    staker: bool,
+) -> Result<u128, AccessError> {
+    if nb_days == 0 || buffer.len() != STAKE_BUFFER_LEN as usize {
+        return Ok(0);
+    }
+
+    // Saturating as we don't want to wrap around when there haven't been sufficient cranks
+    let mut i = end_day_idx.saturating_sub(nb_days) % STAKE_BUFFER_LEN;
+
+    // Compute reward for all past days
+    let mut reward: u128 = 0;
+    loop {
+        let curr_day_reward = if staker {
+            buffer[i as usize].stakers_reward
+        } else {
+            buffer[i as usize].pool_reward
+        };
+        reward = reward
+            .checked_add(curr_day_reward)
+            .ok_or(AccessError::Overflow)?;
+        i = (i + 1) % STAKE_BUFFER_LEN;
+        if i == end_day_idx % STAKE_BUFFER_LEN {
+            break;
+        }
+    }
+    Ok(reward)
+}
+
+/// The stakers rewards of the days before the switch of a pool to the reward index which are
+/// still in the claim window of `STAKE_BUFFER_LEN` days
+fn legacy_stakers_reward<H, B>(
+    current_offset: u64,
+    last_claimed_offset: u64,
+    stake_pool: &StakePool<H, B>,
+) -> Result<u128, AccessError>
+where
+    H: Deref<Target = StakePoolHeader>,
+    B: Deref<Target = [RewardsTuple]>,
+{
+    let start = stake_pool.header.reward_index_start as u64;
+    let first_claimable = core::cmp::max(
+        last_claimed_offset,
+        current_offset.saturating_sub(STAKE_BUFFER_LEN),
+    );
+    sum_rewards_buffer(
+        stake_pool.rewards_buffer(),
+        start,
+        start.saturating_sub(first_claimable),
+        true,
+    )
+}
+
+/// The stakers reward index at the last claim of an account. The accounts which did not claim
+/// since the switch of their pool to the reward index are credited with their unclaimed days of
+/// the rewards buffer.
+pub fn reward_checkpoint<H, B>(
+    current_offset: u64,
+    last_claimed_offset: u64,
+    last_claimed_index: u128,
+    stake_pool: &StakePool<H, B>,
+) -> Result<u128, AccessError>
+where
+    H: Deref<Target = StakePoolHeader>,
+    B: Deref<Target = [RewardsTuple]>,
+{
+    let index = stake_pool
+        .reward_index()
+        .ok_or(AccessError::RewardIndexNotEnabled)?;
+    if last_claimed_offset > stake_pool.header.reward_index_start as u64 {
+        return Ok(last_claimed_index);
+    }
+    index
+        .stakers_reward_index_start
+        .checked_sub(legacy_stakers_reward(
+            current_offset,
+            last_claimed_offset,
+            stake_pool,
+        )?)
+        .ok_or(AccessError::Overflow)
+}
+
+/// The index values of a pool switching to the reward index at its current day, the unclaimed
+/// days of its rewards buffer stay claimable
+pub fn initial_reward_index<H, B>(stake_pool: &StakePool<H, B>) -> Result<RewardIndex, AccessError>
+where
+    H: Deref<Target = StakePoolHeader>,
+    B: Deref<Target = [RewardsTuple]>,
+{
+    let current_day_idx = stake_pool.header.current_day_idx as u64;
+    let buffered_days = core::cmp::min(current_day_idx, STAKE_BUFFER_LEN);
+    let buffer = stake_pool.rewards_buffer();
+    let stakers_reward_index = sum_rewards_buffer(buffer, current_day_idx, buffered_days, true)?;
+    let pool_reward_index = sum_rewards_buffer(buffer, current_day_idx, buffered_days, false)?;
+    let pool_unclaimed = sum_rewards_buffer(
+        buffer,
+        current_day_idx,
+        core::cmp::min(
+            current_day_idx.saturating_sub(stake_pool.header.last_claimed_offset),
+            STAKE_BUFFER_LEN,
+        ),
+        false,
+    )?;
+    Ok(RewardIndex {
+        pool_reward_index,
+        stakers_reward_index,
+        pool_claimed_index: pool_reward_index
+            .checked_sub(pool_unclaimed)
+            .ok_or(AccessError::Overflow)?,
+        stakers_reward_index_start: stakers_reward_index,
+    })
+}
+
+/// Converts an FP32 value to a token amount, rounding half up
+pub fn round_fp32(value: u128) -> u128 {
+    ((value >> 31) + 1) >> 1
+}
+
+/// The share of `amount` staked tokens in the FP32 stakers reward of a pool
+pub fn staker_reward(reward_fp32: u128, amount: u64) -> Result<u64, AccessError> {
+    reward_fp32
+        .checked_mul(amount as u128)
+        .map(round_fp32)
+        .ok_or(AccessError::Overflow)?
+        .try_into()
+        .map_err(|_| AccessError::Overflow)
+}
+
+/// The rewards of a bond V2 for an FP32 stakers reward, including the rewards settled by its
+/// boost refreshes
+pub fn bond_v2_reward(
+    reward_fp32: u128,
+    bond_v2_account: &BondV2Account,
+) -> Result<u64, AccessError> {
+    let weight = bond_v2_account
+        .amount
+        .checked_add(bond_v2_account.boost)
+        .ok_or(AccessError::Overflow)?;
+    staker_reward(reward_fp32, weight)?
+        .checked_add(bond_v2_account.unclaimed_rewards)
+        .ok_or(AccessError::Overflow)
+}
+
+/// The boost of a bond given its remaining lock time at `now`
+///
+/// The boost grows linearly with the remaining lock time up to `max_lock_duration`, forever bonds
+/// get the fixed forever boost
+pub fn bond_boost(
+    amount: u64,
+    unlock_timestamp: Option<i64>,
+    now: i64,
+    boost_config: &BoostConfig,
+) -> Result<u64, AccessError> {
+    let boost = match unlock_timestamp {
+        None => (amount as u128)
+            .checked_mul(boost_config.forever_boost_basis_points as u128)
+            .ok_or(AccessError::Overflow)?,
+        Some(unlock_timestamp) => {
+            if boost_config.max_lock_duration <= 0 {
+                return Ok(0);
+            }
+            let remaining = unlock_timestamp
+                .saturating_sub(now)
+                .clamp(0, boost_config.max_lock_duration);
+            (amount as u128)
+                .checked_mul(boost_config.max_lock_boost_basis_points as u128)
+                .ok_or(AccessError::Overflow)?
+                .checked_mul(remaining as u128)
+                .ok_or(AccessError::Overflow)?
+                .checked_div(boost_config.max_lock_duration as u128)
+                .ok_or(AccessError::Overflow)?
+        }
+    };
+    (boost / 10_000)
+        .try_into()
+        .map_err(|_| AccessError::Overflow)
+}
+
+/// The share of the receipt reserve of `amount` receipt tokens
+///
+/// Redemptions round down and the premium paid to mint new receipts rounds up, the reserve keeps
+/// the rounding
+pub fn receipt_reserve_share(
+    amount: u64,
+    reserve: u64,
+    receipt_supply: u64,
+    round_up: bool,
+) -> Result<u64, AccessError> {
+    if receipt_supply == 0 {
+        return Ok(0);
+    }
+    let product = (amount as u128)
+        .checked_mul(reserve as u128)
+        .ok_or(AccessError::Overflow)?;
+    let rounding = if round_up {
+        receipt_supply as u128 - 1
+    } else {
+        0
+    };
+    (product
+        .checked_add(rounding)
+        .ok_or(AccessError::Overflow)?
+        / receipt_supply as u128)
+        .try_into()
+        .map_err(|_| AccessError::Overflow)
+}
+
+/// The FP32 pool owner reward of a pool as a token amount
+pub fn pool_reward(reward_fp32: u128) -> Result<u64, AccessError> {
+    round_fp32(reward_fp32)
+        .try_into()
+        .map_err(|_| AccessError::Overflow)
+}
+
+/// The royalty part of `amount`, rounded up
+pub fn royalty_amount(amount: u64, royalty_basis_points: u16) -> Result<u64, AccessError> {
+    let royalty = amount
+        .checked_mul(royalty_basis_points as u64)
+        .ok_or(AccessError::Overflow)?
+        .checked_add(9_999) // rounding
+        .ok_or(AccessError::Overflow)?
+        .checked_div(10_000)
+        .ok_or(AccessError::Overflow)?;
+    if royalty > amount {
+        return Err(AccessError::Overflow);
+    }
+    Ok(royalty)
+}
+
+/// Expired royalty accounts are ignored by the claims
+pub fn is_royalty_active(royalty_account: &RoyaltyAccount, now: i64) -> bool {
+    royalty_account.expiration_date >= now as u64
+}
+
+/// Splits a reward between the claimer and an active royalty account
+pub fn split_royalty(
+    reward: u64,
+    royalty_account: Option<&RoyaltyAccount>,
+) -> Result<ClaimableRewards, AccessError> {
+    let royalty = match royalty_account {
+        Some(royalty_account) => royalty_amount(reward, royalty_account.royalty_basis_points)?,
+        None => 0,
+    };
+    Ok(ClaimableRewards {
+        reward: reward.checked_sub(royalty).ok_or(AccessError::Overflow)?,
+        royalty,
+    })
+}
+
+fn active_royalty(royalty_account: Option<&RoyaltyAccount>, now: i64) -> Option<&RoyaltyAccount> {
+    royalty_account.filter(|r| is_royalty_active(r, now))
+}
+
+/// The amounts minted by `claim_rewards` for a stake account
+pub fn claimable_staker_rewards<H, B>(
+    central_state: &CentralStateV2,
+    stake_pool: &StakePool<H, B>,
+    stake_account: &StakeAccount,
+    royalty_account: Option<&RoyaltyAccount>,
+    now: i64,
+    allow_zero_rewards: bool,
+) -> Result<ClaimableRewards, AccessError>
+where
+    H: Deref<Target = StakePoolHeader>,
+    B: Deref<Target = [RewardsTuple]>,
+{
+    let reward_fp32 = calc_reward_fp32(
+        central_state.last_snapshot_offset,
+        stake_account.last_claimed_offset,
+        Some(stake_account.last_claimed_index),
+        stake_pool,
+        true,
+        allow_zero_rewards,
+    )?;
+    let reward = staker_reward(reward_fp32, stake_account.stake_amount)?;
+    split_royalty(reward, active_royalty(royalty_account, now))
+}
+
+/// The amounts minted by `claim_bond_v2_rewards` for a bond V2 account
+pub fn claimable_bond_v2_rewards<H, B>(
+    central_state: &CentralStateV2,
+    stake_pool: &StakePool<H, B>,
+    bond_v2_account: &BondV2Account,
+    royalty_account: Option<&RoyaltyAccount>,
+    now: i64,
+) -> Result<ClaimableRewards, AccessError>
+where
+    H: Deref<Target = StakePoolHeader>,
+    B: Deref<Target = [RewardsTuple]>,
+{
+    let reward_fp32 = calc_reward_fp32(
+        central_state.last_snapshot_offset,
+        bond_v2_account.last_claimed_offset,
+        Some(bond_v2_account.last_claimed_index),
+        stake_pool,
+        true,
+        bond_v2_account.unclaimed_rewards != 0,
+    )?;
+    let reward = bond_v2_reward(reward_fp32, bond_v2_account)?;
+    split_royalty(reward, active_royalty(royalty_account, now))
+}
+
+/// The amounts minted by `claim_pool_rewards` for a pool owner
+pub fn claimable_pool_rewards<H, B>(
+    central_state: &CentralStateV2,
+    stake_pool: &StakePool<H, B>,
+    royalty_account: Option<&RoyaltyAccount>,
+    now: i64,
+) -> Result<ClaimableRewards, AccessError>
+where
+    H: Deref<Target = StakePoolHeader>,
+    B: Deref<Target = [RewardsTuple]>,
+{
+    let reward_fp32 = calc_reward_fp32(
+        central_state.last_snapshot_offset,
+        stake_pool.header.last_claimed_offset,
+        None,
+        stake_pool,
+        false,
+        false,
+    )?;
+    let reward = pool_reward(reward_fp32)?;
+    split_royalty(reward, active_royalty(royalty_account, now))
+}
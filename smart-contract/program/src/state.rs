@@ -1,11 +1,15 @@
 use std::cell::RefMut;
 use std::convert::TryInto;
+use std::io::{ErrorKind, Read, Write};
 use std::mem::size_of;
-use std::ops::DerefMut;
+use std::ops::{Deref, DerefMut};
 
 use bonfida_utils::BorshSize;
 use borsh::{BorshDeserialize, BorshSerialize};
-use bytemuck::{cast_slice, from_bytes, from_bytes_mut, try_cast_slice_mut, Pod, Zeroable};
+use bytemuck::{
+    cast_slice, cast_slice_mut, from_bytes, from_bytes_mut, pod_read_unaligned,
+    try_cast_slice_mut, Pod, Zeroable,
+};
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::FromPrimitive;
 use solana_program::account_info::AccountInfo;
@@ -15,20 +19,17 @@ use solana_program::msg;
 use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
 use solana_program::sysvar::Sysvar;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
 
 use crate::error::AccessError;
 use crate::instruction::ProgramInstruction;
-use crate::instruction::ProgramInstruction::AdminProgramFreeze;
+use crate::instruction::ProgramInstruction::{AdminProgramFreeze, UnlockBondV2, Unstake};
+use crate::rewards::royalty_amount;
 use crate::utils::is_admin_renouncable_instruction;
-
-/// ACCESS token mint
-pub const ACCESS_MINT: Pubkey =
-    solana_program::pubkey!("5MAYDfq5yxtudAhtfyuMBuHZjgAbaS9tbEyEQYAhDS5y");
-
-// todo fill in the production one
-/// ACCESS NFT program address
-pub const ACCESS_NFT_PROGRAM_SIGNER: Pubkey =
-    solana_program::pubkey!("97VhuEes8ExokBvG7hxyexpFPGzZu18SZERVKseqVV9");
+use crate::versioning::{
+    load_migrated, read_header, read_versioned_tag, store_migrated, write_header, Migrate,
+    Versioned, VERSIONED_HEADER_LEN,
+};
 
 /// Specify the number of seconds in a day, used only for testing purposes
 pub const SECONDS_IN_DAY: u64 = if cfg!(feature = "days-to-sec-15m") {
@@ -51,17 +52,42 @@ pub const STAKE_BUFFER_LEN: u64 = 274;
 /// Maximum count of recipients of the fees
 pub const MAX_FEE_RECIPIENTS: usize = 10;
 
+/// Maximum number of delegated admin roles in a role table
+pub const MAX_ADMIN_ROLES: usize = 32;
+
 /// Minimum balance of the fee split account allowed for token distribution
 pub const MIN_DISTRIBUTE_AMOUNT: u64 = 100_000_000;
 
+/// Maximum keeper reward in basis points (i.e 1% = 100) paid out of each fee distribution
+pub const MAX_KEEPER_REWARD_BASIS_POINTS: u16 = 100;
+
 /// Maximum delay between last fee split distribution and fee split account setup
 pub const MAX_FEE_SPLIT_SETUP_DELAY: u64 = 5 * 60; // 5 minutes
 
 /// Amount in basis points (i.e 1% = 100) added to each locking operation as a protocol fee
 pub const DEFAULT_FEE_BASIS_POINTS: u16 = 200;
 
+/// Maximum boost of a bond in basis points of its amount (i.e 100% = 10_000)
+pub const MAX_BOOST_BASIS_POINTS: u16 = 40_000;
+
+/// Duration in seconds of a freeze of the unstakes and unlocks after which the stakers can withdraw
+/// their principal with the emergency withdrawal
+pub const MAX_FREEZE_DURATION: i64 = 30 * SECONDS_IN_DAY as i64;
+
+/// Duration in seconds the unstakes and unlocks must stay unfrozen before a new freeze restarts the
+/// [`MAX_FREEZE_DURATION`]
+pub const MIN_UNFREEZE_DURATION: i64 = 7 * SECONDS_IN_DAY as i64;
+
 #[derive(
-    BorshSerialize, BorshDeserialize, BorshSize, PartialEq, FromPrimitive, ToPrimitive, Debug,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSize,
+    PartialEq,
+    FromPrimitive,
+    ToPrimitive,
+    Debug,
+    Clone,
+    Copy,
 )]
 #[repr(u8)]
 #[allow(missing_docs)]
@@ -84,6 +110,16 @@ pub enum Tag {
     BondV2Account,
     CentralStateV2,
     RoyaltyAccount,
+    PoolFeeOverride,
+    FeeDistributionConfig,
+    ProtocolConfig,
+    BondV2Listing,
+    BoostConfig,
+    PoolBoost,
+    GovernanceConfig,
+    PoolControls,
+    PoolAllowlistEntry,
+    RoleTable,
 }
 
 impl Tag {
@@ -116,8 +152,15 @@ pub struct StakePoolHeader {
     /// Updated by a trustless cranker
     pub current_day_idx: u16,
 
-    /// Padding
-    pub _padding: [u8; 4],
+    /// 1 if the rewards accrue in the reward index of the pool instead of its rewards buffer
+    pub reward_index_enabled: u8,
+
+    /// The pool features requiring an additional account, see [`StakePoolHeader::BOOST_FLAG`] and
+    /// [`StakePoolHeader::CONTROLS_FLAG`]
+    pub flags: u8,
+
+    /// The day index at which the pool switched to the reward index
+    pub reward_index_start: u16,
 
     /// Minimum amount to stake to get access to the pool
     pub minimum_stake_amount: u64,
@@ -145,6 +188,24 @@ pub struct StakePool<H, B> {
     pub balances: B,
 }
 
+/// The cumulated rewards of a pool using the reward index, stored in the first slots of its buffer
+/// The values are stored in the FP32 format.
+#[derive(Pod, Clone, Copy, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct RewardIndex {
+    /// Cumulated rewards of the pool owner
+    pub pool_reward_index: u128,
+    /// Cumulated rewards per staked token
+    pub stakers_reward_index: u128,
+    /// The pool reward index at the last claim of the pool owner
+    pub pool_claimed_index: u128,
+    /// The stakers reward index when the pool switched to the reward index
+    pub stakers_reward_index_start: u128,
+}
+
+/// Number of buffer slots holding the reward index
+pub const REWARD_INDEX_SLOTS: usize = size_of::<RewardIndex>() / size_of::<RewardsTuple>();
+
 /// The Rewards structure that is held in the stake pools circular buffer.
 /// The two fields represent the share that is owed to the pool owner and the stakers respectively.
 /// The values are stored in the FP32 format.
@@ -187,28 +248,89 @@ impl<'a> StakePoolRef<'a> {
 #[allow(missing_docs)]
 impl StakePoolHeaped {
     pub fn from_buffer(buf: &[u8]) -> Self {
-        println!("StakePoolHeaped::from_buffer: buf.len() = {}", buf.len());
         let (header, balances) = buf.split_at(size_of::<StakePoolHeader>());
-        println!(
-            "StakePoolHeaped::from_buffer: header.len() = {}",
-            header.len()
-        );
-        let header = from_bytes::<StakePoolHeader>(header);
-        println!("StakePoolHeaped::from_buffer: header = {:?}", header);
-        let balances = cast_slice::<_, RewardsTuple>(balances);
-        println!(
-            "StakePoolHeaped::from_buffer: balances.len() = {}",
-            balances.len()
-        );
+        // Account data is not guaranteed to be aligned off-chain
+        let header = pod_read_unaligned::<StakePoolHeader>(header);
+        let balances = balances
+            .chunks_exact(size_of::<RewardsTuple>())
+            .map(pod_read_unaligned::<RewardsTuple>)
+            .collect();
         Self {
-            header: Box::new(*header),
-            balances: Box::from(balances),
+            header: Box::new(header),
+            balances,
+        }
+    }
+}
+
+#[allow(missing_docs)]
+impl<H: Deref<Target = StakePoolHeader>, B: Deref<Target = [RewardsTuple]>> StakePool<H, B> {
+    /// The reward index of a pool which switched from its rewards buffer
+    pub fn reward_index(&self) -> Option<&RewardIndex> {
+        if !self.header.uses_reward_index() {
+            return None;
+        }
+        self.balances
+            .get(..REWARD_INDEX_SLOTS)
+            .map(|slots| from_bytes(cast_slice(slots)))
+    }
+
+    /// The reward checkpoint of an account claiming now, 0 for the pools without reward index
+    pub fn stakers_reward_index(&self) -> u128 {
+        self.reward_index()
+            .map_or(0, |index| index.stakers_reward_index)
+    }
+
+    /// The daily rewards, the buffer of a pool using the reward index is kept until it is compacted
+    pub fn rewards_buffer(&self) -> &[RewardsTuple] {
+        if self.header.uses_reward_index() {
+            self.balances.get(REWARD_INDEX_SLOTS..).unwrap_or(&[])
+        } else {
+            &self.balances
         }
     }
 }
 
 #[allow(missing_docs)]
 impl<H: DerefMut<Target = StakePoolHeader>, B: DerefMut<Target = [RewardsTuple]>> StakePool<H, B> {
+    pub fn reward_index_mut(&mut self) -> Option<&mut RewardIndex> {
+        if !self.header.uses_reward_index() {
+            return None;
+        }
+        self.balances
+            .get_mut(..REWARD_INDEX_SLOTS)
+            .map(|slots| from_bytes_mut(cast_slice_mut(slots)))
+    }
+
+    /// Records the rewards of the current day in the reward index or in the rewards buffer
+    pub fn add_daily_rewards(
+        &mut self,
+        current_offset: u64,
+        rewards: RewardsTuple,
+    ) -> Result<(), ProgramError> {
+        if !self.header.uses_reward_index() {
+            return self.push_balances_buff(current_offset, rewards);
+        }
+        if current_offset <= self.header.current_day_idx as u64 {
+            return Err(AccessError::Overflow.into());
+        }
+        self.header.current_day_idx = current_offset
+            .try_into()
+            .map_err(|_| AccessError::Overflow)?;
+        // The days without a crank earn nothing, as in the rewards buffer
+        let index = self
+            .reward_index_mut()
+            .ok_or(AccessError::RewardIndexNotEnabled)?;
+        index.pool_reward_index = index
+            .pool_reward_index
+            .checked_add(rewards.pool_reward)
+            .ok_or(AccessError::Overflow)?;
+        index.stakers_reward_index = index
+            .stakers_reward_index
+            .checked_add(rewards.stakers_reward)
+            .ok_or(AccessError::Overflow)?;
+        Ok(())
+    }
+
     pub fn push_balances_buff(
         &mut self,
         current_offset: u64,
@@ -246,22 +368,63 @@ impl<H: DerefMut<Target = StakePoolHeader>, B: DerefMut<Target = [RewardsTuple]>
         owner: &Pubkey,
         program_id: &Pubkey,
     ) -> Result<Pubkey, ProgramError> {
-        let seeds: &[&[u8]] = &[StakePoolHeader::SEED, &owner.to_bytes(), &[*nonce]];
-        Pubkey::create_program_address(seeds, program_id).map_err(|_| ProgramError::InvalidSeeds)
+        StakePool::create_scoped_key(nonce, owner, None, program_id)
     }
 }
 
 #[allow(missing_docs)]
 impl StakePool<(), ()> {
     pub fn find_key(owner: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
-        let seeds: &[&[u8]] = &[StakePoolHeader::SEED, &owner.to_bytes()];
-        Pubkey::find_program_address(seeds, program_id)
+        Self::find_scoped_key(owner, None, program_id)
+    }
+
+    /// Find the key of a stake pool belonging to the central state with the given scope
+    pub fn find_scoped_key(
+        owner: &Pubkey,
+        scope: Option<&Pubkey>,
+        program_id: &Pubkey,
+    ) -> (Pubkey, u8) {
+        let seeds = StakePoolHeader::seeds(owner, scope, &[]);
+        Pubkey::find_program_address(&seeds, program_id)
+    }
+
+    /// Derive the key of a stake pool belonging to the central state with the given scope
+    pub fn create_scoped_key(
+        nonce: &u8,
+        owner: &Pubkey,
+        scope: Option<&Pubkey>,
+        program_id: &Pubkey,
+    ) -> Result<Pubkey, ProgramError> {
+        let nonce = [*nonce];
+        let seeds = StakePoolHeader::seeds(owner, scope, &nonce);
+        Pubkey::create_program_address(&seeds, program_id).map_err(|_| ProgramError::InvalidSeeds)
     }
 }
 
 #[allow(missing_docs)]
 impl StakePoolHeader {
     pub const SEED: &'static [u8; 10] = b"stake_pool";
+    pub const RECEIPT_MINT_SEED: &'static [u8; 12] = b"receipt_mint";
+    /// Set once the boosts of the bonds of the pool are tracked by its pool boost account
+    pub const BOOST_FLAG: u8 = 1;
+    /// Set once the new stakes of the pool are checked against its pool controls account
+    pub const CONTROLS_FLAG: u8 = 2;
+
+    /// Seeds of a stake pool, the scope is only set for pools of a tenant central state
+    pub fn seeds<'a>(
+        owner: &'a Pubkey,
+        scope: Option<&'a Pubkey>,
+        nonce: &'a [u8],
+    ) -> Vec<&'a [u8]> {
+        let mut seeds: Vec<&[u8]> = vec![StakePoolHeader::SEED, owner.as_ref()];
+        if let Some(scope) = scope {
+            seeds.push(scope.as_ref());
+        }
+        if !nonce.is_empty() {
+            seeds.push(nonce);
+        }
+        seeds
+    }
 
     pub fn new(
         owner: Pubkey,
@@ -273,7 +436,9 @@ impl StakePoolHeader {
             tag: Tag::InactiveStakePool as u8,
             total_staked: 0,
             current_day_idx: 0,
-            _padding: [0; 4],
+            reward_index_enabled: 0,
+            flags: 0,
+            reward_index_start: 0,
             last_claimed_offset: 0,
             owner: owner.to_bytes(),
             nonce,
@@ -287,6 +452,24 @@ impl StakePoolHeader {
         self.tag = Tag::Deleted as u8
     }
 
+    pub fn uses_reward_index(&self) -> bool {
+        self.reward_index_enabled != 0
+    }
+
+    pub fn uses_boost(&self) -> bool {
+        self.flags & StakePoolHeader::BOOST_FLAG != 0
+    }
+
+    pub fn has_controls(&self) -> bool {
+        self.flags & StakePoolHeader::CONTROLS_FLAG != 0
+    }
+
+    /// The receipt mint of a stake pool, the stake account of the receipt holders is owned by it
+    pub fn find_receipt_mint(stake_pool: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[StakePoolHeader::RECEIPT_MINT_SEED, &stake_pool.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
     pub fn deposit(&mut self, amount: u64) -> ProgramResult {
         self.total_staked = self
             .total_staked
@@ -304,7 +487,7 @@ impl StakePoolHeader {
     }
 }
 
-#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+#[derive(Debug)]
 #[allow(missing_docs)]
 pub struct StakeAccount {
     /// Tag
@@ -325,6 +508,160 @@ pub struct StakeAccount {
     /// Minimum stakeable amount of the pool when the account
     /// was created
     pub pool_minimum_at_creation: u64,
+
+    /// Stakers reward index of the pool at the last claim
+    pub last_claimed_index: u128,
+}
+
+impl BorshSerialize for StakeAccount {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        write_header(self.tag, Self::VERSION, writer)?;
+        self.owner.serialize(writer)?;
+        self.stake_amount.serialize(writer)?;
+        self.stake_pool.serialize(writer)?;
+        self.last_claimed_offset.serialize(writer)?;
+        self.pool_minimum_at_creation.serialize(writer)?;
+        self.last_claimed_index.serialize(writer)
+    }
+}
+
+impl BorshDeserialize for StakeAccount {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        Ok(Self {
+            tag: read_versioned_tag(reader, Self::VERSION)?,
+            owner: BorshDeserialize::deserialize_reader(reader)?,
+            stake_amount: BorshDeserialize::deserialize_reader(reader)?,
+            stake_pool: BorshDeserialize::deserialize_reader(reader)?,
+            last_claimed_offset: BorshDeserialize::deserialize_reader(reader)?,
+            pool_minimum_at_creation: BorshDeserialize::deserialize_reader(reader)?,
+            last_claimed_index: BorshDeserialize::deserialize_reader(reader)?,
+        })
+    }
+}
+
+impl BorshSize for StakeAccount {
+    fn borsh_len(&self) -> usize {
+        VERSIONED_HEADER_LEN
+            + self.owner.borsh_len()
+            + self.stake_amount.borsh_len()
+            + self.stake_pool.borsh_len()
+            + self.last_claimed_offset.borsh_len()
+            + self.pool_minimum_at_creation.borsh_len()
+            + self.last_claimed_index.borsh_len()
+    }
+}
+
+impl Versioned for StakeAccount {
+    const VERSION: u8 = 1;
+
+    fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        load_migrated(data)
+    }
+
+    fn store(&self, dst: &mut [u8]) -> ProgramResult {
+        store_migrated(self, dst)
+    }
+}
+
+impl Migrate for StakeAccount {
+    type Previous = StakeAccountV0;
+
+    fn from_previous(previous: StakeAccountV0) -> Result<Self, ProgramError> {
+        Ok(Self {
+            tag: previous.tag,
+            owner: previous.owner,
+            stake_amount: previous.stake_amount,
+            stake_pool: previous.stake_pool,
+            last_claimed_offset: previous.last_claimed_offset,
+            pool_minimum_at_creation: previous.pool_minimum_at_creation,
+            last_claimed_index: previous.last_claimed_index,
+        })
+    }
+
+    fn to_previous(&self) -> Result<StakeAccountV0, ProgramError> {
+        Ok(StakeAccountV0 {
+            tag: self.tag,
+            owner: self.owner,
+            stake_amount: self.stake_amount,
+            stake_pool: self.stake_pool,
+            last_claimed_offset: self.last_claimed_offset,
+            pool_minimum_at_creation: self.pool_minimum_at_creation,
+            last_claimed_index: self.last_claimed_index,
+        })
+    }
+}
+
+/// Layout of the stake accounts created before the version byte, the accounts created before
+/// the reward index have no room for their reward checkpoint
+#[derive(BorshSerialize, BorshSize, Debug)]
+#[allow(missing_docs)]
+pub struct StakeAccountV0 {
+    pub tag: Tag,
+    pub owner: Pubkey,
+    pub stake_amount: u64,
+    pub stake_pool: Pubkey,
+    pub last_claimed_offset: u64,
+    pub pool_minimum_at_creation: u64,
+    pub last_claimed_index: u128,
+}
+
+impl BorshDeserialize for StakeAccountV0 {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        Ok(Self {
+            tag: BorshDeserialize::deserialize_reader(reader)?,
+            owner: BorshDeserialize::deserialize_reader(reader)?,
+            stake_amount: BorshDeserialize::deserialize_reader(reader)?,
+            stake_pool: BorshDeserialize::deserialize_reader(reader)?,
+            last_claimed_offset: BorshDeserialize::deserialize_reader(reader)?,
+            pool_minimum_at_creation: BorshDeserialize::deserialize_reader(reader)?,
+            last_claimed_index: read_reward_checkpoint(reader)?,
+        })
+    }
+}
+
+impl Versioned for StakeAccountV0 {
+    const VERSION: u8 = 0;
+
+    fn load(mut data: &[u8]) -> Result<Self, ProgramError> {
+        Ok(Self::deserialize(&mut data)?)
+    }
+
+    fn store(&self, dst: &mut [u8]) -> ProgramResult {
+        save_with_reward_checkpoint(self, self.last_claimed_index, dst)
+    }
+}
+
+/// Reads the trailing reward checkpoint of an account, 0 if the account has no room for it
+fn read_reward_checkpoint<R: Read>(reader: &mut R) -> std::io::Result<u128> {
+    let mut buf = [0u8; size_of::<u128>()];
+    match reader.read_exact(&mut buf) {
+        Ok(()) => Ok(u128::from_le_bytes(buf)),
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => Ok(0),
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes an account ending with its reward checkpoint, the checkpoint can only be left out
+/// of an account without room for it while it is 0
+fn save_with_reward_checkpoint(
+    account: &impl BorshSerialize,
+    last_claimed_index: u128,
+    dst: &mut [u8],
+) -> ProgramResult {
+    let data = account
+        .try_to_vec()
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    let len = if data.len() <= dst.len() {
+        data.len()
+    } else if last_claimed_index == 0 {
+        data.len() - size_of::<u128>()
+    } else {
+        return Err(AccessError::RewardCheckpointNotAllocated.into());
+    };
+    dst.get_mut(..len)
+        .ok_or(ProgramError::InvalidAccountData)?
+        .copy_from_slice(&data[..len]);
+    Ok(())
 }
 
 #[allow(missing_docs)]
@@ -339,6 +676,7 @@ impl StakeAccount {
             stake_pool,
             last_claimed_offset: 0,
             pool_minimum_at_creation,
+            last_claimed_index: 0,
         }
     }
 
@@ -366,18 +704,17 @@ impl StakeAccount {
         Pubkey::find_program_address(seeds, program_id)
     }
 
-    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
-        self.serialize(&mut dst)
-            .map_err(|_| ProgramError::InvalidAccountData)
+    pub fn save(&self, dst: &mut [u8]) -> ProgramResult {
+        self.store(dst)
     }
 
     pub fn from_account_info(a: &AccountInfo) -> Result<StakeAccount, ProgramError> {
-        let mut data = &a.data.borrow() as &[u8];
-        if data[0] != Tag::StakeAccount as u8 && data[0] != Tag::Uninitialized as u8 {
+        let data = &a.data.borrow() as &[u8];
+        let (tag, _) = read_header(data)?;
+        if tag != Tag::StakeAccount && tag != Tag::Uninitialized {
             return Err(AccessError::DataTypeMismatch.into());
         }
-        let result = StakeAccount::deserialize(&mut data)?;
-        Ok(result)
+        StakeAccount::load(data)
     }
 
     pub fn close(&mut self) {
@@ -485,7 +822,7 @@ impl CentralState {
     }
 }
 
-#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+#[derive(Debug)]
 #[allow(missing_docs)]
 pub struct CentralStateV2 {
     /// Tag
@@ -513,6 +850,12 @@ pub struct CentralStateV2 {
     /// The daily total_staked snapshot to correctly calculate the pool rewards
     pub total_staked_snapshot: u64,
 
+    /// Sum of the boosts of the bonds V2, shares the inflation with the staked tokens
+    pub total_boost: u64,
+
+    /// The daily total_boost snapshot taken with the total_staked snapshot
+    pub total_boost_snapshot: u64,
+
     /// The offset of the total_staked_snapshot from the creation_time in days
     pub last_snapshot_offset: u64,
 
@@ -533,11 +876,267 @@ pub struct CentralStateV2 {
     /// Last fee distribution timestamp
     pub last_fee_distribution_time: i64,
 
+    /// The time `admin_program_freeze` froze the unstakes or the unlocks of the bonds V2, 0 if
+    /// they were never frozen
+    pub exits_frozen_since: i64,
+
+    /// The time the unstakes and the unlocks were unfrozen, 0 while they are frozen
+    pub exits_unfrozen_since: i64,
+
+    /// Maximum of the supply of the mint reachable by the mints of the protocol, 0 for no cap
+    pub emission_cap: u64,
+
+    /// Staker rewards minted by the claims of the stake accounts, bonds and receipt reserves
+    pub staker_rewards_minted: u64,
+
+    /// Pool owner rewards minted
+    pub pool_rewards_minted: u64,
+
+    /// Royalties minted to the royalty recipients
+    pub royalties_minted: u64,
+
+    /// Tokens minted by the authority with `admin_mint`
+    pub admin_minted: u64,
+
+    /// Tokens sold by the V1 bonds and minted to the pool vaults
+    pub bonds_minted: u64,
+
+    /// Protocol fees burned by `distribute_fees`
+    pub fees_burned: u64,
+
     /// List of the fee recipients and their share of the fees. The sum of the shares must be <=100%,
     /// the rest is getting burned
     pub recipients: Vec<FeeRecipient>,
 }
 
+impl BorshSerialize for CentralStateV2 {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        write_header(self.tag, Self::VERSION, writer)?;
+        self.bump_seed.serialize(writer)?;
+        self.daily_inflation.serialize(writer)?;
+        self.token_mint.serialize(writer)?;
+        self.authority.serialize(writer)?;
+        self.creation_time.serialize(writer)?;
+        self.total_staked.serialize(writer)?;
+        self.total_staked_snapshot.serialize(writer)?;
+        self.total_boost.serialize(writer)?;
+        self.total_boost_snapshot.serialize(writer)?;
+        self.last_snapshot_offset.serialize(writer)?;
+        self.ix_gate.serialize(writer)?;
+        self.freeze_authority.serialize(writer)?;
+        self.admin_ix_gate.serialize(writer)?;
+        self.fee_basis_points.serialize(writer)?;
+        self.last_fee_distribution_time.serialize(writer)?;
+        self.exits_frozen_since.serialize(writer)?;
+        self.exits_unfrozen_since.serialize(writer)?;
+        self.emission_cap.serialize(writer)?;
+        self.staker_rewards_minted.serialize(writer)?;
+        self.pool_rewards_minted.serialize(writer)?;
+        self.royalties_minted.serialize(writer)?;
+        self.admin_minted.serialize(writer)?;
+        self.bonds_minted.serialize(writer)?;
+        self.fees_burned.serialize(writer)?;
+        self.recipients.serialize(writer)
+    }
+}
+
+impl BorshDeserialize for CentralStateV2 {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        Ok(Self {
+            tag: read_versioned_tag(reader, Self::VERSION)?,
+            bump_seed: BorshDeserialize::deserialize_reader(reader)?,
+            daily_inflation: BorshDeserialize::deserialize_reader(reader)?,
+            token_mint: BorshDeserialize::deserialize_reader(reader)?,
+            authority: BorshDeserialize::deserialize_reader(reader)?,
+            creation_time: BorshDeserialize::deserialize_reader(reader)?,
+            total_staked: BorshDeserialize::deserialize_reader(reader)?,
+            total_staked_snapshot: BorshDeserialize::deserialize_reader(reader)?,
+            total_boost: BorshDeserialize::deserialize_reader(reader)?,
+            total_boost_snapshot: BorshDeserialize::deserialize_reader(reader)?,
+            last_snapshot_offset: BorshDeserialize::deserialize_reader(reader)?,
+            ix_gate: BorshDeserialize::deserialize_reader(reader)?,
+            freeze_authority: BorshDeserialize::deserialize_reader(reader)?,
+            admin_ix_gate: BorshDeserialize::deserialize_reader(reader)?,
+            fee_basis_points: BorshDeserialize::deserialize_reader(reader)?,
+            last_fee_distribution_time: BorshDeserialize::deserialize_reader(reader)?,
+            exits_frozen_since: BorshDeserialize::deserialize_reader(reader)?,
+            exits_unfrozen_since: BorshDeserialize::deserialize_reader(reader)?,
+            emission_cap: BorshDeserialize::deserialize_reader(reader)?,
+            staker_rewards_minted: BorshDeserialize::deserialize_reader(reader)?,
+            pool_rewards_minted: BorshDeserialize::deserialize_reader(reader)?,
+            royalties_minted: BorshDeserialize::deserialize_reader(reader)?,
+            admin_minted: BorshDeserialize::deserialize_reader(reader)?,
+            bonds_minted: BorshDeserialize::deserialize_reader(reader)?,
+            fees_burned: BorshDeserialize::deserialize_reader(reader)?,
+            recipients: BorshDeserialize::deserialize_reader(reader)?,
+        })
+    }
+}
+
+impl BorshSize for CentralStateV2 {
+    fn borsh_len(&self) -> usize {
+        VERSIONED_HEADER_LEN
+            + self.bump_seed.borsh_len()
+            + self.daily_inflation.borsh_len()
+            + self.token_mint.borsh_len()
+            + self.authority.borsh_len()
+            + self.creation_time.borsh_len()
+            + self.total_staked.borsh_len()
+            + self.total_staked_snapshot.borsh_len()
+            + self.total_boost.borsh_len()
+            + self.total_boost_snapshot.borsh_len()
+            + self.last_snapshot_offset.borsh_len()
+            + self.ix_gate.borsh_len()
+            + self.freeze_authority.borsh_len()
+            + self.admin_ix_gate.borsh_len()
+            + self.fee_basis_points.borsh_len()
+            + self.last_fee_distribution_time.borsh_len()
+            + self.exits_frozen_since.borsh_len()
+            + self.exits_unfrozen_since.borsh_len()
+            + self.emission_cap.borsh_len()
+            + self.staker_rewards_minted.borsh_len()
+            + self.pool_rewards_minted.borsh_len()
+            + self.royalties_minted.borsh_len()
+            + self.admin_minted.borsh_len()
+            + self.bonds_minted.borsh_len()
+            + self.fees_burned.borsh_len()
+            + self.recipients.borsh_len()
+    }
+}
+
+impl Versioned for CentralStateV2 {
+    const VERSION: u8 = 1;
+
+    fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        load_migrated(data)
+    }
+
+    fn store(&self, dst: &mut [u8]) -> ProgramResult {
+        store_migrated(self, dst)
+    }
+}
+
+impl Migrate for CentralStateV2 {
+    type Previous = CentralStateV2V0;
+
+    fn from_previous(previous: CentralStateV2V0) -> Result<Self, ProgramError> {
+        Ok(Self {
+            tag: previous.tag,
+            bump_seed: previous.bump_seed,
+            daily_inflation: previous.daily_inflation,
+            token_mint: previous.token_mint,
+            authority: previous.authority,
+            creation_time: previous.creation_time,
+            total_staked: previous.total_staked,
+            total_staked_snapshot: previous.total_staked_snapshot,
+            total_boost: 0,
+            total_boost_snapshot: 0,
+            last_snapshot_offset: previous.last_snapshot_offset,
+            ix_gate: previous.ix_gate,
+            freeze_authority: previous.freeze_authority,
+            admin_ix_gate: previous.admin_ix_gate,
+            fee_basis_points: previous.fee_basis_points,
+            last_fee_distribution_time: previous.last_fee_distribution_time,
+            exits_frozen_since: 0,
+            exits_unfrozen_since: 0,
+            emission_cap: 0,
+            staker_rewards_minted: 0,
+            pool_rewards_minted: 0,
+            royalties_minted: 0,
+            admin_minted: 0,
+            bonds_minted: 0,
+            fees_burned: 0,
+            recipients: previous.recipients,
+        })
+    }
+
+    // The emission counters start with the migration, a non migrated central state cannot mint
+    fn to_previous(&self) -> Result<CentralStateV2V0, ProgramError> {
+        if self.emission_cap != 0 || self.total_emitted()? != 0 || self.fees_burned != 0 {
+            msg!("The central state must be migrated to hold the emission counters");
+            return Err(AccessError::AccountMigrationRequired.into());
+        }
+        if self.total_boost != 0 || self.total_boost_snapshot != 0 {
+            msg!("The central state must be migrated to hold the boosts of the bonds");
+            return Err(AccessError::AccountMigrationRequired.into());
+        }
+        if self.exits_frozen_since != 0 || self.exits_unfrozen_since != 0 {
+            msg!("The central state must be migrated to hold the freeze of the exits");
+            return Err(AccessError::AccountMigrationRequired.into());
+        }
+        Ok(CentralStateV2V0 {
+            tag: self.tag,
+            bump_seed: self.bump_seed,
+            daily_inflation: self.daily_inflation,
+            token_mint: self.token_mint,
+            authority: self.authority,
+            creation_time: self.creation_time,
+            total_staked: self.total_staked,
+            total_staked_snapshot: self.total_staked_snapshot,
+            last_snapshot_offset: self.last_snapshot_offset,
+            ix_gate: self.ix_gate,
+            freeze_authority: self.freeze_authority,
+            admin_ix_gate: self.admin_ix_gate,
+            fee_basis_points: self.fee_basis_points,
+            last_fee_distribution_time: self.last_fee_distribution_time,
+            recipients: self.recipients.clone(),
+        })
+    }
+
+    fn reserved_len(&self) -> usize {
+        size_of::<FeeRecipient>() * MAX_FEE_RECIPIENTS.saturating_sub(self.recipients.len())
+    }
+}
+
+/// Layout of the central states migrated to V2 before the emission counters
+#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
+#[allow(missing_docs)]
+pub struct CentralStateV2V0 {
+    pub tag: Tag,
+    pub bump_seed: u8,
+    pub daily_inflation: u64,
+    pub token_mint: Pubkey,
+    pub authority: Pubkey,
+    pub creation_time: i64,
+    pub total_staked: u64,
+    pub total_staked_snapshot: u64,
+    pub last_snapshot_offset: u64,
+    pub ix_gate: u128,
+    pub freeze_authority: Pubkey,
+    pub admin_ix_gate: u128,
+    pub fee_basis_points: u16,
+    pub last_fee_distribution_time: i64,
+    pub recipients: Vec<FeeRecipient>,
+}
+
+impl Versioned for CentralStateV2V0 {
+    const VERSION: u8 = 0;
+
+    fn load(mut data: &[u8]) -> Result<Self, ProgramError> {
+        Ok(Self::deserialize(&mut data)?)
+    }
+
+    fn store(&self, mut dst: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut dst)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+}
+
+/// The mints counted by the emission counters of the central state
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Emission {
+    /// Rewards of the stake accounts, the bonds and the receipt reserves
+    StakerRewards,
+    /// Rewards of the pool owners
+    PoolRewards,
+    /// Royalties split from the rewards
+    Royalties,
+    /// Mints of the authority
+    AdminMint,
+    /// Tokens sold by the V1 bonds
+    BondSale,
+}
+
 impl CentralStateV2 {
     #[allow(missing_docs)]
     pub fn from_central_state(central_state: CentralState) -> Result<Self, ProgramError> {
@@ -550,12 +1149,23 @@ impl CentralStateV2 {
             creation_time: central_state.creation_time,
             total_staked: central_state.total_staked,
             total_staked_snapshot: central_state.total_staked_snapshot,
+            total_boost: 0,
+            total_boost_snapshot: 0,
             last_snapshot_offset: central_state.last_snapshot_offset,
             freeze_authority: central_state.authority,
             ix_gate: u128::MAX,       // all instructions enabled
             admin_ix_gate: u128::MAX, // all instructions enabled
             fee_basis_points: DEFAULT_FEE_BASIS_POINTS,
             last_fee_distribution_time: Clock::get()?.unix_timestamp,
+            exits_frozen_since: 0,
+            exits_unfrozen_since: 0,
+            emission_cap: 0,
+            staker_rewards_minted: 0,
+            pool_rewards_minted: 0,
+            royalties_minted: 0,
+            admin_minted: 0,
+            bonds_minted: 0,
+            fees_burned: 0,
             recipients: vec![], // the default behaviour is that 100% of the fees is getting burned
         })
     }
@@ -569,19 +1179,40 @@ impl CentralStateV2 {
     pub fn find_key(program_id: &Pubkey) -> (Pubkey, u8) {
         Pubkey::find_program_address(&[&program_id.to_bytes()], program_id)
     }
+    /// Find the key of the tenant central state of a token mint
+    pub fn find_tenant_key(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[&program_id.to_bytes(), &mint.to_bytes()], program_id)
+    }
+    /// The seed scoping the pools and royalty accounts to this central state.
+    /// The original central state derived from the program id only has no scope
+    pub fn scope(&self, key: &Pubkey, program_id: &Pubkey) -> Option<Pubkey> {
+        let original_key =
+            Pubkey::create_program_address(&[&program_id.to_bytes(), &[self.bump_seed]], program_id);
+        match original_key {
+            Ok(original_key) if &original_key == key => None,
+            _ => Some(*key),
+        }
+    }
+    /// Seeds used by the central state to sign
+    pub fn signer_seeds<'a>(&'a self, key: &Pubkey, program_id: &'a Pubkey) -> Vec<&'a [u8]> {
+        let mut seeds: Vec<&[u8]> = vec![program_id.as_ref()];
+        if self.scope(key, program_id).is_some() {
+            seeds.push(self.token_mint.as_ref());
+        }
+        seeds.push(std::slice::from_ref(&self.bump_seed));
+        seeds
+    }
     #[allow(missing_docs)]
-    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
-        self.serialize(&mut dst)
-            .map_err(|_| ProgramError::InvalidAccountData)
+    pub fn save(&self, dst: &mut [u8]) -> ProgramResult {
+        self.store(dst)
     }
     #[allow(missing_docs)]
     pub fn from_account_info(a: &AccountInfo) -> Result<CentralStateV2, ProgramError> {
-        let mut data = &a.data.borrow() as &[u8];
-        if data[0] != Tag::CentralStateV2 as u8 && data[0] != Tag::Uninitialized as u8 {
-            return Err(AccessError::DataTypeMismatch.into());
+        let data = &a.data.borrow() as &[u8];
+        match read_header(data)? {
+            (Tag::CentralStateV2, _) | (Tag::Uninitialized, _) => Self::load(data),
+            _ => Err(AccessError::DataTypeMismatch.into()),
         }
-        let result = CentralStateV2::deserialize(&mut data)?;
-        Ok(result)
     }
     #[allow(missing_docs)]
     pub fn get_current_offset(&self) -> Result<u64, ProgramError> {
@@ -601,10 +1232,123 @@ impl CentralStateV2 {
         }
         Ok(())
     }
-    /// Calculate the protocol fee for a given amount
-    pub fn calculate_fee(&self, amount: u64) -> Result<u64, ProgramError> {
+    /// The tokens minted by the protocol since the central state holds the emission counters
+    pub fn total_emitted(&self) -> Result<u64, ProgramError> {
+        let total = self
+            .staker_rewards_minted
+            .checked_add(self.pool_rewards_minted)
+            .and_then(|t| t.checked_add(self.royalties_minted))
+            .and_then(|t| t.checked_add(self.admin_minted))
+            .and_then(|t| t.checked_add(self.bonds_minted))
+            .ok_or(AccessError::Overflow)?;
+        Ok(total)
+    }
+    /// Fails if minting `amount` tokens on top of the `supply` of the mint exceeds the emission cap.
+    /// The cap bounds the supply, the counters only start with the migration of the central state
+    pub fn check_emission_cap(&self, supply: u64, amount: u64) -> ProgramResult {
+        let new_supply = supply.checked_add(amount).ok_or(AccessError::Overflow)?;
+        if self.emission_cap != 0 && new_supply > self.emission_cap {
+            msg!(
+                "Minting {} tokens exceeds the emission cap {}, the supply is {}",
+                amount,
+                self.emission_cap,
+                supply
+            );
+            return Err(AccessError::EmissionCapExceeded.into());
+        }
+        Ok(())
+    }
+    /// Counts a mint of `amount` tokens
+    pub fn record_emission(&mut self, emission: Emission, amount: u64) -> ProgramResult {
+        let counter = match emission {
+            Emission::StakerRewards => &mut self.staker_rewards_minted,
+            Emission::PoolRewards => &mut self.pool_rewards_minted,
+            Emission::Royalties => &mut self.royalties_minted,
+            Emission::AdminMint => &mut self.admin_minted,
+            Emission::BondSale => &mut self.bonds_minted,
+        };
+        *counter = counter.checked_add(amount).ok_or(AccessError::Overflow)?;
+        Ok(())
+    }
+    /// Counts a burn of `amount` tokens of protocol fees
+    pub fn record_burned_fees(&mut self, amount: u64) -> ProgramResult {
+        self.fees_burned = self
+            .fees_burned
+            .checked_add(amount)
+            .ok_or(AccessError::Overflow)?;
+        Ok(())
+    }
+    /// Applies the change of the boost of a bond V2
+    pub fn update_boost(&mut self, previous_boost: u64, boost: u64) -> ProgramResult {
+        self.total_boost = self
+            .total_boost
+            .checked_sub(previous_boost)
+            .and_then(|total| total.checked_add(boost))
+            .ok_or(AccessError::Overflow)?;
+        Ok(())
+    }
+    /// The staked tokens and the boosts sharing the inflation at the last snapshot
+    pub fn total_weight_snapshot(&self) -> Result<u64, ProgramError> {
+        Ok(self
+            .total_staked_snapshot
+            .checked_add(self.total_boost_snapshot)
+            .ok_or(AccessError::Overflow)?)
+    }
+    /// Returns `true` if the unstakes or the unlocks of the bonds V2 are frozen by the ix gate
+    pub fn exits_frozen(&self) -> bool {
+        self.ix_gate & (1 << Unstake as u32) == 0 || self.ix_gate & (1 << UnlockBondV2 as u32) == 0
+    }
+    /// Records the freeze of the exits after a change of the ix gate. A new freeze keeps the
+    /// previous start unless the exits stayed unfrozen for [`MIN_UNFREEZE_DURATION`], so that
+    /// short unfreezes cannot postpone the emergency withdrawal
+    pub fn record_exits_freeze(&mut self, now: i64) {
+        if self.exits_frozen() {
+            if self.exits_frozen_since == 0
+                || (self.exits_unfrozen_since != 0
+                    && now.saturating_sub(self.exits_unfrozen_since) >= MIN_UNFREEZE_DURATION)
+            {
+                self.exits_frozen_since = now;
+            }
+            self.exits_unfrozen_since = 0;
+        } else if self.exits_frozen_since != 0 && self.exits_unfrozen_since == 0 {
+            self.exits_unfrozen_since = now;
+        }
+    }
+    /// Returns `true` if the unstakes or the unlocks have been frozen for more than
+    /// [`MAX_FREEZE_DURATION`]
+    pub fn emergency_exit_open(&self, now: i64) -> bool {
+        self.exits_frozen()
+            && self.exits_frozen_since != 0
+            && now.saturating_sub(self.exits_frozen_since) >= MAX_FREEZE_DURATION
+    }
+    /// Derive the token accounts of the fee recipients in the order expected by `distribute_fees`
+    pub fn get_fee_recipient_atas(&self, token_program_id: &Pubkey) -> Vec<Pubkey> {
+        self.recipients
+            .iter()
+            .map(|r| {
+                get_associated_token_address_with_program_id(
+                    &r.owner,
+                    &self.token_mint,
+                    token_program_id,
+                )
+            })
+            .collect()
+    }
+    /// Calculate the protocol fee for a given amount.
+    /// A pool fee override can only lower the protocol fee, never raise it
+    pub fn calculate_fee(
+        &self,
+        amount: u64,
+        pool_fee_override: Option<&PoolFeeOverride>,
+    ) -> Result<u64, ProgramError> {
+        let fee_basis_points = match pool_fee_override {
+            Some(fee_override) => {
+                std::cmp::min(fee_override.fee_basis_points, self.fee_basis_points)
+            }
+            None => self.fee_basis_points,
+        };
         let fee = amount
-            .checked_mul(self.fee_basis_points as u64)
+            .checked_mul(fee_basis_points as u64)
             .ok_or(AccessError::Overflow)?
             .checked_add(9_999) // rounding
             .ok_or(AccessError::Overflow)?
@@ -780,7 +1524,6 @@ impl BondAccount {
     }
 }
 
-#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
 #[allow(missing_docs)]
 pub struct BondV2Account {
     /// Tag
@@ -803,29 +1546,258 @@ pub struct BondV2Account {
 
     /// Unlock start date
     pub unlock_timestamp: Option<i64>,
-}
 
-#[allow(missing_docs)]
-impl BondV2Account {
-    pub const SEED: &'static [u8; 15] = b"bond_v2_account";
+    /// Stakers reward index of the pool at the last claim
+    pub last_claimed_index: u128,
 
-    pub fn create_key(
-        owner: &Pubkey,
-        pool: &Pubkey,
-        unlock_timestamp: Option<i64>,
-        program_id: &Pubkey,
-    ) -> (Pubkey, u8) {
-        let seeds: &[&[u8]] = &[
-            BondV2Account::SEED,
-            &owner.to_bytes(),
-            &pool.to_bytes(),
-            &unlock_timestamp.unwrap_or(0).to_le_bytes(),
-        ];
-        Pubkey::find_program_address(seeds, program_id)
+    /// Weight added to the amount by the lock of the bond at its last boost refresh
+    pub boost: u64,
+
+    /// Rewards settled by a boost refresh and paid by the next claim
+    pub unclaimed_rewards: u64,
+}
+
+impl BorshSerialize for BondV2Account {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        write_header(self.tag, Self::VERSION, writer)?;
+        self.owner.serialize(writer)?;
+        self.amount.serialize(writer)?;
+        self.pool.serialize(writer)?;
+        self.last_claimed_offset.serialize(writer)?;
+        self.pool_minimum_at_creation.serialize(writer)?;
+        self.unlock_timestamp.serialize(writer)?;
+        self.last_claimed_index.serialize(writer)?;
+        self.boost.serialize(writer)?;
+        self.unclaimed_rewards.serialize(writer)
     }
+}
 
-    #[allow(clippy::too_many_arguments)]
-    pub fn new(
+impl BorshDeserialize for BondV2Account {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        Ok(Self {
+            tag: read_versioned_tag(reader, Self::VERSION)?,
+            owner: BorshDeserialize::deserialize_reader(reader)?,
+            amount: BorshDeserialize::deserialize_reader(reader)?,
+            pool: BorshDeserialize::deserialize_reader(reader)?,
+            last_claimed_offset: BorshDeserialize::deserialize_reader(reader)?,
+            pool_minimum_at_creation: BorshDeserialize::deserialize_reader(reader)?,
+            unlock_timestamp: BorshDeserialize::deserialize_reader(reader)?,
+            last_claimed_index: BorshDeserialize::deserialize_reader(reader)?,
+            boost: BorshDeserialize::deserialize_reader(reader)?,
+            unclaimed_rewards: BorshDeserialize::deserialize_reader(reader)?,
+        })
+    }
+}
+
+impl BorshSize for BondV2Account {
+    fn borsh_len(&self) -> usize {
+        VERSIONED_HEADER_LEN
+            + self.owner.borsh_len()
+            + self.amount.borsh_len()
+            + self.pool.borsh_len()
+            + self.last_claimed_offset.borsh_len()
+            + self.pool_minimum_at_creation.borsh_len()
+            + self.unlock_timestamp.borsh_len()
+            + self.last_claimed_index.borsh_len()
+            + self.boost.borsh_len()
+            + self.unclaimed_rewards.borsh_len()
+    }
+}
+
+impl Versioned for BondV2Account {
+    const VERSION: u8 = 2;
+
+    fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        load_migrated(data)
+    }
+
+    fn store(&self, dst: &mut [u8]) -> ProgramResult {
+        store_migrated(self, dst)
+    }
+}
+
+impl Migrate for BondV2Account {
+    type Previous = BondV2AccountV1;
+
+    fn from_previous(previous: BondV2AccountV1) -> Result<Self, ProgramError> {
+        Ok(Self {
+            tag: previous.tag,
+            owner: previous.owner,
+            amount: previous.amount,
+            pool: previous.pool,
+            last_claimed_offset: previous.last_claimed_offset,
+            pool_minimum_at_creation: previous.pool_minimum_at_creation,
+            unlock_timestamp: previous.unlock_timestamp,
+            last_claimed_index: previous.last_claimed_index,
+            boost: 0,
+            unclaimed_rewards: 0,
+        })
+    }
+
+    fn to_previous(&self) -> Result<BondV2AccountV1, ProgramError> {
+        if self.boost != 0 || self.unclaimed_rewards != 0 {
+            msg!("The bond account must be migrated to hold a boost");
+            return Err(AccessError::AccountMigrationRequired.into());
+        }
+        Ok(BondV2AccountV1 {
+            tag: self.tag,
+            owner: self.owner,
+            amount: self.amount,
+            pool: self.pool,
+            last_claimed_offset: self.last_claimed_offset,
+            pool_minimum_at_creation: self.pool_minimum_at_creation,
+            unlock_timestamp: self.unlock_timestamp,
+            last_claimed_index: self.last_claimed_index,
+        })
+    }
+}
+
+/// Layout of the bond V2 accounts created before the lock boosts
+#[allow(missing_docs)]
+pub struct BondV2AccountV1 {
+    pub tag: Tag,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub pool: Pubkey,
+    pub last_claimed_offset: u64,
+    pub pool_minimum_at_creation: u64,
+    pub unlock_timestamp: Option<i64>,
+    pub last_claimed_index: u128,
+}
+
+impl BorshSerialize for BondV2AccountV1 {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        write_header(self.tag, Self::VERSION, writer)?;
+        self.owner.serialize(writer)?;
+        self.amount.serialize(writer)?;
+        self.pool.serialize(writer)?;
+        self.last_claimed_offset.serialize(writer)?;
+        self.pool_minimum_at_creation.serialize(writer)?;
+        self.unlock_timestamp.serialize(writer)?;
+        self.last_claimed_index.serialize(writer)
+    }
+}
+
+impl BorshDeserialize for BondV2AccountV1 {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        Ok(Self {
+            tag: read_versioned_tag(reader, Self::VERSION)?,
+            owner: BorshDeserialize::deserialize_reader(reader)?,
+            amount: BorshDeserialize::deserialize_reader(reader)?,
+            pool: BorshDeserialize::deserialize_reader(reader)?,
+            last_claimed_offset: BorshDeserialize::deserialize_reader(reader)?,
+            pool_minimum_at_creation: BorshDeserialize::deserialize_reader(reader)?,
+            unlock_timestamp: BorshDeserialize::deserialize_reader(reader)?,
+            last_claimed_index: BorshDeserialize::deserialize_reader(reader)?,
+        })
+    }
+}
+
+impl Versioned for BondV2AccountV1 {
+    const VERSION: u8 = 1;
+
+    fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        load_migrated(data)
+    }
+
+    fn store(&self, dst: &mut [u8]) -> ProgramResult {
+        store_migrated(self, dst)
+    }
+}
+
+impl Migrate for BondV2AccountV1 {
+    type Previous = BondV2AccountV0;
+
+    fn from_previous(previous: BondV2AccountV0) -> Result<Self, ProgramError> {
+        Ok(Self {
+            tag: previous.tag,
+            owner: previous.owner,
+            amount: previous.amount,
+            pool: previous.pool,
+            last_claimed_offset: previous.last_claimed_offset,
+            pool_minimum_at_creation: previous.pool_minimum_at_creation,
+            unlock_timestamp: previous.unlock_timestamp,
+            last_claimed_index: previous.last_claimed_index,
+        })
+    }
+
+    fn to_previous(&self) -> Result<BondV2AccountV0, ProgramError> {
+        Ok(BondV2AccountV0 {
+            tag: self.tag,
+            owner: self.owner,
+            amount: self.amount,
+            pool: self.pool,
+            last_claimed_offset: self.last_claimed_offset,
+            pool_minimum_at_creation: self.pool_minimum_at_creation,
+            unlock_timestamp: self.unlock_timestamp,
+            last_claimed_index: self.last_claimed_index,
+        })
+    }
+}
+
+/// Layout of the bond V2 accounts created before the version byte, the accounts created before
+/// the reward index have no room for their reward checkpoint
+#[derive(BorshSerialize, BorshSize)]
+#[allow(missing_docs)]
+pub struct BondV2AccountV0 {
+    pub tag: Tag,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub pool: Pubkey,
+    pub last_claimed_offset: u64,
+    pub pool_minimum_at_creation: u64,
+    pub unlock_timestamp: Option<i64>,
+    pub last_claimed_index: u128,
+}
+
+impl BorshDeserialize for BondV2AccountV0 {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        Ok(Self {
+            tag: BorshDeserialize::deserialize_reader(reader)?,
+            owner: BorshDeserialize::deserialize_reader(reader)?,
+            amount: BorshDeserialize::deserialize_reader(reader)?,
+            pool: BorshDeserialize::deserialize_reader(reader)?,
+            last_claimed_offset: BorshDeserialize::deserialize_reader(reader)?,
+            pool_minimum_at_creation: BorshDeserialize::deserialize_reader(reader)?,
+            unlock_timestamp: BorshDeserialize::deserialize_reader(reader)?,
+            last_claimed_index: read_reward_checkpoint(reader)?,
+        })
+    }
+}
+
+impl Versioned for BondV2AccountV0 {
+    const VERSION: u8 = 0;
+
+    fn load(mut data: &[u8]) -> Result<Self, ProgramError> {
+        Ok(Self::deserialize(&mut data)?)
+    }
+
+    fn store(&self, dst: &mut [u8]) -> ProgramResult {
+        save_with_reward_checkpoint(self, self.last_claimed_index, dst)
+    }
+}
+
+#[allow(missing_docs)]
+impl BondV2Account {
+    pub const SEED: &'static [u8; 15] = b"bond_v2_account";
+
+    pub fn create_key(
+        owner: &Pubkey,
+        pool: &Pubkey,
+        unlock_timestamp: Option<i64>,
+        program_id: &Pubkey,
+    ) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[
+            BondV2Account::SEED,
+            &owner.to_bytes(),
+            &pool.to_bytes(),
+            &unlock_timestamp.unwrap_or(0).to_le_bytes(),
+        ];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
         owner: Pubkey,
         pool: Pubkey,
         pool_minimum_at_creation: u64,
@@ -839,22 +1811,31 @@ impl BondV2Account {
             last_claimed_offset: 0,
             pool_minimum_at_creation,
             unlock_timestamp,
+            last_claimed_index: 0,
+            boost: 0,
+            unclaimed_rewards: 0,
         }
     }
 
-    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
-        self.serialize(&mut dst)
-            .map_err(|_| ProgramError::InvalidAccountData)
+    pub fn save(&self, dst: &mut [u8]) -> ProgramResult {
+        self.store(dst)
+    }
+
+    /// The amount of the bond and its boost, the weight of its share of the stakers rewards
+    pub fn weight(&self) -> Result<u64, ProgramError> {
+        Ok(self
+            .amount
+            .checked_add(self.boost)
+            .ok_or(AccessError::Overflow)?)
     }
 
     pub fn from_account_info(a: &AccountInfo) -> Result<BondV2Account, ProgramError> {
-        let mut data = &a.data.borrow() as &[u8];
-        let tag = Tag::BondV2Account;
-        if data[0] != tag as u8 && data[0] != Tag::Uninitialized as u8 {
+        let data = &a.data.borrow() as &[u8];
+        let (tag, _) = read_header(data)?;
+        if tag != Tag::BondV2Account && tag != Tag::Uninitialized {
             return Err(AccessError::DataTypeMismatch.into());
         }
-        let result = BondV2Account::deserialize(&mut data)?;
-        Ok(result)
+        BondV2Account::load(data)
     }
 
     pub fn withdraw(&mut self, amount: u64) -> ProgramResult {
@@ -864,6 +1845,90 @@ impl BondV2Account {
             .ok_or(AccessError::Overflow)?;
         Ok(())
     }
+
+    pub fn close(&mut self) {
+        self.tag = Tag::Deleted
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+#[allow(missing_docs)]
+pub struct BondV2Listing {
+    /// Tag
+    pub tag: Tag,
+
+    /// The owner of the bond before the listing, receives the price
+    pub seller: Pubkey,
+
+    /// The escrowed bond V2 account, owned by the listing
+    pub escrow: Pubkey,
+
+    /// The pool of the bond
+    pub pool: Pubkey,
+
+    /// The unlock timestamp of the bond
+    pub unlock_timestamp: Option<i64>,
+
+    /// The mint in which the price is paid
+    pub quote_mint: Pubkey,
+
+    /// The token account of the seller receiving the price
+    pub quote_destination: Pubkey,
+
+    /// The price of the bond in quote tokens
+    pub price: u64,
+}
+
+#[allow(missing_docs)]
+impl BondV2Listing {
+    pub const SEED: &'static [u8; 15] = b"bond_v2_listing";
+
+    /// The listing of the bond V2 account of the seller
+    pub fn create_key(bond_v2_account: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[BondV2Listing::SEED, &bond_v2_account.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        seller: Pubkey,
+        escrow: Pubkey,
+        pool: Pubkey,
+        unlock_timestamp: Option<i64>,
+        quote_mint: Pubkey,
+        quote_destination: Pubkey,
+        price: u64,
+    ) -> Self {
+        Self {
+            tag: Tag::BondV2Listing,
+            seller,
+            escrow,
+            pool,
+            unlock_timestamp,
+            quote_mint,
+            quote_destination,
+            price,
+        }
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut dst)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<BondV2Listing, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        let tag = Tag::BondV2Listing;
+        if data[0] != tag as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(AccessError::DataTypeMismatch.into());
+        }
+        let result = BondV2Listing::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    pub fn close(&mut self) {
+        self.tag = Tag::Deleted
+    }
 }
 
 #[derive(BorshSerialize, BorshDeserialize, BorshSize)]
@@ -893,8 +1958,29 @@ impl RoyaltyAccount {
     pub const SEED: &'static [u8; 15] = b"royalty_account";
 
     pub fn create_key(payer: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
-        let seeds: &[&[u8]] = &[RoyaltyAccount::SEED, &payer.to_bytes()];
-        Pubkey::find_program_address(seeds, program_id)
+        Self::create_scoped_key(payer, None, program_id)
+    }
+
+    /// Derive the key of a royalty account belonging to the central state with the given scope
+    pub fn create_scoped_key(
+        payer: &Pubkey,
+        scope: Option<&Pubkey>,
+        program_id: &Pubkey,
+    ) -> (Pubkey, u8) {
+        let seeds = RoyaltyAccount::seeds(payer, scope, &[]);
+        Pubkey::find_program_address(&seeds, program_id)
+    }
+
+    /// Seeds of a royalty account, the scope is only set for accounts of a tenant central state
+    pub fn seeds<'a>(payer: &'a Pubkey, scope: Option<&'a Pubkey>, bump: &'a [u8]) -> Vec<&'a [u8]> {
+        let mut seeds: Vec<&[u8]> = vec![RoyaltyAccount::SEED, payer.as_ref()];
+        if let Some(scope) = scope {
+            seeds.push(scope.as_ref());
+        }
+        if !bump.is_empty() {
+            seeds.push(bump);
+        }
+        seeds
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -935,17 +2021,7 @@ impl RoyaltyAccount {
     }
 
     pub fn calculate_royalty_amount(&self, amount: u64) -> Result<u64, ProgramError> {
-        let royalty = amount
-            .checked_mul(self.royalty_basis_points as u64)
-            .ok_or(AccessError::Overflow)?
-            .checked_add(9_999) // rounding
-            .ok_or(AccessError::Overflow)?
-            .checked_div(10_000)
-            .ok_or(AccessError::Overflow)?;
-        if royalty > amount {
-            return Err(AccessError::Overflow.into());
-        }
-        Ok(royalty)
+        Ok(royalty_amount(amount, self.royalty_basis_points)?)
     }
 }
 
@@ -955,3 +2031,633 @@ pub struct FeeRecipient {
     pub owner: Pubkey,
     pub percentage: u64,
 }
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+#[allow(missing_docs)]
+pub struct PoolFeeOverride {
+    /// Tag
+    pub tag: Tag,
+
+    /// The stake pool the override applies to
+    pub stake_pool: Pubkey,
+
+    /// Protocol fee basis points (i.e 1% = 100) used for this pool instead of the central state one
+    pub fee_basis_points: u16,
+}
+
+#[allow(missing_docs)]
+impl PoolFeeOverride {
+    pub const SEED: &'static [u8; 17] = b"pool_fee_override";
+
+    pub fn create_key(stake_pool: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[PoolFeeOverride::SEED, &stake_pool.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn new(stake_pool: Pubkey, fee_basis_points: u16) -> Self {
+        Self {
+            tag: Tag::PoolFeeOverride,
+            stake_pool,
+            fee_basis_points,
+        }
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut dst)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<PoolFeeOverride, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        let tag = Tag::PoolFeeOverride;
+        if data[0] != tag as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(AccessError::DataTypeMismatch.into());
+        }
+        let result = PoolFeeOverride::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    pub fn close(&mut self) {
+        self.tag = Tag::Deleted
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+#[allow(missing_docs)]
+pub struct FeeDistributionConfig {
+    /// Tag
+    pub tag: Tag,
+
+    /// The central state the config belongs to
+    pub central_state: Pubkey,
+
+    /// Share of each fee distribution in basis points (i.e 1% = 100) paid to the caller
+    pub keeper_reward_basis_points: u16,
+}
+
+#[allow(missing_docs)]
+impl FeeDistributionConfig {
+    pub const SEED: &'static [u8; 23] = b"fee_distribution_config";
+
+    pub fn create_key(central_state: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[FeeDistributionConfig::SEED, &central_state.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn new(central_state: Pubkey, keeper_reward_basis_points: u16) -> Self {
+        Self {
+            tag: Tag::FeeDistributionConfig,
+            central_state,
+            keeper_reward_basis_points,
+        }
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut dst)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<FeeDistributionConfig, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        let tag = Tag::FeeDistributionConfig;
+        if data[0] != tag as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(AccessError::DataTypeMismatch.into());
+        }
+        let result = FeeDistributionConfig::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    pub fn calculate_keeper_reward(&self, amount: u64) -> Result<u64, ProgramError> {
+        let reward = amount
+            .checked_mul(self.keeper_reward_basis_points as u64)
+            .ok_or(AccessError::Overflow)?
+            .checked_div(10_000)
+            .ok_or(AccessError::Overflow)?;
+        Ok(reward)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+#[allow(missing_docs)]
+pub struct ProtocolConfig {
+    /// Tag
+    pub tag: Tag,
+
+    /// The central state the config belongs to
+    pub central_state: Pubkey,
+
+    /// The signer of the ACCESS NFT program allowed to claim rewards on behalf of the stakers
+    pub access_nft_program_signer: Pubkey,
+}
+
+#[allow(missing_docs)]
+impl ProtocolConfig {
+    pub const SEED: &'static [u8; 15] = b"protocol_config";
+
+    pub fn create_key(central_state: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[ProtocolConfig::SEED, &central_state.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn new(central_state: Pubkey, access_nft_program_signer: Pubkey) -> Self {
+        Self {
+            tag: Tag::ProtocolConfig,
+            central_state,
+            access_nft_program_signer,
+        }
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut dst)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<ProtocolConfig, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        let tag = Tag::ProtocolConfig;
+        if data[0] != tag as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(AccessError::DataTypeMismatch.into());
+        }
+        let result = ProtocolConfig::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    /// Loads the protocol config of the central state, checking its derivation and ownership
+    pub fn get_checked(
+        a: &AccountInfo,
+        central_state: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<ProtocolConfig, ProgramError> {
+        let (derived_key, _) = ProtocolConfig::create_key(central_state, program_id);
+        if a.key != &derived_key {
+            msg!("Invalid protocol config account");
+            return Err(AccessError::AccountNotDeterministic.into());
+        }
+        if a.owner != program_id {
+            msg!("The protocol config account must be owned by the program");
+            return Err(AccessError::WrongOwner.into());
+        }
+        ProtocolConfig::from_account_info(a)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+#[allow(missing_docs)]
+pub struct BoostConfig {
+    /// Tag
+    pub tag: Tag,
+
+    /// The central state the config belongs to
+    pub central_state: Pubkey,
+
+    /// The remaining lock duration in seconds earning the maximum boost
+    pub max_lock_duration: i64,
+
+    /// Boost in basis points of the amount of a bond locked for at least `max_lock_duration`
+    pub max_lock_boost_basis_points: u16,
+
+    /// Boost in basis points of the amount of a forever bond
+    pub forever_boost_basis_points: u16,
+}
+
+#[allow(missing_docs)]
+impl BoostConfig {
+    pub const SEED: &'static [u8; 12] = b"boost_config";
+
+    pub fn create_key(central_state: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[BoostConfig::SEED, &central_state.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn new(
+        central_state: Pubkey,
+        max_lock_duration: i64,
+        max_lock_boost_basis_points: u16,
+        forever_boost_basis_points: u16,
+    ) -> Self {
+        Self {
+            tag: Tag::BoostConfig,
+            central_state,
+            max_lock_duration,
+            max_lock_boost_basis_points,
+            forever_boost_basis_points,
+        }
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut dst)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<BoostConfig, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        let tag = Tag::BoostConfig;
+        if data[0] != tag as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(AccessError::DataTypeMismatch.into());
+        }
+        let result = BoostConfig::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    /// Loads the boost config of the central state, checking its derivation and ownership
+    pub fn get_checked(
+        a: &AccountInfo,
+        central_state: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<BoostConfig, ProgramError> {
+        let (derived_key, _) = BoostConfig::create_key(central_state, program_id);
+        if a.key != &derived_key {
+            msg!("Invalid boost config account");
+            return Err(AccessError::AccountNotDeterministic.into());
+        }
+        if a.owner != program_id {
+            msg!("The boost config account must be owned by the program");
+            return Err(AccessError::WrongOwner.into());
+        }
+        BoostConfig::from_account_info(a)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+#[allow(missing_docs)]
+pub struct GovernanceConfig {
+    /// Tag
+    pub tag: Tag,
+
+    /// The central state the config belongs to
+    pub central_state: Pubkey,
+
+    /// The SPL Governance realm using the program as voter weight addin
+    pub realm: Pubkey,
+
+    /// The governing token mint of the realm the voter weights are computed for
+    pub governing_token_mint: Pubkey,
+
+    /// Adds the boost of the bonds V2 to their amount
+    pub lock_weighting: bool,
+}
+
+#[allow(missing_docs)]
+impl GovernanceConfig {
+    pub const SEED: &'static [u8; 17] = b"governance_config";
+
+    pub fn create_key(central_state: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[GovernanceConfig::SEED, &central_state.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn new(
+        central_state: Pubkey,
+        realm: Pubkey,
+        governing_token_mint: Pubkey,
+        lock_weighting: bool,
+    ) -> Self {
+        Self {
+            tag: Tag::GovernanceConfig,
+            central_state,
+            realm,
+            governing_token_mint,
+            lock_weighting,
+        }
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut dst)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<GovernanceConfig, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        let tag = Tag::GovernanceConfig;
+        if data[0] != tag as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(AccessError::DataTypeMismatch.into());
+        }
+        let result = GovernanceConfig::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    /// Loads the governance config of the central state, checking its derivation and ownership
+    pub fn get_checked(
+        a: &AccountInfo,
+        central_state: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<GovernanceConfig, ProgramError> {
+        let (derived_key, _) = GovernanceConfig::create_key(central_state, program_id);
+        if a.key != &derived_key {
+            msg!("Invalid governance config account");
+            return Err(AccessError::AccountNotDeterministic.into());
+        }
+        if a.owner != program_id {
+            msg!("The governance config account must be owned by the program");
+            return Err(AccessError::WrongOwner.into());
+        }
+        GovernanceConfig::from_account_info(a)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+#[allow(missing_docs)]
+pub struct PoolBoost {
+    /// Tag
+    pub tag: Tag,
+
+    /// The stake pool of the boosted bonds
+    pub stake_pool: Pubkey,
+
+    /// Sum of the boosts of the bonds of the pool
+    pub total_boost: u64,
+}
+
+#[allow(missing_docs)]
+impl PoolBoost {
+    pub const SEED: &'static [u8; 10] = b"pool_boost";
+
+    pub fn create_key(stake_pool: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[PoolBoost::SEED, &stake_pool.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn new(stake_pool: Pubkey) -> Self {
+        Self {
+            tag: Tag::PoolBoost,
+            stake_pool,
+            total_boost: 0,
+        }
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut dst)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<PoolBoost, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        let tag = Tag::PoolBoost;
+        if data[0] != tag as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(AccessError::DataTypeMismatch.into());
+        }
+        let result = PoolBoost::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    /// Loads the pool boost of a stake pool, checking its derivation and ownership
+    pub fn get_checked(
+        a: &AccountInfo,
+        stake_pool: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<PoolBoost, ProgramError> {
+        let (derived_key, _) = PoolBoost::create_key(stake_pool, program_id);
+        if a.key != &derived_key {
+            msg!("Invalid pool boost account");
+            return Err(AccessError::AccountNotDeterministic.into());
+        }
+        if a.owner != program_id {
+            msg!("The pool boost account must be owned by the program");
+            return Err(AccessError::WrongOwner.into());
+        }
+        PoolBoost::from_account_info(a)
+    }
+
+    /// Applies the change of the boost of a bond of the pool
+    pub fn update(&mut self, previous_boost: u64, boost: u64) -> ProgramResult {
+        self.total_boost = self
+            .total_boost
+            .checked_sub(previous_boost)
+            .and_then(|total| total.checked_add(boost))
+            .ok_or(AccessError::Overflow)?;
+        Ok(())
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+#[allow(missing_docs)]
+pub struct PoolControls {
+    /// Tag
+    pub tag: Tag,
+
+    /// The stake pool the controls belong to
+    pub stake_pool: Pubkey,
+
+    /// The maximum total staked of the pool, 0 for no cap
+    pub max_total_staked: u64,
+
+    /// Rejects the new stakes, the stakers can still unstake and claim
+    pub paused: bool,
+
+    /// Only accepts the new stakes of the stakers with a pool allowlist entry
+    pub allowlist_enabled: bool,
+}
+
+#[allow(missing_docs)]
+impl PoolControls {
+    pub const SEED: &'static [u8; 13] = b"pool_controls";
+
+    pub fn create_key(stake_pool: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[PoolControls::SEED, &stake_pool.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn new(
+        stake_pool: Pubkey,
+        max_total_staked: u64,
+        paused: bool,
+        allowlist_enabled: bool,
+    ) -> Self {
+        Self {
+            tag: Tag::PoolControls,
+            stake_pool,
+            max_total_staked,
+            paused,
+            allowlist_enabled,
+        }
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut dst)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<PoolControls, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        let tag = Tag::PoolControls;
+        if data[0] != tag as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(AccessError::DataTypeMismatch.into());
+        }
+        let result = PoolControls::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    /// Loads the pool controls of a stake pool, checking its derivation and ownership
+    pub fn get_checked(
+        a: &AccountInfo,
+        stake_pool: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<PoolControls, ProgramError> {
+        let (derived_key, _) = PoolControls::create_key(stake_pool, program_id);
+        if a.key != &derived_key {
+            msg!("Invalid pool controls account");
+            return Err(AccessError::AccountNotDeterministic.into());
+        }
+        if a.owner != program_id {
+            msg!("The pool controls account must be owned by the program");
+            return Err(AccessError::WrongOwner.into());
+        }
+        PoolControls::from_account_info(a)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+#[allow(missing_docs)]
+pub struct PoolAllowlistEntry {
+    /// Tag
+    pub tag: Tag,
+
+    /// The stake pool of the allowlist
+    pub stake_pool: Pubkey,
+
+    /// The allowlisted staker, the owner of the stake account or of the bond V2
+    pub staker: Pubkey,
+}
+
+#[allow(missing_docs)]
+impl PoolAllowlistEntry {
+    pub const SEED: &'static [u8; 14] = b"pool_allowlist";
+
+    pub fn create_key(stake_pool: &Pubkey, staker: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[
+            PoolAllowlistEntry::SEED,
+            &stake_pool.to_bytes(),
+            &staker.to_bytes(),
+        ];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn new(stake_pool: Pubkey, staker: Pubkey) -> Self {
+        Self {
+            tag: Tag::PoolAllowlistEntry,
+            stake_pool,
+            staker,
+        }
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut dst)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<PoolAllowlistEntry, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        let tag = Tag::PoolAllowlistEntry;
+        if data[0] != tag as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(AccessError::DataTypeMismatch.into());
+        }
+        let result = PoolAllowlistEntry::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    pub fn close(&mut self) {
+        self.tag = Tag::Deleted
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Clone, Copy, Debug, PartialEq)]
+#[allow(missing_docs)]
+pub struct AdminRole {
+    /// The admin instruction of the role
+    pub ix: u8,
+    /// The authority allowed to call the instruction
+    pub authority: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+#[allow(missing_docs)]
+pub struct RoleTable {
+    /// Tag
+    pub tag: Tag,
+
+    /// The central state the roles belong to, its authority is the role table once the roles are enabled
+    pub central_state: Pubkey,
+
+    /// The authority of the admin instructions without a role
+    pub admin: Pubkey,
+
+    /// The delegated admin instructions and their authority
+    pub roles: Vec<AdminRole>,
+}
+
+#[allow(missing_docs)]
+impl RoleTable {
+    pub const SEED: &'static [u8; 10] = b"role_table";
+
+    /// The allocated length, the roles of all the admin instructions fit in it
+    pub const LEN: usize = 1 + 32 + 32 + 4 + MAX_ADMIN_ROLES * size_of::<AdminRole>();
+
+    pub fn create_key(central_state: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[RoleTable::SEED, &central_state.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn new(central_state: Pubkey, admin: Pubkey) -> Self {
+        Self {
+            tag: Tag::RoleTable,
+            central_state,
+            admin,
+            roles: vec![],
+        }
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) -> ProgramResult {
+        self.serialize(&mut dst)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<RoleTable, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        let tag = Tag::RoleTable;
+        if data[0] != tag as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(AccessError::DataTypeMismatch.into());
+        }
+        let result = RoleTable::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    /// Loads the role table of the central state, checking its derivation and ownership
+    pub fn get_checked(
+        a: &AccountInfo,
+        central_state: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<RoleTable, ProgramError> {
+        let (derived_key, _) = RoleTable::create_key(central_state, program_id);
+        if a.key != &derived_key {
+            msg!("Invalid role table account");
+            return Err(AccessError::AccountNotDeterministic.into());
+        }
+        if a.owner != program_id {
+            msg!("The role table account must be owned by the program");
+            return Err(AccessError::WrongOwner.into());
+        }
+        RoleTable::from_account_info(a)
+    }
+
+    /// The authority allowed to call an admin instruction
+    pub fn authority(&self, ix: &ProgramInstruction) -> Pubkey {
+        self.roles
+            .iter()
+            .find(|r| r.ix == *ix as u8)
+            .map(|r| r.authority)
+            .unwrap_or(self.admin)
+    }
+
+    /// Delegates an admin instruction to `authority`, the role is removed when it is the admin
+    pub fn set_role(&mut self, ix: &ProgramInstruction, authority: Pubkey) -> ProgramResult {
+        let ix = *ix as u8;
+        self.roles.retain(|r| r.ix != ix);
+        if authority != self.admin {
+            if self.roles.len() >= MAX_ADMIN_ROLES {
+                return Err(AccessError::Overflow.into());
+            }
+            self.roles.push(AdminRole { ix, authority });
+        }
+        Ok(())
+    }
+}
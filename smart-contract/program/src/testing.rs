@@ -0,0 +1,2078 @@
+//! In-process test harness
+//!
+//! [`ProtocolTest`] executes the instructions of the program in the test process against an
+//! in-memory account store, without a validator, a `BanksClient` or any prebuilt program. The clock
+//! and the rent are set by the test, and the invocations of the system program and of the token
+//! programs are executed in-process. Integrators can unit test their CPI integrations with the
+//! protocol in milliseconds; the `solana-program-test` suite of this repository stays the reference.
+//!
+//! Signatures are not verified: the signer flags of the instructions are trusted. The syscall
+//! stubs are global to the process, so the harness must not be used in the same test binary as
+//! `solana-program-test`.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::mem::size_of;
+use std::rc::Rc;
+
+use base64::Engine;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE, SUCCESS},
+    instruction::Instruction,
+    msg,
+    program_error::ProgramError,
+    program_option::COption,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    program_utils::limited_deserialize,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::SystemInstruction,
+    system_program,
+};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList};
+use spl_token_2022::extension::transfer_fee::{TransferFeeAmount, TransferFeeConfig};
+use spl_token_2022::extension::transfer_hook::{TransferHook, TransferHookAccount};
+use spl_token_2022::extension::{
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions, StateWithExtensionsMut,
+};
+use spl_token_2022::state::{Account as TokenAccount, AccountState, Mint};
+use spl_transfer_hook_interface::get_extra_account_metas_address;
+use spl_transfer_hook_interface::instruction::{ExecuteInstruction, TransferHookInstruction};
+
+use crate::events::{Event, PROGRAM_DATA_PREFIX};
+use crate::governance::{MaxVoterWeightRecord, VoterWeightAction, VoterWeightRecord};
+use crate::instruction::{
+    activate_stake_pool, add_to_bond_v2, admin_program_freeze, admin_renounce,
+    admin_set_boost_config, admin_set_emission_cap, admin_set_governance_config,
+    admin_set_protocol_fee, admin_set_role, buy_bond_v2, cancel_bond_v2_listing,
+    change_central_state_authority, change_inflation, change_pool_controls, change_pool_multiplier,
+    claim_bond_v2_rewards, claim_pool_rewards, claim_receipt_rewards, claim_rewards,
+    compact_stake_pool, crank, create_bond_v2, create_central_state, create_receipt_mint,
+    create_stake_account, create_stake_pool, emergency_withdraw, enable_reward_index, list_bond_v2,
+    migrate_account, migrate_central_state_v2, refresh_bond_v2_boost, set_pool_allowlist_entry,
+    stake, transfer_bond_v2, unlock_bond_v2, unstake, update_max_voter_weight_record,
+    update_voter_weight_record, ProgramInstruction,
+};
+use crate::state::{
+    BondV2Account, BondV2Listing, BoostConfig, CentralState, CentralStateV2, GovernanceConfig,
+    PoolAllowlistEntry, PoolBoost, PoolControls, PoolFeeOverride, ProtocolConfig, RoleTable,
+    RewardsTuple, RoyaltyAccount, StakeAccount, StakePool, StakePoolHeader, StakePoolHeaped,
+    SECONDS_IN_DAY, STAKE_BUFFER_LEN,
+};
+use crate::versioning::Versioned;
+
+/// The unix timestamp of the clock of a new harness
+pub const START_TIMESTAMP: i64 = 1_700_000_000;
+
+/// The lamports of the fee payer of a new harness
+pub const PAYER_LAMPORTS: u64 = 1_000_000_000_000_000;
+
+/// The decimals of the mint of a new harness
+pub const MINT_DECIMALS: u8 = 6;
+
+/// The transfer fee of a mint with [`MintExtension::TransferFee`], in basis points
+pub const TRANSFER_FEE_BASIS_POINTS: u16 = 100;
+
+/// The in-process transfer hook program of the mints with [`MintExtension::TransferHook`]
+pub const TRANSFER_HOOK_PROGRAM_ID: Pubkey = Pubkey::new_from_array([7; 32]);
+
+// Maximum size of a system instruction
+const SYSTEM_INSTRUCTION_LIMIT: u64 = 1232;
+
+/// An account of the in-memory store
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Account {
+    /// Lamports of the account, accounts without lamports are removed after each transaction
+    pub lamports: u64,
+    /// Data of the account
+    pub data: Vec<u8>,
+    /// Program owning the account
+    pub owner: Pubkey,
+    /// The account is a program
+    pub executable: bool,
+}
+
+#[derive(Default)]
+struct Context {
+    clock: Clock,
+    rent: Rent,
+    logs: Vec<String>,
+    return_data: Option<(Pubkey, Vec<u8>)>,
+    // The programs being executed, the last one is the current program
+    call_stack: Vec<Pubkey>,
+}
+
+thread_local! {
+    static CONTEXT: RefCell<Context> = RefCell::new(Context::default());
+}
+
+fn log(line: String) {
+    CONTEXT.with(|c| c.borrow_mut().logs.push(line));
+}
+
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_log(&self, message: &str) {
+        log(format!("Program log: {}", message));
+    }
+
+    fn sol_log_compute_units(&self) {}
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        invoke(instruction, account_infos, signers_seeds)
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = CONTEXT.with(|c| c.borrow().clock.clone());
+        unsafe { *(var_addr as *mut Clock) = clock };
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let rent = CONTEXT.with(|c| c.borrow().rent);
+        unsafe { *(var_addr as *mut Rent) = rent };
+        SUCCESS
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        CONTEXT.with(|c| c.borrow().return_data.clone())
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        CONTEXT.with(|c| {
+            let mut c = c.borrow_mut();
+            let program_id = c.call_stack.last().copied().unwrap_or_default();
+            c.return_data = if data.is_empty() {
+                None
+            } else {
+                Some((program_id, data.to_vec()))
+            };
+        });
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let fields: Vec<String> = fields
+            .iter()
+            .map(|f| base64::engine::general_purpose::STANDARD.encode(f))
+            .collect();
+        log(format!("{}{}", PROGRAM_DATA_PREFIX, fields.join(" ")));
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        CONTEXT.with(|c| c.borrow().call_stack.len() as u64)
+    }
+}
+
+/// Runs an instruction of one of the programs available in-process
+fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let depth = CONTEXT.with(|c| {
+        let mut c = c.borrow_mut();
+        c.call_stack.push(*program_id);
+        c.call_stack.len()
+    });
+    log(format!("Program {} invoke [{}]", program_id, depth));
+
+    let result = if program_id == &crate::ID {
+        crate::entrypoint::process_instruction(program_id, accounts, data)
+    } else if program_id == &system_program::ID {
+        process_system_instruction(accounts, data)
+    } else if program_id == &spl_token::ID {
+        spl_token::processor::Processor::process(program_id, accounts, data)
+    } else if program_id == &spl_token_2022::ID {
+        spl_token_2022::processor::Processor::process(program_id, accounts, data)
+    } else if program_id == &TRANSFER_HOOK_PROGRAM_ID {
+        process_transfer_hook_instruction(accounts, data)
+    } else {
+        msg!("Program {} is not available in-process", program_id);
+        Err(ProgramError::IncorrectProgramId)
+    };
+
+    match &result {
+        Ok(()) => log(format!("Program {} success", program_id)),
+        Err(e) => log(format!("Program {} failed: {}", program_id, e)),
+    }
+    CONTEXT.with(|c| c.borrow_mut().call_stack.pop());
+    result
+}
+
+/// Cross program invocation, the signatures of the caller PDAs are checked against the seeds
+fn invoke(
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let caller = CONTEXT.with(|c| c.borrow().call_stack.last().copied().unwrap_or_default());
+    let mut accounts = Vec::with_capacity(instruction.accounts.len());
+    for meta in &instruction.accounts {
+        let mut account = account_infos
+            .iter()
+            .find(|a| a.key == &meta.pubkey)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?
+            .clone();
+        if meta.is_signer && !account.is_signer {
+            let signed = signers_seeds
+                .iter()
+                .any(|seeds| Pubkey::create_program_address(seeds, &caller) == Ok(meta.pubkey));
+            if !signed {
+                msg!("Missing signature of {}", meta.pubkey);
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            account.is_signer = true;
+        }
+        if meta.is_writable && !account.is_writable {
+            msg!("{} is not writable", meta.pubkey);
+            return Err(ProgramError::InvalidArgument);
+        }
+        account.is_writable = meta.is_writable;
+        accounts.push(account);
+    }
+    CONTEXT.with(|c| c.borrow_mut().return_data = None);
+    process(&instruction.program_id, &accounts, &instruction.data)
+}
+
+fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    if !from.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let from_lamports = from
+        .lamports()
+        .checked_sub(lamports)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    let to_lamports = to
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::InvalidArgument)?;
+    **from.try_borrow_mut_lamports()? = from_lamports;
+    **to.try_borrow_mut_lamports()? = to_lamports;
+    Ok(())
+}
+
+/// The subset of the system program used by the protocol
+fn process_system_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let instruction: SystemInstruction = limited_deserialize(data, SYSTEM_INSTRUCTION_LIMIT)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let account = |i: usize| accounts.get(i).ok_or(ProgramError::NotEnoughAccountKeys);
+    match instruction {
+        SystemInstruction::CreateAccount {
+            lamports,
+            space,
+            owner,
+        } => {
+            let (from, to) = (account(0)?, account(1)?);
+            if !to.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if to.lamports() > 0 || !to.data_is_empty() || to.owner != &system_program::ID {
+                msg!("Account {} already in use", to.key);
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            transfer_lamports(from, to, lamports)?;
+            to.realloc(space as usize, true)?;
+            to.assign(&owner);
+            Ok(())
+        }
+        SystemInstruction::Transfer { lamports } => {
+            transfer_lamports(account(0)?, account(1)?, lamports)
+        }
+        SystemInstruction::Allocate { space } => {
+            let to = account(0)?;
+            if !to.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            to.realloc(space as usize, true)
+        }
+        SystemInstruction::Assign { owner } => {
+            let to = account(0)?;
+            if !to.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            to.assign(&owner);
+            Ok(())
+        }
+        _ => {
+            msg!("System instruction not available in-process");
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+}
+
+/// A transfer hook only checking the extra accounts listed in its validation account
+fn process_transfer_hook_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    match TransferHookInstruction::unpack(data)? {
+        TransferHookInstruction::Execute { .. } => {
+            let account = |i: usize| accounts.get(i).ok_or(ProgramError::NotEnoughAccountKeys);
+            let (mint, validation) = (account(1)?, account(4)?);
+            if validation.key
+                != &get_extra_account_metas_address(mint.key, &TRANSFER_HOOK_PROGRAM_ID)
+            {
+                msg!("Invalid validation account {}", validation.key);
+                return Err(ProgramError::InvalidSeeds);
+            }
+            let validation_data = validation.try_borrow_data()?;
+            ExtraAccountMetaList::check_account_infos::<ExecuteInstruction>(
+                accounts,
+                data,
+                &TRANSFER_HOOK_PROGRAM_ID,
+                &validation_data,
+            )
+        }
+        _ => {
+            msg!("Transfer hook instruction not available in-process");
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+}
+
+// Layout of an account buffer, the same as in the input of a program so that `realloc` and
+// `assign` work. The data is 8 bytes aligned as on-chain, the rewards buffer of the stake pools
+// then starts on a 16 bytes boundary where `u128` requires it
+const ORIGINAL_DATA_LEN_OFFSET: usize = 20;
+const KEY_OFFSET: usize = 24;
+const OWNER_OFFSET: usize = 56;
+const LAMPORTS_OFFSET: usize = 88;
+const DATA_LEN_OFFSET: usize = 96;
+const DATA_OFFSET: usize = 104;
+
+struct AccountBuffer {
+    memory: Vec<u128>,
+    executable: bool,
+}
+
+impl AccountBuffer {
+    fn new(key: &Pubkey, account: &Account) -> Self {
+        let len = DATA_OFFSET + account.data.len() + MAX_PERMITTED_DATA_INCREASE;
+        let mut buffer = Self {
+            memory: vec![0; len / 16 + 1],
+            executable: account.executable,
+        };
+        let bytes = buffer.bytes_mut();
+        bytes[ORIGINAL_DATA_LEN_OFFSET..KEY_OFFSET]
+            .copy_from_slice(&(account.data.len() as u32).to_ne_bytes());
+        bytes[KEY_OFFSET..OWNER_OFFSET].copy_from_slice(key.as_ref());
+        bytes[OWNER_OFFSET..LAMPORTS_OFFSET].copy_from_slice(account.owner.as_ref());
+        bytes[LAMPORTS_OFFSET..DATA_LEN_OFFSET].copy_from_slice(&account.lamports.to_ne_bytes());
+        bytes[DATA_LEN_OFFSET..DATA_OFFSET]
+            .copy_from_slice(&(account.data.len() as u64).to_ne_bytes());
+        bytes[DATA_OFFSET..DATA_OFFSET + account.data.len()].copy_from_slice(&account.data);
+        buffer
+    }
+
+    fn bytes(&self) -> &[u8] {
+        unsafe {
+            std::slice::from_raw_parts(self.memory.as_ptr() as *const u8, self.memory.len() * 16)
+        }
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        unsafe {
+            std::slice::from_raw_parts_mut(
+                self.memory.as_mut_ptr() as *mut u8,
+                self.memory.len() * 16,
+            )
+        }
+    }
+
+    fn account_info(&mut self, is_signer: bool, is_writable: bool) -> AccountInfo<'_> {
+        let executable = self.executable;
+        let bytes = self.bytes_mut();
+        let data_len = u64::from_ne_bytes(bytes[DATA_LEN_OFFSET..DATA_OFFSET].try_into().unwrap());
+        let ptr = bytes.as_mut_ptr();
+        // The fields point into the buffer which outlives the account info
+        unsafe {
+            AccountInfo {
+                key: &*(ptr.add(KEY_OFFSET) as *const Pubkey),
+                is_signer,
+                is_writable,
+                lamports: Rc::new(RefCell::new(&mut *(ptr.add(LAMPORTS_OFFSET) as *mut u64))),
+                data: Rc::new(RefCell::new(std::slice::from_raw_parts_mut(
+                    ptr.add(DATA_OFFSET),
+                    data_len as usize,
+                ))),
+                owner: &*(ptr.add(OWNER_OFFSET) as *const Pubkey),
+                executable,
+                rent_epoch: 0,
+            }
+        }
+    }
+
+    fn account(&self) -> Account {
+        let bytes = self.bytes();
+        let data_len =
+            u64::from_ne_bytes(bytes[DATA_LEN_OFFSET..DATA_OFFSET].try_into().unwrap()) as usize;
+        Account {
+            lamports: u64::from_ne_bytes(
+                bytes[LAMPORTS_OFFSET..DATA_LEN_OFFSET].try_into().unwrap(),
+            ),
+            data: bytes[DATA_OFFSET..DATA_OFFSET + data_len].to_vec(),
+            owner: Pubkey::new_from_array(bytes[OWNER_OFFSET..LAMPORTS_OFFSET].try_into().unwrap()),
+            executable: self.executable,
+        }
+    }
+}
+
+/// Executes a top level instruction against the store
+fn execute(store: &mut HashMap<Pubkey, Account>, instruction: &Instruction) -> ProgramResult {
+    let mut keys: Vec<Pubkey> = vec![];
+    for meta in &instruction.accounts {
+        if !keys.contains(&meta.pubkey) {
+            keys.push(meta.pubkey);
+        }
+    }
+    let originals: Vec<Account> = keys
+        .iter()
+        .map(|k| store.get(k).cloned().unwrap_or_default())
+        .collect();
+    let writable: Vec<bool> = keys
+        .iter()
+        .map(|k| {
+            instruction
+                .accounts
+                .iter()
+                .any(|m| &m.pubkey == k && m.is_writable)
+        })
+        .collect();
+    let mut buffers: Vec<AccountBuffer> = keys
+        .iter()
+        .zip(originals.iter())
+        .map(|(k, a)| AccountBuffer::new(k, a))
+        .collect();
+
+    let result = {
+        let infos: Vec<AccountInfo> = buffers
+            .iter_mut()
+            .zip(keys.iter().zip(writable.iter()))
+            .map(|(buffer, (key, is_writable))| {
+                let is_signer = instruction
+                    .accounts
+                    .iter()
+                    .any(|m| &m.pubkey == key && m.is_signer);
+                buffer.account_info(is_signer, *is_writable)
+            })
+            .collect();
+        let accounts: Vec<AccountInfo> = instruction
+            .accounts
+            .iter()
+            .map(|m| infos[keys.iter().position(|k| k == &m.pubkey).unwrap()].clone())
+            .collect();
+        process(&instruction.program_id, &accounts, &instruction.data)
+    };
+    result?;
+
+    for (i, key) in keys.iter().enumerate() {
+        let account = buffers[i].account();
+        if account == originals[i] {
+            continue;
+        }
+        if !writable[i] {
+            msg!("The read-only account {} was modified", key);
+            return Err(ProgramError::InvalidArgument);
+        }
+        if account.lamports == 0 {
+            store.remove(key);
+        } else {
+            store.insert(*key, account);
+        }
+    }
+    Ok(())
+}
+
+/// The Token-2022 extensions of the mint of a harness
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MintExtension {
+    /// A transfer fee of [`TRANSFER_FEE_BASIS_POINTS`]
+    TransferFee,
+    /// A transfer hook of [`TRANSFER_HOOK_PROGRAM_ID`] requiring one extra account
+    TransferHook,
+}
+
+impl MintExtension {
+    fn extension_type(&self) -> ExtensionType {
+        match self {
+            MintExtension::TransferFee => ExtensionType::TransferFeeConfig,
+            MintExtension::TransferHook => ExtensionType::TransferHook,
+        }
+    }
+}
+
+/// In-process harness bound to a central state of the program and its mint
+pub struct ProtocolTest {
+    /// Pays for the created accounts and is the authority of the central state
+    pub payer: Pubkey,
+    /// The central state
+    pub central_state: Pubkey,
+    /// The vault of the central state receiving the protocol fees
+    pub central_state_vault: Pubkey,
+    /// The token mint of the central state
+    pub mint: Pubkey,
+    /// The token program of the mint
+    pub token_program: Pubkey,
+    /// Signer of the NFT program, set in the protocol config
+    pub access_nft_program_signer: Pubkey,
+    /// The accounts of the transfer hook of the mint, passed to the instructions moving tokens
+    pub transfer_hook_accounts: Vec<Pubkey>,
+    accounts: HashMap<Pubkey, Account>,
+    logs: Vec<String>,
+    return_data: Option<(Pubkey, Vec<u8>)>,
+}
+
+impl ProtocolTest {
+    /// Creates the original central state with a new mint of the SPL token program
+    pub fn new(daily_inflation: u64) -> Result<Self, ProgramError> {
+        Self::new_with_token_program(daily_inflation, spl_token::ID)
+    }
+
+    /// Creates the original central state with a new mint of `token_program`
+    pub fn new_with_token_program(
+        daily_inflation: u64,
+        token_program: Pubkey,
+    ) -> Result<Self, ProgramError> {
+        Self::new_with_mint(daily_inflation, token_program, &[])
+    }
+
+    /// Creates the original central state with a new Token-2022 mint with `extensions`
+    pub fn new_with_mint_extensions(
+        daily_inflation: u64,
+        extensions: &[MintExtension],
+    ) -> Result<Self, ProgramError> {
+        Self::new_with_mint(daily_inflation, spl_token_2022::ID, extensions)
+    }
+
+    fn new_with_mint(
+        daily_inflation: u64,
+        token_program: Pubkey,
+        extensions: &[MintExtension],
+    ) -> Result<Self, ProgramError> {
+        set_syscall_stubs(Box::new(Stubs));
+        CONTEXT.with(|c| {
+            *c.borrow_mut() = Context {
+                clock: Clock {
+                    unix_timestamp: START_TIMESTAMP,
+                    ..Clock::default()
+                },
+                rent: Rent::default(),
+                ..Context::default()
+            }
+        });
+
+        let (central_state, _) = CentralState::find_key(&crate::ID);
+        let mint = Pubkey::new_unique();
+        let mut test = Self {
+            payer: Pubkey::new_unique(),
+            central_state,
+            central_state_vault: get_associated_token_address_with_program_id(
+                &central_state,
+                &mint,
+                &token_program,
+            ),
+            mint,
+            token_program,
+            access_nft_program_signer: Pubkey::new_unique(),
+            transfer_hook_accounts: vec![],
+            accounts: HashMap::new(),
+            logs: vec![],
+            return_data: None,
+        };
+        test.set_account(
+            test.payer,
+            Account {
+                lamports: PAYER_LAMPORTS,
+                ..Account::default()
+            },
+        );
+
+        // The mint authority is handed over to the central state
+        let mint_state = Mint {
+            mint_authority: COption::Some(central_state),
+            supply: 0,
+            decimals: MINT_DECIMALS,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        let extension_types: Vec<ExtensionType> = extensions
+            .iter()
+            .map(MintExtension::extension_type)
+            .collect();
+        let mut data = vec![0; ExtensionType::try_calculate_account_len::<Mint>(&extension_types)?];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data)?;
+        for extension in extensions {
+            match extension {
+                MintExtension::TransferFee => {
+                    let config = state.init_extension::<TransferFeeConfig>(true)?;
+                    config.newer_transfer_fee.transfer_fee_basis_points =
+                        TRANSFER_FEE_BASIS_POINTS.into();
+                    config.newer_transfer_fee.maximum_fee = u64::MAX.into();
+                }
+                MintExtension::TransferHook => {
+                    let transfer_hook = state.init_extension::<TransferHook>(true)?;
+                    transfer_hook.program_id = Some(TRANSFER_HOOK_PROGRAM_ID).try_into()?;
+                }
+            }
+        }
+        state.base = mint_state;
+        state.pack_base();
+        state.init_account_type()?;
+        test.set_account(
+            mint,
+            Account {
+                lamports: test.rent().minimum_balance(data.len()),
+                data,
+                owner: token_program,
+                executable: false,
+            },
+        );
+        if extensions.contains(&MintExtension::TransferHook) {
+            test.create_transfer_hook_accounts()?;
+        }
+
+        let (protocol_config, _) = ProtocolConfig::create_key(&central_state, &crate::ID);
+        test.process_transaction(&[
+            create_central_state(
+                crate::ID,
+                create_central_state::Accounts {
+                    central_state: &central_state,
+                    system_program: &system_program::ID,
+                    fee_payer: &test.payer,
+                    mint: &mint,
+                    protocol_config: &protocol_config,
+                },
+                create_central_state::Params {
+                    daily_inflation,
+                    authority: test.payer,
+                    access_nft_program_signer: test.access_nft_program_signer,
+                },
+            ),
+            migrate_central_state_v2(
+                crate::ID,
+                migrate_central_state_v2::Accounts {
+                    fee_payer: &test.payer,
+                    central_state: &central_state,
+                    system_program: &system_program::ID,
+                },
+                migrate_central_state_v2::Params {},
+            ),
+        ])?;
+        test.create_ata(&central_state);
+        Ok(test)
+    }
+
+    // ---------------------------------------------------------------------------------------------
+    // Clock, rent and accounts
+    // ---------------------------------------------------------------------------------------------
+
+    /// The current clock
+    pub fn clock(&self) -> Clock {
+        CONTEXT.with(|c| c.borrow().clock.clone())
+    }
+
+    /// Replaces the clock
+    pub fn set_clock(&mut self, clock: Clock) {
+        CONTEXT.with(|c| c.borrow_mut().clock = clock);
+    }
+
+    /// Moves the clock forward by `seconds`
+    pub fn sleep(&mut self, seconds: u64) {
+        let mut clock = self.clock();
+        clock.unix_timestamp += seconds as i64;
+        // Roughly 2.5 slots per second
+        clock.slot += seconds * 5 / 2;
+        self.set_clock(clock);
+    }
+
+    /// Moves the clock forward by `days` of the program
+    pub fn sleep_days(&mut self, days: u64) {
+        self.sleep(days * SECONDS_IN_DAY);
+    }
+
+    /// The current rent
+    pub fn rent(&self) -> Rent {
+        CONTEXT.with(|c| c.borrow().rent)
+    }
+
+    /// Replaces the rent
+    pub fn set_rent(&mut self, rent: Rent) {
+        CONTEXT.with(|c| c.borrow_mut().rent = rent);
+    }
+
+    /// An account of the store
+    pub fn account(&self, key: &Pubkey) -> Option<&Account> {
+        self.accounts.get(key)
+    }
+
+    /// Writes an account to the store
+    pub fn set_account(&mut self, key: Pubkey, account: Account) {
+        self.accounts.insert(key, account);
+    }
+
+    /// Decodes a Borsh account of the store
+    pub fn get<T: BorshDeserialize>(&self, key: &Pubkey) -> Result<T, ProgramError> {
+        let account = self
+            .accounts
+            .get(key)
+            .ok_or(ProgramError::UninitializedAccount)?;
+        T::deserialize(&mut &account.data[..]).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Decodes an account of the store with any of its layouts
+    pub fn get_versioned<T: Versioned>(&self, key: &Pubkey) -> Result<T, ProgramError> {
+        let account = self
+            .accounts
+            .get(key)
+            .ok_or(ProgramError::UninitializedAccount)?;
+        T::load(&account.data)
+    }
+
+    // ---------------------------------------------------------------------------------------------
+    // Transactions
+    // ---------------------------------------------------------------------------------------------
+
+    /// Executes the instructions atomically, the store is left untouched if one of them fails
+    pub fn process_transaction(&mut self, instructions: &[Instruction]) -> ProgramResult {
+        CONTEXT.with(|c| {
+            let mut c = c.borrow_mut();
+            c.logs.clear();
+            c.return_data = None;
+        });
+        let mut accounts = self.accounts.clone();
+        let result = instructions
+            .iter()
+            .try_for_each(|ix| execute(&mut accounts, ix));
+        CONTEXT.with(|c| {
+            let mut c = c.borrow_mut();
+            self.logs = std::mem::take(&mut c.logs);
+            self.return_data = c.return_data.take();
+        });
+        if result.is_ok() {
+            self.accounts = accounts;
+        }
+        result
+    }
+
+    /// Executes one instruction
+    pub fn process_instruction(&mut self, instruction: Instruction) -> ProgramResult {
+        self.process_transaction(&[instruction])
+    }
+
+    /// Executes a view instruction without changing the store and decodes its return data
+    pub fn view<T: BorshDeserialize>(
+        &mut self,
+        instruction: Instruction,
+    ) -> Result<T, ProgramError> {
+        let accounts = self.accounts.clone();
+        let result = self.process_instruction(instruction);
+        self.accounts = accounts;
+        result?;
+        let (_, data) = self
+            .return_data
+            .as_ref()
+            .ok_or(ProgramError::InvalidAccountData)?;
+        T::try_from_slice(data).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// The logs of the last transaction
+    pub fn logs(&self) -> &[String] {
+        &self.logs
+    }
+
+    /// The events logged by the last transaction
+    pub fn events(&self) -> Vec<Event> {
+        Event::from_logs(&crate::ID, &self.logs)
+    }
+
+    /// The return data of the last transaction
+    pub fn return_data(&self) -> Option<&(Pubkey, Vec<u8>)> {
+        self.return_data.as_ref()
+    }
+
+    // ---------------------------------------------------------------------------------------------
+    // Tokens
+    // ---------------------------------------------------------------------------------------------
+
+    /// The associated token account of `owner` for the mint
+    pub fn get_ata(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(owner, &self.mint, &self.token_program)
+    }
+
+    /// Writes an empty associated token account of `owner` to the store
+    pub fn create_ata(&mut self, owner: &Pubkey) -> Pubkey {
+        let mint = self.mint;
+        self.create_token_account(owner, &mint)
+    }
+
+    /// Writes the validation account of the transfer hook of the mint and its extra account to
+    /// the store
+    fn create_transfer_hook_accounts(&mut self) -> ProgramResult {
+        let validation = get_extra_account_metas_address(&self.mint, &TRANSFER_HOOK_PROGRAM_ID);
+        let extra_account = Pubkey::new_unique();
+        let mut data = vec![0; ExtraAccountMetaList::size_of(1)?];
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut data,
+            &[ExtraAccountMeta::new_with_pubkey(
+                &extra_account,
+                false,
+                false,
+            )?],
+        )?;
+        let lamports = self.rent().minimum_balance(data.len());
+        self.set_account(
+            validation,
+            Account {
+                lamports,
+                data,
+                owner: TRANSFER_HOOK_PROGRAM_ID,
+                executable: false,
+            },
+        );
+        self.set_account(
+            TRANSFER_HOOK_PROGRAM_ID,
+            Account {
+                lamports: 1,
+                executable: true,
+                ..Account::default()
+            },
+        );
+        self.transfer_hook_accounts = vec![TRANSFER_HOOK_PROGRAM_ID, validation, extra_account];
+        Ok(())
+    }
+
+    /// A skipped optional account of an instruction, set to the program id when the transfer hook
+    /// accounts follow
+    fn skipped_account(&self) -> Option<&'static Pubkey> {
+        if self.transfer_hook_accounts.is_empty() {
+            None
+        } else {
+            Some(&crate::ID)
+        }
+    }
+
+    /// Writes an empty associated token account of `owner` for any mint to the store, with the
+    /// account extensions required by the mint
+    pub fn create_token_account(&mut self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let key = get_associated_token_address_with_program_id(owner, mint, &self.token_program);
+        let mint_extension_types = self
+            .accounts
+            .get(mint)
+            .and_then(|a| {
+                StateWithExtensions::<Mint>::unpack(&a.data)
+                    .ok()?
+                    .get_extension_types()
+                    .ok()
+            })
+            .unwrap_or_default();
+        let extension_types =
+            ExtensionType::get_required_init_account_extensions(&mint_extension_types);
+        let token_account = TokenAccount {
+            mint: *mint,
+            owner: *owner,
+            amount: 0,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        };
+        let mut data =
+            vec![
+                0;
+                ExtensionType::try_calculate_account_len::<TokenAccount>(&extension_types).unwrap()
+            ];
+        let mut state =
+            StateWithExtensionsMut::<TokenAccount>::unpack_uninitialized(&mut data).unwrap();
+        for extension_type in extension_types {
+            match extension_type {
+                ExtensionType::TransferFeeAmount => {
+                    state.init_extension::<TransferFeeAmount>(true).unwrap();
+                }
+                ExtensionType::TransferHookAccount => {
+                    state.init_extension::<TransferHookAccount>(true).unwrap();
+                }
+                _ => unimplemented!("{:?} is not available in-process", extension_type),
+            }
+        }
+        state.base = token_account;
+        state.pack_base();
+        state.init_account_type().unwrap();
+        let account = Account {
+            lamports: self.rent().minimum_balance(data.len()),
+            data,
+            owner: self.token_program,
+            executable: false,
+        };
+        self.set_account(key, account);
+        key
+    }
+
+    /// Creates a user with an associated token account
+    pub fn create_user_with_ata(&mut self) -> Pubkey {
+        let user = Pubkey::new_unique();
+        self.create_ata(&user);
+        user
+    }
+
+    /// The token balance of the associated token account of `owner`
+    pub fn token_balance(&self, owner: &Pubkey) -> Result<u64, ProgramError> {
+        self.token_account_balance(&self.get_ata(owner))
+    }
+
+    /// The token balance of a token account
+    pub fn token_account_balance(&self, key: &Pubkey) -> Result<u64, ProgramError> {
+        let account = self
+            .accounts
+            .get(key)
+            .ok_or(ProgramError::UninitializedAccount)?;
+        Ok(StateWithExtensions::<TokenAccount>::unpack(&account.data)?
+            .base
+            .amount)
+    }
+
+    /// The supply of the mint
+    pub fn supply(&self) -> Result<u64, ProgramError> {
+        let account = self
+            .accounts
+            .get(&self.mint)
+            .ok_or(ProgramError::UninitializedAccount)?;
+        Ok(StateWithExtensions::<Mint>::unpack(&account.data)?
+            .base
+            .supply)
+    }
+
+    /// Mints tokens to the associated token account of `owner` directly in the store, `admin_mint`
+    /// is only available with the V1 instructions
+    pub fn mint(&mut self, owner: &Pubkey, amount: u64) -> ProgramResult {
+        let destination = self.get_ata(owner);
+        let mut token_account = self
+            .accounts
+            .get(&destination)
+            .cloned()
+            .ok_or(ProgramError::UninitializedAccount)?;
+        let mut state = StateWithExtensionsMut::<TokenAccount>::unpack(&mut token_account.data)?;
+        state.base.amount = state
+            .base
+            .amount
+            .checked_add(amount)
+            .ok_or(ProgramError::InvalidArgument)?;
+        state.pack_base();
+
+        let mut mint = self
+            .accounts
+            .get(&self.mint)
+            .cloned()
+            .ok_or(ProgramError::UninitializedAccount)?;
+        let mut mint_state = StateWithExtensionsMut::<Mint>::unpack(&mut mint.data)?;
+        mint_state.base.supply = mint_state
+            .base
+            .supply
+            .checked_add(amount)
+            .ok_or(ProgramError::InvalidArgument)?;
+        mint_state.pack_base();
+
+        self.set_account(destination, token_account);
+        self.set_account(self.mint, mint);
+        Ok(())
+    }
+
+    // ---------------------------------------------------------------------------------------------
+    // Protocol
+    // ---------------------------------------------------------------------------------------------
+
+    /// The stake pool of `owner`
+    pub fn get_pool_pda(&self, owner: &Pubkey) -> Pubkey {
+        StakePool::find_scoped_key(owner, None, &crate::ID).0
+    }
+
+    /// The stake account of `owner` in `stake_pool`
+    pub fn get_stake_account_pda(&self, stake_pool: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+        StakeAccount::find_key(owner, stake_pool, &crate::ID)
+    }
+
+    /// Creates the stake pool of `owner` and its vault
+    pub fn create_pool(&mut self, owner: &Pubkey, minimum_stake_amount: u64) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(owner);
+        let vault = self.create_ata(&stake_pool);
+        self.process_instruction(create_stake_pool(
+            crate::ID,
+            create_stake_pool::Accounts {
+                stake_pool_account: &stake_pool,
+                owner,
+                system_program: &system_program::ID,
+                fee_payer: &self.payer,
+                vault: &vault,
+                central_state: &self.central_state,
+            },
+            create_stake_pool::Params {
+                minimum_stake_amount,
+            },
+        ))
+    }
+
+    /// Creates the stake pool of `owner` with the rewards buffer of the pools created before the
+    /// reward index
+    pub fn create_legacy_pool(&mut self, owner: &Pubkey, minimum_stake_amount: u64) -> ProgramResult {
+        self.create_pool(owner, minimum_stake_amount)?;
+        let stake_pool = self.get_pool_pda(owner);
+        let mut header = *self.stake_pool(owner)?.header;
+        header.reward_index_enabled = 0;
+        let mut account = self.accounts[&stake_pool].clone();
+        account.data = header.try_to_vec()?;
+        account.data.resize(
+            size_of::<StakePoolHeader>() + size_of::<RewardsTuple>() * STAKE_BUFFER_LEN as usize,
+            0,
+        );
+        account.lamports = self.rent().minimum_balance(account.data.len());
+        self.set_account(stake_pool, account);
+        Ok(())
+    }
+
+    /// Activates the stake pool of `owner`
+    pub fn activate_stake_pool(&mut self, owner: &Pubkey) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(owner);
+        self.process_instruction(activate_stake_pool(
+            crate::ID,
+            activate_stake_pool::Accounts {
+                stake_pool: &stake_pool,
+                central_state: &self.central_state,
+            },
+            activate_stake_pool::Params {},
+        ))
+    }
+
+    /// Creates the stake account of `staker` in the pool of `pool_owner`
+    pub fn create_stake_account(&mut self, pool_owner: &Pubkey, staker: &Pubkey) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        let (stake_account, nonce) = self.get_stake_account_pda(&stake_pool, staker);
+        self.process_instruction(create_stake_account(
+            crate::ID,
+            create_stake_account::Accounts {
+                stake_account: &stake_account,
+                system_program: &system_program::ID,
+                fee_payer: &self.payer,
+                stake_pool: &stake_pool,
+                central_state: &self.central_state,
+                pool_controls: Some(&PoolControls::create_key(&stake_pool, &crate::ID).0),
+                allowlist_entry: Some(
+                    &PoolAllowlistEntry::create_key(&stake_pool, staker, &crate::ID).0,
+                ),
+            },
+            create_stake_account::Params {
+                nonce,
+                owner: *staker,
+            },
+        ))
+    }
+
+    /// Stakes `amount` tokens of `staker` in the pool of `pool_owner`
+    pub fn stake(&mut self, pool_owner: &Pubkey, staker: &Pubkey, amount: u64) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        let (stake_account, _) = self.get_stake_account_pda(&stake_pool, staker);
+        let pool_fee_override = PoolFeeOverride::create_key(&stake_pool, &crate::ID).0;
+        self.process_instruction(stake(
+            crate::ID,
+            stake::Accounts {
+                stake_account: &stake_account,
+                stake_pool: &stake_pool,
+                token_owner: staker,
+                source_token: &self.get_ata(staker),
+                spl_token_program: &self.token_program,
+                vault: &self.get_ata(&stake_pool),
+                central_state: &self.central_state,
+                central_state_vault: &self.central_state_vault,
+                pool_fee_override: Some(&pool_fee_override),
+                mint: &self.mint,
+                pool_controls: Some(&PoolControls::create_key(&stake_pool, &crate::ID).0),
+                allowlist_entry: Some(
+                    &PoolAllowlistEntry::create_key(&stake_pool, staker, &crate::ID).0,
+                ),
+                receipt_mint: self.skipped_account(),
+                receipt_account: self.skipped_account(),
+                receipt_reserve: self.skipped_account(),
+                transfer_hook_accounts: &self.transfer_hook_accounts,
+            },
+            stake::Params { amount },
+        ))
+    }
+
+    /// Unstakes `amount` tokens of `staker` from the pool of `pool_owner`
+    pub fn unstake(&mut self, pool_owner: &Pubkey, staker: &Pubkey, amount: u64) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        let (stake_account, _) = self.get_stake_account_pda(&stake_pool, staker);
+        self.process_instruction(unstake(
+            crate::ID,
+            unstake::Accounts {
+                stake_account: &stake_account,
+                stake_pool: &stake_pool,
+                owner: staker,
+                destination_token: &self.get_ata(staker),
+                spl_token_program: &self.token_program,
+                central_state: &self.central_state,
+                vault: &self.get_ata(&stake_pool),
+                mint: &self.mint,
+                receipt_mint: self.skipped_account(),
+                receipt_account: self.skipped_account(),
+                receipt_reserve: self.skipped_account(),
+                transfer_hook_accounts: &self.transfer_hook_accounts,
+            },
+            unstake::Params { amount },
+        ))
+    }
+
+    /// Cranks the pool of `pool_owner`
+    pub fn crank_pool(&mut self, pool_owner: &Pubkey) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        self.process_instruction(crank(
+            crate::ID,
+            crank::Accounts {
+                stake_pool: &stake_pool,
+                central_state: &self.central_state,
+                pool_boost: Some(&PoolBoost::create_key(&stake_pool, &crate::ID).0),
+            },
+            crank::Params {},
+        ))
+    }
+
+    /// Claims the rewards of `staker` in the pool of `pool_owner` to their associated token account
+    pub fn claim_staker_rewards(&mut self, pool_owner: &Pubkey, staker: &Pubkey) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        let (stake_account, _) = self.get_stake_account_pda(&stake_pool, staker);
+        let royalty_account = RoyaltyAccount::create_key(staker, &crate::ID).0;
+        let royalty_ata = self.royalty_ata(&royalty_account);
+        self.process_instruction(claim_rewards(
+            crate::ID,
+            claim_rewards::Accounts {
+                stake_pool: &stake_pool,
+                stake_account: &stake_account,
+                owner: staker,
+                rewards_destination: &self.get_ata(staker),
+                central_state: &self.central_state,
+                mint: &self.mint,
+                access_nft_signer: &self.access_nft_program_signer,
+                spl_token_program: &self.token_program,
+                owner_royalty_account: &royalty_account,
+                royalty_ata: royalty_ata.as_ref(),
+                protocol_config: None,
+            },
+            claim_rewards::Params {
+                allow_zero_rewards: true,
+            },
+            false,
+        ))
+    }
+
+    /// Claims the rewards of the owner of a pool to their associated token account
+    pub fn claim_pool_rewards(&mut self, pool_owner: &Pubkey) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        let royalty_account = RoyaltyAccount::create_key(pool_owner, &crate::ID).0;
+        let royalty_ata = self.royalty_ata(&royalty_account);
+        self.process_instruction(claim_pool_rewards(
+            crate::ID,
+            claim_pool_rewards::Accounts {
+                stake_pool: &stake_pool,
+                owner: pool_owner,
+                rewards_destination: &self.get_ata(pool_owner),
+                central_state: &self.central_state,
+                mint: &self.mint,
+                spl_token_program: &self.token_program,
+                owner_royalty_account: &royalty_account,
+                royalty_ata: royalty_ata.as_ref(),
+            },
+            claim_pool_rewards::Params {},
+            false,
+        ))
+    }
+
+    /// Switches the pool of `pool_owner` to the reward index
+    pub fn enable_reward_index(&mut self, pool_owner: &Pubkey) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        self.process_instruction(enable_reward_index(
+            crate::ID,
+            enable_reward_index::Accounts {
+                fee_payer: &self.payer,
+                stake_pool: &stake_pool,
+                owner: pool_owner,
+                central_state: &self.central_state,
+                system_program: &system_program::ID,
+            },
+            enable_reward_index::Params {},
+        ))
+    }
+
+    /// Removes the rewards buffer of the pool of `pool_owner`
+    pub fn compact_stake_pool(&mut self, pool_owner: &Pubkey) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        self.process_instruction(compact_stake_pool(
+            crate::ID,
+            compact_stake_pool::Accounts {
+                stake_pool: &stake_pool,
+                owner: pool_owner,
+                central_state: &self.central_state,
+            },
+            compact_stake_pool::Params {},
+        ))
+    }
+
+    /// Rewrites an account with the latest layout of its type
+    pub fn migrate_account(&mut self, account: &Pubkey) -> ProgramResult {
+        self.process_instruction(migrate_account(
+            crate::ID,
+            migrate_account::Accounts {
+                fee_payer: &self.payer,
+                account,
+                system_program: &system_program::ID,
+            },
+            migrate_account::Params {},
+        ))
+    }
+
+    /// Creates a bond V2 of `owner` in the pool of `pool_owner`
+    pub fn create_bond_v2(
+        &mut self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+    ) -> ProgramResult {
+        let pool = self.get_pool_pda(pool_owner);
+        let (bond_v2_account, _) =
+            BondV2Account::create_key(owner, &pool, unlock_timestamp, &crate::ID);
+        self.process_instruction(create_bond_v2(
+            crate::ID,
+            create_bond_v2::Accounts {
+                fee_payer: &self.payer,
+                bond_v2_account: &bond_v2_account,
+                central_state: &self.central_state,
+                pool: &pool,
+                system_program: &system_program::ID,
+            },
+            create_bond_v2::Params {
+                unlock_timestamp,
+                owner: *owner,
+            },
+        ))
+    }
+
+    /// Locks `amount` tokens of `from` in the bond V2 of `to`
+    pub fn add_to_bond_v2(
+        &mut self,
+        from: &Pubkey,
+        to: &Pubkey,
+        pool_owner: &Pubkey,
+        amount: u64,
+        unlock_timestamp: Option<i64>,
+    ) -> ProgramResult {
+        let pool = self.get_pool_pda(pool_owner);
+        let (bond_v2_account, _) =
+            BondV2Account::create_key(to, &pool, unlock_timestamp, &crate::ID);
+        let pool_fee_override = PoolFeeOverride::create_key(&pool, &crate::ID).0;
+        self.process_instruction(add_to_bond_v2(
+            crate::ID,
+            add_to_bond_v2::Accounts {
+                from,
+                from_ata: &self.get_ata(from),
+                bond_v2_account: &bond_v2_account,
+                pool: &pool,
+                central_state: &self.central_state,
+                pool_vault: &self.get_ata(&pool),
+                central_state_vault: &self.central_state_vault,
+                mint: &self.mint,
+                spl_token_program: &self.token_program,
+                system_program: &system_program::ID,
+                pool_fee_override: Some(&pool_fee_override),
+                pool_controls: Some(&PoolControls::create_key(&pool, &crate::ID).0),
+                allowlist_entry: Some(&PoolAllowlistEntry::create_key(&pool, to, &crate::ID).0),
+                boost_config: Some(&BoostConfig::create_key(&self.central_state, &crate::ID).0),
+                pool_boost: Some(&PoolBoost::create_key(&pool, &crate::ID).0),
+                transfer_hook_accounts: &self.transfer_hook_accounts,
+            },
+            add_to_bond_v2::Params { amount },
+        ))
+    }
+
+    /// Claims the rewards of a bond V2 to the associated token account of its owner
+    pub fn claim_bond_v2_rewards(
+        &mut self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+    ) -> ProgramResult {
+        let pool = self.get_pool_pda(pool_owner);
+        let (bond_v2_account, _) =
+            BondV2Account::create_key(owner, &pool, unlock_timestamp, &crate::ID);
+        let royalty_account = RoyaltyAccount::create_key(owner, &crate::ID).0;
+        let royalty_ata = self.royalty_ata(&royalty_account);
+        self.process_instruction(claim_bond_v2_rewards(
+            crate::ID,
+            claim_bond_v2_rewards::Accounts {
+                pool: &pool,
+                bond_v2_account: &bond_v2_account,
+                owner,
+                rewards_destination: &self.get_ata(owner),
+                central_state: &self.central_state,
+                mint: &self.mint,
+                access_nft_signer: &self.access_nft_program_signer,
+                spl_token_program: &self.token_program,
+                owner_royalty_account: &royalty_account,
+                royalty_ata: royalty_ata.as_ref(),
+                protocol_config: None,
+            },
+            claim_bond_v2_rewards::Params {},
+            false,
+        ))
+    }
+
+    /// Withdraws the tokens of an unlocked bond V2 to the associated token account of its owner
+    pub fn unlock_bond_v2(
+        &mut self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+    ) -> ProgramResult {
+        let pool = self.get_pool_pda(pool_owner);
+        let (bond_v2_account, _) =
+            BondV2Account::create_key(owner, &pool, unlock_timestamp, &crate::ID);
+        self.process_instruction(unlock_bond_v2(
+            crate::ID,
+            unlock_bond_v2::Accounts {
+                bond_v2_account: &bond_v2_account,
+                pool: &pool,
+                owner,
+                destination_account: &self.get_ata(owner),
+                spl_token_program: &self.token_program,
+                central_state: &self.central_state,
+                pool_vault: &self.get_ata(&pool),
+                mint: &self.mint,
+                pool_boost: Some(&PoolBoost::create_key(&pool, &crate::ID).0),
+                transfer_hook_accounts: &self.transfer_hook_accounts,
+            },
+            unlock_bond_v2::Params {},
+        ))
+    }
+
+    /// Transfers the bond V2 of `owner` in the pool of `pool_owner` to `new_owner`
+    pub fn transfer_bond_v2(
+        &mut self,
+        owner: &Pubkey,
+        new_owner: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+    ) -> ProgramResult {
+        let pool = self.get_pool_pda(pool_owner);
+        let (bond_v2_account, _) =
+            BondV2Account::create_key(owner, &pool, unlock_timestamp, &crate::ID);
+        let (new_bond_v2_account, _) =
+            BondV2Account::create_key(new_owner, &pool, unlock_timestamp, &crate::ID);
+        self.process_instruction(transfer_bond_v2(
+            crate::ID,
+            transfer_bond_v2::Accounts {
+                central_state: &self.central_state,
+                bond_v2_account: &bond_v2_account,
+                new_bond_v2_account: &new_bond_v2_account,
+                owner,
+                pool: &pool,
+                fee_payer: &self.payer,
+                system_program: &system_program::ID,
+            },
+            transfer_bond_v2::Params {
+                new_owner: *new_owner,
+            },
+        ))
+    }
+
+    /// The listing of the bond V2 of `seller` and the bond V2 account escrowing it
+    pub fn get_bond_v2_listing_pda(
+        &self,
+        seller: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+    ) -> (Pubkey, Pubkey) {
+        let pool = self.get_pool_pda(pool_owner);
+        let (bond_v2_account, _) =
+            BondV2Account::create_key(seller, &pool, unlock_timestamp, &crate::ID);
+        let (listing, _) = BondV2Listing::create_key(&bond_v2_account, &crate::ID);
+        let (escrow, _) = BondV2Account::create_key(&listing, &pool, unlock_timestamp, &crate::ID);
+        (listing, escrow)
+    }
+
+    /// Lists the bond V2 of `seller` for `price` tokens of the central state mint
+    pub fn list_bond_v2(
+        &mut self,
+        seller: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+        price: u64,
+    ) -> ProgramResult {
+        let pool = self.get_pool_pda(pool_owner);
+        let (bond_v2_account, _) =
+            BondV2Account::create_key(seller, &pool, unlock_timestamp, &crate::ID);
+        let (listing, escrow) = self.get_bond_v2_listing_pda(seller, pool_owner, unlock_timestamp);
+        self.process_instruction(list_bond_v2(
+            crate::ID,
+            list_bond_v2::Accounts {
+                central_state: &self.central_state,
+                bond_v2_account: &bond_v2_account,
+                listing: &listing,
+                escrow: &escrow,
+                seller,
+                quote_mint: &self.mint,
+                quote_destination: &self.get_ata(seller),
+                pool: &pool,
+                fee_payer: &self.payer,
+                system_program: &system_program::ID,
+            },
+            list_bond_v2::Params { price },
+        ))
+    }
+
+    /// Cancels the listing of the bond V2 of `seller`
+    pub fn cancel_bond_v2_listing(
+        &mut self,
+        seller: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+    ) -> ProgramResult {
+        let pool = self.get_pool_pda(pool_owner);
+        let (bond_v2_account, _) =
+            BondV2Account::create_key(seller, &pool, unlock_timestamp, &crate::ID);
+        let (listing, escrow) = self.get_bond_v2_listing_pda(seller, pool_owner, unlock_timestamp);
+        self.process_instruction(cancel_bond_v2_listing(
+            crate::ID,
+            cancel_bond_v2_listing::Accounts {
+                central_state: &self.central_state,
+                listing: &listing,
+                escrow: &escrow,
+                bond_v2_account: &bond_v2_account,
+                seller,
+                pool: &pool,
+                fee_payer: &self.payer,
+                system_program: &system_program::ID,
+            },
+            cancel_bond_v2_listing::Params {},
+        ))
+    }
+
+    /// Buys the listed bond V2 of `seller` for `price` tokens of the central state mint, the
+    /// rewards earned while listed are minted to the associated token account of `seller`
+    pub fn buy_bond_v2(
+        &mut self,
+        buyer: &Pubkey,
+        seller: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+        price: u64,
+    ) -> ProgramResult {
+        let pool = self.get_pool_pda(pool_owner);
+        let (buyer_bond_v2_account, _) =
+            BondV2Account::create_key(buyer, &pool, unlock_timestamp, &crate::ID);
+        let (listing, escrow) = self.get_bond_v2_listing_pda(seller, pool_owner, unlock_timestamp);
+        self.process_instruction(buy_bond_v2(
+            crate::ID,
+            buy_bond_v2::Accounts {
+                central_state: &self.central_state,
+                listing: &listing,
+                escrow: &escrow,
+                buyer_bond_v2_account: &buyer_bond_v2_account,
+                buyer,
+                seller,
+                quote_source: &self.get_ata(buyer),
+                quote_destination: &self.get_ata(seller),
+                quote_mint: &self.mint,
+                spl_token_program: &self.token_program,
+                pool: &pool,
+                fee_payer: &self.payer,
+                system_program: &system_program::ID,
+                mint: &self.mint,
+                seller_rewards_destination: &self.get_ata(seller),
+                rewards_token_program: &self.token_program,
+                transfer_hook_accounts: &self.transfer_hook_accounts,
+            },
+            buy_bond_v2::Params { price },
+        ))
+    }
+
+    /// Sets the boost curve of the bonds
+    pub fn set_boost_config(
+        &mut self,
+        max_lock_duration: i64,
+        max_lock_boost_basis_points: u16,
+        forever_boost_basis_points: u16,
+    ) -> ProgramResult {
+        let (boost_config, _) = BoostConfig::create_key(&self.central_state, &crate::ID);
+        self.process_instruction(admin_set_boost_config(
+            crate::ID,
+            admin_set_boost_config::Accounts {
+                authority: &self.payer,
+                central_state: &self.central_state,
+                boost_config: &boost_config,
+                fee_payer: &self.payer,
+                system_program: &system_program::ID,
+                role_table: Some(&RoleTable::create_key(&self.central_state, &crate::ID).0),
+            },
+            admin_set_boost_config::Params {
+                max_lock_duration,
+                max_lock_boost_basis_points,
+                forever_boost_basis_points,
+            },
+        ))
+    }
+
+    /// Settles the rewards of the bond V2 of `owner` and refreshes its boost
+    pub fn refresh_bond_v2_boost(
+        &mut self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+    ) -> ProgramResult {
+        let pool = self.get_pool_pda(pool_owner);
+        let (bond_v2_account, _) =
+            BondV2Account::create_key(owner, &pool, unlock_timestamp, &crate::ID);
+        self.process_instruction(refresh_bond_v2_boost(
+            crate::ID,
+            refresh_bond_v2_boost::Accounts {
+                central_state: &self.central_state,
+                bond_v2_account: &bond_v2_account,
+                pool: &pool,
+                pool_boost: &PoolBoost::create_key(&pool, &crate::ID).0,
+                boost_config: &BoostConfig::create_key(&self.central_state, &crate::ID).0,
+                fee_payer: &self.payer,
+                system_program: &system_program::ID,
+            },
+            refresh_bond_v2_boost::Params {},
+        ))
+    }
+
+    /// The receipt mint of the pool of `pool_owner`
+    pub fn get_receipt_mint(&self, pool_owner: &Pubkey) -> Pubkey {
+        StakePoolHeader::find_receipt_mint(&self.get_pool_pda(pool_owner), &crate::ID).0
+    }
+
+    /// The receipt token account of `owner` for the pool of `pool_owner`
+    pub fn get_receipt_account(&self, owner: &Pubkey, pool_owner: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(
+            owner,
+            &self.get_receipt_mint(pool_owner),
+            &self.token_program,
+        )
+    }
+
+    /// Creates the receipt mint of the pool of `pool_owner` along with its reserve
+    pub fn create_receipt_mint(&mut self, pool_owner: &Pubkey) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        let receipt_mint = self.get_receipt_mint(pool_owner);
+        let receipt_reserve = self.create_ata(&receipt_mint);
+        self.process_instruction(create_receipt_mint(
+            crate::ID,
+            create_receipt_mint::Accounts {
+                central_state: &self.central_state,
+                stake_pool: &stake_pool,
+                pool_owner,
+                receipt_mint: &receipt_mint,
+                receipt_stake_account: &self.get_stake_account_pda(&stake_pool, &receipt_mint).0,
+                receipt_reserve: &receipt_reserve,
+                mint: &self.mint,
+                spl_token_program: &self.token_program,
+                fee_payer: &self.payer,
+                system_program: &system_program::ID,
+            },
+            create_receipt_mint::Params {},
+        ))
+    }
+
+    /// Stakes `amount` tokens of `staker` in the pool of `pool_owner` for receipts, the receipt
+    /// token account of `staker` is created if needed
+    pub fn stake_for_receipts(
+        &mut self,
+        pool_owner: &Pubkey,
+        staker: &Pubkey,
+        amount: u64,
+    ) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        let receipt_mint = self.get_receipt_mint(pool_owner);
+        let receipt_account = self.get_receipt_account(staker, pool_owner);
+        if !self.accounts.contains_key(&receipt_account) {
+            self.create_token_account(staker, &receipt_mint);
+        }
+        let pool_fee_override = PoolFeeOverride::create_key(&stake_pool, &crate::ID).0;
+        self.process_instruction(stake(
+            crate::ID,
+            stake::Accounts {
+                stake_account: &self.get_stake_account_pda(&stake_pool, &receipt_mint).0,
+                stake_pool: &stake_pool,
+                token_owner: staker,
+                source_token: &self.get_ata(staker),
+                spl_token_program: &self.token_program,
+                vault: &self.get_ata(&stake_pool),
+                central_state: &self.central_state,
+                central_state_vault: &self.central_state_vault,
+                pool_fee_override: Some(&pool_fee_override),
+                mint: &self.mint,
+                pool_controls: Some(&PoolControls::create_key(&stake_pool, &crate::ID).0),
+                allowlist_entry: Some(
+                    &PoolAllowlistEntry::create_key(&stake_pool, staker, &crate::ID).0,
+                ),
+                receipt_mint: Some(&receipt_mint),
+                receipt_account: Some(&receipt_account),
+                receipt_reserve: Some(&self.get_ata(&receipt_mint)),
+                transfer_hook_accounts: &self.transfer_hook_accounts,
+            },
+            stake::Params { amount },
+        ))
+    }
+
+    /// Redeems `amount` receipts of `holder` in the pool of `pool_owner` to their associated token account
+    pub fn redeem_receipts(
+        &mut self,
+        pool_owner: &Pubkey,
+        holder: &Pubkey,
+        amount: u64,
+    ) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        let receipt_mint = self.get_receipt_mint(pool_owner);
+        self.process_instruction(unstake(
+            crate::ID,
+            unstake::Accounts {
+                stake_account: &self.get_stake_account_pda(&stake_pool, &receipt_mint).0,
+                stake_pool: &stake_pool,
+                owner: holder,
+                destination_token: &self.get_ata(holder),
+                spl_token_program: &self.token_program,
+                central_state: &self.central_state,
+                vault: &self.get_ata(&stake_pool),
+                mint: &self.mint,
+                receipt_mint: Some(&receipt_mint),
+                receipt_account: Some(&self.get_receipt_account(holder, pool_owner)),
+                receipt_reserve: Some(&self.get_ata(&receipt_mint)),
+                transfer_hook_accounts: &self.transfer_hook_accounts,
+            },
+            unstake::Params { amount },
+        ))
+    }
+
+    /// Claims the rewards of the receipt holders of the pool of `pool_owner` to the receipt reserve
+    pub fn claim_receipt_rewards(&mut self, pool_owner: &Pubkey) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        let receipt_mint = self.get_receipt_mint(pool_owner);
+        self.process_instruction(claim_receipt_rewards(
+            crate::ID,
+            claim_receipt_rewards::Accounts {
+                central_state: &self.central_state,
+                stake_pool: &stake_pool,
+                receipt_stake_account: &self.get_stake_account_pda(&stake_pool, &receipt_mint).0,
+                receipt_mint: &receipt_mint,
+                receipt_reserve: &self.get_ata(&receipt_mint),
+                mint: &self.mint,
+                spl_token_program: &self.token_program,
+            },
+            claim_receipt_rewards::Params {
+                allow_zero_rewards: true,
+            },
+        ))
+    }
+
+    /// Sets the realm of the voter weight records
+    pub fn set_governance_config(
+        &mut self,
+        realm: Pubkey,
+        governing_token_mint: Pubkey,
+        lock_weighting: bool,
+    ) -> ProgramResult {
+        self.process_instruction(admin_set_governance_config(
+            crate::ID,
+            admin_set_governance_config::Accounts {
+                authority: &self.payer,
+                central_state: &self.central_state,
+                governance_config: &GovernanceConfig::create_key(&self.central_state, &crate::ID).0,
+                fee_payer: &self.payer,
+                system_program: &system_program::ID,
+                role_table: Some(&RoleTable::create_key(&self.central_state, &crate::ID).0),
+            },
+            admin_set_governance_config::Params {
+                realm,
+                governing_token_mint,
+                lock_weighting,
+            },
+        ))
+    }
+
+    /// Updates the voter weight record of `voter` from the pools, stake accounts and bonds V2
+    /// of `voter_accounts`
+    pub fn update_voter_weight_record(
+        &mut self,
+        voter: &Pubkey,
+        voter_accounts: &[Pubkey],
+        weight_action: Option<VoterWeightAction>,
+    ) -> ProgramResult {
+        self.process_instruction(update_voter_weight_record(
+            crate::ID,
+            update_voter_weight_record::Accounts {
+                central_state: &self.central_state,
+                governance_config: &GovernanceConfig::create_key(&self.central_state, &crate::ID).0,
+                boost_config: &BoostConfig::create_key(&self.central_state, &crate::ID).0,
+                voter_weight_record: &VoterWeightRecord::create_key(
+                    &self.central_state,
+                    voter,
+                    &crate::ID,
+                )
+                .0,
+                voter,
+                fee_payer: &self.payer,
+                system_program: &system_program::ID,
+                voter_accounts,
+            },
+            update_voter_weight_record::Params {
+                weight_action,
+                weight_action_target: None,
+            },
+        ))
+    }
+
+    /// Updates the max voter weight record of the central state
+    pub fn update_max_voter_weight_record(&mut self) -> ProgramResult {
+        self.process_instruction(update_max_voter_weight_record(
+            crate::ID,
+            update_max_voter_weight_record::Accounts {
+                central_state: &self.central_state,
+                governance_config: &GovernanceConfig::create_key(&self.central_state, &crate::ID).0,
+                max_voter_weight_record: &MaxVoterWeightRecord::create_key(
+                    &self.central_state,
+                    &crate::ID,
+                )
+                .0,
+                fee_payer: &self.payer,
+                system_program: &system_program::ID,
+            },
+            update_max_voter_weight_record::Params {},
+        ))
+    }
+
+    /// Changes the % of the rewards of the pool of `pool_owner` going to its stakers
+    pub fn change_pool_multiplier(
+        &mut self,
+        pool_owner: &Pubkey,
+        new_multiplier: u64,
+    ) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        self.process_instruction(change_pool_multiplier(
+            crate::ID,
+            change_pool_multiplier::Accounts {
+                stake_pool: &stake_pool,
+                stake_pool_owner: pool_owner,
+                central_state: &self.central_state,
+            },
+            change_pool_multiplier::Params { new_multiplier },
+        ))
+    }
+
+    /// Sets the cap, the pause and the allowlist of the pool of `pool_owner`
+    pub fn change_pool_controls(
+        &mut self,
+        pool_owner: &Pubkey,
+        max_total_staked: u64,
+        paused: bool,
+        allowlist_enabled: bool,
+    ) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        self.process_instruction(change_pool_controls(
+            crate::ID,
+            change_pool_controls::Accounts {
+                stake_pool: &stake_pool,
+                stake_pool_owner: pool_owner,
+                central_state: &self.central_state,
+                pool_controls: &PoolControls::create_key(&stake_pool, &crate::ID).0,
+                fee_payer: &self.payer,
+                system_program: &system_program::ID,
+            },
+            change_pool_controls::Params {
+                max_total_staked,
+                paused,
+                allowlist_enabled,
+            },
+        ))
+    }
+
+    /// Adds `staker` to the allowlist of the pool of `pool_owner`, or removes it
+    pub fn set_pool_allowlist_entry(
+        &mut self,
+        pool_owner: &Pubkey,
+        staker: &Pubkey,
+        allowed: bool,
+    ) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        self.process_instruction(set_pool_allowlist_entry(
+            crate::ID,
+            set_pool_allowlist_entry::Accounts {
+                stake_pool: &stake_pool,
+                stake_pool_owner: pool_owner,
+                central_state: &self.central_state,
+                allowlist_entry: &PoolAllowlistEntry::create_key(&stake_pool, staker, &crate::ID).0,
+                fee_payer: &self.payer,
+                system_program: &system_program::ID,
+            },
+            set_pool_allowlist_entry::Params {
+                staker: *staker,
+                allowed,
+            },
+        ))
+    }
+
+    /// Sets the ix gate of the central state, 0 freezes every instruction
+    pub fn program_freeze(&mut self, ix_gate: u128) -> ProgramResult {
+        self.process_instruction(admin_program_freeze(
+            crate::ID,
+            admin_program_freeze::Accounts {
+                central_state: &self.central_state,
+                authority: &self.payer,
+                fee_payer: &self.payer,
+                system_program: &system_program::ID,
+                role_table: Some(&RoleTable::create_key(&self.central_state, &crate::ID).0),
+            },
+            admin_program_freeze::Params { ix_gate },
+        ))
+    }
+
+    /// Withdraws the principal of the stake account of `owner` in the pool of `pool_owner`, or of
+    /// its bond V2 unlocking at `bond_unlock_timestamp`, to its associated token account
+    pub fn emergency_withdraw(
+        &mut self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        bond_unlock_timestamp: Option<Option<i64>>,
+    ) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        let position = match bond_unlock_timestamp {
+            Some(unlock_timestamp) => {
+                BondV2Account::create_key(owner, &stake_pool, unlock_timestamp, &crate::ID).0
+            }
+            None => self.get_stake_account_pda(&stake_pool, owner).0,
+        };
+        self.process_instruction(emergency_withdraw(
+            crate::ID,
+            emergency_withdraw::Accounts {
+                central_state: &self.central_state,
+                stake_pool: &stake_pool,
+                position: &position,
+                owner,
+                destination_token: &self.get_ata(owner),
+                vault: &self.get_ata(&stake_pool),
+                spl_token_program: &self.token_program,
+                mint: &self.mint,
+                pool_boost: Some(&PoolBoost::create_key(&stake_pool, &crate::ID).0),
+                receipt_mint: self.skipped_account(),
+                receipt_account: self.skipped_account(),
+                receipt_reserve: self.skipped_account(),
+                transfer_hook_accounts: &self.transfer_hook_accounts,
+            },
+            emergency_withdraw::Params {},
+        ))
+    }
+
+    /// Burns the receipts of `holder` in the pool of `pool_owner` to withdraw the stake backing them
+    /// after a maximum freeze
+    pub fn emergency_withdraw_receipts(
+        &mut self,
+        holder: &Pubkey,
+        pool_owner: &Pubkey,
+    ) -> ProgramResult {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        let receipt_mint = self.get_receipt_mint(pool_owner);
+        self.process_instruction(emergency_withdraw(
+            crate::ID,
+            emergency_withdraw::Accounts {
+                central_state: &self.central_state,
+                stake_pool: &stake_pool,
+                position: &self.get_stake_account_pda(&stake_pool, &receipt_mint).0,
+                owner: holder,
+                destination_token: &self.get_ata(holder),
+                vault: &self.get_ata(&stake_pool),
+                spl_token_program: &self.token_program,
+                mint: &self.mint,
+                pool_boost: Some(&PoolBoost::create_key(&stake_pool, &crate::ID).0),
+                receipt_mint: Some(&receipt_mint),
+                receipt_account: Some(&self.get_receipt_account(holder, pool_owner)),
+                receipt_reserve: Some(&self.get_ata(&receipt_mint)),
+                transfer_hook_accounts: &self.transfer_hook_accounts,
+            },
+            emergency_withdraw::Params {},
+        ))
+    }
+
+    /// Delegates the admin instruction `ix` to `new_authority`, signed by the current `authority`
+    /// of the role
+    pub fn set_admin_role(
+        &mut self,
+        authority: &Pubkey,
+        ix: ProgramInstruction,
+        new_authority: &Pubkey,
+    ) -> ProgramResult {
+        self.process_instruction(admin_set_role(
+            crate::ID,
+            admin_set_role::Accounts {
+                authority,
+                central_state: &self.central_state,
+                role_table: &RoleTable::create_key(&self.central_state, &crate::ID).0,
+                fee_payer: &self.payer,
+                system_program: &system_program::ID,
+            },
+            admin_set_role::Params {
+                ix,
+                authority: *new_authority,
+            },
+        ))
+    }
+
+    /// Renounces the admin instruction `ix`, signed by the `authority` of its role
+    pub fn admin_renounce(&mut self, authority: &Pubkey, ix: ProgramInstruction) -> ProgramResult {
+        self.process_instruction(admin_renounce(
+            crate::ID,
+            admin_renounce::Accounts {
+                central_state: &self.central_state,
+                authority,
+                role_table: Some(&RoleTable::create_key(&self.central_state, &crate::ID).0),
+            },
+            admin_renounce::Params { ix },
+        ))
+    }
+
+    /// Changes the daily inflation, signed by `authority`
+    pub fn change_inflation(&mut self, authority: &Pubkey, daily_inflation: u64) -> ProgramResult {
+        self.process_instruction(change_inflation(
+            crate::ID,
+            change_inflation::Accounts {
+                central_state: &self.central_state,
+                authority,
+                mint: &self.mint,
+                role_table: Some(&RoleTable::create_key(&self.central_state, &crate::ID).0),
+            },
+            change_inflation::Params { daily_inflation },
+        ))
+    }
+
+    /// Sets the protocol fee, signed by `authority`
+    pub fn set_protocol_fee(
+        &mut self,
+        authority: &Pubkey,
+        protocol_fee_basis_points: u16,
+    ) -> ProgramResult {
+        self.process_instruction(admin_set_protocol_fee(
+            crate::ID,
+            admin_set_protocol_fee::Accounts {
+                authority,
+                central_state: &self.central_state,
+                role_table: Some(&RoleTable::create_key(&self.central_state, &crate::ID).0),
+            },
+            admin_set_protocol_fee::Params {
+                protocol_fee_basis_points,
+            },
+        ))
+    }
+
+    /// Changes the central state authority, the admin of the role table once the roles are enabled
+    pub fn change_central_state_authority(
+        &mut self,
+        authority: &Pubkey,
+        new_authority: &Pubkey,
+    ) -> ProgramResult {
+        self.process_instruction(change_central_state_authority(
+            crate::ID,
+            change_central_state_authority::Accounts {
+                central_state: &self.central_state,
+                authority,
+                role_table: Some(&RoleTable::create_key(&self.central_state, &crate::ID).0),
+            },
+            change_central_state_authority::Params {
+                new_authority: *new_authority,
+            },
+        ))
+    }
+
+    /// Sets the emission cap of the central state, 0 removes it
+    pub fn set_emission_cap(&mut self, authority: &Pubkey, emission_cap: u64) -> ProgramResult {
+        self.process_instruction(admin_set_emission_cap(
+            crate::ID,
+            admin_set_emission_cap::Accounts {
+                authority,
+                central_state: &self.central_state,
+                mint: &self.mint,
+                role_table: Some(&RoleTable::create_key(&self.central_state, &crate::ID).0),
+            },
+            admin_set_emission_cap::Params { emission_cap },
+        ))
+    }
+
+    // The recipient token account of an existing royalty account
+    fn royalty_ata(&self, royalty_account: &Pubkey) -> Option<Pubkey> {
+        self.get::<RoyaltyAccount>(royalty_account)
+            .ok()
+            .map(|r| r.recipient_ata)
+    }
+
+    // ---------------------------------------------------------------------------------------------
+    // Stats
+    // ---------------------------------------------------------------------------------------------
+
+    /// The central state
+    pub fn central_state_stats(&self) -> Result<CentralStateV2, ProgramError> {
+        self.get_versioned(&self.central_state)
+    }
+
+    /// The header of the stake pool of `pool_owner`
+    pub fn pool_stats(&self, pool_owner: &Pubkey) -> Result<StakePoolHeader, ProgramError> {
+        self.get(&self.get_pool_pda(pool_owner))
+    }
+
+    /// The pool boost of the pool of `pool_owner`
+    pub fn pool_boost_stats(&self, pool_owner: &Pubkey) -> Result<PoolBoost, ProgramError> {
+        let pool = self.get_pool_pda(pool_owner);
+        self.get(&PoolBoost::create_key(&pool, &crate::ID).0)
+    }
+
+    /// The pool of `pool_owner` with its rewards buffer or reward index
+    pub fn stake_pool(&self, pool_owner: &Pubkey) -> Result<StakePoolHeaped, ProgramError> {
+        let account = self
+            .accounts
+            .get(&self.get_pool_pda(pool_owner))
+            .ok_or(ProgramError::UninitializedAccount)?;
+        Ok(StakePoolHeaped::from_buffer(&account.data))
+    }
+
+    /// The stake account of `staker` in the pool of `pool_owner`
+    pub fn stake_account_stats(
+        &self,
+        staker: &Pubkey,
+        pool_owner: &Pubkey,
+    ) -> Result<StakeAccount, ProgramError> {
+        let stake_pool = self.get_pool_pda(pool_owner);
+        self.get_versioned(&self.get_stake_account_pda(&stake_pool, staker).0)
+    }
+
+    /// The bond V2 of `owner` in the pool of `pool_owner`
+    pub fn bond_v2_stats(
+        &self,
+        owner: &Pubkey,
+        pool_owner: &Pubkey,
+        unlock_timestamp: Option<i64>,
+    ) -> Result<BondV2Account, ProgramError> {
+        let pool = self.get_pool_pda(pool_owner);
+        self.get_versioned(&BondV2Account::create_key(owner, &pool, unlock_timestamp, &crate::ID).0)
+    }
+
+    /// The voter weight record of `voter`
+    pub fn voter_weight_record(&self, voter: &Pubkey) -> Result<VoterWeightRecord, ProgramError> {
+        self.get(&VoterWeightRecord::create_key(&self.central_state, voter, &crate::ID).0)
+    }
+
+    /// The max voter weight record of the central state
+    pub fn max_voter_weight_record(&self) -> Result<MaxVoterWeightRecord, ProgramError> {
+        self.get(&MaxVoterWeightRecord::create_key(&self.central_state, &crate::ID).0)
+    }
+
+    /// The role table of the central state
+    pub fn role_table(&self) -> Result<RoleTable, ProgramError> {
+        self.get(&RoleTable::create_key(&self.central_state, &crate::ID).0)
+    }
+}
@@ -1,79 +1,64 @@
 //! Utils
 use solana_program::{
-    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
-    program_error::ProgramError, program_pack::Pack,
+    account_info::{next_account_info, AccountInfo}, clock::Clock, entrypoint::ProgramResult, msg,
+    program::invoke,
+    program_error::ProgramError,
     pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
     sysvar::Sysvar,
 };
-use spl_token::state::Account;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token_2022::extension::cpi_guard::CpiGuard;
+use spl_token_2022::extension::memo_transfer::MemoTransfer;
+use spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use spl_token_2022::state::{Account, Mint};
 
 use crate::error::AccessError;
 use crate::instruction::ProgramInstruction;
-use crate::state::{AUTHORIZED_BOND_SELLERS, BondAccount, RoyaltyAccount};
-use crate::state::{ACCESS_MINT, STAKE_BUFFER_LEN, StakeAccount, StakePoolRef};
+use crate::rewards;
+use crate::state::{AUTHORIZED_BOND_SELLERS, BondAccount, BondV2Account, PoolAllowlistEntry, PoolControls, PoolFeeOverride, RoleTable, RoyaltyAccount, Tag};
+use crate::state::{CentralStateV2, STAKE_BUFFER_LEN, StakeAccount, StakePool, StakePoolHeader, StakePoolRef};
 
 /// Cumulate the claimable rewards from the last claimed day to the present.
 /// Result is in FP32 format.
 ///
+/// * `last_claimed_index` The reward checkpoint of the account, `None` for the pool owner and the V1 bonds
 /// * `staker` Compute the reward for a staker or a pool owner
 pub fn calc_reward_fp32(
     current_offset: u64,
     last_claimed_offset: u64,
+    last_claimed_index: Option<u128>,
     stake_pool: &StakePoolRef,
     staker: bool,
     allow_zero_rewards: bool,
 ) -> Result<u128, ProgramError> {
-    let mut nb_days_to_claim = current_offset.saturating_sub(last_claimed_offset);
-    msg!("Nb of days behind {}", nb_days_to_claim);
+    msg!("Nb of days behind {}", current_offset.saturating_sub(last_claimed_offset));
     msg!("Last claimed offset {}", last_claimed_offset);
     msg!("Current offset {}", current_offset);
-    nb_days_to_claim = std::cmp::min(nb_days_to_claim, STAKE_BUFFER_LEN);
-    msg!("Nb of days to claim {}", nb_days_to_claim);
-    if nb_days_to_claim == 0 {
-        if !allow_zero_rewards {
-            msg!("No rewards to claim, no operation.");
-            return Err(AccessError::NoOp.into());
-        }
-        return Ok(0);
-    }
-
-    if current_offset > stake_pool.header.current_day_idx as u64 {
-        #[cfg(not(any(feature = "days-to-sec-10s", feature = "days-to-sec-15m")))]
-        return Err(AccessError::PoolMustBeCranked.into());
-    }
-
     msg!(
         "Stake pool current day idx wrapped {}",
         (stake_pool.header.current_day_idx as u64) % STAKE_BUFFER_LEN
     );
-    // Saturating as we don't want to wrap around when there haven't been sufficient cranks
-    let mut i = (stake_pool.header.current_day_idx as u64).saturating_sub(nb_days_to_claim)
-        % STAKE_BUFFER_LEN;
-
-    // Compute reward for all past days
-    let mut reward: u128 = 0;
-    loop {
-        let curr_day_reward = if staker {
-            stake_pool.balances[i as usize].stakers_reward
-        } else {
-            stake_pool.balances[i as usize].pool_reward
-        };
-        reward = reward
-            .checked_add(curr_day_reward)
-            .ok_or(AccessError::Overflow)?;
-        i = (i + 1) % STAKE_BUFFER_LEN;
-        if i == (stake_pool.header.current_day_idx as u64) % STAKE_BUFFER_LEN {
-            break;
+
+    let reward = rewards::calc_reward_fp32(
+        current_offset,
+        last_claimed_offset,
+        last_claimed_index,
+        stake_pool,
+        staker,
+        allow_zero_rewards,
+    )
+    .map_err(|e| {
+        if let AccessError::NoOp = e {
+            msg!("No rewards to claim, no operation.");
         }
-    }
+        e
+    })?;
 
     msg!("Reward is {}", reward);
 
-    if reward == 0 && !allow_zero_rewards {
-        msg!("No rewards to claim, no operation.");
-        return Err(AccessError::NoOp.into());
-    }
-
     Ok(reward)
 }
 
@@ -133,6 +118,84 @@ pub fn check_signer(account: &AccountInfo, error: AccessError) -> ProgramResult
     Ok(())
 }
 
+#[allow(missing_docs)]
+pub fn is_token_program(key: &Pubkey) -> bool {
+    key == &spl_token::ID || key == &spl_token_2022::ID
+}
+
+/// Checks that the account is either the SPL token or the Token-2022 program
+pub fn check_token_program(account: &AccountInfo) -> ProgramResult {
+    if !is_token_program(account.key) {
+        return Err(AccessError::WrongSplTokenProgramId.into());
+    }
+    Ok(())
+}
+
+/// Checks that the account is owned by either the SPL token or the Token-2022 program
+pub fn check_token_program_owner(account: &AccountInfo, error: AccessError) -> ProgramResult {
+    if !is_token_program(account.owner) {
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+/// Unpacks the base state of a token account owned by either token program
+pub fn unpack_token_account(account: &AccountInfo) -> Result<Account, ProgramError> {
+    check_token_program_owner(account, AccessError::WrongTokenAccountOwner)?;
+    let data = account.data.borrow();
+    Ok(StateWithExtensions::<Account>::unpack(&data)?.base)
+}
+
+/// Unpacks the base state of a mint owned by either token program
+pub fn unpack_mint(account: &AccountInfo) -> Result<Mint, ProgramError> {
+    check_token_program_owner(account, AccessError::WrongOwner)?;
+    let data = account.data.borrow();
+    Ok(StateWithExtensions::<Mint>::unpack(&data)?.base)
+}
+
+/// Token-2022 mint extensions that would break the accounting of the program, the transfer fees
+/// would credit the stakes and the bonds with more tokens than the vaults receive
+pub fn assert_supported_mint(account: &AccountInfo) -> ProgramResult {
+    check_token_program_owner(account, AccessError::WrongOwner)?;
+    let data = account.data.borrow();
+    let state = StateWithExtensions::<Mint>::unpack(&data)?;
+    if state.get_extension::<TransferFeeConfig>().is_ok() {
+        msg!("The mint cannot have transfer fees");
+        return Err(AccessError::UnsupportedTokenExtension.into());
+    }
+    Ok(())
+}
+
+/// The next optional account of an instruction, the program id stands for an optional account
+/// skipped before the trailing accounts
+pub fn next_optional_account_info<'a, 'b>(
+    accounts_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    program_id: &Pubkey,
+) -> Option<&'a AccountInfo<'b>> {
+    next_account_info(accounts_iter)
+        .ok()
+        .filter(|account| account.key != program_id)
+}
+
+/// Token-2022 account extensions that would prevent the program from moving tokens in or out of its vaults
+fn assert_supported_extensions(account: &AccountInfo) -> ProgramResult {
+    let data = account.data.borrow();
+    let state = StateWithExtensions::<Account>::unpack(&data)?;
+    if let Ok(memo_transfer) = state.get_extension::<MemoTransfer>() {
+        if bool::from(memo_transfer.require_incoming_transfer_memos) {
+            msg!("The vault cannot require incoming transfer memos");
+            return Err(AccessError::UnsupportedTokenExtension.into());
+        }
+    }
+    if let Ok(cpi_guard) = state.get_extension::<CpiGuard>() {
+        if bool::from(cpi_guard.lock_cpi) {
+            msg!("The vault cannot have the CPI guard enabled");
+            return Err(AccessError::UnsupportedTokenExtension.into());
+        }
+    }
+    Ok(())
+}
+
 #[allow(missing_docs)]
 pub fn assert_empty_stake_pool(stake_pool: &StakePoolRef) -> ProgramResult {
     if stake_pool.header.total_staked != 0 {
@@ -152,8 +215,30 @@ pub fn assert_empty_stake_account(stake_account: &StakeAccount) -> ProgramResult
 }
 
 #[allow(missing_docs)]
-pub fn assert_valid_vault(account: &AccountInfo, vault_signer: &Pubkey) -> ProgramResult {
-    let acc = Account::unpack(&account.data.borrow())?;
+pub fn assert_bond_v2_rewards_claimed(
+    bond: &BondV2Account,
+    stake_pool_header: &StakePoolHeader,
+    central_state: &CentralStateV2,
+) -> ProgramResult {
+    if (stake_pool_header.current_day_idx as u64) < central_state.get_current_offset()? {
+        return Err(AccessError::PoolMustBeCranked.into());
+    }
+    if bond.last_claimed_offset < stake_pool_header.current_day_idx as u64
+        || bond.unclaimed_rewards != 0
+    {
+        msg!("The rewards of the bond must be claimed first");
+        return Err(AccessError::UnclaimedRewards.into());
+    }
+    Ok(())
+}
+
+#[allow(missing_docs)]
+pub fn assert_valid_vault(
+    account: &AccountInfo,
+    vault_signer: &Pubkey,
+    mint: &Pubkey,
+) -> ProgramResult {
+    let acc = unpack_token_account(account)?;
     if &acc.owner != vault_signer {
         msg!("The vault account should be owned by the stake pool signer");
         return Err(ProgramError::InvalidArgument);
@@ -162,11 +247,12 @@ pub fn assert_valid_vault(account: &AccountInfo, vault_signer: &Pubkey) -> Progr
         msg!("Invalid vault account provided");
         return Err(ProgramError::InvalidArgument);
     }
-    if acc.mint != ACCESS_MINT {
+    if &acc.mint != mint {
         msg!("Invalid ACCESS mint");
         #[cfg(not(feature = "no-mint-check"))]
-        return Err(ProgramError::InvalidArgument);
+        return Err(AccessError::WrongMint.into());
     }
+    assert_supported_extensions(account)?;
     Ok(())
 }
 
@@ -203,16 +289,41 @@ pub fn assert_bond_derivation(
 
 #[allow(missing_docs)]
 pub fn assert_valid_fee(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
-    check_account_owner(account, &spl_token::ID, AccessError::WrongOwner)?;
-    let acc = Account::unpack(&account.data.borrow())?;
+    check_token_program_owner(account, AccessError::WrongOwner)?;
+    let acc = unpack_token_account(account)?;
     if owner != &acc.owner {
         msg!("Invalid fee account owner");
         return Err(ProgramError::IllegalOwner);
     }
     assert_no_close_or_delegate(&acc)?;
+    assert_supported_extensions(account)?;
     Ok(())
 }
 
+/// Checks the receipt mint of a stake pool, its reserve (the receipt mint ATA of the ACS mint) and the stake account of the receipt holders.
+/// Returns the nonce of the receipt mint
+pub fn check_receipt_accounts(
+    stake_pool: &Pubkey,
+    token_mint: &Pubkey,
+    token_program: &Pubkey,
+    receipt_mint: &AccountInfo,
+    receipt_reserve: &AccountInfo,
+    stake_account: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (receipt_mint_key, receipt_mint_nonce) =
+        StakePoolHeader::find_receipt_mint(stake_pool, program_id);
+    check_account_key(receipt_mint, &receipt_mint_key, AccessError::WrongReceiptMint)?;
+    check_account_key(
+        receipt_reserve,
+        &get_associated_token_address_with_program_id(&receipt_mint_key, token_mint, token_program),
+        AccessError::WrongReceiptMint,
+    )?;
+    let (stake_key, _) = StakeAccount::find_key(&receipt_mint_key, stake_pool, program_id);
+    check_account_key(stake_account, &stake_key, AccessError::AccountNotDeterministic)?;
+    Ok(receipt_mint_nonce)
+}
+
 #[allow(missing_docs)]
 pub fn assert_no_close_or_delegate(token_account: &Account) -> ProgramResult {
     if token_account.delegate.is_some() || token_account.close_authority.is_some() {
@@ -234,7 +345,7 @@ pub fn retrieve_royalty_account(
     }
 
     let royalty_account_data = RoyaltyAccount::from_account_info(royalty_account)?;
-    if royalty_account_data.expiration_date < Clock::get()?.unix_timestamp as u64 {
+    if !rewards::is_royalty_active(&royalty_account_data, Clock::get()?.unix_timestamp) {
         return Ok(None); // Royalty account has expired - no royalty split is applicable
     }
 
@@ -242,11 +353,7 @@ pub fn retrieve_royalty_account(
         return Err(AccessError::RoyaltyAtaNotProvided.into());
     }
 
-    check_account_owner(
-        royalty_ata.unwrap(),
-        &spl_token::ID,
-        AccessError::WrongOwner,
-    )?;
+    check_token_program_owner(royalty_ata.unwrap(), AccessError::WrongOwner)?;
 
     check_account_key(
         royalty_ata.unwrap(),
@@ -257,6 +364,171 @@ pub fn retrieve_royalty_account(
     Ok(Some(royalty_account_data))
 }
 
+/// Checks that the central state is the original one derived from the program id only.
+/// Used by the deprecated instructions which cannot relate their accounts to a tenant central state
+pub fn assert_original_central_state(
+    central_state_key: &Pubkey,
+    central_state: &CentralStateV2,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    if central_state.scope(central_state_key, program_id).is_some() {
+        msg!("This instruction is only available for the original central state");
+        return Err(AccessError::WrongCentralState.into());
+    }
+    Ok(())
+}
+
+/// Checks that the stake pool was derived for the given central state
+pub fn check_pool_central_state(
+    stake_pool_key: &Pubkey,
+    stake_pool_header: &StakePoolHeader,
+    central_state_key: &Pubkey,
+    central_state: &CentralStateV2,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    let scope = central_state.scope(central_state_key, program_id);
+    let derived_key = StakePool::create_scoped_key(
+        &stake_pool_header.nonce,
+        &Pubkey::new_from_array(stake_pool_header.owner),
+        scope.as_ref(),
+        program_id,
+    )?;
+    if &derived_key != stake_pool_key {
+        msg!("The stake pool does not belong to the central state");
+        return Err(AccessError::WrongCentralState.into());
+    }
+    Ok(())
+}
+
+///  This function checks if there is an existing fee override for the given stake pool.
+///  Checks the derivation and the ownership of the override account.
+///  Returns the override data if it exists. Otherwise returns None.
+pub fn retrieve_pool_fee_override(
+    pool_fee_override: Option<&AccountInfo>,
+    stake_pool: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Option<PoolFeeOverride>, ProgramError> {
+    let pool_fee_override = match pool_fee_override {
+        Some(account) => account,
+        None => return Ok(None),
+    };
+
+    let (derived_key, _) = PoolFeeOverride::create_key(stake_pool, program_id);
+    check_account_key(
+        pool_fee_override,
+        &derived_key,
+        AccessError::AccountNotDeterministic,
+    )?;
+
+    if pool_fee_override.data_is_empty() {
+        return Ok(None);
+    }
+
+    check_account_owner(pool_fee_override, program_id, AccessError::WrongOwner)?;
+    let pool_fee_override_data = PoolFeeOverride::from_account_info(pool_fee_override)?;
+
+    Ok(Some(pool_fee_override_data))
+}
+
+/// Checks a new stake of `amount` for `staker` against the pool controls of the stake pool, if any.
+/// The allowlist entry is only required when the allowlist of the pool is enabled
+pub fn check_pool_controls(
+    stake_pool_header: &StakePoolHeader,
+    stake_pool: &Pubkey,
+    pool_controls: Option<&AccountInfo>,
+    allowlist_entry: Option<&AccountInfo>,
+    staker: &Pubkey,
+    amount: u64,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    if !stake_pool_header.has_controls() {
+        return Ok(());
+    }
+    let pool_controls = PoolControls::get_checked(
+        pool_controls.ok_or(ProgramError::NotEnoughAccountKeys)?,
+        stake_pool,
+        program_id,
+    )?;
+
+    if pool_controls.paused {
+        return Err(AccessError::StakePoolPaused.into());
+    }
+
+    let total_staked = stake_pool_header
+        .total_staked
+        .checked_add(amount)
+        .ok_or(AccessError::Overflow)?;
+    if pool_controls.max_total_staked != 0 && total_staked > pool_controls.max_total_staked {
+        msg!(
+            "The stake pool cap is {}, {} already staked",
+            pool_controls.max_total_staked,
+            stake_pool_header.total_staked
+        );
+        return Err(AccessError::StakePoolCapExceeded.into());
+    }
+
+    if pool_controls.allowlist_enabled {
+        let allowlist_entry = allowlist_entry.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let (derived_key, _) = PoolAllowlistEntry::create_key(stake_pool, staker, program_id);
+        check_account_key(
+            allowlist_entry,
+            &derived_key,
+            AccessError::AccountNotDeterministic,
+        )?;
+        if allowlist_entry.data_is_empty()
+            || allowlist_entry.owner != program_id
+            || PoolAllowlistEntry::from_account_info(allowlist_entry)?.tag != Tag::PoolAllowlistEntry
+        {
+            msg!("No allowlist entry for {}", staker);
+            return Err(AccessError::StakerNotAllowlisted.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the signer of an admin instruction. Once the roles are enabled the authority of the
+/// central state is the role table, the signer must then be the authority of the role of `ix`
+pub fn check_admin_authority(
+    authority: &AccountInfo,
+    role_table: Option<&AccountInfo>,
+    central_state_key: &Pubkey,
+    central_state: &CentralStateV2,
+    ix: &ProgramInstruction,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    let expected_authority = match role_table {
+        Some(role_table) if role_table.key == &central_state.authority => {
+            RoleTable::get_checked(role_table, central_state_key, program_id)?.authority(ix)
+        }
+        _ => central_state.authority,
+    };
+    check_account_key(
+        authority,
+        &expected_authority,
+        AccessError::WrongCentralStateAuthority,
+    )
+}
+
+/// Grows a program account to `new_data_len`, the fee payer tops up its rent exemption
+pub fn grow_account<'a>(
+    account: &AccountInfo<'a>,
+    new_data_len: usize,
+    fee_payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    let lamports_diff = Rent::get()?
+        .minimum_balance(new_data_len)
+        .saturating_sub(account.lamports());
+    if lamports_diff > 0 {
+        invoke(
+            &system_instruction::transfer(fee_payer.key, account.key, lamports_diff),
+            &[fee_payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    account.realloc(new_data_len, true)
+}
+
 #[allow(missing_docs)]
 pub fn is_admin_renouncable_instruction(instruction: &ProgramInstruction) -> bool {
     matches!(instruction,
@@ -268,6 +540,12 @@ pub fn is_admin_renouncable_instruction(instruction: &ProgramInstruction) -> boo
         ProgramInstruction::AdminSetupFeeSplit |
         ProgramInstruction::AdminSetProtocolFee |
         ProgramInstruction::AdminProgramFreeze |
-        ProgramInstruction::AdminChangeFreezeAuthority
+        ProgramInstruction::AdminChangeFreezeAuthority |
+        ProgramInstruction::AdminSetPoolFeeOverride |
+        ProgramInstruction::AdminSetKeeperReward |
+        ProgramInstruction::AdminSetProtocolConfig |
+        ProgramInstruction::AdminSetBoostConfig |
+        ProgramInstruction::AdminSetGovernanceConfig |
+        ProgramInstruction::AdminSetEmissionCap
     )
 }
\ No newline at end of file
@@ -0,0 +1,171 @@
+//! Versioned account layouts
+//!
+//! An account with a versioned layout starts with its tag and a version byte, the high bit of the
+//! tag marks the presence of the version byte. The layouts without version byte are version 0.
+//!
+//! A new layout of an account implements [`Migrate`] from its previous layout. The processors
+//! decode an account stored with any earlier layout and write it back with the layout it has until
+//! the permissionless `MigrateAccount` instruction grows it and rewrites it with the latest one.
+use std::cmp::Ordering;
+use std::io::{Result as IoResult, Write};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use num_traits::FromPrimitive;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+};
+
+use crate::error::AccessError;
+use crate::state::Tag;
+use crate::utils::grow_account;
+
+/// Set on the tag byte of the accounts followed by a version byte
+pub const VERSION_FLAG: u8 = 0x80;
+
+/// Length of the tag and the version byte of a versioned layout
+pub const VERSIONED_HEADER_LEN: usize = 2;
+
+/// The tag and the layout version of an account
+pub fn read_header(data: &[u8]) -> Result<(Tag, u8), ProgramError> {
+    let tag_byte = *data.first().ok_or(ProgramError::InvalidAccountData)?;
+    let version = if tag_byte & VERSION_FLAG == 0 {
+        0
+    } else {
+        *data.get(1).ok_or(ProgramError::InvalidAccountData)?
+    };
+    let tag = Tag::from_u8(tag_byte & !VERSION_FLAG).ok_or(ProgramError::InvalidAccountData)?;
+    Ok((tag, version))
+}
+
+/// Writes the tag of a layout and its version byte if it is versioned
+pub fn write_header<W: Write>(tag: Tag, version: u8, writer: &mut W) -> IoResult<()> {
+    if version == 0 {
+        return writer.write_all(&[tag as u8]);
+    }
+    writer.write_all(&[tag as u8 | VERSION_FLAG, version])
+}
+
+/// Reads the tag of a versioned layout and checks its version
+pub fn read_versioned_tag<R: std::io::Read>(reader: &mut R, version: u8) -> IoResult<Tag> {
+    let header = <[u8; VERSIONED_HEADER_LEN]>::deserialize_reader(reader)?;
+    let (tag, stored_version) =
+        read_header(&header).map_err(|_| std::io::Error::from(std::io::ErrorKind::InvalidData))?;
+    if header[0] & VERSION_FLAG == 0 || stored_version != version {
+        return Err(std::io::ErrorKind::InvalidData.into());
+    }
+    Ok(tag)
+}
+
+/// Replaces the tag of an account and keeps its version
+pub fn set_tag(data: &mut [u8], tag: Tag) -> ProgramResult {
+    let tag_byte = data.first_mut().ok_or(ProgramError::InvalidAccountData)?;
+    *tag_byte = (*tag_byte & VERSION_FLAG) | tag as u8;
+    Ok(())
+}
+
+/// The layout version of the data of an account, the uninitialized accounts get the latest one
+fn stored_version<T: Versioned>(data: &[u8]) -> Result<u8, ProgramError> {
+    match read_header(data)? {
+        (Tag::Uninitialized, _) => Ok(T::VERSION),
+        (_, version) => Ok(version),
+    }
+}
+
+/// A layout of a protocol account
+pub trait Versioned: Sized {
+    /// Version of the layout, 0 for a layout without version byte
+    const VERSION: u8;
+
+    /// Decodes an account stored with this layout or an earlier one
+    fn load(data: &[u8]) -> Result<Self, ProgramError>;
+
+    /// Writes the account with the layout of `dst`
+    fn store(&self, dst: &mut [u8]) -> ProgramResult;
+}
+
+/// A layout replacing a previous layout of the same account
+pub trait Migrate: Versioned + BorshSerialize + BorshDeserialize {
+    /// The layout replaced by this one
+    type Previous: Versioned;
+
+    /// Converts an account decoded with the previous layout
+    fn from_previous(previous: Self::Previous) -> Result<Self, ProgramError>;
+
+    /// Converts the account to the previous layout to write it in a non migrated account
+    fn to_previous(&self) -> Result<Self::Previous, ProgramError>;
+
+    /// Space allocated after the migrated data for the collections growing in place
+    fn reserved_len(&self) -> usize {
+        0
+    }
+}
+
+/// [`Versioned::load`] of a layout with a previous layout
+pub fn load_migrated<T: Migrate>(data: &[u8]) -> Result<T, ProgramError> {
+    let version = stored_version::<T>(data)?;
+    match version.cmp(&T::VERSION) {
+        Ordering::Equal => {
+            T::deserialize(&mut &data[..]).map_err(|_| ProgramError::InvalidAccountData)
+        }
+        Ordering::Less => T::from_previous(T::Previous::load(data)?),
+        Ordering::Greater => {
+            msg!("Unsupported account version {}", version);
+            Err(AccessError::UnsupportedAccountVersion.into())
+        }
+    }
+}
+
+/// [`Versioned::store`] of a layout with a previous layout
+pub fn store_migrated<T: Migrate>(account: &T, mut dst: &mut [u8]) -> ProgramResult {
+    if stored_version::<T>(dst)? < T::VERSION {
+        return account.to_previous()?.store(dst);
+    }
+    account
+        .serialize(&mut dst)
+        .map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// Rewrites an account with the latest layout `T`, the fee payer tops up the rent of the
+/// additional space
+pub fn migrate<'a, T: Migrate + bonfida_utils::BorshSize>(
+    account: &AccountInfo<'a>,
+    fee_payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    let (migrated, from_version) = {
+        let data = account.data.borrow();
+        (T::load(&data)?, stored_version::<T>(&data)?)
+    };
+    if from_version == T::VERSION {
+        msg!("The account already has the latest layout");
+        return Err(AccessError::NoOp.into());
+    }
+
+    let new_data_len = migrated.borsh_len() + migrated.reserved_len();
+    if account.data_len() < new_data_len {
+        grow_account(account, new_data_len, fee_payer, system_program)?;
+    }
+    migrated
+        .serialize(&mut &mut account.data.borrow_mut()[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    msg!(
+        "Account migrated from version {} to {}",
+        from_version,
+        T::VERSION
+    );
+    Ok(())
+}
+
+/// [`migrate`] for the instructions writing the fields of the latest layout, a no-op when the
+/// account already has it
+pub fn migrate_if_needed<'a, T: Migrate + bonfida_utils::BorshSize>(
+    account: &AccountInfo<'a>,
+    fee_payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    if stored_version::<T>(&account.data.borrow())? == T::VERSION {
+        return Ok(());
+    }
+    migrate::<T>(account, fee_payer, system_program)
+}
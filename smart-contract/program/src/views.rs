@@ -0,0 +1,107 @@
+//! Results of the read-only view instructions
+//!
+//! The view instructions change no state, they write one of these structures Borsh encoded with
+//! `set_return_data`. Off-chain they are read from the return data of a simulated transaction,
+//! on-chain integrators read them with [`read`] right after the CPI.
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    entrypoint::ProgramResult,
+    program::{get_return_data, set_return_data},
+    pubkey::Pubkey,
+};
+
+use crate::state::{CentralStateV2, StakePoolHeader};
+
+/// Returned by `get_claimable_rewards`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ClaimableRewardsView {
+    /// The rewards minted to the staker by a claim
+    pub reward: u64,
+    /// The royalties minted to the royalty recipient by a claim
+    pub royalty: u64,
+    /// The rewards are computed up to this day offset
+    pub offset: u64,
+    /// The pool has not been cranked for the current day yet
+    pub needs_crank: bool,
+}
+
+/// Returned by `get_pool_apr`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PoolAprView {
+    /// The yearly staker rewards per staked token in basis points (i.e 1% = 100)
+    pub stakers_apr_basis_points: u64,
+    /// The daily staker rewards per staked token in FP32
+    pub stakers_daily_reward_fp32: u128,
+    /// The daily rewards of the pool owner
+    pub pool_daily_reward: u64,
+    /// The amount staked in the pool
+    pub total_staked: u64,
+}
+
+/// Returned by `get_stake_summary`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct StakeSummaryView {
+    /// The owner of the stake
+    pub owner: Pubkey,
+    /// The stake pool
+    pub stake_pool: Pubkey,
+    /// The amount staked in the stake account
+    pub staked_amount: u64,
+    /// The amount locked in the bond V2 accounts
+    pub bonds_v2_amount: u64,
+    /// The sum of the staked and locked amounts
+    pub effective_stake: u64,
+    /// The minimum stake amount of the pool
+    pub minimum_stake_amount: u64,
+    /// The effective stake is at least the minimum stake amount of the pool
+    pub meets_minimum: bool,
+    /// The rewards of the stake account and of the bond V2 accounts before royalties
+    pub claimable_rewards: u64,
+}
+
+/// Returned by `get_central_state_info`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CentralStateInfoView {
+    /// Mint of the token being emitted
+    pub token_mint: Pubkey,
+    /// The admin authority
+    pub authority: Pubkey,
+    /// Daily inflation in token amount
+    pub daily_inflation: u64,
+    /// Total amount of staked tokens
+    pub total_staked: u64,
+    /// The last daily snapshot of the total amount staked
+    pub total_staked_snapshot: u64,
+    /// The day offset of the last snapshot
+    pub last_snapshot_offset: u64,
+    /// The day offset of the current time
+    pub current_offset: u64,
+    /// Creation timestamp
+    pub creation_time: i64,
+    /// Protocol fee basis points (i.e 1% = 100)
+    pub fee_basis_points: u16,
+    /// Map of the instructions that are not frozen
+    pub ix_gate: u128,
+}
+
+/// Writes a view to the return data of the transaction
+pub fn set<T: BorshSerialize>(view: &T) -> ProgramResult {
+    set_return_data(&view.try_to_vec()?);
+    Ok(())
+}
+
+/// Reads the view returned by a CPI to the program
+pub fn read<T: BorshDeserialize>(program_id: &Pubkey) -> Option<T> {
+    match get_return_data() {
+        Some((key, data)) if &key == program_id => T::try_from_slice(&data).ok(),
+        _ => None,
+    }
+}
+
+/// The last day offset for which the rewards of a pool can be computed
+pub fn claimable_offset(central_state: &CentralStateV2, stake_pool: &StakePoolHeader) -> u64 {
+    std::cmp::min(
+        central_state.last_snapshot_offset,
+        stake_pool.current_day_idx as u64,
+    )
+}
@@ -0,0 +1,66 @@
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+use access_protocol::error::AccessError;
+use access_protocol::instruction::claim_rewards;
+use access_protocol::state::{ProtocolConfig, RoyaltyAccount};
+use access_protocol::testing::ProtocolTest;
+
+// Runs with `cargo test --features testing --test access_nft_claim`, see `access_protocol::testing`
+
+#[test]
+fn access_nft_claim() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+    let pool_owner = pt.create_user_with_ata();
+    let staker = pt.create_user_with_ata();
+    pt.mint(&staker, 10_200).unwrap();
+    pt.create_pool(&pool_owner, 10_000).unwrap();
+    pt.activate_stake_pool(&pool_owner).unwrap();
+    pt.create_stake_account(&pool_owner, &staker).unwrap();
+    pt.stake(&pool_owner, &staker, 10_000).unwrap();
+    pt.sleep_days(1);
+    pt.crank_pool(&pool_owner).unwrap();
+
+    let stake_pool = pt.get_pool_pda(&pool_owner);
+    let (stake_account, _) = pt.get_stake_account_pda(&stake_pool, &staker);
+    let rewards_destination = pt.get_ata(&staker);
+    let royalty_account = RoyaltyAccount::create_key(&staker, &access_protocol::ID).0;
+    let protocol_config = ProtocolConfig::create_key(&pt.central_state, &access_protocol::ID).0;
+    let (central_state, mint, token_program) = (pt.central_state, pt.mint, pt.token_program);
+    let claim = |access_nft_signer: &Pubkey, protocol_config: Option<&Pubkey>| {
+        claim_rewards(
+            access_protocol::ID,
+            claim_rewards::Accounts {
+                stake_pool: &stake_pool,
+                stake_account: &stake_account,
+                owner: &staker,
+                rewards_destination: &rewards_destination,
+                central_state: &central_state,
+                mint: &mint,
+                access_nft_signer,
+                spl_token_program: &token_program,
+                owner_royalty_account: &royalty_account,
+                royalty_ata: protocol_config.map(|_| &access_protocol::ID),
+                protocol_config,
+            },
+            claim_rewards::Params {
+                allow_zero_rewards: false,
+            },
+            true,
+        )
+    };
+
+    // The signer of the Access NFT program is checked against the protocol config appended last
+    let signer = pt.access_nft_program_signer;
+    assert_eq!(
+        pt.process_instruction(claim(&signer, None)),
+        Err(ProgramError::NotEnoughAccountKeys)
+    );
+    assert_eq!(
+        pt.process_instruction(claim(&Pubkey::new_unique(), Some(&protocol_config))),
+        Err(AccessError::WrongAccessCnftAuthority.into())
+    );
+    pt.process_instruction(claim(&signer, Some(&protocol_config)))
+        .unwrap();
+    assert_eq!(pt.token_balance(&staker).unwrap(), 500_000);
+}
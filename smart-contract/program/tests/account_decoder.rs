@@ -0,0 +1,89 @@
+use solana_sdk::signer::Signer;
+
+use access_protocol::decoder::ProtocolAccount;
+use access_protocol::state::{Tag, REWARD_INDEX_SLOTS};
+
+use crate::common::test_runner::TestRunner;
+
+pub mod common;
+
+#[tokio::test]
+async fn account_decoder() {
+    // Setup the token + basic accounts
+    let mut tr = TestRunner::new(1_000_000).await.unwrap();
+
+    // Create users
+    let stake_pool_owner = tr.create_user_with_ata().await.unwrap();
+    let staker = tr.create_user_with_ata().await.unwrap();
+    let stake_pool_key = tr.get_pool_pda(&stake_pool_owner.pubkey());
+    let (stake_account_key, _) = tr.get_stake_account_pda(&stake_pool_key, &staker.pubkey());
+
+    // Setup a pool with one staker
+    tr.mint(&staker.pubkey(), 10_200).await.unwrap();
+    tr.create_pool(&stake_pool_owner, 10_000)
+        .await
+        .unwrap();
+    tr.activate_stake_pool(&stake_pool_owner.pubkey())
+        .await
+        .unwrap();
+    tr.create_stake_account(&stake_pool_owner.pubkey(), &staker.pubkey())
+        .await
+        .unwrap();
+    tr.stake(&stake_pool_owner.pubkey(), &staker, 10_000)
+        .await
+        .unwrap();
+
+    // Central state
+    let central_state_key = tr.get_central_state();
+    match tr.protocol_account(&central_state_key).await.unwrap() {
+        ProtocolAccount::CentralStateV2(central_state) => {
+            assert_eq!(central_state.daily_inflation, 1_000_000);
+            assert_eq!(central_state.total_staked, 10_000);
+        }
+        _ => panic!("The central state was not decoded"),
+    }
+
+    // Stake pool with its reward index
+    let account = tr.protocol_account(&stake_pool_key).await.unwrap();
+    assert_eq!(account.tag(), Tag::StakePool);
+    match account {
+        ProtocolAccount::StakePool(stake_pool) => {
+            assert_eq!(stake_pool.header.owner, stake_pool_owner.pubkey().to_bytes());
+            assert_eq!(stake_pool.header.total_staked, 10_000);
+            assert!(stake_pool.header.uses_reward_index());
+            assert_eq!(stake_pool.balances.len(), REWARD_INDEX_SLOTS);
+        }
+        _ => panic!("The stake pool was not decoded"),
+    }
+
+    // Stake account
+    match tr.protocol_account(&stake_account_key).await.unwrap() {
+        ProtocolAccount::StakeAccount(stake_account) => {
+            assert_eq!(stake_account.owner, staker.pubkey());
+            assert_eq!(stake_account.stake_pool, stake_pool_key);
+            assert_eq!(stake_account.stake_amount, 10_000);
+        }
+        _ => panic!("The stake account was not decoded"),
+    }
+
+    // Frozen accounts decode to the variant of their type
+    tr.freeze_account(&stake_account_key).await.unwrap();
+    let account = tr.protocol_account(&stake_account_key).await.unwrap();
+    assert!(account.is_frozen());
+    assert_eq!(account.tag(), Tag::FrozenStakeAccount);
+    assert!(matches!(account, ProtocolAccount::StakeAccount(_)));
+
+    tr.freeze_account(&stake_pool_key).await.unwrap();
+    let account = tr.protocol_account(&stake_pool_key).await.unwrap();
+    assert!(account.is_frozen());
+    assert!(matches!(account, ProtocolAccount::StakePool(_)));
+
+    // Unknown data
+    assert!(ProtocolAccount::decode(&[]).is_err());
+    assert!(ProtocolAccount::decode(&[Tag::Uninitialized as u8]).is_err());
+    assert!(ProtocolAccount::decode(&[Tag::StakePool as u8, 0, 0]).is_err());
+    assert!(matches!(
+        ProtocolAccount::decode(&[Tag::Deleted as u8]),
+        Ok(ProtocolAccount::Deleted)
+    ));
+}
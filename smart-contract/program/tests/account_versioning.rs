@@ -0,0 +1,95 @@
+use borsh::BorshSerialize;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+use access_protocol::error::AccessError;
+use access_protocol::state::{BondV2Account, StakeAccount, Tag, SECONDS_IN_DAY};
+use access_protocol::testing::ProtocolTest;
+use access_protocol::versioning::{read_header, Migrate, Versioned, VERSION_FLAG};
+
+// Runs with `cargo test --features testing --test account_versioning`, see `access_protocol::testing`
+
+fn err(e: AccessError) -> Result<(), ProgramError> {
+    Err(e.into())
+}
+
+fn version(pt: &ProtocolTest, key: &Pubkey) -> u8 {
+    read_header(&pt.account(key).unwrap().data).unwrap().1
+}
+
+// Rewrites an account with the layout it had before the version byte
+fn downgrade<T: Migrate>(pt: &mut ProtocolTest, key: &Pubkey)
+where
+    T::Previous: BorshSerialize,
+{
+    let mut account = pt.account(key).unwrap().clone();
+    let previous = T::load(&account.data).unwrap().to_previous().unwrap();
+    account.data = previous.try_to_vec().unwrap();
+    pt.set_account(*key, account);
+}
+
+#[test]
+fn account_versioning() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+    let pool_owner = pt.create_user_with_ata();
+    let staker = pt.create_user_with_ata();
+    pt.mint(&staker, 20_000).unwrap();
+    pt.create_pool(&pool_owner, 1_000).unwrap();
+    pt.activate_stake_pool(&pool_owner).unwrap();
+    pt.create_stake_account(&pool_owner, &staker).unwrap();
+    let pool_key = pt.get_pool_pda(&pool_owner);
+    let (stake_account, _) = pt.get_stake_account_pda(&pool_key, &staker);
+
+    // The new accounts have the latest layout
+    assert_eq!(version(&pt, &stake_account), StakeAccount::VERSION);
+    assert_eq!(pt.migrate_account(&stake_account), err(AccessError::NoOp));
+
+    // An account with the previous layout is used and written back with it
+    downgrade::<StakeAccount>(&mut pt, &stake_account);
+    let legacy_len = pt.account(&stake_account).unwrap().data.len();
+    pt.stake(&pool_owner, &staker, 10_000).unwrap();
+    assert_eq!(version(&pt, &stake_account), 0);
+    assert_eq!(pt.account(&stake_account).unwrap().data.len(), legacy_len);
+
+    // The migration keeps the fields
+    pt.migrate_account(&stake_account).unwrap();
+    assert_eq!(version(&pt, &stake_account), StakeAccount::VERSION);
+    let migrated = pt.stake_account_stats(&staker, &pool_owner).unwrap();
+    assert_eq!(migrated.tag, Tag::StakeAccount);
+    assert_eq!(migrated.owner, staker);
+    assert_eq!(migrated.stake_amount, 10_000);
+    assert_eq!(migrated.stake_pool, pool_key);
+    assert!(pt.account(&stake_account).unwrap().data.len() > legacy_len);
+    pt.unstake(&pool_owner, &staker, 1_000).unwrap();
+
+    // Bond V2 accounts
+    let unlock_date = Some(pt.clock().unix_timestamp + SECONDS_IN_DAY as i64);
+    pt.create_bond_v2(&staker, &pool_owner, unlock_date)
+        .unwrap();
+    let (bond, _) =
+        BondV2Account::create_key(&staker, &pool_key, unlock_date, &access_protocol::ID);
+    downgrade::<BondV2Account>(&mut pt, &bond);
+    pt.add_to_bond_v2(&staker, &staker, &pool_owner, 5_000, unlock_date)
+        .unwrap();
+    pt.migrate_account(&bond).unwrap();
+    assert_eq!(version(&pt, &bond), BondV2Account::VERSION);
+    assert_eq!(
+        pt.bond_v2_stats(&staker, &pool_owner, unlock_date)
+            .unwrap()
+            .amount,
+        5_000
+    );
+
+    // The accounts without a newer layout
+    assert_eq!(pt.migrate_account(&pool_key), err(AccessError::NoOp));
+
+    // A layout newer than the program
+    let mut account = pt.account(&stake_account).unwrap().clone();
+    assert_eq!(account.data[0], Tag::StakeAccount as u8 | VERSION_FLAG);
+    account.data[1] = StakeAccount::VERSION + 1;
+    pt.set_account(stake_account, account);
+    assert_eq!(
+        pt.unstake(&pool_owner, &staker, 1_000),
+        err(AccessError::UnsupportedAccountVersion)
+    );
+}
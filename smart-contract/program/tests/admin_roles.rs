@@ -0,0 +1,114 @@
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+use access_protocol::error::AccessError;
+use access_protocol::instruction::ProgramInstruction;
+use access_protocol::state::{RoleTable, SECONDS_IN_DAY};
+use access_protocol::testing::ProtocolTest;
+
+// Runs with `cargo test --features testing --test admin_roles`, see `access_protocol::testing`
+
+fn err(e: AccessError) -> Result<(), ProgramError> {
+    Err(e.into())
+}
+
+#[test]
+fn admin_roles() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+    let admin = pt.payer;
+    let fee_admin = Pubkey::new_unique();
+    let treasury = Pubkey::new_unique();
+    let new_admin = Pubkey::new_unique();
+    let role_table = RoleTable::create_key(&pt.central_state, &access_protocol::ID).0;
+
+    // Without roles the central state authority calls every admin instruction
+    pt.set_protocol_fee(&admin, 100).unwrap();
+    assert_eq!(
+        pt.set_admin_role(
+            &fee_admin,
+            ProgramInstruction::AdminSetProtocolFee,
+            &fee_admin
+        ),
+        err(AccessError::WrongCentralStateAuthority)
+    );
+    assert_eq!(
+        pt.set_admin_role(&admin, ProgramInstruction::Stake, &fee_admin),
+        err(AccessError::NotAnAdminInstruction)
+    );
+
+    // The first role makes the role table the authority of the central state
+    pt.set_admin_role(&admin, ProgramInstruction::AdminSetProtocolFee, &fee_admin)
+        .unwrap();
+    assert_eq!(pt.central_state_stats().unwrap().authority, role_table);
+    assert_eq!(pt.role_table().unwrap().admin, admin);
+    assert_eq!(
+        pt.set_protocol_fee(&admin, 200),
+        err(AccessError::WrongCentralStateAuthority)
+    );
+    pt.set_protocol_fee(&fee_admin, 200).unwrap();
+    assert_eq!(pt.central_state_stats().unwrap().fee_basis_points, 200);
+
+    // The admin keeps the instructions without a role but cannot take a role back
+    pt.set_boost_config(100 * SECONDS_IN_DAY as i64, 10_000, 20_000)
+        .unwrap();
+    assert_eq!(
+        pt.set_admin_role(&admin, ProgramInstruction::AdminSetProtocolFee, &admin),
+        err(AccessError::WrongCentralStateAuthority)
+    );
+    assert_eq!(
+        pt.set_admin_role(
+            &fee_admin,
+            ProgramInstruction::AdminSetProtocolFee,
+            &fee_admin
+        ),
+        err(AccessError::NoOp)
+    );
+
+    // A role is transferred by its authority
+    pt.set_admin_role(
+        &fee_admin,
+        ProgramInstruction::AdminSetProtocolFee,
+        &treasury,
+    )
+    .unwrap();
+    assert_eq!(
+        pt.set_protocol_fee(&fee_admin, 300),
+        err(AccessError::WrongCentralStateAuthority)
+    );
+    pt.set_protocol_fee(&treasury, 300).unwrap();
+
+    // Changing the central state authority replaces the admin of the role table
+    pt.change_central_state_authority(&admin, &new_admin)
+        .unwrap();
+    assert_eq!(pt.central_state_stats().unwrap().authority, role_table);
+    assert_eq!(pt.role_table().unwrap().admin, new_admin);
+    assert_eq!(
+        pt.set_boost_config(100 * SECONDS_IN_DAY as i64, 10_000, 20_000),
+        err(AccessError::WrongCentralStateAuthority)
+    );
+    assert_eq!(
+        pt.set_protocol_fee(&new_admin, 400),
+        err(AccessError::WrongCentralStateAuthority)
+    );
+
+    // A role is renounced by its authority only
+    assert_eq!(
+        pt.admin_renounce(&new_admin, ProgramInstruction::AdminSetProtocolFee),
+        err(AccessError::WrongCentralStateAuthority)
+    );
+    pt.admin_renounce(&treasury, ProgramInstruction::AdminSetProtocolFee)
+        .unwrap();
+    assert_eq!(
+        pt.set_protocol_fee(&treasury, 400),
+        err(AccessError::AlreadyRenounced)
+    );
+    assert_eq!(
+        pt.set_admin_role(
+            &treasury,
+            ProgramInstruction::AdminSetProtocolFee,
+            &new_admin
+        ),
+        err(AccessError::AlreadyRenounced)
+    );
+    assert_eq!(pt.central_state_stats().unwrap().fee_basis_points, 300);
+}
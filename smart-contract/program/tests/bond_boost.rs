@@ -0,0 +1,200 @@
+use std::mem::size_of;
+
+use borsh::BorshSerialize;
+use solana_program::program_error::ProgramError;
+
+use access_protocol::error::AccessError;
+use access_protocol::state::{CentralStateV2, FeeRecipient, MAX_FEE_RECIPIENTS, SECONDS_IN_DAY};
+use access_protocol::testing::ProtocolTest;
+use access_protocol::versioning::{read_header, Migrate, Versioned};
+use bonfida_utils::BorshSize;
+
+// Runs with `cargo test --features testing --test bond_boost`, see `access_protocol::testing`
+
+fn err(e: AccessError) -> Result<(), ProgramError> {
+    Err(e.into())
+}
+
+#[test]
+fn bond_boost() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+    let pool_owner = pt.create_user_with_ata();
+    let alice = pt.create_user_with_ata();
+    let bob = pt.create_user_with_ata();
+    let carol = pt.create_user_with_ata();
+    pt.mint(&alice, 11_000).unwrap();
+    pt.mint(&bob, 11_000).unwrap();
+    pt.mint(&carol, 1_500).unwrap();
+    pt.create_pool(&pool_owner, 1_000).unwrap();
+    pt.activate_stake_pool(&pool_owner).unwrap();
+
+    // Alice stakes, Bob locks the same amount for 100 days
+    pt.create_stake_account(&pool_owner, &alice).unwrap();
+    pt.stake(&pool_owner, &alice, 10_000).unwrap();
+    let unlock_date = Some(pt.clock().unix_timestamp + 100 * SECONDS_IN_DAY as i64);
+    pt.create_bond_v2(&bob, &pool_owner, unlock_date).unwrap();
+    pt.add_to_bond_v2(&bob, &bob, &pool_owner, 10_000, unlock_date)
+        .unwrap();
+
+    let alice_balance = pt.token_balance(&alice).unwrap();
+    let bob_balance = pt.token_balance(&bob).unwrap();
+
+    // The boosts cannot exceed the maximum
+    assert_eq!(
+        pt.set_boost_config(100 * SECONDS_IN_DAY as i64, 50_000, 20_000),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        pt.set_boost_config(0, 10_000, 20_000),
+        Err(ProgramError::InvalidArgument)
+    );
+    pt.set_boost_config(100 * SECONDS_IN_DAY as i64, 10_000, 20_000)
+        .unwrap();
+
+    // A full lock doubles the weight of the bond
+    pt.refresh_bond_v2_boost(&bob, &pool_owner, unlock_date)
+        .unwrap();
+    assert_eq!(
+        pt.refresh_bond_v2_boost(&bob, &pool_owner, unlock_date),
+        err(AccessError::NoOp)
+    );
+    assert_eq!(
+        pt.bond_v2_stats(&bob, &pool_owner, unlock_date)
+            .unwrap()
+            .boost,
+        10_000
+    );
+    assert_eq!(
+        pt.pool_boost_stats(&pool_owner).unwrap().total_boost,
+        10_000
+    );
+    // The boosts are tracked apart from the staked tokens
+    let central_state = pt.central_state_stats().unwrap();
+    assert_eq!(central_state.total_staked, 20_000);
+    assert_eq!(central_state.total_boost, 10_000);
+    assert_eq!(pt.pool_stats(&pool_owner).unwrap().total_staked, 20_000);
+
+    pt.sleep_days(1);
+    assert_eq!(
+        pt.refresh_bond_v2_boost(&bob, &pool_owner, unlock_date),
+        err(AccessError::PoolMustBeCranked)
+    );
+    pt.crank_pool(&pool_owner).unwrap();
+    pt.claim_staker_rewards(&pool_owner, &alice).unwrap();
+    pt.claim_bond_v2_rewards(&bob, &pool_owner, unlock_date)
+        .unwrap();
+    let alice_reward = pt.token_balance(&alice).unwrap() - alice_balance;
+    let bob_balance_after_claim = pt.token_balance(&bob).unwrap();
+    let bob_reward = bob_balance_after_claim - bob_balance;
+    assert!(alice_reward > 0);
+    assert!((bob_reward as i64 - 2 * alice_reward as i64).abs() <= 2);
+
+    // The boost decays with the remaining lock time, the rewards earned with the previous boost
+    // are settled in the bond
+    pt.sleep_days(49);
+    pt.crank_pool(&pool_owner).unwrap();
+    pt.refresh_bond_v2_boost(&bob, &pool_owner, unlock_date)
+        .unwrap();
+    let bond = pt.bond_v2_stats(&bob, &pool_owner, unlock_date).unwrap();
+    assert_eq!(bond.boost, 5_000);
+    assert!(bond.unclaimed_rewards > 0);
+    assert_eq!(pt.pool_boost_stats(&pool_owner).unwrap().total_boost, 5_000);
+    assert_eq!(pt.central_state_stats().unwrap().total_boost, 5_000);
+
+    // The settled rewards are paid by the next claim
+    pt.claim_bond_v2_rewards(&bob, &pool_owner, unlock_date)
+        .unwrap();
+    assert_eq!(
+        pt.token_balance(&bob).unwrap(),
+        bob_balance_after_claim + bond.unclaimed_rewards
+    );
+    assert_eq!(
+        pt.bond_v2_stats(&bob, &pool_owner, unlock_date)
+            .unwrap()
+            .unclaimed_rewards,
+        0
+    );
+
+    // The unlock removes the boost that was not refreshed
+    pt.sleep_days(51);
+    pt.crank_pool(&pool_owner).unwrap();
+    pt.claim_bond_v2_rewards(&bob, &pool_owner, unlock_date)
+        .unwrap();
+    pt.unlock_bond_v2(&bob, &pool_owner, unlock_date).unwrap();
+    assert_eq!(pt.pool_boost_stats(&pool_owner).unwrap().total_boost, 0);
+    let central_state = pt.central_state_stats().unwrap();
+    assert_eq!(central_state.total_staked, 10_000);
+    assert_eq!(central_state.total_boost, 0);
+
+    // Forever bonds get the fixed boost
+    pt.create_bond_v2(&carol, &pool_owner, None).unwrap();
+    pt.add_to_bond_v2(&carol, &carol, &pool_owner, 1_000, None)
+        .unwrap();
+    pt.refresh_bond_v2_boost(&carol, &pool_owner, None).unwrap();
+    assert_eq!(
+        pt.bond_v2_stats(&carol, &pool_owner, None).unwrap().boost,
+        2_000
+    );
+    assert_eq!(pt.pool_boost_stats(&pool_owner).unwrap().total_boost, 2_000);
+    assert_eq!(pt.central_state_stats().unwrap().total_staked, 11_000);
+
+    // Adding to a boosted bond updates its boost
+    pt.add_to_bond_v2(&carol, &carol, &pool_owner, 500, None)
+        .unwrap();
+    assert_eq!(
+        pt.bond_v2_stats(&carol, &pool_owner, None).unwrap().boost,
+        3_000
+    );
+    assert_eq!(pt.pool_boost_stats(&pool_owner).unwrap().total_boost, 3_000);
+    let central_state = pt.central_state_stats().unwrap();
+    assert_eq!(central_state.total_staked, 11_500);
+    assert_eq!(central_state.total_boost, 3_000);
+    assert_eq!(
+        central_state.total_staked,
+        pt.pool_stats(&pool_owner).unwrap().total_staked
+    );
+}
+
+#[test]
+fn boost_migrates_the_central_state() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+    let pool_owner = pt.create_user_with_ata();
+    let bob = pt.create_user_with_ata();
+    pt.mint(&bob, 11_000).unwrap();
+    pt.create_pool(&pool_owner, 1_000).unwrap();
+    pt.activate_stake_pool(&pool_owner).unwrap();
+    let unlock_date = Some(pt.clock().unix_timestamp + 100 * SECONDS_IN_DAY as i64);
+    pt.create_bond_v2(&bob, &pool_owner, unlock_date).unwrap();
+    pt.add_to_bond_v2(&bob, &bob, &pool_owner, 5_000, unlock_date)
+        .unwrap();
+    pt.set_boost_config(100 * SECONDS_IN_DAY as i64, 10_000, 20_000)
+        .unwrap();
+
+    // The central state still has the layout without the boosts
+    let key = pt.central_state;
+    let mut account = pt.account(&key).unwrap().clone();
+    let previous = CentralStateV2::load(&account.data)
+        .unwrap()
+        .to_previous()
+        .unwrap();
+    account.data = previous.try_to_vec().unwrap();
+    account.data.resize(
+        previous.borsh_len() + size_of::<FeeRecipient>() * MAX_FEE_RECIPIENTS,
+        0,
+    );
+    pt.set_account(key, account);
+
+    // The first boost migrates it, the next additions follow the boost
+    pt.refresh_bond_v2_boost(&bob, &pool_owner, unlock_date)
+        .unwrap();
+    assert_eq!(
+        read_header(&pt.account(&key).unwrap().data).unwrap().1,
+        CentralStateV2::VERSION
+    );
+    assert_eq!(pt.central_state_stats().unwrap().total_boost, 5_000);
+    pt.add_to_bond_v2(&bob, &bob, &pool_owner, 5_000, unlock_date)
+        .unwrap();
+    let central_state = pt.central_state_stats().unwrap();
+    assert_eq!(central_state.total_staked, 10_000);
+    assert_eq!(central_state.total_boost, 10_000);
+}
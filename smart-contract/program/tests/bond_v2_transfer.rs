@@ -0,0 +1,105 @@
+use solana_program::program_error::ProgramError;
+
+use access_protocol::error::AccessError;
+use access_protocol::state::{BondV2Account, SECONDS_IN_DAY};
+use access_protocol::testing::ProtocolTest;
+
+// Runs with `cargo test --features testing --test bond_v2_transfer`, see `access_protocol::testing`
+
+fn err(e: AccessError) -> Result<(), ProgramError> {
+    Err(e.into())
+}
+
+#[test]
+fn bond_v2_transfer() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+    let pool_owner = pt.create_user_with_ata();
+    let alice = pt.create_user_with_ata();
+    let bob = pt.create_user_with_ata();
+    let carol = pt.create_user_with_ata();
+    pt.mint(&alice, 10_000).unwrap();
+    pt.mint(&carol, 2_000).unwrap();
+    pt.create_pool(&pool_owner, 1_000).unwrap();
+    pt.activate_stake_pool(&pool_owner).unwrap();
+    let pool = pt.get_pool_pda(&pool_owner);
+
+    let unlock_date = Some(pt.clock().unix_timestamp + 10 * SECONDS_IN_DAY as i64);
+    pt.create_bond_v2(&alice, &pool_owner, unlock_date).unwrap();
+    pt.add_to_bond_v2(&alice, &alice, &pool_owner, 5_000, unlock_date)
+        .unwrap();
+    let (alice_bond, _) =
+        BondV2Account::create_key(&alice, &pool, unlock_date, &access_protocol::ID);
+
+    // The rewards have to be claimed before a transfer
+    pt.sleep_days(1);
+    pt.crank_pool(&pool_owner).unwrap();
+    assert_eq!(
+        pt.transfer_bond_v2(&alice, &bob, &pool_owner, unlock_date),
+        err(AccessError::UnclaimedRewards)
+    );
+    pt.claim_bond_v2_rewards(&alice, &pool_owner, unlock_date)
+        .unwrap();
+    assert_eq!(
+        pt.transfer_bond_v2(&bob, &alice, &pool_owner, unlock_date),
+        err(AccessError::WrongBondAccountOwner)
+    );
+    pt.transfer_bond_v2(&alice, &bob, &pool_owner, unlock_date)
+        .unwrap();
+    assert!(pt.account(&alice_bond).is_none());
+    let bond = pt.bond_v2_stats(&bob, &pool_owner, unlock_date).unwrap();
+    assert_eq!(bond.owner, bob);
+    assert_eq!(bond.amount, 5_000);
+    assert_eq!(bond.unlock_timestamp, unlock_date);
+    assert_eq!(pt.pool_stats(&pool_owner).unwrap().total_staked, 5_000);
+
+    // Listing escrows the bond
+    pt.list_bond_v2(&bob, &pool_owner, unlock_date, 1_500)
+        .unwrap();
+    let (listing, escrow) = pt.get_bond_v2_listing_pda(&bob, &pool_owner, unlock_date);
+    assert!(pt.bond_v2_stats(&bob, &pool_owner, unlock_date).is_err());
+    assert_eq!(
+        pt.get_versioned::<BondV2Account>(&escrow).unwrap().owner,
+        listing
+    );
+
+    // The buyer pays the listed price
+    assert_eq!(
+        pt.buy_bond_v2(&carol, &bob, &pool_owner, unlock_date, 1_000),
+        err(AccessError::ListingPriceMismatch)
+    );
+    pt.sleep_days(1);
+    pt.crank_pool(&pool_owner).unwrap();
+    pt.buy_bond_v2(&carol, &bob, &pool_owner, unlock_date, 1_500)
+        .unwrap();
+    assert_eq!(pt.token_balance(&carol).unwrap(), 500);
+    assert!(pt.account(&listing).is_none());
+    assert!(pt.account(&escrow).is_none());
+
+    // The rewards earned while listed go to the seller
+    assert!(pt.token_balance(&bob).unwrap() > 1_500);
+    assert_eq!(
+        pt.claim_bond_v2_rewards(&carol, &pool_owner, unlock_date),
+        err(AccessError::NoOp)
+    );
+
+    // A cancelled listing gives the bond back
+    pt.list_bond_v2(&carol, &pool_owner, unlock_date, 1_000)
+        .unwrap();
+    pt.cancel_bond_v2_listing(&carol, &pool_owner, unlock_date)
+        .unwrap();
+    assert_eq!(
+        pt.bond_v2_stats(&carol, &pool_owner, unlock_date)
+            .unwrap()
+            .amount,
+        5_000
+    );
+
+    // The new owner unlocks the bond
+    pt.sleep_days(10);
+    pt.crank_pool(&pool_owner).unwrap();
+    pt.claim_bond_v2_rewards(&carol, &pool_owner, unlock_date)
+        .unwrap();
+    let balance = pt.token_balance(&carol).unwrap();
+    pt.unlock_bond_v2(&carol, &pool_owner, unlock_date).unwrap();
+    assert_eq!(pt.token_balance(&carol).unwrap(), balance + 5_000);
+}
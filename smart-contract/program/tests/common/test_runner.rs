@@ -1,16 +1,19 @@
 use std::error::Error;
+use std::mem::size_of;
 
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{pubkey::Pubkey, system_program};
 use solana_program::system_instruction::transfer;
 use solana_program_test::{processor, ProgramTest};
+use solana_sdk::account::AccountSharedData;
 use solana_sdk::signer::{keypair::Keypair, Signer};
 use solana_sdk::sysvar::clock;
 use solana_test_framework::*;
 use spl_associated_token_account::{
-    get_associated_token_address, instruction::create_associated_token_account,
+    get_associated_token_address_with_program_id, instruction::create_associated_token_account,
 };
-use spl_token::instruction::AuthorityType::MintTokens;
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::instruction::AuthorityType::MintTokens;
 
 use access_protocol::{
     entrypoint::process_instruction,
@@ -19,10 +22,17 @@ use access_protocol::{
         crank, create_central_state, create_stake_account, create_stake_pool, stake, unstake,
     },
 };
-use access_protocol::instruction::{admin_change_freeze_authority, admin_program_freeze, admin_renounce, admin_set_protocol_fee, change_central_state_authority, change_inflation, change_pool_minimum, change_pool_multiplier, claim_bond, claim_bond_rewards, create_bond, migrate_central_state_v2, ProgramInstruction, unlock_bond_tokens, unlock_bond_v2};
-use access_protocol::state::{ACCESS_NFT_PROGRAM_SIGNER, BondAccount, BondV2Account, CentralState, CentralStateV2, FeeRecipient, RoyaltyAccount, StakeAccount, StakePoolHeader};
-
-use crate::common::utils::{mint_bootstrap, sign_send_instructions, sign_send_instructions_without_authority};
+use access_protocol::instruction::{admin_change_freeze_authority, admin_freeze, get_central_state_info, get_claimable_rewards, get_pool_apr, get_stake_summary, admin_program_freeze, admin_renounce, admin_set_keeper_reward, admin_set_pool_fee_override, admin_set_protocol_config, admin_set_protocol_fee, change_central_state_authority, change_inflation, change_pool_minimum, change_pool_multiplier, claim_bond, claim_bond_rewards, create_bond, migrate_central_state_v2, ProgramInstruction, unlock_bond_tokens, unlock_bond_v2};
+use access_protocol::decoder::ProtocolAccount;
+use access_protocol::events::Event;
+use access_protocol::views::{CentralStateInfoView, ClaimableRewardsView, PoolAprView, StakeSummaryView};
+use access_protocol::state::{BondAccount, BondV2Account, CentralState, CentralStateV2, FeeDistributionConfig, FeeRecipient, PoolFeeOverride, ProtocolConfig, RewardsTuple, RoyaltyAccount, StakeAccount, StakePool, StakePoolHeaped, StakePoolHeader, STAKE_BUFFER_LEN};
+use access_protocol::versioning::Versioned;
+
+use crate::common::utils::{
+    mint_bootstrap, sign_send_instructions, sign_send_instructions_with_metadata,
+    sign_send_instructions_without_authority, simulate_view, LastTransaction,
+};
 
 pub const INITIAL_SUPPLY: u64 = 100_000_000_000_000_000;
 
@@ -32,13 +42,20 @@ pub struct TestRunner {
     local_env: BanksClient,
     authority_ata: Pubkey,
     central_state: Pubkey,
+    original_central_state: Pubkey,
+    scope: Option<Pubkey>,
     central_state_vault: Pubkey,
     mint: Pubkey,
+    token_program: Pubkey,
+    protocol_config: Pubkey,
+    access_nft_program_signer: Pubkey,
     // hashmap from user pubkey to a bond account
     bond_accounts: std::collections::HashMap<String, Pubkey>,
     royalty_atas: std::collections::HashMap<String, Pubkey>,
     bond_seller: Keypair,
     supply_owner: Keypair,
+    // events and compute units of the last transaction sent by the runner
+    last_transaction: LastTransaction,
 }
 
 pub struct StakerStats {
@@ -75,6 +92,27 @@ pub struct TokenStats {
 
 impl TestRunner {
     pub async fn new(daily_inflation: u64) -> Result<Self, BanksClientError> {
+        Self::new_with_token_program(daily_inflation, spl_token::ID).await
+    }
+
+    pub async fn new_with_token_program(
+        daily_inflation: u64,
+        token_program: Pubkey,
+    ) -> Result<Self, BanksClientError> {
+        Self::setup(daily_inflation, token_program, false).await
+    }
+
+    /// Runs the tests against a tenant central state keyed by its own mint.
+    /// The original central state is created alongside it
+    pub async fn new_tenant(daily_inflation: u64) -> Result<Self, BanksClientError> {
+        Self::setup(daily_inflation, spl_token::ID, true).await
+    }
+
+    async fn setup(
+        daily_inflation: u64,
+        token_program: Pubkey,
+        tenant: bool,
+    ) -> Result<Self, BanksClientError> {
         // Create program and test environment
         let program_id = access_protocol::ID;
 
@@ -93,18 +131,36 @@ impl TestRunner {
         //
         // Derive central vault
         //
-        let (central_state_address, _) = CentralState::find_key(&program_id);
+        let (original_central_state, _) = CentralState::find_key(&program_id);
 
         //
-        // Create mint
+        // Create mints
         //
         let temp_mint_authority = Keypair::new();
-        let (mint, _) = mint_bootstrap(
+        let (original_mint, _) = mint_bootstrap(
             Some("acsT7dFjiyevrBbvpsD7Vqcwj1QN96fbWKdq49wcdWZ"),
             6,
             &mut program_test,
             &temp_mint_authority.pubkey(),
+            &token_program,
         );
+        let (mint, central_state_address) = if tenant {
+            let (tenant_mint, _) = mint_bootstrap(
+                None,
+                6,
+                &mut program_test,
+                &temp_mint_authority.pubkey(),
+                &token_program,
+            );
+            (tenant_mint, CentralStateV2::find_tenant_key(&tenant_mint, &program_id).0)
+        } else {
+            (original_mint, original_central_state)
+        };
+        let scope = if tenant {
+            Some(central_state_address)
+        } else {
+            None
+        };
 
         ////
         // Create test context
@@ -112,6 +168,99 @@ impl TestRunner {
         let mut prg_test_ctx = program_test.start_with_context().await;
         let local_env = prg_test_ctx.banks_client.clone();
 
+        if tenant {
+            Self::bootstrap_central_state(
+                &mut prg_test_ctx,
+                &original_central_state,
+                &original_mint,
+                &temp_mint_authority,
+                daily_inflation,
+                &token_program,
+            )
+                .await?;
+        }
+        let (supply_owner, protocol_config, access_nft_program_signer) =
+            Self::bootstrap_central_state(
+                &mut prg_test_ctx,
+                &central_state_address,
+                &mint,
+                &temp_mint_authority,
+                daily_inflation,
+                &token_program,
+            )
+                .await?;
+
+        //
+        // Create token accounts
+        //
+        let ix = create_associated_token_account(
+            &prg_test_ctx.payer.pubkey(),
+            &prg_test_ctx.payer.pubkey(),
+            &mint,
+            &token_program,
+        );
+        sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![]).await?;
+        let authority_ata = get_associated_token_address_with_program_id(
+            &prg_test_ctx.payer.pubkey(),
+            &mint,
+            &token_program,
+        );
+
+        // Create bond seller
+        let bond_seller = Keypair::new();
+        let create_ata_bond_seller_ix = create_associated_token_account(
+            &prg_test_ctx.payer.pubkey(),
+            &bond_seller.pubkey(),
+            &mint,
+            &token_program,
+        );
+        sign_send_instructions(
+            &mut prg_test_ctx,
+            vec![create_ata_bond_seller_ix],
+            vec![],
+        )
+            .await?;
+
+        let central_state_vault = get_associated_token_address_with_program_id(
+            &central_state_address,
+            &mint,
+            &token_program,
+        );
+
+        Ok(Self {
+            program_id,
+            prg_test_ctx,
+            local_env,
+            authority_ata,
+            central_state: central_state_address,
+            original_central_state,
+            scope,
+            mint,
+            bond_accounts: std::collections::HashMap::new(),
+            royalty_atas: std::collections::HashMap::new(),
+            bond_seller,
+            central_state_vault,
+            supply_owner,
+            token_program,
+            protocol_config,
+            access_nft_program_signer,
+            last_transaction: LastTransaction::default(),
+        })
+    }
+
+    /// Mints the initial supply, hands the mint over and creates a migrated central state with its vault
+    async fn bootstrap_central_state(
+        prg_test_ctx: &mut ProgramTestContext,
+        central_state: &Pubkey,
+        mint: &Pubkey,
+        temp_mint_authority: &Keypair,
+        daily_inflation: u64,
+        token_program: &Pubkey,
+    ) -> Result<(Keypair, Pubkey, Pubkey), BanksClientError> {
+        let program_id = access_protocol::ID;
+        let (protocol_config, _) = ProtocolConfig::create_key(central_state, &program_id);
+        let access_nft_program_signer = Keypair::new().pubkey();
+
         ////
         // Mint initial supply and transfer mint ownership
         ////
@@ -120,32 +269,35 @@ impl TestRunner {
         let ix = create_associated_token_account(
             &prg_test_ctx.payer.pubkey(),
             &supply_owner.pubkey(),
-            &mint,
-            &spl_token::ID,
+            mint,
+            token_program,
+        );
+        sign_send_instructions(prg_test_ctx, vec![ix], vec![]).await?;
+        let supply_owner_ata = get_associated_token_address_with_program_id(
+            &supply_owner.pubkey(),
+            mint,
+            token_program,
         );
-        sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![]).await?;
-        let supply_owner_ata = get_associated_token_address(&supply_owner.pubkey(), &mint);
-
 
-        let mint_ix = spl_token::instruction::mint_to(
-            &spl_token::ID,
-            &mint,
+        let mint_ix = spl_token_2022::instruction::mint_to(
+            token_program,
+            mint,
             &supply_owner_ata,
             &temp_mint_authority.pubkey(),
             &[],
             INITIAL_SUPPLY,
         ).unwrap();
-        sign_send_instructions(&mut prg_test_ctx, vec![mint_ix], vec![&temp_mint_authority]).await?;
+        sign_send_instructions(prg_test_ctx, vec![mint_ix], vec![temp_mint_authority]).await?;
 
-        let ix = spl_token::instruction::set_authority(
-            &spl_token::ID,
-            &mint,
-            Some(&central_state_address),
+        let ix = spl_token_2022::instruction::set_authority(
+            token_program,
+            mint,
+            Some(central_state),
             MintTokens,
             &temp_mint_authority.pubkey(),
             &[],
         ).unwrap();
-        sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&temp_mint_authority]).await?;
+        sign_send_instructions(prg_test_ctx, vec![ix], vec![temp_mint_authority]).await?;
 
         //
         // Create central state
@@ -153,76 +305,40 @@ impl TestRunner {
         let create_central_state_ix = create_central_state(
             program_id,
             create_central_state::Accounts {
-                central_state: &central_state_address,
+                central_state,
                 system_program: &system_program::ID,
                 fee_payer: &prg_test_ctx.payer.pubkey(),
-                mint: &mint,
+                mint,
+                protocol_config: &protocol_config,
             },
             create_central_state::Params {
                 daily_inflation,
                 authority: prg_test_ctx.payer.pubkey(),
+                access_nft_program_signer,
             },
         );
-        sign_send_instructions(&mut prg_test_ctx, vec![create_central_state_ix], vec![]).await?;
+        sign_send_instructions(prg_test_ctx, vec![create_central_state_ix], vec![]).await?;
 
-        //
-        // Create token accounts
-        //
         let ix = create_associated_token_account(
             &prg_test_ctx.payer.pubkey(),
-            &prg_test_ctx.payer.pubkey(),
-            &mint,
-            &spl_token::ID,
-        );
-        let ix2 = create_associated_token_account(
-            &prg_test_ctx.payer.pubkey(),
-            &central_state_address,
-            &mint,
-            &spl_token::ID,
-        );
-        sign_send_instructions(&mut prg_test_ctx, vec![ix, ix2], vec![]).await?;
-        let authority_ata = get_associated_token_address(&prg_test_ctx.payer.pubkey(), &mint);
-
-        // Create bond seller
-        let bond_seller = Keypair::new();
-        let create_ata_bond_seller_ix = create_associated_token_account(
-            &prg_test_ctx.payer.pubkey(),
-            &bond_seller.pubkey(),
-            &mint,
-            &spl_token::ID,
+            central_state,
+            mint,
+            token_program,
         );
-        sign_send_instructions(
-            &mut prg_test_ctx,
-            vec![create_ata_bond_seller_ix],
-            vec![],
-        )
-            .await?;
+        sign_send_instructions(prg_test_ctx, vec![ix], vec![]).await?;
 
-        let central_state_vault = get_associated_token_address(&central_state_address, &mint);
         let migrate_ix = migrate_central_state_v2(
             program_id,
             migrate_central_state_v2::Accounts {
                 fee_payer: &prg_test_ctx.payer.pubkey(),
-                central_state: &central_state_address,
+                central_state,
                 system_program: &system_program::ID,
             },
             migrate_central_state_v2::Params {},
         );
-        sign_send_instructions(&mut prg_test_ctx, vec![migrate_ix], vec![]).await?;
+        sign_send_instructions(prg_test_ctx, vec![migrate_ix], vec![]).await?;
 
-        Ok(Self {
-            program_id,
-            prg_test_ctx,
-            local_env,
-            authority_ata,
-            central_state: central_state_address,
-            mint,
-            bond_accounts: std::collections::HashMap::new(),
-            royalty_atas: std::collections::HashMap::new(),
-            bond_seller,
-            central_state_vault,
-            supply_owner,
-        })
+        Ok((supply_owner, protocol_config, access_nft_program_signer))
     }
 
     pub async fn get_sol(&mut self, recipient: &Pubkey, amount: i64) -> Result<(), BanksClientError> {
@@ -231,7 +347,7 @@ impl TestRunner {
             recipient,
             amount as u64,
         );
-        sign_send_instructions(&mut self.prg_test_ctx, vec![ix], vec![]).await?;
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![ix], vec![]).await?;
         Ok(())
     }
 
@@ -247,10 +363,11 @@ impl TestRunner {
             &self.prg_test_ctx.payer.pubkey(),
             &owner,
             &self.mint,
-            &spl_token::ID,
+            &self.token_program,
         );
-        sign_send_instructions(
+        sign_send_instructions_with_metadata(
             &mut self.prg_test_ctx,
+            &mut self.last_transaction,
             vec![create_ata_stake_pool_owner_ix],
             vec![],
         )
@@ -265,8 +382,8 @@ impl TestRunner {
         royalty_basis_points: u16,
         expiration_date: u64,
     ) -> Result<(), BanksClientError> {
-        let royalty_ata = get_associated_token_address(royalty_recipient, &self.mint);
-        let royalty_account = &RoyaltyAccount::create_key(&royalty_payer.pubkey(), &self.program_id).0;
+        let royalty_ata = self.get_ata(royalty_recipient);
+        let royalty_account = &RoyaltyAccount::create_scoped_key(&royalty_payer.pubkey(), self.scope.as_ref(), &self.program_id).0;
         let create_royalty_ix = access_protocol::instruction::create_royalty_account(
             self.program_id,
             access_protocol::instruction::create_royalty_account::Accounts {
@@ -288,14 +405,14 @@ impl TestRunner {
             royalty_ata,
         );
 
-        sign_send_instructions(&mut self.prg_test_ctx, vec![create_royalty_ix], vec![royalty_payer]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![create_royalty_ix], vec![royalty_payer]).await
     }
 
     pub async fn close_royalty(
         &mut self,
         royalty_payer: &Keypair,
     ) -> Result<(), BanksClientError> {
-        let royalty_account = &RoyaltyAccount::create_key(&royalty_payer.pubkey(), &self.program_id).0;
+        let royalty_account = &RoyaltyAccount::create_scoped_key(&royalty_payer.pubkey(), self.scope.as_ref(), &self.program_id).0;
         let close_royalty_ix = access_protocol::instruction::close_royalty_account(
             self.program_id,
             access_protocol::instruction::close_royalty_account::Accounts {
@@ -307,7 +424,7 @@ impl TestRunner {
             access_protocol::instruction::close_royalty_account::Params {},
         );
 
-        sign_send_instructions(&mut self.prg_test_ctx, vec![close_royalty_ix], vec![royalty_payer]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![close_royalty_ix], vec![royalty_payer]).await
     }
 
     pub async fn mint(
@@ -315,7 +432,7 @@ impl TestRunner {
         destination: &Pubkey,
         amount: u64,
     ) -> Result<(), BanksClientError> {
-        let destination_ata = get_associated_token_address(destination, &self.mint);
+        let destination_ata = self.get_ata(destination);
         let admin_mint_ix = admin_mint(
             self.program_id,
             admin_mint::Accounts {
@@ -323,11 +440,12 @@ impl TestRunner {
                 mint: &self.mint,
                 access_token_destination: &destination_ata,
                 central_state: &self.central_state,
-                spl_token_program: &spl_token::ID,
+                spl_token_program: &self.token_program,
+                role_table: None,
             },
             admin_mint::Params { amount },
         );
-        sign_send_instructions(&mut self.prg_test_ctx, vec![admin_mint_ix], vec![]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![admin_mint_ix], vec![]).await
     }
 
     pub async fn get_tokens_from_supply(
@@ -335,17 +453,19 @@ impl TestRunner {
         destination: &Pubkey,
         amount: u64,
     ) -> Result<(), BanksClientError> {
-        let destination_ata = get_associated_token_address(destination, &self.mint);
-        let supply_owner_ata = get_associated_token_address(&self.supply_owner.pubkey(), &self.mint);
-        let transfer_ix = spl_token::instruction::transfer(
-            &spl_token::ID,
+        let destination_ata = self.get_ata(destination);
+        let supply_owner_ata = self.get_ata(&self.supply_owner.pubkey());
+        let transfer_ix = spl_token_2022::instruction::transfer_checked(
+            &self.token_program,
             &supply_owner_ata,
+            &self.mint,
             &destination_ata,
             &self.supply_owner.pubkey(),
             &[],
             amount,
+            6,
         ).unwrap();
-        sign_send_instructions(&mut self.prg_test_ctx, vec![transfer_ix], vec![&self.supply_owner]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![transfer_ix], vec![&self.supply_owner]).await
     }
 
     pub async fn create_pool(
@@ -354,15 +474,16 @@ impl TestRunner {
         minimum_stake_amount: u64,
     ) -> Result<(), BanksClientError> {
         let stake_pool_key = self.get_pool_pda(&pool_owner.pubkey());
-        let pool_vault = get_associated_token_address(&stake_pool_key, &self.mint);
+        let pool_vault = self.get_ata(&stake_pool_key);
         let create_ata_pool_vault_ix = create_associated_token_account(
             &self.prg_test_ctx.payer.pubkey(),
             &stake_pool_key,
             &self.mint,
-            &spl_token::ID,
+            &self.token_program,
         );
-        sign_send_instructions(
+        sign_send_instructions_with_metadata(
             &mut self.prg_test_ctx,
+            &mut self.last_transaction,
             vec![create_ata_pool_vault_ix],
             vec![],
         )
@@ -382,7 +503,35 @@ impl TestRunner {
                 minimum_stake_amount,
             },
         );
-        sign_send_instructions(&mut self.prg_test_ctx, vec![create_stake_pool_ix], vec![&pool_owner]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![create_stake_pool_ix], vec![&pool_owner]).await
+    }
+
+    // A pool with the rewards buffer of the pools created before the reward index
+    pub async fn create_legacy_pool(
+        &mut self,
+        pool_owner: &Keypair,
+        minimum_stake_amount: u64,
+    ) -> Result<(), BanksClientError> {
+        self.create_pool(pool_owner, minimum_stake_amount).await?;
+        let stake_pool_key = self.get_pool_pda(&pool_owner.pubkey());
+        let mut account = self
+            .prg_test_ctx
+            .banks_client
+            .get_account(stake_pool_key)
+            .await?
+            .ok_or(BanksClientError::ClientError("Stake pool not found"))?;
+        let mut header = *StakePoolHeaped::from_buffer(&account.data).header;
+        header.reward_index_enabled = 0;
+        account.data = header.try_to_vec().unwrap();
+        account.data.resize(
+            size_of::<StakePoolHeader>() + size_of::<RewardsTuple>() * STAKE_BUFFER_LEN as usize,
+            0,
+        );
+        let rent = self.prg_test_ctx.banks_client.get_rent().await?;
+        account.lamports = rent.minimum_balance(account.data.len());
+        self.prg_test_ctx
+            .set_account(&stake_pool_key, &AccountSharedData::from(account));
+        Ok(())
     }
 
     pub async fn activate_stake_pool(
@@ -399,7 +548,7 @@ impl TestRunner {
             activate_stake_pool::Params {},
         );
 
-        sign_send_instructions(&mut self.prg_test_ctx, vec![activate_stake_pool_ix], vec![]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![activate_stake_pool_ix], vec![]).await
     }
 
     pub fn get_stake_account_pda(
@@ -419,10 +568,8 @@ impl TestRunner {
     }
 
     pub fn get_pool_pda(&mut self, stake_pool_owner: &Pubkey) -> Pubkey {
-        let (stake_pool_key, _) = Pubkey::find_program_address(
-            &["stake_pool".as_bytes(), &stake_pool_owner.to_bytes()],
-            &self.program_id,
-        );
+        let (stake_pool_key, _) =
+            StakePool::find_scoped_key(stake_pool_owner, self.scope.as_ref(), &self.program_id);
         stake_pool_key
     }
 
@@ -441,14 +588,17 @@ impl TestRunner {
                 fee_payer: &self.prg_test_ctx.payer.pubkey(),
                 stake_pool: &stake_pool_key,
                 central_state: &self.central_state,
+                pool_controls: None,
+                allowlist_entry: None,
             },
             create_stake_account::Params {
                 nonce: stake_nonce,
                 owner: *staker_key,
             },
         );
-        sign_send_instructions(
+        sign_send_instructions_with_metadata(
             &mut self.prg_test_ctx,
+            &mut self.last_transaction,
             vec![create_stake_account_ix],
             vec![],
         )
@@ -477,8 +627,9 @@ impl TestRunner {
         let staker_key = staker.pubkey();
         let stake_pool_key = self.get_pool_pda(stake_pool_owner_key);
         let (stake_acc_key, _) = self.get_stake_account_pda(&stake_pool_key, &staker_key);
-        let staker_token_acc = get_associated_token_address(&staker_key, &self.mint);
-        let pool_vault = get_associated_token_address(&stake_pool_key, &self.mint);
+        let staker_token_acc = self.get_ata(&staker_key);
+        let pool_vault = self.get_ata(&stake_pool_key);
+        let pool_fee_override = PoolFeeOverride::create_key(&stake_pool_key, &self.program_id).0;
 
         let stake_ix = stake(
             self.program_id,
@@ -487,38 +638,63 @@ impl TestRunner {
                 stake_pool: &stake_pool_key,
                 token_owner: &staker_key,
                 source_token: &staker_token_acc,
-                spl_token_program: &spl_token::ID,
+                spl_token_program: &self.token_program,
                 vault: &pool_vault,
                 central_state: &self.central_state,
                 central_state_vault: &self.central_state_vault,
+                pool_fee_override: Some(&pool_fee_override),
+                mint: &self.mint,
+                pool_controls: None,
+                allowlist_entry: None,
+                receipt_mint: None,
+                receipt_account: None,
+                receipt_reserve: None,
+                transfer_hook_accounts: &[],
             },
             stake::Params {
                 amount: token_amount,
             },
         );
-        sign_send_instructions(&mut self.prg_test_ctx, vec![stake_ix], vec![staker]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![stake_ix], vec![staker]).await
     }
 
     pub async fn distribute_fees(&mut self) -> Result<(), BanksClientError> {
         let central_state_stats = self.central_state_stats().await.unwrap();
-        let recipient_pubkeys: Vec<Pubkey> = central_state_stats
-            .account
-            .recipients
-            .iter()
-            .map(|r| self.get_ata(&r.owner))
-            .collect();
+        let recipient_pubkeys = central_state_stats.account.get_fee_recipient_atas(&self.token_program);
         let distribute_fees_ix = access_protocol::instruction::distribute_fees(
             self.program_id,
             access_protocol::instruction::distribute_fees::Accounts {
                 central_state: &self.central_state,
                 central_state_vault: &self.central_state_vault,
-                spl_token_program: &spl_token::ID,
+                spl_token_program: &self.token_program,
                 mint: &self.mint,
                 token_accounts: recipient_pubkeys.leak(),
             },
             access_protocol::instruction::distribute_fees::Params {},
         );
-        sign_send_instructions(&mut self.prg_test_ctx, vec![distribute_fees_ix], vec![]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![distribute_fees_ix], vec![]).await
+    }
+
+    pub async fn distribute_fees_with_keeper(
+        &mut self,
+        keeper: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let central_state_stats = self.central_state_stats().await.unwrap();
+        let mut token_accounts = central_state_stats.account.get_fee_recipient_atas(&self.token_program);
+        token_accounts.push(FeeDistributionConfig::create_key(&self.central_state, &self.program_id).0);
+        token_accounts.push(self.get_ata(keeper));
+        let distribute_fees_ix = access_protocol::instruction::distribute_fees(
+            self.program_id,
+            access_protocol::instruction::distribute_fees::Accounts {
+                central_state: &self.central_state,
+                central_state_vault: &self.central_state_vault,
+                spl_token_program: &self.token_program,
+                mint: &self.mint,
+                token_accounts: token_accounts.leak(),
+            },
+            access_protocol::instruction::distribute_fees::Params {},
+        );
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![distribute_fees_ix], vec![]).await
     }
 
     pub async fn crank_pool(
@@ -531,11 +707,31 @@ impl TestRunner {
             crank::Accounts {
                 stake_pool: &stake_pool_key,
                 central_state: &self.central_state,
+                pool_boost: None,
+            },
+            crank::Params {},
+        );
+
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![crank_ix], vec![]).await
+    }
+
+    /// Cranks the pool against the original central state instead of the one of the runner
+    pub async fn crank_pool_with_original_central_state(
+        &mut self,
+        stake_pool_owner_key: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let stake_pool_key = self.get_pool_pda(stake_pool_owner_key);
+        let crank_ix = crank(
+            self.program_id,
+            crank::Accounts {
+                stake_pool: &stake_pool_key,
+                central_state: &self.original_central_state,
+                pool_boost: None,
             },
             crank::Params {},
         );
 
-        sign_send_instructions(&mut self.prg_test_ctx, vec![crank_ix], vec![]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![crank_ix], vec![]).await
     }
 
     pub async fn claim_pool_rewards(
@@ -558,8 +754,7 @@ impl TestRunner {
         owner_must_sign: bool,
     ) -> Result<(), BanksClientError> {
         let stake_pool_key = self.get_pool_pda(&stake_pool_owner.pubkey());
-        let stake_pool_owner_token_acc =
-            get_associated_token_address(&stake_pool_owner.pubkey(), &self.mint);
+        let stake_pool_owner_token_acc = self.get_ata(&stake_pool_owner.pubkey());
         let royalty_ata = self
             .royalty_atas
             .get(&stake_pool_owner.pubkey().to_string());
@@ -571,16 +766,17 @@ impl TestRunner {
                 rewards_destination: &stake_pool_owner_token_acc,
                 central_state: &self.central_state,
                 mint: &self.mint,
-                spl_token_program: &spl_token::ID,
-                owner_royalty_account: &RoyaltyAccount::create_key(&stake_pool_owner.pubkey(), &self.program_id).0,
+                spl_token_program: &self.token_program,
+                owner_royalty_account: &RoyaltyAccount::create_scoped_key(&stake_pool_owner.pubkey(), self.scope.as_ref(), &self.program_id).0,
                 royalty_ata,
             },
             claim_pool_rewards::Params {},
             owner_must_sign,
         );
 
-        sign_send_instructions(
+        sign_send_instructions_with_metadata(
             &mut self.prg_test_ctx,
+            &mut self.last_transaction,
             vec![claim_stake_pool_ix],
             if owner_must_sign {
                 vec![stake_pool_owner]
@@ -598,7 +794,7 @@ impl TestRunner {
     ) -> Result<(), BanksClientError> {
         let stake_pool_key = self.get_pool_pda(stake_pool_owner);
         let (stake_acc_key, _) = self.get_stake_account_pda(&stake_pool_key, &staker.pubkey());
-        let staker_token_acc = get_associated_token_address(&staker.pubkey(), &self.mint);
+        let staker_token_acc = self.get_ata(&staker.pubkey());
         let royalty_ata = self
             .royalty_atas
             .get(&staker.pubkey().to_string());
@@ -612,10 +808,11 @@ impl TestRunner {
                 rewards_destination: &staker_token_acc,
                 central_state: &self.central_state,
                 mint: &self.mint,
-                access_nft_signer: &ACCESS_NFT_PROGRAM_SIGNER,
-                spl_token_program: &spl_token::ID,
-                owner_royalty_account: &RoyaltyAccount::create_key(&staker.pubkey(), &self.program_id).0,
+                access_nft_signer: &self.access_nft_program_signer,
+                spl_token_program: &self.token_program,
+                owner_royalty_account: &RoyaltyAccount::create_scoped_key(&staker.pubkey(), self.scope.as_ref(), &self.program_id).0,
                 royalty_ata,
+                protocol_config: None,
             },
             claim_rewards::Params {
                 allow_zero_rewards: true,
@@ -623,7 +820,7 @@ impl TestRunner {
             false,
         );
 
-        sign_send_instructions(&mut self.prg_test_ctx, vec![claim_ix], vec![staker]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![claim_ix], vec![staker]).await
     }
 
 
@@ -640,7 +837,7 @@ impl TestRunner {
             unlock_date,
             &self.program_id,
         );
-        let owner_token_acc = get_associated_token_address(&owner.pubkey(), &self.mint);
+        let owner_token_acc = self.get_ata(&owner.pubkey());
         let royalty_ata = self
             .royalty_atas
             .get(&owner.pubkey().to_string());
@@ -654,16 +851,17 @@ impl TestRunner {
                 rewards_destination: &owner_token_acc,
                 central_state: &self.central_state,
                 mint: &self.mint,
-                access_nft_signer: &ACCESS_NFT_PROGRAM_SIGNER,
-                spl_token_program: &spl_token::ID,
-                owner_royalty_account: &RoyaltyAccount::create_key(&owner.pubkey(), &self.program_id).0,
+                access_nft_signer: &self.access_nft_program_signer,
+                spl_token_program: &self.token_program,
+                owner_royalty_account: &RoyaltyAccount::create_scoped_key(&owner.pubkey(), self.scope.as_ref(), &self.program_id).0,
                 royalty_ata,
+                protocol_config: None,
             },
             access_protocol::instruction::claim_bond_v2_rewards::Params {},
             false,
         );
 
-        sign_send_instructions(&mut self.prg_test_ctx, vec![claim_ix], vec![owner]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![claim_ix], vec![owner]).await
     }
 
     pub async fn unlock_bond_v2_tokens(
@@ -679,8 +877,8 @@ impl TestRunner {
             unlock_date,
             &self.program_id,
         );
-        let staker_token_acc = get_associated_token_address(&owner.pubkey(), &self.mint);
-        let pool_vault = get_associated_token_address(&stake_pool_key, &self.mint);
+        let staker_token_acc = self.get_ata(&owner.pubkey());
+        let pool_vault = self.get_ata(&stake_pool_key);
 
         // Request Unstake
         let unstake_ix = unlock_bond_v2(
@@ -690,14 +888,16 @@ impl TestRunner {
                 pool: &stake_pool_key,
                 owner: &owner.pubkey(),
                 destination_account: &staker_token_acc,
-                spl_token_program: &spl_token::ID,
+                spl_token_program: &self.token_program,
                 central_state: &self.central_state,
                 pool_vault: &pool_vault,
+                mint: &self.mint,
+                transfer_hook_accounts: &[],
             },
             unlock_bond_v2::Params {},
         );
         // if error, return
-        sign_send_instructions(&mut self.prg_test_ctx, vec![unstake_ix], vec![owner]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![unstake_ix], vec![owner]).await
     }
 
     pub async fn unstake(
@@ -708,8 +908,8 @@ impl TestRunner {
     ) -> Result<(), BanksClientError> {
         let stake_pool_key = self.get_pool_pda(stake_pool_owner);
         let (stake_acc_key, _) = self.get_stake_account_pda(&stake_pool_key, &staker.pubkey());
-        let staker_token_acc = get_associated_token_address(&staker.pubkey(), &self.mint);
-        let pool_vault = get_associated_token_address(&stake_pool_key, &self.mint);
+        let staker_token_acc = self.get_ata(&staker.pubkey());
+        let pool_vault = self.get_ata(&stake_pool_key);
 
         // Request Unstake
         let unstake_ix = unstake(
@@ -719,28 +919,29 @@ impl TestRunner {
                 stake_pool: &stake_pool_key,
                 owner: &staker.pubkey(),
                 destination_token: &staker_token_acc,
-                spl_token_program: &spl_token::ID,
+                spl_token_program: &self.token_program,
                 central_state: &self.central_state,
                 vault: &pool_vault,
+                mint: &self.mint,
+                receipt_mint: None,
+                receipt_account: None,
+                receipt_reserve: None,
+                transfer_hook_accounts: &[],
             },
             unstake::Params {
                 amount: token_amount,
             },
         );
         // if error, return
-        sign_send_instructions(&mut self.prg_test_ctx, vec![unstake_ix], vec![staker]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![unstake_ix], vec![staker]).await
     }
 
     pub async fn staker_stats(
         &mut self,
         staker_key: Pubkey,
     ) -> Result<StakerStats, BanksClientError> {
-        let staker_token_acc = get_associated_token_address(&staker_key, &self.mint);
-        let balance = self
-            .local_env
-            .get_packed_account_data::<spl_token::state::Account>(staker_token_acc)
-            .await?
-            .amount;
+        let staker_token_acc = self.get_ata(&staker_key);
+        let balance = self.get_token_account(staker_token_acc).await?.amount;
         Ok(StakerStats { balance })
     }
 
@@ -767,24 +968,12 @@ impl TestRunner {
         &mut self,
         stake_pool_owner: Pubkey,
     ) -> Result<PoolOwnerStats, BanksClientError> {
-        let stake_pool_owner_token_acc =
-            get_associated_token_address(&stake_pool_owner, &self.mint);
-        let balance = self
-            .local_env
-            .get_packed_account_data::<spl_token::state::Account>(stake_pool_owner_token_acc)
-            .await?
-            .amount;
+        let stake_pool_owner_token_acc = self.get_ata(&stake_pool_owner);
+        let balance = self.get_token_account(stake_pool_owner_token_acc).await?.amount;
 
         let stake_pool_key = self.get_pool_pda(&stake_pool_owner);
-        let stake_pool_associated_token_account =
-            get_associated_token_address(&stake_pool_key, &self.mint);
-        let vault = self
-            .local_env
-            .get_packed_account_data::<spl_token::state::Account>(
-                stake_pool_associated_token_account,
-            )
-            .await?
-            .amount;
+        let stake_pool_associated_token_account = self.get_ata(&stake_pool_key);
+        let vault = self.get_token_account(stake_pool_associated_token_account).await?.amount;
 
         let acc = self
             .prg_test_ctx
@@ -802,6 +991,38 @@ impl TestRunner {
         })
     }
 
+    pub async fn stake_pool_with_balances(
+        &mut self,
+        stake_pool_owner: Pubkey,
+    ) -> Result<StakePoolHeaped, BanksClientError> {
+        let stake_pool_key = self.get_pool_pda(&stake_pool_owner);
+        let acc = self
+            .prg_test_ctx
+            .banks_client
+            .get_account(stake_pool_key)
+            .await?
+            .unwrap();
+        Ok(StakePoolHeaped::from_buffer(&acc.data))
+    }
+
+    pub async fn royalty_account_stats(
+        &mut self,
+        royalty_payer: Pubkey,
+    ) -> Result<Option<RoyaltyAccount>, BanksClientError> {
+        let royalty_key = self.get_royalty_account_key(&royalty_payer).await;
+        let acc = self
+            .prg_test_ctx
+            .banks_client
+            .get_account(royalty_key)
+            .await?;
+        match acc {
+            Some(acc) if !acc.data.is_empty() => {
+                Ok(Some(RoyaltyAccount::deserialize(&mut &acc.data[..])?))
+            }
+            _ => Ok(None),
+        }
+    }
+
     // bond stats
     pub async fn bond_stats(
         &mut self,
@@ -823,6 +1044,16 @@ impl TestRunner {
         Ok(bond_account)
     }
 
+    /// The events logged by the last transaction sent by the runner
+    pub fn last_events(&self) -> &[Event] {
+        &self.last_transaction.events
+    }
+
+    /// The compute units consumed by the last transaction sent by the runner
+    pub fn last_compute_units(&self) -> u64 {
+        self.last_transaction.compute_units
+    }
+
     // bond v2 stats
     pub async fn bond_v2_stats(
         &mut self,
@@ -856,11 +1087,7 @@ impl TestRunner {
     }
 
     pub async fn central_state_stats(&mut self) -> Result<CentralStateStats, Box<dyn Error>> {
-        let balance = self
-            .local_env
-            .get_packed_account_data::<spl_token::state::Account>(self.central_state_vault)
-            .await?
-            .amount;
+        let balance = self.get_token_account(self.central_state_vault).await?.amount;
 
         let acc = self
             .prg_test_ctx
@@ -869,7 +1096,7 @@ impl TestRunner {
             .await
             .unwrap()
             .unwrap();
-        let cs = CentralStateV2::deserialize(&mut &acc.data[..])?;
+        let cs = CentralStateV2::load(&acc.data)?;
         Ok(CentralStateStats {
             account: cs,
             balance,
@@ -886,12 +1113,15 @@ impl TestRunner {
             admin_program_freeze::Accounts {
                 authority: &auth.pubkey(),
                 central_state: &self.central_state,
+                fee_payer: &auth.pubkey(),
+                system_program: &system_program::ID,
+                role_table: None,
             },
             admin_program_freeze::Params { ix_gate },
         );
         match authority {
             Some(a) => sign_send_instructions_without_authority(&mut self.prg_test_ctx, vec![freeze_ix], vec![&a]).await,
-            None => sign_send_instructions(&mut self.prg_test_ctx, vec![freeze_ix], vec![]).await
+            None => sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![freeze_ix], vec![]).await
         }
     }
 
@@ -901,10 +1131,11 @@ impl TestRunner {
             admin_renounce::Accounts {
                 authority: &self.prg_test_ctx.payer.pubkey(),
                 central_state: &self.central_state,
+                role_table: None,
             },
             admin_renounce::Params { ix },
         );
-        sign_send_instructions(&mut self.prg_test_ctx, vec![renounce_ix], vec![]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![renounce_ix], vec![]).await
     }
 
     pub async fn create_bond(
@@ -920,8 +1151,7 @@ impl TestRunner {
             BondAccount::create_key(bond_owner, total_amount, &self.program_id);
 
         let stake_pool_key = self.get_pool_pda(stake_pool_owner);
-        let seller_token_account =
-            get_associated_token_address(&self.bond_seller.pubkey(), &self.mint);
+        let seller_token_account = self.get_ata(&self.bond_seller.pubkey());
         self.mint(&self.bond_seller.pubkey(), total_amount).await?;
         let current_time = self
             .local_env
@@ -953,8 +1183,9 @@ impl TestRunner {
             },
         );
 
-        sign_send_instructions(
+        sign_send_instructions_with_metadata(
             &mut self.prg_test_ctx,
+            &mut self.last_transaction,
             vec![create_bond_ix],
             vec![&self.bond_seller],
         )
@@ -978,9 +1209,9 @@ impl TestRunner {
             .get((stake_pool_owner.to_string() + &bond_owner.to_string()).as_str())
             .unwrap();
         let stake_pool_key = self.get_pool_pda(stake_pool_owner);
-        let seller_token_acc = get_associated_token_address(&self.bond_seller.pubkey(), &self.mint);
-        let bond_owner_ata = get_associated_token_address(bond_owner, &self.mint);
-        let pool_vault = get_associated_token_address(&stake_pool_key, &self.mint);
+        let seller_token_acc = self.get_ata(&self.bond_seller.pubkey());
+        let bond_owner_ata = self.get_ata(bond_owner);
+        let pool_vault = self.get_ata(&stake_pool_key);
 
         let mut claim_bond_ix = claim_bond(
             self.program_id,
@@ -993,14 +1224,15 @@ impl TestRunner {
                 access_mint: &self.mint,
                 pool_vault: &pool_vault,
                 central_state: &self.central_state,
-                spl_token_program: &spl_token::ID,
+                spl_token_program: &self.token_program,
+                quote_mint: Some(&self.mint),
             },
             claim_bond::Params {},
         );
         claim_bond_ix.accounts[1].is_signer = false;
 
         println!("claiming bond");
-        sign_send_instructions(&mut self.prg_test_ctx, vec![claim_bond_ix], vec![]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![claim_bond_ix], vec![]).await
     }
 
     pub async fn create_bond_with_quote(
@@ -1015,8 +1247,7 @@ impl TestRunner {
             BondAccount::create_key(bond_owner, bond_amount, &self.program_id);
 
         let stake_pool_key = self.get_pool_pda(stake_pool_owner);
-        let seller_token_account =
-            get_associated_token_address(&self.bond_seller.pubkey(), &self.mint);
+        let seller_token_account = self.get_ata(&self.bond_seller.pubkey());
         self.mint(&self.bond_seller.pubkey(), bond_amount).await?;
         let current_time = self
             .local_env
@@ -1048,8 +1279,9 @@ impl TestRunner {
             },
         );
 
-        sign_send_instructions(
+        sign_send_instructions_with_metadata(
             &mut self.prg_test_ctx,
+            &mut self.last_transaction,
             vec![create_bond_ix],
             vec![&self.bond_seller],
         )
@@ -1086,7 +1318,7 @@ impl TestRunner {
                 owner: *owner,
             },
         );
-        sign_send_instructions(&mut self.prg_test_ctx, vec![create_bond_v2_ix], vec![]).await?;
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![create_bond_v2_ix], vec![]).await?;
         Ok(())
     }
 
@@ -1100,26 +1332,31 @@ impl TestRunner {
     ) -> Result<(), BanksClientError> {
         let pool_key = self.get_pool_pda(pool_owner);
         let (bond_key, _) = BondV2Account::create_key(to, &pool_key, unlock_date, &self.program_id);
+        let pool_fee_override = PoolFeeOverride::create_key(&pool_key, &self.program_id).0;
 
         let add_to_bond_v2_ix = access_protocol::instruction::add_to_bond_v2(
             self.program_id,
             access_protocol::instruction::add_to_bond_v2::Accounts {
                 from: &from.pubkey(),
-                from_ata: &get_associated_token_address(&from.pubkey(), &self.mint),
+                from_ata: &self.get_ata(&from.pubkey()),
                 bond_v2_account: &bond_key,
                 pool: &pool_key,
                 central_state: &self.central_state,
-                pool_vault: &get_associated_token_address(&pool_key, &self.mint),
+                pool_vault: &self.get_ata(&pool_key),
                 central_state_vault: &self.central_state_vault,
                 mint: &self.mint,
-                spl_token_program: &spl_token::ID,
+                spl_token_program: &self.token_program,
                 system_program: &system_program::ID,
+                pool_fee_override: Some(&pool_fee_override),
+                pool_controls: None,
+                allowlist_entry: None,
+                transfer_hook_accounts: &[],
             },
             access_protocol::instruction::add_to_bond_v2::Params {
                 amount: bond_amount,
             },
         );
-        sign_send_instructions(&mut self.prg_test_ctx, vec![add_to_bond_v2_ix], vec![from]).await?;
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![add_to_bond_v2_ix], vec![from]).await?;
         Ok(())
     }
 
@@ -1133,9 +1370,9 @@ impl TestRunner {
             .get((stake_pool_owner.to_string() + bond_owner.pubkey().to_string().as_str()).as_str())
             .unwrap();
         let stake_pool_key = self.get_pool_pda(stake_pool_owner);
-        let seller_token_acc = get_associated_token_address(&self.bond_seller.pubkey(), &self.mint);
-        let bond_owner_ata = get_associated_token_address(&bond_owner.pubkey(), &self.mint);
-        let pool_vault = get_associated_token_address(&stake_pool_key, &self.mint);
+        let seller_token_acc = self.get_ata(&self.bond_seller.pubkey());
+        let bond_owner_ata = self.get_ata(&bond_owner.pubkey());
+        let pool_vault = self.get_ata(&stake_pool_key);
 
         let claim_bond_ix = claim_bond(
             self.program_id,
@@ -1148,13 +1385,15 @@ impl TestRunner {
                 access_mint: &self.mint,
                 pool_vault: &pool_vault,
                 central_state: &self.central_state,
-                spl_token_program: &spl_token::ID,
+                spl_token_program: &self.token_program,
+                quote_mint: Some(&self.mint),
             },
             claim_bond::Params {},
         );
 
-        sign_send_instructions(
+        sign_send_instructions_with_metadata(
             &mut self.prg_test_ctx,
+            &mut self.last_transaction,
             vec![claim_bond_ix],
             vec![bond_owner],
         )
@@ -1171,8 +1410,8 @@ impl TestRunner {
             .get((stake_pool_owner.to_string() + &bond_owner.pubkey().to_string()).as_str())
             .unwrap();
         let stake_pool_key = self.get_pool_pda(stake_pool_owner);
-        let pool_vault = get_associated_token_address(&stake_pool_key, &self.mint);
-        let bond_owner_ata = get_associated_token_address(&bond_owner.pubkey(), &self.mint);
+        let pool_vault = self.get_ata(&stake_pool_key);
+        let bond_owner_ata = self.get_ata(&bond_owner.pubkey());
 
         let unlock_ix = unlock_bond_tokens(
             self.program_id,
@@ -1182,14 +1421,14 @@ impl TestRunner {
                 mint: &self.mint,
                 access_token_destination: &bond_owner_ata,
                 central_state: &self.central_state,
-                spl_token_program: &spl_token::ID,
+                spl_token_program: &self.token_program,
                 stake_pool: &stake_pool_key,
                 pool_vault: &pool_vault,
             },
             unlock_bond_tokens::Params {},
         );
 
-        sign_send_instructions(&mut self.prg_test_ctx, vec![unlock_ix], vec![bond_owner]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![unlock_ix], vec![bond_owner]).await
     }
 
     pub async fn claim_bond_rewards(
@@ -1202,7 +1441,7 @@ impl TestRunner {
             .bond_accounts
             .get((stake_pool_owner.to_string() + &bond_owner.pubkey().to_string()).as_str())
             .unwrap();
-        let seller_token_acc = get_associated_token_address(&bond_owner.pubkey(), &self.mint);
+        let seller_token_acc = self.get_ata(&bond_owner.pubkey());
 
         let claim_bond_rewards_ix = claim_bond_rewards(
             self.program_id,
@@ -1213,13 +1452,13 @@ impl TestRunner {
                 rewards_destination: &seller_token_acc,
                 central_state: &self.central_state,
                 mint: &self.mint,
-                spl_token_program: &spl_token::ID,
+                spl_token_program: &self.token_program,
             },
             claim_bond_rewards::Params {},
             false,
         );
 
-        sign_send_instructions(&mut self.prg_test_ctx, vec![claim_bond_rewards_ix], vec![]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![claim_bond_rewards_ix], vec![]).await
     }
 
     pub fn get_authority(&self) -> Pubkey {
@@ -1230,6 +1469,10 @@ impl TestRunner {
         self.mint
     }
 
+    pub fn get_central_state(&self) -> Pubkey {
+        self.central_state
+    }
+
     pub async fn get_current_time(&mut self) -> i64 {
         self.local_env
             .get_sysvar::<clock::Clock>()
@@ -1243,7 +1486,7 @@ impl TestRunner {
     }
 
     pub fn get_bond_seller_ata(&self) -> Pubkey {
-        get_associated_token_address(&self.bond_seller.pubkey(), &self.mint)
+        self.get_ata(&self.bond_seller.pubkey())
     }
 
     pub async fn change_pool_minimum(
@@ -1262,8 +1505,9 @@ impl TestRunner {
             change_pool_minimum::Params { new_minimum },
         );
 
-        sign_send_instructions(
+        sign_send_instructions_with_metadata(
             &mut self.prg_test_ctx,
+            &mut self.last_transaction,
             vec![change_min_ix],
             vec![stake_pool_owner],
         )
@@ -1286,8 +1530,9 @@ impl TestRunner {
             change_pool_multiplier::Params { new_multiplier },
         );
 
-        sign_send_instructions(
+        sign_send_instructions_with_metadata(
             &mut self.prg_test_ctx,
+            &mut self.last_transaction,
             vec![change_min_ix],
             vec![stake_pool_owner],
         )
@@ -1301,13 +1546,109 @@ impl TestRunner {
                 central_state: &self.central_state,
                 authority: &self.prg_test_ctx.payer.pubkey(),
                 mint: &self.mint,
+                role_table: None,
             },
             change_inflation::Params {
                 daily_inflation: new_inflation,
             },
         );
 
-        sign_send_instructions(&mut self.prg_test_ctx, vec![change_inflation_ix], vec![]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![change_inflation_ix], vec![]).await
+    }
+
+    pub async fn freeze_account(&mut self, account: &Pubkey) -> Result<(), BanksClientError> {
+        let freeze_ix = admin_freeze(
+            self.program_id,
+            admin_freeze::Accounts {
+                central_state: &self.central_state,
+                account_to_freeze: account,
+                authority: &self.prg_test_ctx.payer.pubkey(),
+                role_table: None,
+            },
+            admin_freeze::Params {},
+        );
+
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![freeze_ix], vec![]).await
+    }
+
+    pub async fn protocol_account(&mut self, key: &Pubkey) -> Result<ProtocolAccount, BanksClientError> {
+        let acc = self
+            .prg_test_ctx
+            .banks_client
+            .get_account(*key)
+            .await?
+            .ok_or(BanksClientError::ClientError("Account not found"))?;
+        ProtocolAccount::decode(&acc.data)
+            .map_err(|_| BanksClientError::ClientError("Account could not be decoded"))
+    }
+
+    pub async fn view_claimable_rewards(
+        &mut self,
+        stake_pool_owner: &Pubkey,
+        staker: &Pubkey,
+    ) -> Result<ClaimableRewardsView, BanksClientError> {
+        let stake_pool_key = self.get_pool_pda(stake_pool_owner);
+        let (stake_acc_key, _) = self.get_stake_account_pda(&stake_pool_key, staker);
+        let owner_royalty_account = self.get_royalty_account_key(staker).await;
+        let ix = get_claimable_rewards(
+            self.program_id,
+            get_claimable_rewards::Accounts {
+                central_state: &self.central_state,
+                stake_pool: &stake_pool_key,
+                stake_account: &stake_acc_key,
+                owner_royalty_account: Some(&owner_royalty_account),
+            },
+            get_claimable_rewards::Params {},
+        );
+        simulate_view(&mut self.prg_test_ctx, ix).await
+    }
+
+    pub async fn view_pool_apr(
+        &mut self,
+        stake_pool_owner: &Pubkey,
+    ) -> Result<PoolAprView, BanksClientError> {
+        let stake_pool_key = self.get_pool_pda(stake_pool_owner);
+        let ix = get_pool_apr(
+            self.program_id,
+            get_pool_apr::Accounts {
+                central_state: &self.central_state,
+                stake_pool: &stake_pool_key,
+            },
+            get_pool_apr::Params {},
+        );
+        simulate_view(&mut self.prg_test_ctx, ix).await
+    }
+
+    pub async fn view_stake_summary(
+        &mut self,
+        stake_pool_owner: &Pubkey,
+        owner: &Pubkey,
+        bond_v2_accounts: &[Pubkey],
+    ) -> Result<StakeSummaryView, BanksClientError> {
+        let stake_pool_key = self.get_pool_pda(stake_pool_owner);
+        let (stake_acc_key, _) = self.get_stake_account_pda(&stake_pool_key, owner);
+        let ix = get_stake_summary(
+            self.program_id,
+            get_stake_summary::Accounts {
+                central_state: &self.central_state,
+                stake_pool: &stake_pool_key,
+                stake_account: &stake_acc_key,
+                bond_v2_accounts,
+            },
+            get_stake_summary::Params { owner: *owner },
+        );
+        simulate_view(&mut self.prg_test_ctx, ix).await
+    }
+
+    pub async fn view_central_state_info(&mut self) -> Result<CentralStateInfoView, BanksClientError> {
+        let ix = get_central_state_info(
+            self.program_id,
+            get_central_state_info::Accounts {
+                central_state: &self.central_state,
+            },
+            get_central_state_info::Params {},
+        );
+        simulate_view(&mut self.prg_test_ctx, ix).await
     }
 
     pub async fn setup_fee_split(
@@ -1319,11 +1660,13 @@ impl TestRunner {
             admin_setup_fee_split::Accounts {
                 authority: &self.prg_test_ctx.payer.pubkey(),
                 central_state: &self.central_state,
+                role_table: None,
             },
             admin_setup_fee_split::Params { recipients },
         );
-        sign_send_instructions(
+        sign_send_instructions_with_metadata(
             &mut self.prg_test_ctx,
+            &mut self.last_transaction,
             vec![admin_setup_fee_split_ix],
             vec![],
         )
@@ -1339,12 +1682,109 @@ impl TestRunner {
             admin_set_protocol_fee::Accounts {
                 central_state: &self.central_state,
                 authority: &self.prg_test_ctx.payer.pubkey(),
+                role_table: None,
             },
             admin_set_protocol_fee::Params {
                 protocol_fee_basis_points: new_fee,
             },
         );
-        sign_send_instructions(&mut self.prg_test_ctx, vec![ix], vec![]).await
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![ix], vec![]).await
+    }
+
+    pub async fn set_pool_fee_override(
+        &mut self,
+        stake_pool_owner: &Pubkey,
+        fee_basis_points: Option<u16>,
+    ) -> Result<(), BanksClientError> {
+        let stake_pool_key = self.get_pool_pda(stake_pool_owner);
+        let pool_fee_override = PoolFeeOverride::create_key(&stake_pool_key, &self.program_id).0;
+        let ix = admin_set_pool_fee_override(
+            self.program_id,
+            admin_set_pool_fee_override::Accounts {
+                authority: &self.prg_test_ctx.payer.pubkey(),
+                central_state: &self.central_state,
+                stake_pool: &stake_pool_key,
+                pool_fee_override: &pool_fee_override,
+                fee_payer: &self.prg_test_ctx.payer.pubkey(),
+                system_program: &system_program::ID,
+                role_table: None,
+            },
+            admin_set_pool_fee_override::Params { fee_basis_points },
+        );
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![ix], vec![]).await
+    }
+
+    pub async fn pool_fee_override_stats(
+        &mut self,
+        stake_pool_owner: &Pubkey,
+    ) -> Result<Option<PoolFeeOverride>, BanksClientError> {
+        let stake_pool_key = self.get_pool_pda(stake_pool_owner);
+        let pool_fee_override = PoolFeeOverride::create_key(&stake_pool_key, &self.program_id).0;
+        let acc = self
+            .prg_test_ctx
+            .banks_client
+            .get_account(pool_fee_override)
+            .await?;
+        match acc {
+            Some(acc) => Ok(Some(PoolFeeOverride::deserialize(&mut &acc.data[..])?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn set_keeper_reward(
+        &mut self,
+        keeper_reward_basis_points: u16,
+    ) -> Result<(), BanksClientError> {
+        let fee_distribution_config =
+            FeeDistributionConfig::create_key(&self.central_state, &self.program_id).0;
+        let ix = admin_set_keeper_reward(
+            self.program_id,
+            admin_set_keeper_reward::Accounts {
+                authority: &self.prg_test_ctx.payer.pubkey(),
+                central_state: &self.central_state,
+                fee_distribution_config: &fee_distribution_config,
+                fee_payer: &self.prg_test_ctx.payer.pubkey(),
+                system_program: &system_program::ID,
+                role_table: None,
+            },
+            admin_set_keeper_reward::Params {
+                keeper_reward_basis_points,
+            },
+        );
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![ix], vec![]).await
+    }
+
+    pub async fn set_protocol_config(
+        &mut self,
+        access_nft_program_signer: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let ix = admin_set_protocol_config(
+            self.program_id,
+            admin_set_protocol_config::Accounts {
+                authority: &self.prg_test_ctx.payer.pubkey(),
+                central_state: &self.central_state,
+                protocol_config: &self.protocol_config,
+                fee_payer: &self.prg_test_ctx.payer.pubkey(),
+                system_program: &system_program::ID,
+                role_table: None,
+            },
+            admin_set_protocol_config::Params {
+                access_nft_program_signer: *access_nft_program_signer,
+            },
+        );
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![ix], vec![]).await?;
+        self.access_nft_program_signer = *access_nft_program_signer;
+        Ok(())
+    }
+
+    pub async fn protocol_config_stats(&mut self) -> Result<ProtocolConfig, BanksClientError> {
+        let acc = self
+            .prg_test_ctx
+            .banks_client
+            .get_account(self.protocol_config)
+            .await?
+            .unwrap();
+        Ok(ProtocolConfig::deserialize(&mut &acc.data[..])?)
     }
 
     pub async fn change_central_state_authority(
@@ -1356,15 +1796,15 @@ impl TestRunner {
             change_central_state_authority::Accounts {
                 central_state: &self.central_state,
                 authority: &self.prg_test_ctx.payer.pubkey(),
+                role_table: None,
             },
             change_central_state_authority::Params {
                 new_authority: new_authority.pubkey(),
             },
         );
-        sign_send_instructions(&mut self.prg_test_ctx, vec![ix], vec![]).await?;
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![ix], vec![]).await?;
 
-        let authority_ata =
-            get_associated_token_address(&self.prg_test_ctx.payer.pubkey(), &self.mint);
+        let authority_ata = self.get_ata(&self.prg_test_ctx.payer.pubkey());
         self.authority_ata = authority_ata;
         Ok(())
     }
@@ -1378,31 +1818,41 @@ impl TestRunner {
             admin_change_freeze_authority::Accounts {
                 central_state: &self.central_state,
                 authority: &self.prg_test_ctx.payer.pubkey(),
+                role_table: None,
             },
             admin_change_freeze_authority::Params {
                 new_freeze_authority: new_authority.pubkey(),
             },
         );
-        sign_send_instructions(&mut self.prg_test_ctx, vec![ix], vec![]).await?;
+        sign_send_instructions_with_metadata(&mut self.prg_test_ctx, &mut self.last_transaction, vec![ix], vec![]).await?;
 
-        let authority_ata =
-            get_associated_token_address(&self.prg_test_ctx.payer.pubkey(), &self.mint);
+        let authority_ata = self.get_ata(&self.prg_test_ctx.payer.pubkey());
         self.authority_ata = authority_ata;
         Ok(())
     }
 
     pub async fn get_ata_balance(&mut self, owner: &Pubkey) -> Result<u64, BanksClientError> {
-        let ata = get_associated_token_address(owner, &self.mint);
-        let balance = self
+        let ata = self.get_ata(owner);
+        let balance = self.get_token_account(ata).await?.amount;
+        Ok(balance)
+    }
+
+    pub async fn get_token_account(
+        &mut self,
+        key: Pubkey,
+    ) -> Result<spl_token_2022::state::Account, BanksClientError> {
+        let acc = self
             .local_env
-            .get_packed_account_data::<spl_token::state::Account>(ata)
+            .get_account(key)
             .await?
-            .amount;
-        Ok(balance)
+            .ok_or(BanksClientError::ClientError("Token account not found"))?;
+        Ok(StateWithExtensions::<spl_token_2022::state::Account>::unpack(&acc.data)
+            .unwrap()
+            .base)
     }
 
-    pub fn get_ata(&mut self, owner: &Pubkey) -> Pubkey {
-        get_associated_token_address(owner, &self.mint)
+    pub fn get_ata(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(owner, &self.mint, &self.token_program)
     }
 
     pub async fn get_protocol_fees(&mut self) -> f64 {
@@ -1415,10 +1865,14 @@ impl TestRunner {
     }
 
     pub async fn token_stats(&mut self) -> Result<TokenStats, BanksClientError> {
-        let token_mint = self
+        let acc = self
             .local_env
-            .get_packed_account_data::<spl_token::state::Mint>(self.mint)
-            .await?;
+            .get_account(self.mint)
+            .await?
+            .ok_or(BanksClientError::ClientError("Mint not found"))?;
+        let token_mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&acc.data)
+            .unwrap()
+            .base;
         Ok(TokenStats {
             supply: token_mint.supply,
             decimals: token_mint.decimals,
@@ -1436,6 +1890,6 @@ impl TestRunner {
     }
 
     pub async fn get_royalty_account_key(&self, payer: &Pubkey) -> Pubkey {
-        RoyaltyAccount::create_key(payer, &self.program_id).0
+        RoyaltyAccount::create_scoped_key(payer, self.scope.as_ref(), &self.program_id).0
     }
 }
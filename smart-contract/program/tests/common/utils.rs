@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use borsh::BorshDeserialize;
+
 use solana_program::instruction::Instruction;
 use solana_program::program_pack::Pack;
 use solana_program::pubkey::Pubkey;
@@ -10,6 +12,8 @@ use solana_sdk::signature::Signer;
 
 use spl_token::state::Mint;
 
+use access_protocol::events::Event;
+
 // Utils
 // todo maybe don't sign everything with an authority here
 pub async fn sign_send_instructions(
@@ -26,6 +30,59 @@ pub async fn sign_send_instructions(
     ctx.banks_client.process_transaction(transaction).await
 }
 
+/// The events and the compute units of a transaction
+#[derive(Default)]
+pub struct LastTransaction {
+    pub events: Vec<Event>,
+    pub compute_units: u64,
+}
+
+/// Same as `sign_send_instructions` but replaces `last_transaction` with the metadata of the transaction
+pub async fn sign_send_instructions_with_metadata(
+    ctx: &mut ProgramTestContext,
+    last_transaction: &mut LastTransaction,
+    instructions: Vec<Instruction>,
+    signers: Vec<&Keypair>,
+) -> Result<(), BanksClientError> {
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&ctx.payer.pubkey()));
+    let mut payer_signers = vec![&ctx.payer];
+    for s in signers {
+        payer_signers.push(s);
+    }
+    transaction.partial_sign(&payer_signers, ctx.last_blockhash);
+    let result = ctx
+        .banks_client
+        .process_transaction_with_metadata(transaction)
+        .await?;
+    *last_transaction = result
+        .metadata
+        .map(|metadata| LastTransaction {
+            events: Event::from_logs(&access_protocol::ID, &metadata.log_messages),
+            compute_units: metadata.compute_units_consumed,
+        })
+        .unwrap_or_default();
+    result.result.map_err(Into::into)
+}
+
+/// Simulates a view instruction and decodes its return data
+pub async fn simulate_view<T: BorshDeserialize>(
+    ctx: &mut ProgramTestContext,
+    instruction: Instruction,
+) -> Result<T, BanksClientError> {
+    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&ctx.payer.pubkey()));
+    transaction.partial_sign(&[&ctx.payer], ctx.last_blockhash);
+    let simulation = ctx.banks_client.simulate_transaction(transaction).await?;
+    if let Some(Err(err)) = simulation.result {
+        return Err(err.into());
+    }
+    let return_data = simulation
+        .simulation_details
+        .and_then(|details| details.return_data)
+        .ok_or(BanksClientError::ClientError("No return data"))?;
+    T::try_from_slice(&return_data.data)
+        .map_err(|_| BanksClientError::ClientError("Invalid return data"))
+}
+
 pub async fn sign_send_instructions_without_authority(
     ctx: &mut ProgramTestContext,
     instructions: Vec<Instruction>,
@@ -41,6 +98,7 @@ pub fn mint_bootstrap(
     decimals: u8,
     program_test: &mut ProgramTest,
     mint_authority: &Pubkey,
+    token_program: &Pubkey,
 ) -> (Pubkey, Mint) {
     let address = address
         .map(|s| Pubkey::from_str(s).unwrap())
@@ -59,7 +117,7 @@ pub fn mint_bootstrap(
         Account {
             lamports: u32::MAX.into(),
             data: data.into(),
-            owner: spl_token::ID,
+            owner: *token_program,
             executable: false,
             ..Account::default()
         },
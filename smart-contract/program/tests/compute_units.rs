@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::fs;
+
+use solana_sdk::signer::Signer;
+
+use access_protocol::state::{FeeRecipient, MAX_FEE_RECIPIENTS, SECONDS_IN_DAY, STAKE_BUFFER_LEN};
+
+use crate::common::test_runner::TestRunner;
+
+pub mod common;
+
+// The budgets are checked in next to this file, they can be regenerated after an intended change
+// with `UPDATE_COMPUTE_BUDGET=1`
+const BUDGET_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/compute_budget.txt");
+// Headroom added to the measured compute units when regenerating the budgets, in %
+const BUDGET_HEADROOM: u64 = 10;
+
+fn parse_budget(content: &str) -> HashMap<String, u64> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| {
+            let mut parts = l.split_whitespace();
+            let name = parts.next().unwrap().to_string();
+            let units = parts
+                .next()
+                .and_then(|u| u.parse().ok())
+                .unwrap_or_else(|| panic!("Invalid budget line: {}", l));
+            (name, units)
+        })
+        .collect()
+}
+
+fn write_budget(measured: &[(&str, u64)]) {
+    let mut content = String::from(
+        "# Maximum compute units of each instruction at worst case, checked by tests/compute_units.rs\n\
+         # Regenerate with UPDATE_COMPUTE_BUDGET=1 after an intended change\n",
+    );
+    for (name, units) in measured {
+        content += &format!("{} {}\n", name, units + units * BUDGET_HEADROOM / 100);
+    }
+    fs::write(BUDGET_FILE, content).unwrap();
+}
+
+#[tokio::test]
+async fn compute_units() {
+    // Setup the token + basic accounts
+    let mut tr = TestRunner::new(1_000_000).await.unwrap();
+    let mut measured: Vec<(&str, u64)> = vec![];
+
+    // Create users
+    let pool_owner = tr.create_user_with_ata().await.unwrap();
+    let staker = tr.create_user_with_ata().await.unwrap();
+    let royalty_recipient = tr.create_user_with_ata().await.unwrap();
+    let bond_owner = tr.create_user_with_ata().await.unwrap();
+    let keeper = tr.create_user_with_ata().await.unwrap();
+    tr.get_tokens_from_supply(&staker.pubkey(), 100_000_000_000)
+        .await
+        .unwrap();
+
+    // Pool and stake account
+    // Created with the rewards buffer, the worst case of the claims
+    tr.create_legacy_pool(&pool_owner, 10_000).await.unwrap();
+    measured.push(("create_stake_pool", tr.last_compute_units()));
+    tr.activate_stake_pool(&pool_owner.pubkey()).await.unwrap();
+    measured.push(("activate_stake_pool", tr.last_compute_units()));
+    tr.create_stake_account(&pool_owner.pubkey(), &staker.pubkey())
+        .await
+        .unwrap();
+    measured.push(("create_stake_account", tr.last_compute_units()));
+
+    // The maximum number of fee recipients, 10 % of the fees are burned
+    let mut recipients = vec![];
+    for _ in 0..MAX_FEE_RECIPIENTS {
+        let recipient = tr.create_user_with_ata().await.unwrap();
+        recipients.push(FeeRecipient {
+            owner: recipient.pubkey(),
+            percentage: 90 / MAX_FEE_RECIPIENTS as u64,
+        });
+    }
+    tr.setup_fee_split(recipients).await.unwrap();
+    measured.push(("admin_setup_fee_split", tr.last_compute_units()));
+    tr.set_keeper_reward(100).await.unwrap();
+    measured.push(("admin_set_keeper_reward", tr.last_compute_units()));
+    tr.set_pool_fee_override(&pool_owner.pubkey(), Some(150))
+        .await
+        .unwrap();
+    measured.push(("admin_set_pool_fee_override", tr.last_compute_units()));
+
+    // The claims pay royalties
+    let current_time = tr.get_current_time().await;
+    tr.create_royalty(
+        &staker,
+        &royalty_recipient.pubkey(),
+        1000,
+        (current_time + 1000 * SECONDS_IN_DAY as i64) as u64,
+    )
+    .await
+    .unwrap();
+    measured.push(("create_royalty_account", tr.last_compute_units()));
+
+    // Enough fees for a distribution
+    tr.stake(&pool_owner.pubkey(), &staker, 7_000_000_000)
+        .await
+        .unwrap();
+    measured.push(("stake", tr.last_compute_units()));
+
+    // Bonds
+    let unlock_date = Some(current_time + SECONDS_IN_DAY as i64);
+    tr.create_bond_v2(&staker.pubkey(), &pool_owner.pubkey(), unlock_date)
+        .await
+        .unwrap();
+    measured.push(("create_bond_v2", tr.last_compute_units()));
+    tr.add_to_bond_v2(
+        &staker,
+        &staker.pubkey(),
+        &pool_owner.pubkey(),
+        20_000,
+        unlock_date,
+    )
+    .await
+    .unwrap();
+    measured.push(("add_to_bond_v2", tr.last_compute_units()));
+    tr.create_bond(
+        &pool_owner.pubkey(),
+        &bond_owner.pubkey(),
+        20_000,
+        1,
+        5 * SECONDS_IN_DAY as i64,
+        1,
+    )
+    .await
+    .unwrap();
+    measured.push(("create_bond", tr.last_compute_units()));
+    tr.claim_bond(&pool_owner.pubkey(), &bond_owner.pubkey())
+        .await
+        .unwrap();
+    measured.push(("claim_bond", tr.last_compute_units()));
+
+    // The claims go through the full rewards buffer of the pool
+    tr.sleep((STAKE_BUFFER_LEN + 10) * SECONDS_IN_DAY).await.unwrap();
+    tr.crank_pool(&pool_owner.pubkey()).await.unwrap();
+    measured.push(("crank", tr.last_compute_units()));
+    tr.claim_staker_rewards(&pool_owner.pubkey(), &staker)
+        .await
+        .unwrap();
+    measured.push(("claim_rewards", tr.last_compute_units()));
+    tr.claim_pool_rewards(&pool_owner).await.unwrap();
+    measured.push(("claim_pool_rewards", tr.last_compute_units()));
+    tr.claim_bond_v2_rewards(&staker, &pool_owner.pubkey(), unlock_date)
+        .await
+        .unwrap();
+    measured.push(("claim_bond_v2_rewards", tr.last_compute_units()));
+    tr.claim_bond_rewards(&pool_owner.pubkey(), &bond_owner)
+        .await
+        .unwrap();
+    measured.push(("claim_bond_rewards", tr.last_compute_units()));
+
+    // Withdrawals
+    tr.unstake(&pool_owner.pubkey(), &staker, 1_000_000)
+        .await
+        .unwrap();
+    measured.push(("unstake", tr.last_compute_units()));
+    tr.unlock_bond_v2_tokens(&staker, &pool_owner.pubkey(), unlock_date)
+        .await
+        .unwrap();
+    measured.push(("unlock_bond_v2", tr.last_compute_units()));
+
+    // Fee distribution to every recipient and the keeper
+    tr.distribute_fees_with_keeper(&keeper.pubkey())
+        .await
+        .unwrap();
+    measured.push(("distribute_fees", tr.last_compute_units()));
+    tr.close_royalty(&staker).await.unwrap();
+    measured.push(("close_royalty_account", tr.last_compute_units()));
+
+    // Admin
+    tr.mint(&staker.pubkey(), 1_000).await.unwrap();
+    measured.push(("admin_mint", tr.last_compute_units()));
+    tr.change_inflation(2_000_000).await.unwrap();
+    measured.push(("change_inflation", tr.last_compute_units()));
+    tr.change_protocol_fee(300).await.unwrap();
+    measured.push(("admin_set_protocol_fee", tr.last_compute_units()));
+    tr.change_pool_minimum(&pool_owner, 20_000).await.unwrap();
+    measured.push(("change_pool_minimum", tr.last_compute_units()));
+    tr.change_pool_multiplier(&pool_owner, 40).await.unwrap();
+    measured.push(("change_pool_multiplier", tr.last_compute_units()));
+    let stake_pool_key = tr.get_pool_pda(&pool_owner.pubkey());
+    let (stake_account_key, _) = tr.get_stake_account_pda(&stake_pool_key, &staker.pubkey());
+    tr.freeze_account(&stake_account_key).await.unwrap();
+    measured.push(("admin_freeze", tr.last_compute_units()));
+    tr.freeze_program(u128::MAX, None).await.unwrap();
+    measured.push(("admin_program_freeze", tr.last_compute_units()));
+
+    if std::env::var("UPDATE_COMPUTE_BUDGET").is_ok() {
+        write_budget(&measured);
+        return;
+    }
+
+    let budget = parse_budget(&fs::read_to_string(BUDGET_FILE).unwrap());
+    let mut over_budget = vec![];
+    for (name, units) in &measured {
+        let limit = *budget
+            .get(*name)
+            .unwrap_or_else(|| panic!("No compute budget for {}", name));
+        println!("{:<30} {:>8} / {:>8}", name, units, limit);
+        if *units > limit {
+            over_budget.push(format!("{} uses {} compute units, budget {}", name, units, limit));
+        }
+    }
+    assert!(
+        over_budget.is_empty(),
+        "Compute budget exceeded:\n{}",
+        over_budget.join("\n")
+    );
+}
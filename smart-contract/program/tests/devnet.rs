@@ -73,6 +73,7 @@ fn devnet() {
             access_token_destination: &token_bank_ata,
             central_state: &central_state_key,
             spl_token_program: &spl_token::ID,
+            role_table: None,
         },
         admin_mint::Params { amount: 10_200_000_000 },
     );
@@ -258,6 +259,8 @@ fn create_stake_acc(
             fee_payer: &fee_payer.pubkey(),
             stake_pool: &pool,
             central_state: &central_state_key,
+            pool_controls: None,
+            allowlist_entry: None,
         },
         create_stake_account::Params {
             nonce: bump_seed,
@@ -297,6 +300,14 @@ fn lock(
             vault: &pool_vault,
             central_state: &central_state,
             central_state_vault: &central_state_vault,
+            pool_fee_override: None,
+            mint: &token_mint,
+            pool_controls: None,
+            allowlist_entry: None,
+            receipt_mint: None,
+            receipt_account: None,
+            receipt_reserve: None,
+            transfer_hook_accounts: &[],
         },
         stake::Params {
             amount,
@@ -354,6 +365,7 @@ fn set_fee_recipients(
         admin_setup_fee_split::Accounts {
             authority: &authority.pubkey(),
             central_state: &central_state_key,
+            role_table: None,
         },
         admin_setup_fee_split::Params { recipients },
     );
@@ -396,6 +408,7 @@ pub fn crank_pool(
         crank::Accounts {
             stake_pool: &pool,
             central_state: &central_state_key,
+            pool_boost: None,
         },
         crank::Params {},
     );
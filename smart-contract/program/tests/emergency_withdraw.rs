@@ -0,0 +1,204 @@
+use std::mem::size_of;
+
+use borsh::BorshSerialize;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+use access_protocol::error::AccessError;
+use access_protocol::state::{
+    CentralStateV2, FeeRecipient, Tag, MAX_FEE_RECIPIENTS, SECONDS_IN_DAY,
+};
+use access_protocol::testing::ProtocolTest;
+use access_protocol::versioning::{read_header, set_tag, Migrate, Versioned};
+use bonfida_utils::BorshSize;
+
+// Runs with `cargo test --features testing --test emergency_withdraw`, see `access_protocol::testing`
+
+fn err(e: AccessError) -> Result<(), ProgramError> {
+    Err(e.into())
+}
+
+// Freezes an account the way the deprecated `admin_freeze` did
+fn freeze(pt: &mut ProtocolTest, key: &Pubkey, tag: Tag) {
+    let mut account = pt.account(key).unwrap().clone();
+    set_tag(&mut account.data, tag).unwrap();
+    pt.set_account(*key, account);
+}
+
+#[test]
+fn emergency_withdraw() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+    let pool_owner = pt.create_user_with_ata();
+    let alice = pt.create_user_with_ata();
+    let bob = pt.create_user_with_ata();
+    pt.mint(&alice, 11_000).unwrap();
+    pt.mint(&bob, 11_000).unwrap();
+    let carol = pt.create_user_with_ata();
+    pt.mint(&carol, 2_000).unwrap();
+    pt.create_pool(&pool_owner, 1_000).unwrap();
+    pt.activate_stake_pool(&pool_owner).unwrap();
+
+    // Alice stakes and Bob locks a boosted bond V2
+    pt.create_stake_account(&pool_owner, &alice).unwrap();
+    pt.stake(&pool_owner, &alice, 5_000).unwrap();
+    let unlock_date = Some(pt.clock().unix_timestamp + 100 * SECONDS_IN_DAY as i64);
+    pt.create_bond_v2(&bob, &pool_owner, unlock_date).unwrap();
+    pt.add_to_bond_v2(&bob, &bob, &pool_owner, 5_000, unlock_date)
+        .unwrap();
+    pt.set_boost_config(100 * SECONDS_IN_DAY as i64, 10_000, 20_000)
+        .unwrap();
+    pt.refresh_bond_v2_boost(&bob, &pool_owner, unlock_date)
+        .unwrap();
+    // Carol stakes for receipts
+    pt.create_receipt_mint(&pool_owner).unwrap();
+    pt.stake_for_receipts(&pool_owner, &carol, 1_000).unwrap();
+    let central_state = pt.central_state_stats().unwrap();
+    assert_eq!(central_state.total_staked, 11_000);
+    assert_eq!(central_state.total_boost, 5_000);
+
+    // The emergency withdrawal is closed while the exits are not frozen
+    assert_eq!(
+        pt.emergency_withdraw(&alice, &pool_owner, None),
+        err(AccessError::EmergencyExitNotOpen)
+    );
+    assert_eq!(pt.central_state_stats().unwrap().exits_frozen_since, 0);
+
+    // The freeze of the exits is recorded by the admin program freeze
+    pt.sleep_days(1);
+    pt.program_freeze(0).unwrap();
+    assert_eq!(
+        pt.unstake(&pool_owner, &alice, 5_000),
+        err(AccessError::FrozenInstruction)
+    );
+    let frozen_since = pt.clock().unix_timestamp;
+    assert_eq!(
+        pt.central_state_stats().unwrap().exits_frozen_since,
+        frozen_since
+    );
+
+    // A short unfreeze does not restart the freeze
+    pt.sleep_days(1);
+    pt.program_freeze(u128::MAX).unwrap();
+    let central_state = pt.central_state_stats().unwrap();
+    assert_eq!(central_state.exits_frozen_since, frozen_since);
+    assert_eq!(central_state.exits_unfrozen_since, pt.clock().unix_timestamp);
+    pt.sleep_days(1);
+    pt.program_freeze(0).unwrap();
+    let central_state = pt.central_state_stats().unwrap();
+    assert_eq!(central_state.exits_frozen_since, frozen_since);
+    assert_eq!(central_state.exits_unfrozen_since, 0);
+
+    // The emergency withdrawal opens after the maximum freeze duration
+    pt.sleep_days(26);
+    assert_eq!(
+        pt.emergency_withdraw(&alice, &pool_owner, None),
+        err(AccessError::EmergencyExitNotOpen)
+    );
+    pt.sleep_days(2);
+    assert_eq!(
+        pt.emergency_withdraw(&bob, &pool_owner, None),
+        err(AccessError::WrongStakeAccountOwner)
+    );
+    // The frozen pools and stake accounts are covered
+    let stake_pool = pt.get_pool_pda(&pool_owner);
+    let alice_stake = pt.get_stake_account_pda(&stake_pool, &alice).0;
+    freeze(&mut pt, &stake_pool, Tag::FrozenStakePool);
+    freeze(&mut pt, &alice_stake, Tag::FrozenStakeAccount);
+    let balance = pt.token_balance(&alice).unwrap();
+    pt.emergency_withdraw(&alice, &pool_owner, None).unwrap();
+    assert_eq!(pt.token_balance(&alice).unwrap(), balance + 5_000);
+    assert_eq!(
+        pt.stake_account_stats(&alice, &pool_owner)
+            .unwrap()
+            .stake_amount,
+        0
+    );
+    assert_eq!(
+        pt.emergency_withdraw(&alice, &pool_owner, None),
+        Err(ProgramError::InvalidArgument)
+    );
+    freeze(&mut pt, &stake_pool, Tag::StakePool);
+
+    // The bond V2 stays locked until its unlock date
+    assert_eq!(
+        pt.emergency_withdraw(&bob, &pool_owner, Some(unlock_date)),
+        Err(ProgramError::InvalidArgument)
+    );
+
+    // The receipt holder burns their receipts for the stake backing them
+    let balance = pt.token_balance(&carol).unwrap();
+    pt.emergency_withdraw_receipts(&carol, &pool_owner).unwrap();
+    assert_eq!(pt.token_balance(&carol).unwrap(), balance + 1_000);
+    assert_eq!(
+        pt.token_account_balance(&pt.get_receipt_account(&carol, &pool_owner))
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        pt.emergency_withdraw_receipts(&carol, &pool_owner),
+        Err(ProgramError::InvalidArgument)
+    );
+
+    // Past its unlock date, the bond V2 returns its principal and drops its boost
+    pt.sleep_days(70);
+    let balance = pt.token_balance(&bob).unwrap();
+    pt.emergency_withdraw(&bob, &pool_owner, Some(unlock_date))
+        .unwrap();
+    assert_eq!(pt.token_balance(&bob).unwrap(), balance + 5_000);
+    let bond = pt.bond_v2_stats(&bob, &pool_owner, unlock_date).unwrap();
+    assert_eq!((bond.amount, bond.boost), (0, 0));
+    assert_eq!(pt.pool_boost_stats(&pool_owner).unwrap().total_boost, 0);
+    assert_eq!(pt.pool_stats(&pool_owner).unwrap().total_staked, 0);
+    let central_state = pt.central_state_stats().unwrap();
+    assert_eq!(central_state.total_staked, 0);
+    assert_eq!(central_state.total_boost, 0);
+
+    // A long enough unfreeze closes the emergency withdrawal and restarts the freeze
+    pt.program_freeze(u128::MAX).unwrap();
+    pt.sleep_days(7);
+    pt.program_freeze(0).unwrap();
+    assert_eq!(
+        pt.central_state_stats().unwrap().exits_frozen_since,
+        pt.clock().unix_timestamp
+    );
+    assert_eq!(
+        pt.emergency_withdraw(&alice, &pool_owner, None),
+        err(AccessError::EmergencyExitNotOpen)
+    );
+}
+
+#[test]
+fn program_freeze_migrates_the_central_state() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+
+    // A central state still on the V0 layout has no room for the freeze of the exits
+    let key = pt.central_state;
+    let mut account = pt.account(&key).unwrap().clone();
+    let previous = CentralStateV2::load(&account.data)
+        .unwrap()
+        .to_previous()
+        .unwrap();
+    account.data = previous.try_to_vec().unwrap();
+    account.data.resize(
+        previous.borsh_len() + size_of::<FeeRecipient>() * MAX_FEE_RECIPIENTS,
+        0,
+    );
+    pt.set_account(key, account);
+    assert_eq!(read_header(&pt.account(&key).unwrap().data).unwrap().1, 0);
+
+    // The freeze migrates it before recording the freeze
+    pt.sleep_days(1);
+    pt.program_freeze(0).unwrap();
+    assert_eq!(
+        read_header(&pt.account(&key).unwrap().data).unwrap().1,
+        CentralStateV2::VERSION
+    );
+    let central_state = pt.central_state_stats().unwrap();
+    assert_eq!(central_state.ix_gate, 0);
+    assert_eq!(central_state.exits_frozen_since, pt.clock().unix_timestamp);
+    pt.program_freeze(u128::MAX).unwrap();
+    assert_eq!(
+        pt.central_state_stats().unwrap().exits_unfrozen_since,
+        pt.clock().unix_timestamp
+    );
+}
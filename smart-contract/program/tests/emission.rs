@@ -0,0 +1,186 @@
+use std::mem::size_of;
+
+use borsh::BorshSerialize;
+use solana_program::program_error::ProgramError;
+
+use access_protocol::error::AccessError;
+use access_protocol::state::{CentralStateV2, FeeRecipient, MAX_FEE_RECIPIENTS};
+use access_protocol::testing::ProtocolTest;
+use access_protocol::versioning::{read_header, Migrate, Versioned};
+use bonfida_utils::BorshSize;
+
+// Runs with `cargo test --features testing --test emission`, see `access_protocol::testing`
+
+fn err(e: AccessError) -> Result<(), ProgramError> {
+    Err(e.into())
+}
+
+#[test]
+fn emission() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+    let admin = pt.payer;
+    let pool_owner = pt.create_user_with_ata();
+    let staker = pt.create_user_with_ata();
+    pt.mint(&staker, 20_000).unwrap();
+    pt.create_pool(&pool_owner, 1_000).unwrap();
+    pt.activate_stake_pool(&pool_owner).unwrap();
+    pt.create_stake_account(&pool_owner, &staker).unwrap();
+    pt.stake(&pool_owner, &staker, 10_000).unwrap();
+
+    // A central state migrated to V2 before the counters cannot mint until it is migrated
+    let key = pt.central_state;
+    let mut account = pt.account(&key).unwrap().clone();
+    let previous = CentralStateV2::load(&account.data)
+        .unwrap()
+        .to_previous()
+        .unwrap();
+    account.data = previous.try_to_vec().unwrap();
+    account.data.resize(
+        previous.borsh_len() + size_of::<FeeRecipient>() * MAX_FEE_RECIPIENTS,
+        0,
+    );
+    pt.set_account(key, account);
+    pt.sleep_days(1);
+    pt.crank_pool(&pool_owner).unwrap();
+    assert_eq!(read_header(&pt.account(&key).unwrap().data).unwrap().1, 0);
+    assert_eq!(
+        pt.claim_staker_rewards(&pool_owner, &staker),
+        err(AccessError::AccountMigrationRequired)
+    );
+    assert_eq!(
+        pt.set_emission_cap(&admin, 1_000_000_000),
+        err(AccessError::AccountMigrationRequired)
+    );
+
+    // The migration starts the counters and keeps the room of the fee recipients
+    pt.migrate_account(&key).unwrap();
+    let central_state = pt.central_state_stats().unwrap();
+    assert_eq!(
+        read_header(&pt.account(&key).unwrap().data).unwrap().1,
+        CentralStateV2::VERSION
+    );
+    assert_eq!(central_state.total_staked, 10_000);
+    assert_eq!(central_state.total_emitted().unwrap(), 0);
+    assert_eq!(
+        pt.account(&key).unwrap().data.len(),
+        central_state.borsh_len() + size_of::<FeeRecipient>() * MAX_FEE_RECIPIENTS
+    );
+
+    // The claims count the minted rewards
+    let migrated_supply = pt.supply().unwrap();
+    let balance = pt.token_balance(&staker).unwrap();
+    pt.claim_staker_rewards(&pool_owner, &staker).unwrap();
+    pt.claim_pool_rewards(&pool_owner).unwrap();
+    let central_state = pt.central_state_stats().unwrap();
+    assert_eq!(
+        central_state.staker_rewards_minted,
+        pt.token_balance(&staker).unwrap() - balance
+    );
+    assert_eq!(
+        central_state.pool_rewards_minted,
+        pt.token_balance(&pool_owner).unwrap()
+    );
+    assert_eq!(central_state.royalties_minted, 0);
+    assert_eq!(
+        central_state.total_emitted().unwrap(),
+        pt.supply().unwrap() - migrated_supply
+    );
+
+    // The cap bounds the supply, including the tokens minted before the counters
+    let supply = pt.supply().unwrap();
+    assert_eq!(
+        pt.set_emission_cap(&staker, supply + 100_000),
+        err(AccessError::WrongCentralStateAuthority)
+    );
+    assert_eq!(
+        pt.set_emission_cap(&admin, central_state.total_emitted().unwrap()),
+        err(AccessError::EmissionCapExceeded)
+    );
+    assert_eq!(
+        pt.set_emission_cap(&admin, supply - 1),
+        err(AccessError::EmissionCapExceeded)
+    );
+    pt.set_emission_cap(&admin, supply + 100_000).unwrap();
+    assert_eq!(
+        pt.set_emission_cap(&admin, supply + 100_000),
+        err(AccessError::NoOp)
+    );
+
+    // The claims exceeding the cap fail until it is raised or removed
+    pt.sleep_days(1);
+    pt.crank_pool(&pool_owner).unwrap();
+    assert_eq!(
+        pt.claim_staker_rewards(&pool_owner, &staker),
+        err(AccessError::EmissionCapExceeded)
+    );
+    assert_eq!(
+        pt.claim_pool_rewards(&pool_owner),
+        err(AccessError::EmissionCapExceeded)
+    );
+    pt.set_emission_cap(&admin, 0).unwrap();
+    pt.claim_staker_rewards(&pool_owner, &staker).unwrap();
+    pt.claim_pool_rewards(&pool_owner).unwrap();
+    let emitted = pt.central_state_stats().unwrap().total_emitted().unwrap();
+    assert_eq!(emitted, pt.supply().unwrap() - migrated_supply);
+
+    // The counters cannot be dropped by the previous layout
+    assert_eq!(
+        pt.central_state_stats().unwrap().to_previous().err(),
+        Some(AccessError::AccountMigrationRequired.into())
+    );
+}
+
+#[test]
+fn migration_order() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+    let admin = pt.payer;
+    let pool_owner = pt.create_user_with_ata();
+    let staker = pt.create_user_with_ata();
+    pt.mint(&staker, 20_000).unwrap();
+    pt.create_pool(&pool_owner, 1_000).unwrap();
+    pt.activate_stake_pool(&pool_owner).unwrap();
+    pt.create_stake_account(&pool_owner, &staker).unwrap();
+
+    // The program is upgraded over a central state with the previous layout
+    let key = pt.central_state;
+    let mut account = pt.account(&key).unwrap().clone();
+    let previous = CentralStateV2::load(&account.data)
+        .unwrap()
+        .to_previous()
+        .unwrap();
+    account.data = previous.try_to_vec().unwrap();
+    account.data.resize(
+        previous.borsh_len() + size_of::<FeeRecipient>() * MAX_FEE_RECIPIENTS,
+        0,
+    );
+    pt.set_account(key, account);
+
+    // The instructions leaving the counters untouched keep working
+    pt.stake(&pool_owner, &staker, 10_000).unwrap();
+    pt.sleep_days(1);
+    pt.crank_pool(&pool_owner).unwrap();
+    assert_eq!(read_header(&pt.account(&key).unwrap().data).unwrap().1, 0);
+
+    // Every instruction counting a mint waits for the migration
+    assert_eq!(
+        pt.claim_staker_rewards(&pool_owner, &staker),
+        err(AccessError::AccountMigrationRequired)
+    );
+    assert_eq!(
+        pt.claim_pool_rewards(&pool_owner),
+        err(AccessError::AccountMigrationRequired)
+    );
+    assert_eq!(
+        pt.set_emission_cap(&admin, 1_000_000_000),
+        err(AccessError::AccountMigrationRequired)
+    );
+
+    // `MigrateAccount` on the central state right after the upgrade unblocks them
+    pt.migrate_account(&key).unwrap();
+    pt.claim_staker_rewards(&pool_owner, &staker).unwrap();
+    pt.claim_pool_rewards(&pool_owner).unwrap();
+    pt.set_emission_cap(&admin, 1_000_000_000).unwrap();
+    let central_state = pt.central_state_stats().unwrap();
+    assert!(central_state.staker_rewards_minted > 0);
+    assert!(central_state.pool_rewards_minted > 0);
+}
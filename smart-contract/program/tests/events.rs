@@ -0,0 +1,202 @@
+use borsh::BorshSerialize;
+use solana_program::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+
+use access_protocol::events::{AdminChange, ClaimKind, Event};
+
+use crate::common::test_runner::TestRunner;
+
+pub mod common;
+
+#[tokio::test]
+async fn events() {
+    // Setup the token + basic accounts
+    let mut tr = TestRunner::new(1_000_000).await.unwrap();
+    let central_state = tr.get_central_state();
+
+    // Create users
+    let stake_pool_owner = tr.create_user_with_ata().await.unwrap();
+    let staker = tr.create_user_with_ata().await.unwrap();
+    let stake_pool = tr.get_pool_pda(&stake_pool_owner.pubkey());
+    let (stake_account, _) = tr.get_stake_account_pda(&stake_pool, &staker.pubkey());
+
+    // Setup a pool with one staker
+    tr.mint(&staker.pubkey(), 10_200).await.unwrap();
+    tr.create_pool(&stake_pool_owner, 10_000)
+        .await
+        .unwrap();
+    tr.activate_stake_pool(&stake_pool_owner.pubkey())
+        .await
+        .unwrap();
+    tr.create_stake_account(&stake_pool_owner.pubkey(), &staker.pubkey())
+        .await
+        .unwrap();
+
+    // Stake
+    tr.stake(&stake_pool_owner.pubkey(), &staker, 10_000)
+        .await
+        .unwrap();
+    match tr.last_events() {
+        [Event::Stake {
+            central_state: cs,
+            stake_pool: sp,
+            stake_account: sa,
+            owner,
+            amount,
+            fee,
+        }] => {
+            assert_eq!(*cs, central_state);
+            assert_eq!(*sp, stake_pool);
+            assert_eq!(*sa, stake_account);
+            assert_eq!(*owner, staker.pubkey());
+            assert_eq!(*amount, 10_000);
+            assert_eq!(*fee, 200);
+        }
+        events => panic!("Unexpected events {:?}", events),
+    }
+
+    // Crank
+    tr.sleep(86400).await.unwrap();
+    tr.crank_pool(&stake_pool_owner.pubkey()).await.unwrap();
+    let current_offset = tr
+        .central_state_stats()
+        .await
+        .unwrap()
+        .account
+        .last_snapshot_offset;
+    match tr.last_events() {
+        [Event::Crank {
+            stake_pool: sp,
+            day,
+            pool_reward,
+            stakers_reward,
+            ..
+        }] => {
+            assert_eq!(*sp, stake_pool);
+            assert_eq!(*day, current_offset);
+            assert!(*pool_reward > 0);
+            assert!(*stakers_reward > 0);
+        }
+        events => panic!("Unexpected events {:?}", events),
+    }
+
+    // Claim the staker and the pool rewards
+    tr.claim_staker_rewards(&stake_pool_owner.pubkey(), &staker)
+        .await
+        .unwrap();
+    match tr.last_events() {
+        [Event::Claim {
+            kind: ClaimKind::StakeAccount,
+            account,
+            owner,
+            destination,
+            reward,
+            ..
+        }] => {
+            assert_eq!(*account, stake_account);
+            assert_eq!(*owner, staker.pubkey());
+            assert_eq!(*destination, tr.get_ata(&staker.pubkey()));
+            assert_eq!(*reward, 500_000);
+        }
+        events => panic!("Unexpected events {:?}", events),
+    }
+    tr.claim_pool_rewards(&stake_pool_owner).await.unwrap();
+    match tr.last_events() {
+        [Event::Claim {
+            kind: ClaimKind::StakePool,
+            account,
+            owner,
+            reward,
+            ..
+        }] => {
+            assert_eq!(*account, stake_pool);
+            assert_eq!(*owner, stake_pool_owner.pubkey());
+            assert_eq!(*reward, 500_000);
+        }
+        events => panic!("Unexpected events {:?}", events),
+    }
+
+    // Unstake
+    tr.unstake(&stake_pool_owner.pubkey(), &staker, 10_000)
+        .await
+        .unwrap();
+    match tr.last_events() {
+        [Event::Unstake {
+            stake_account: sa,
+            owner,
+            amount,
+            ..
+        }] => {
+            assert_eq!(*sa, stake_account);
+            assert_eq!(*owner, staker.pubkey());
+            assert_eq!(*amount, 10_000);
+        }
+        events => panic!("Unexpected events {:?}", events),
+    }
+
+    // Admin change
+    tr.change_inflation(2_000_000).await.unwrap();
+    let authority = tr.central_state_stats().await.unwrap().account.authority;
+    match tr.last_events() {
+        [Event::Admin {
+            authority: a,
+            change: AdminChange::Inflation { daily_inflation },
+            ..
+        }] => {
+            assert_eq!(*a, authority);
+            assert_eq!(*daily_inflation, 2_000_000);
+        }
+        events => panic!("Unexpected events {:?}", events),
+    }
+}
+
+#[test]
+fn events_of_other_programs() {
+    use base64::Engine;
+    let program_id = access_protocol::ID;
+    let other_program = Pubkey::new_unique();
+    let data = |amount| {
+        let event = Event::RoyaltyPaid {
+            central_state: Pubkey::default(),
+            royalty_payer: Pubkey::default(),
+            recipient_ata: Pubkey::default(),
+            amount,
+        };
+        format!(
+            "Program data: {}",
+            base64::engine::general_purpose::STANDARD.encode(event.try_to_vec().unwrap())
+        )
+    };
+    let logs = vec![
+        format!("Program {} invoke [1]", other_program),
+        data(1),
+        format!("Program {} invoke [2]", program_id),
+        data(2),
+        format!("Program {} invoke [3]", other_program),
+        data(3),
+        format!("Program {} success", other_program),
+        data(4),
+        format!(
+            "Program {} consumed 1000 of 200000 compute units",
+            program_id
+        ),
+        format!("Program {} success", program_id),
+        data(5),
+        format!("Program {} success", other_program),
+        format!("Program {} invoke [1]", program_id),
+        data(6),
+        format!("Program {} failed: custom program error: 0x0", program_id),
+        data(7),
+    ];
+
+    // Only the lines logged by the program itself are decoded
+    let amounts: Vec<_> = Event::from_logs(&program_id, &logs)
+        .into_iter()
+        .map(|event| match event {
+            Event::RoyaltyPaid { amount, .. } => amount,
+            event => panic!("Unexpected event {:?}", event),
+        })
+        .collect();
+    assert_eq!(amounts, vec![2, 4, 6]);
+    assert_eq!(Event::from_logs(&other_program, &logs).len(), 3);
+}
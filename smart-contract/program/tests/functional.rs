@@ -22,7 +22,7 @@ use mpl_token_metadata::{instruction::create_metadata_accounts_v3, pda::find_met
 
 use spl_token::{instruction::set_authority, instruction::AuthorityType};
 use access_protocol::instruction::migrate_central_state_v2;
-use access_protocol::state::{ACCESS_NFT_PROGRAM_SIGNER, RoyaltyAccount};
+use access_protocol::state::{ProtocolConfig, RoyaltyAccount};
 
 #[tokio::test]
 async fn functional_10s() {
@@ -42,13 +42,15 @@ async fn functional_10s() {
     //
     let (central_state, _nonce) =
         Pubkey::find_program_address(&[&program_id.to_bytes()], &program_id);
+    let (protocol_config, _) = ProtocolConfig::create_key(&central_state, &program_id);
+    let access_nft_program_signer = Keypair::new();
 
     let authority = Keypair::new();
 
     //
     // Create mint
     //
-    let (mint, _) = mint_bootstrap(None, 6, &mut program_test, &authority.pubkey());
+    let (mint, _) = mint_bootstrap(None, 6, &mut program_test, &authority.pubkey(), &spl_token::ID);
 
     ////
     // Create test context
@@ -71,10 +73,12 @@ async fn functional_10s() {
             system_program: &system_program::ID,
             fee_payer: &prg_test_ctx.payer.pubkey(),
             mint: &mint,
+            protocol_config: &protocol_config,
         },
         create_central_state::Params {
             daily_inflation,
             authority: prg_test_ctx.payer.pubkey(),
+            access_nft_program_signer: access_nft_program_signer.pubkey(),
         },
     );
     sign_send_instructions(&mut prg_test_ctx, vec![create_central_state_ix], vec![])
@@ -181,6 +185,7 @@ async fn functional_10s() {
             authority: &prg_test_ctx.payer.pubkey(),
             metadata: &metadata_key,
             metadata_program: &mpl_token_metadata::ID,
+            role_table: None,
         },
         edit_metadata::Params {
             name: "New name".to_string(),
@@ -252,6 +257,7 @@ async fn functional_10s() {
             access_token_destination: &staker_token_acc,
             central_state: &central_state,
             spl_token_program: &spl_token::ID,
+            role_table: None,
         },
         admin_mint::Params {
             amount: 10_000_000_000 * 1_000_000,
@@ -425,6 +431,7 @@ async fn functional_10s() {
             access_mint: &mint,
             pool_vault: &pool_vault,
             central_state: &central_state,
+            quote_mint: None,
         },
         claim_bond::Params {},
     );
@@ -453,6 +460,8 @@ async fn functional_10s() {
             fee_payer: &prg_test_ctx.payer.pubkey(),
             stake_pool: &stake_pool_key,
             central_state: &central_state,
+            pool_controls: None,
+            allowlist_entry: None,
         },
         create_stake_account::Params {
             nonce: stake_nonce,
@@ -478,6 +487,14 @@ async fn functional_10s() {
             vault: &pool_vault,
             central_state: &central_state,
             central_state_vault: &authority_ata,
+            pool_fee_override: None,
+            mint: &mint,
+            pool_controls: None,
+            allowlist_entry: None,
+            receipt_mint: None,
+            receipt_account: None,
+            receipt_reserve: None,
+            transfer_hook_accounts: &[],
         },
         stake::Params {
             amount: token_amount,
@@ -506,6 +523,7 @@ async fn functional_10s() {
         crank::Accounts {
             stake_pool: &stake_pool_key,
             central_state: &central_state,
+            pool_boost: None,
         },
         crank::Params {},
     );
@@ -582,10 +600,11 @@ async fn functional_10s() {
             rewards_destination: &staker_token_acc,
             central_state: &central_state,
             mint: &mint,
-            access_nft_signer: &ACCESS_NFT_PROGRAM_SIGNER,
+            access_nft_signer: &access_nft_program_signer.pubkey(),
             spl_token_program: &spl_token::ID,
             owner_royalty_account: &RoyaltyAccount::create_key(&staker.pubkey(), &program_id).0,
             royalty_ata: None,
+            protocol_config: None,
         },
         claim_rewards::Params {
             allow_zero_rewards: false,
@@ -606,6 +625,7 @@ async fn functional_10s() {
         crank::Accounts {
             stake_pool: &stake_pool_key,
             central_state: &central_state,
+            pool_boost: None,
         },
         crank::Params {},
     );
@@ -674,6 +694,7 @@ async fn functional_10s() {
             central_state: &central_state,
             authority: &prg_test_ctx.payer.pubkey(),
             mint: &mint,
+            role_table: None,
         },
         change_inflation::Params {
             daily_inflation: new_inflation,
@@ -721,10 +742,11 @@ async fn functional_10s() {
             rewards_destination: &staker_token_acc,
             central_state: &central_state,
             mint: &mint,
-            access_nft_signer: &ACCESS_NFT_PROGRAM_SIGNER,
+            access_nft_signer: &access_nft_program_signer.pubkey(),
             spl_token_program: &spl_token::ID,
             owner_royalty_account: &RoyaltyAccount::create_key(&staker.pubkey(), &program_id).0,
             royalty_ata: None,
+            protocol_config: None,
         },
         claim_rewards::Params {
             allow_zero_rewards: false,
@@ -750,6 +772,11 @@ async fn functional_10s() {
             spl_token_program: &spl_token::ID,
             vault: &pool_vault,
             central_state: &central_state,
+            mint: &mint,
+            receipt_mint: None,
+            receipt_account: None,
+            receipt_reserve: None,
+            transfer_hook_accounts: &[],
         },
         unstake::Params {
             amount: token_amount,
@@ -773,6 +800,7 @@ async fn functional_10s() {
             central_state: &central_state,
             account_to_freeze: &stake_pool_key,
             authority: &prg_test_ctx.payer.pubkey(),
+            role_table: None,
         },
         admin_freeze::Params {},
     );
@@ -795,6 +823,7 @@ async fn functional_10s() {
             central_state: &central_state,
             account_to_freeze: &stake_pool_key,
             authority: &prg_test_ctx.payer.pubkey(),
+            role_table: None,
         },
         admin_freeze::Params {},
     );
@@ -813,6 +842,7 @@ async fn functional_10s() {
             central_state: &central_state,
             account_to_freeze: &central_state,
             authority: &prg_test_ctx.payer.pubkey(),
+            role_table: None,
         },
         admin_freeze::Params {},
     );
@@ -876,6 +906,7 @@ async fn functional_10s() {
         change_central_state_authority::Accounts {
             central_state: &central_state,
             authority: &prg_test_ctx.payer.pubkey(),
+            role_table: None,
         },
         change_central_state_authority::Params {
             new_authority: Keypair::new().pubkey(),
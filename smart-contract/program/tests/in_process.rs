@@ -0,0 +1,134 @@
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+use access_protocol::error::AccessError;
+use access_protocol::instruction::stake;
+use access_protocol::state::SECONDS_IN_DAY;
+use access_protocol::testing::ProtocolTest;
+
+// Runs with `cargo test --features testing --test in_process`, see `access_protocol::testing`
+
+#[test]
+fn in_process() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+
+    // Create users
+    let pool_owner = pt.create_user_with_ata();
+    let staker = pt.create_user_with_ata();
+    pt.mint(&staker, 20_000).unwrap();
+    assert_eq!(pt.token_balance(&staker).unwrap(), 20_000);
+
+    // Pool with a stake account
+    pt.create_pool(&pool_owner, 1_000).unwrap();
+    pt.activate_stake_pool(&pool_owner).unwrap();
+    pt.create_stake_account(&pool_owner, &staker).unwrap();
+    pt.stake(&pool_owner, &staker, 10_000).unwrap();
+    assert_eq!(
+        pt.stake_account_stats(&staker, &pool_owner)
+            .unwrap()
+            .stake_amount,
+        10_000
+    );
+    assert_eq!(pt.pool_stats(&pool_owner).unwrap().total_staked, 10_000);
+    assert_eq!(pt.central_state_stats().unwrap().total_staked, 10_000);
+    assert!(!pt.events().is_empty());
+
+    // Bond V2 unlocked after 2 days
+    let unlock_date = Some(pt.clock().unix_timestamp + 2 * SECONDS_IN_DAY as i64);
+    pt.create_bond_v2(&staker, &pool_owner, unlock_date)
+        .unwrap();
+    pt.add_to_bond_v2(&staker, &staker, &pool_owner, 5_000, unlock_date)
+        .unwrap();
+    assert_eq!(
+        pt.bond_v2_stats(&staker, &pool_owner, unlock_date)
+            .unwrap()
+            .amount,
+        5_000
+    );
+
+    // Failed transactions do not change the store
+    let balance = pt.token_balance(&staker).unwrap();
+    assert!(pt.stake(&pool_owner, &staker, 1_000_000).is_err());
+    assert_eq!(pt.token_balance(&staker).unwrap(), balance);
+
+    // Rewards after one day, half of the inflation goes to the stakers
+    pt.sleep_days(1);
+    pt.crank_pool(&pool_owner).unwrap();
+    pt.claim_staker_rewards(&pool_owner, &staker).unwrap();
+    pt.claim_bond_v2_rewards(&staker, &pool_owner, unlock_date)
+        .unwrap();
+    let rewards = pt.token_balance(&staker).unwrap() - balance;
+    assert!((499_999..=500_001).contains(&rewards));
+    pt.claim_pool_rewards(&pool_owner).unwrap();
+    assert!((499_999..=500_001).contains(&pt.token_balance(&pool_owner).unwrap()));
+
+    // The bond cannot be unlocked before its unlock date
+    assert!(pt
+        .unlock_bond_v2(&staker, &pool_owner, unlock_date)
+        .is_err());
+    pt.sleep_days(1);
+    pt.crank_pool(&pool_owner).unwrap();
+    pt.claim_staker_rewards(&pool_owner, &staker).unwrap();
+    pt.claim_bond_v2_rewards(&staker, &pool_owner, unlock_date)
+        .unwrap();
+    let balance = pt.token_balance(&staker).unwrap();
+    pt.unlock_bond_v2(&staker, &pool_owner, unlock_date)
+        .unwrap();
+    assert_eq!(pt.token_balance(&staker).unwrap(), balance + 5_000);
+
+    // Unstake
+    pt.unstake(&pool_owner, &staker, 10_000).unwrap();
+    assert_eq!(pt.pool_stats(&pool_owner).unwrap().total_staked, 0);
+
+    // Unknown programs are not available in-process
+    let unknown = solana_program::instruction::Instruction {
+        program_id: Pubkey::new_unique(),
+        accounts: vec![],
+        data: vec![],
+    };
+    assert!(pt.process_instruction(unknown).is_err());
+}
+
+#[test]
+fn transfers_require_the_mint() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+    let pool_owner = pt.create_user_with_ata();
+    let staker = pt.create_user_with_ata();
+    pt.mint(&staker, 20_000).unwrap();
+    pt.create_pool(&pool_owner, 1_000).unwrap();
+    pt.activate_stake_pool(&pool_owner).unwrap();
+    pt.create_stake_account(&pool_owner, &staker).unwrap();
+
+    // The transfers use `transfer_checked` with both token programs, the mint cannot be skipped
+    let stake_pool = pt.get_pool_pda(&pool_owner);
+    let (stake_account, _) = pt.get_stake_account_pda(&stake_pool, &staker);
+    let instruction = stake(
+        access_protocol::ID,
+        stake::Accounts {
+            central_state: &pt.central_state,
+            stake_account: &stake_account,
+            stake_pool: &stake_pool,
+            token_owner: &staker,
+            source_token: &pt.get_ata(&staker),
+            spl_token_program: &pt.token_program,
+            vault: &pt.get_ata(&stake_pool),
+            central_state_vault: &pt.central_state_vault,
+            pool_fee_override: Some(&access_protocol::ID),
+            mint: &access_protocol::ID,
+            pool_controls: None,
+            allowlist_entry: None,
+            receipt_mint: None,
+            receipt_account: None,
+            receipt_reserve: None,
+            transfer_hook_accounts: &[],
+        },
+        stake::Params { amount: 10_000 },
+    );
+    assert_eq!(
+        pt.process_instruction(instruction).err(),
+        Some(ProgramError::from(AccessError::WrongMint))
+    );
+
+    pt.stake(&pool_owner, &staker, 10_000).unwrap();
+    assert_eq!(pt.pool_stats(&pool_owner).unwrap().total_staked, 10_000);
+}
@@ -0,0 +1,338 @@
+use std::fmt;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use solana_program::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+
+use access_protocol::state::SECONDS_IN_DAY;
+
+use crate::common::test_runner::TestRunner;
+
+pub mod common;
+
+// Random sequences of operations checked against the global invariants of the protocol, each
+// sequence is generated from a seed that can be replayed with `INVARIANT_SEED`
+
+const DAILY_INFLATION: u64 = 1_000_000;
+const NB_POOLS: usize = 2;
+const NB_STAKERS: usize = 3;
+const MINIMUM_STAKE_AMOUNT: u64 = 1_000;
+const INITIAL_BALANCE: u64 = 1_000_000;
+// The bonds V2 can be unlocked after this delay
+const BOND_LOCK_DAYS: u64 = 2;
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Stake { pool: usize, staker: usize, amount: u64 },
+    Unstake { pool: usize, staker: usize, amount: u64 },
+    Crank { pool: usize },
+    Claim { pool: usize, staker: usize },
+    ClaimPool { pool: usize },
+    AddToBond { pool: usize, staker: usize, amount: u64 },
+    ClaimBond { pool: usize, staker: usize },
+    UnlockBond { pool: usize, staker: usize },
+    Sleep { seconds: u64 },
+}
+
+struct Failure {
+    step: usize,
+    message: String,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "after step {}: {}", self.step, self.message)
+    }
+}
+
+fn generate(rng: &mut StdRng, nb_steps: usize) -> Vec<Op> {
+    (0..nb_steps)
+        .map(|_| {
+            let pool = rng.gen_range(0..NB_POOLS);
+            let staker = rng.gen_range(0..NB_STAKERS);
+            let amount = rng.gen_range(1..=10) * MINIMUM_STAKE_AMOUNT;
+            match rng.gen_range(0..12) {
+                0 | 1 => Op::Stake {
+                    pool,
+                    staker,
+                    amount,
+                },
+                2 => Op::Unstake {
+                    pool,
+                    staker,
+                    amount,
+                },
+                3 | 4 => Op::Crank { pool },
+                5 | 6 => Op::Claim { pool, staker },
+                7 => Op::ClaimPool { pool },
+                8 => Op::AddToBond {
+                    pool,
+                    staker,
+                    amount,
+                },
+                9 => Op::ClaimBond { pool, staker },
+                10 => Op::UnlockBond { pool, staker },
+                _ => Op::Sleep {
+                    seconds: match rng.gen_range(0..3) {
+                        0 => SECONDS_IN_DAY / 2,
+                        1 => SECONDS_IN_DAY,
+                        _ => 3 * SECONDS_IN_DAY,
+                    },
+                },
+            }
+        })
+        .collect()
+}
+
+struct Harness {
+    tr: TestRunner,
+    pool_owners: Vec<Keypair>,
+    stakers: Vec<Keypair>,
+    unlock_date: Option<i64>,
+    supply_after_setup: u64,
+    successful_claims: u64,
+}
+
+impl Harness {
+    async fn new() -> Self {
+        let mut tr = TestRunner::new(DAILY_INFLATION).await.unwrap();
+        let mut pool_owners = vec![];
+        for _ in 0..NB_POOLS {
+            let owner = tr.create_user_with_ata().await.unwrap();
+            tr.create_pool(&owner, MINIMUM_STAKE_AMOUNT).await.unwrap();
+            tr.activate_stake_pool(&owner.pubkey()).await.unwrap();
+            pool_owners.push(owner);
+        }
+        let unlock_date = Some(tr.get_current_time().await + (BOND_LOCK_DAYS * SECONDS_IN_DAY) as i64);
+        let mut stakers = vec![];
+        for _ in 0..NB_STAKERS {
+            let staker = tr.create_user_with_ata().await.unwrap();
+            tr.mint(&staker.pubkey(), INITIAL_BALANCE).await.unwrap();
+            for owner in &pool_owners {
+                tr.create_stake_account(&owner.pubkey(), &staker.pubkey())
+                    .await
+                    .unwrap();
+                tr.create_bond_v2(&staker.pubkey(), &owner.pubkey(), unlock_date)
+                    .await
+                    .unwrap();
+            }
+            stakers.push(staker);
+        }
+        let supply_after_setup = tr.token_stats().await.unwrap().supply;
+        Self {
+            tr,
+            pool_owners,
+            stakers,
+            unlock_date,
+            supply_after_setup,
+            successful_claims: 0,
+        }
+    }
+
+    // Operations are allowed to fail, the invariants must hold either way
+    async fn apply(&mut self, op: Op) {
+        let result = match op {
+            Op::Stake {
+                pool,
+                staker,
+                amount,
+            } => {
+                let pool_owner = self.pool_owner(pool);
+                self.tr.stake(&pool_owner, &self.stakers[staker], amount).await
+            }
+            Op::Unstake {
+                pool,
+                staker,
+                amount,
+            } => {
+                let pool_owner = self.pool_owner(pool);
+                self.tr.unstake(&pool_owner, &self.stakers[staker], amount).await
+            }
+            Op::Crank { pool } => {
+                let pool_owner = self.pool_owner(pool);
+                self.tr.crank_pool(&pool_owner).await
+            }
+            Op::Claim { pool, staker } => {
+                let pool_owner = self.pool_owner(pool);
+                self.tr
+                    .claim_staker_rewards(&pool_owner, &self.stakers[staker])
+                    .await
+            }
+            Op::ClaimPool { pool } => {
+                self.tr.claim_pool_rewards(&self.pool_owners[pool]).await
+            }
+            Op::AddToBond {
+                pool,
+                staker,
+                amount,
+            } => {
+                let pool_owner = self.pool_owner(pool);
+                let staker = &self.stakers[staker];
+                self.tr
+                    .add_to_bond_v2(staker, &staker.pubkey(), &pool_owner, amount, self.unlock_date)
+                    .await
+            }
+            Op::ClaimBond { pool, staker } => {
+                let pool_owner = self.pool_owner(pool);
+                self.tr
+                    .claim_bond_v2_rewards(&self.stakers[staker], &pool_owner, self.unlock_date)
+                    .await
+            }
+            Op::UnlockBond { pool, staker } => {
+                let pool_owner = self.pool_owner(pool);
+                self.tr
+                    .unlock_bond_v2_tokens(&self.stakers[staker], &pool_owner, self.unlock_date)
+                    .await
+            }
+            Op::Sleep { seconds } => {
+                self.tr.sleep(seconds).await.unwrap();
+                Ok(())
+            }
+        };
+        // Failed operations are expected, e.g unstaking more than the stake
+        let is_claim = matches!(
+            op,
+            Op::Claim { .. } | Op::ClaimPool { .. } | Op::ClaimBond { .. }
+        );
+        if is_claim && result.is_ok() {
+            self.successful_claims += 1;
+        }
+    }
+
+    fn pool_owner(&self, pool: usize) -> Pubkey {
+        self.pool_owners[pool].pubkey()
+    }
+
+    async fn check_invariants(&mut self) -> Result<(), String> {
+        let central_state = self.tr.central_state_stats().await.unwrap().account;
+
+        let mut pools_total_staked = 0;
+        for owner in self.pool_owners.iter().map(|o| o.pubkey()) {
+            let pool = self.tr.pool_stats(owner).await.unwrap();
+            pools_total_staked += pool.header.total_staked;
+
+            if pool.vault != pool.header.total_staked {
+                return Err(format!(
+                    "the vault of pool {} holds {} tokens but {} are staked",
+                    owner, pool.vault, pool.header.total_staked
+                ));
+            }
+
+            let mut tracked = 0;
+            for staker in self.stakers.iter().map(|s| s.pubkey()) {
+                tracked += self
+                    .tr
+                    .stake_account_stats(staker, owner)
+                    .await
+                    .unwrap()
+                    .stake_amount;
+                tracked += self
+                    .tr
+                    .bond_v2_stats(staker, owner, self.unlock_date)
+                    .await
+                    .unwrap()
+                    .amount;
+            }
+            if tracked != pool.header.total_staked {
+                return Err(format!(
+                    "the stake and bond accounts of pool {} hold {} tokens but the pool tracks {}",
+                    owner, tracked, pool.header.total_staked
+                ));
+            }
+        }
+
+        if pools_total_staked != central_state.total_staked {
+            return Err(format!(
+                "the pools track {} staked tokens but the central state tracks {}",
+                pools_total_staked, central_state.total_staked
+            ));
+        }
+
+        // Every claim rounds half up, so it can mint at most one token above its exact share
+        let elapsed_days =
+            (self.tr.get_current_time().await - central_state.creation_time) as u64 / SECONDS_IN_DAY;
+        let emitted = elapsed_days * central_state.daily_inflation + self.successful_claims;
+        let minted = self
+            .tr
+            .token_stats()
+            .await
+            .unwrap()
+            .supply
+            .saturating_sub(self.supply_after_setup);
+        if minted > emitted {
+            return Err(format!(
+                "{} tokens were minted as rewards but only {} were emitted in {} days",
+                minted, emitted, elapsed_days
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+async fn run(ops: &[Op]) -> Result<(), Failure> {
+    let mut harness = Harness::new().await;
+    for (step, op) in ops.iter().enumerate() {
+        harness.apply(*op).await;
+        harness
+            .check_invariants()
+            .await
+            .map_err(|message| Failure { step, message })?;
+    }
+    Ok(())
+}
+
+// Removes chunks of operations, then single operations, as long as the sequence still fails
+async fn shrink(mut ops: Vec<Op>, mut failure: Failure) -> (Vec<Op>, Failure) {
+    // Nothing after the failing step is needed to reproduce it
+    ops.truncate(failure.step + 1);
+    let mut chunk_len = ops.len() / 2;
+    while chunk_len > 0 {
+        let mut start = 0;
+        while start < ops.len() {
+            let mut candidate = ops.clone();
+            candidate.drain(start..std::cmp::min(start + chunk_len, ops.len()));
+            match run(&candidate).await {
+                Err(f) => {
+                    candidate.truncate(f.step + 1);
+                    ops = candidate;
+                    failure = f;
+                }
+                Ok(()) => start += chunk_len,
+            }
+        }
+        chunk_len /= 2;
+    }
+    (ops, failure)
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+#[tokio::test]
+async fn invariants() {
+    let cases = env_or("INVARIANT_CASES", 4u64);
+    let nb_steps = env_or("INVARIANT_STEPS", 40usize);
+    let first_seed = env_or("INVARIANT_SEED", rand::random::<u64>());
+    let replay = std::env::var("INVARIANT_SEED").is_ok();
+
+    let cases = if replay { 1 } else { cases };
+
+    for case in 0..cases {
+        let seed = first_seed.wrapping_add(case);
+        let ops = generate(&mut StdRng::seed_from_u64(seed), nb_steps);
+        if let Err(failure) = run(&ops).await {
+            let (ops, failure) = shrink(ops, failure).await;
+            panic!(
+                "Invariant broken with INVARIANT_SEED={} {}\nMinimal sequence:\n{:#?}",
+                seed, failure, ops
+            );
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use solana_sdk::signer::Signer;
+
+use access_protocol::state::{FeeRecipient, MAX_KEEPER_REWARD_BASIS_POINTS};
+
+use crate::common::test_runner::TestRunner;
+
+pub mod common;
+
+#[tokio::test]
+async fn keeper_reward() {
+    // Setup the token + basic accounts
+    let mut tr = TestRunner::new(1_000_000).await.unwrap();
+    tr.sleep(1).await.unwrap();
+
+    let pool_owner = tr.create_user_with_ata().await.unwrap();
+    tr.create_pool(&pool_owner, 200_000_000)
+        .await
+        .unwrap();
+    tr.activate_stake_pool(&pool_owner.pubkey()).await.unwrap();
+
+    let staker = tr.create_user_with_ata().await.unwrap();
+    tr.get_tokens_from_supply(&staker.pubkey(), 100_000_000_000).await.unwrap();
+    tr.create_stake_account(&pool_owner.pubkey(), &staker.pubkey())
+        .await
+        .unwrap();
+
+    let keeper = tr.create_user_with_ata().await.unwrap();
+    let recipient = tr.create_user_with_ata().await.unwrap();
+
+    // Setup the fee split right after a distribution
+    tr.stake(&pool_owner.pubkey(), &staker, 5_000_000_000)
+        .await
+        .unwrap();
+    tr.distribute_fees().await.unwrap();
+    tr.setup_fee_split(vec![FeeRecipient {
+        owner: recipient.pubkey(),
+        percentage: 50,
+    }])
+        .await
+        .unwrap();
+
+    // No keeper reward configured - passing the keeper accounts fails
+    tr.sleep(1).await.unwrap();
+    tr.stake(&pool_owner.pubkey(), &staker, 5_000_000_000)
+        .await
+        .unwrap();
+    tr.distribute_fees_with_keeper(&keeper.pubkey())
+        .await
+        .unwrap_err();
+
+    // Over the maximum - should fail
+    tr.set_keeper_reward(MAX_KEEPER_REWARD_BASIS_POINTS + 1)
+        .await
+        .unwrap_err();
+
+    // 1% keeper reward
+    tr.set_keeper_reward(100).await.unwrap();
+    let token_stats = tr.token_stats().await.unwrap();
+    tr.distribute_fees_with_keeper(&keeper.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(tr.get_ata_balance(&keeper.pubkey()).await.unwrap(), 1_000_000);
+    assert_eq!(tr.get_ata_balance(&recipient.pubkey()).await.unwrap(), 49_500_000);
+    let central_state_stats = tr.central_state_stats().await.unwrap();
+    assert_eq!(central_state_stats.balance, 0);
+    let new_token_stats = tr.token_stats().await.unwrap();
+    assert_eq!(token_stats.supply - new_token_stats.supply, 49_500_000);
+
+    // Distributing without the keeper accounts still works and pays no reward
+    tr.sleep(1).await.unwrap();
+    tr.stake(&pool_owner.pubkey(), &staker, 5_000_000_000)
+        .await
+        .unwrap();
+    tr.distribute_fees().await.unwrap();
+    assert_eq!(tr.get_ata_balance(&keeper.pubkey()).await.unwrap(), 1_000_000);
+    assert_eq!(tr.get_ata_balance(&recipient.pubkey()).await.unwrap(), 99_500_000);
+
+    // The keeper reward can be turned off
+    tr.set_keeper_reward(0).await.unwrap();
+    tr.sleep(1).await.unwrap();
+    tr.stake(&pool_owner.pubkey(), &staker, 5_000_000_000)
+        .await
+        .unwrap();
+    tr.distribute_fees_with_keeper(&keeper.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(tr.get_ata_balance(&keeper.pubkey()).await.unwrap(), 1_000_000);
+    assert_eq!(tr.get_ata_balance(&recipient.pubkey()).await.unwrap(), 149_500_000);
+}
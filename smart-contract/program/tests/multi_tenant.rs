@@ -0,0 +1,65 @@
+use solana_sdk::signer::Signer;
+
+use access_protocol::state::CentralStateV2;
+
+use crate::common::test_runner::TestRunner;
+
+pub mod common;
+
+#[tokio::test]
+async fn multi_tenant() {
+    // Setup a tenant central state next to the original one
+    let mut tr = TestRunner::new_tenant(1_000_000).await.unwrap();
+    let (tenant_key, _) = CentralStateV2::find_tenant_key(&tr.get_mint(), &tr.program_id);
+    assert_eq!(tr.get_central_state(), tenant_key);
+
+    // Create users
+    let stake_pool_owner = tr.create_user_with_ata().await.unwrap();
+    let staker = tr.create_user_with_ata().await.unwrap();
+
+    // Mint with the tenant central state as the mint authority
+    tr.mint(&staker.pubkey(), 10_200).await.unwrap();
+    assert_eq!(tr.staker_stats(staker.pubkey()).await.unwrap().balance, 10_200);
+
+    // Create and activate a stake pool scoped to the tenant
+    tr.create_pool(&stake_pool_owner, 10_000)
+        .await
+        .unwrap();
+    tr.activate_stake_pool(&stake_pool_owner.pubkey())
+        .await
+        .unwrap();
+
+    // Stake
+    tr.create_stake_account(&stake_pool_owner.pubkey(), &staker.pubkey())
+        .await
+        .unwrap();
+    tr.stake(&stake_pool_owner.pubkey(), &staker, 10_000)
+        .await
+        .unwrap();
+    let central_state_stats = tr.central_state_stats().await.unwrap();
+    assert_eq!(central_state_stats.balance, 200);
+    assert_eq!(central_state_stats.account.total_staked, 10_000);
+
+    tr.sleep(86400).await.unwrap();
+
+    // The tenant pool cannot be used with the original central state
+    tr.crank_pool_with_original_central_state(&stake_pool_owner.pubkey())
+        .await
+        .unwrap_err();
+
+    // Claim the rewards of the tenant
+    tr.crank_pool(&stake_pool_owner.pubkey()).await.unwrap();
+    tr.claim_staker_rewards(&stake_pool_owner.pubkey(), &staker)
+        .await
+        .unwrap();
+    tr.claim_pool_rewards(&stake_pool_owner).await.unwrap();
+    assert_eq!(tr.staker_stats(staker.pubkey()).await.unwrap().balance, 500_000);
+    let pool_stats = tr.pool_stats(stake_pool_owner.pubkey()).await.unwrap();
+    assert_eq!(pool_stats.balance, 500_000);
+
+    // Unstake with the tenant pool as the vault signer
+    tr.unstake(&stake_pool_owner.pubkey(), &staker, 10_000)
+        .await
+        .unwrap();
+    assert_eq!(tr.staker_stats(staker.pubkey()).await.unwrap().balance, 510_000);
+}
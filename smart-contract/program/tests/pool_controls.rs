@@ -0,0 +1,119 @@
+use solana_program::program_error::ProgramError;
+
+use access_protocol::error::AccessError;
+use access_protocol::state::SECONDS_IN_DAY;
+use access_protocol::testing::ProtocolTest;
+
+// Runs with `cargo test --features testing --test pool_controls`, see `access_protocol::testing`
+
+fn err(e: AccessError) -> Result<(), ProgramError> {
+    Err(e.into())
+}
+
+#[test]
+fn pool_controls() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+    let pool_owner = pt.create_user_with_ata();
+    let alice = pt.create_user_with_ata();
+    let bob = pt.create_user_with_ata();
+    let carol = pt.create_user_with_ata();
+    pt.mint(&alice, 11_000).unwrap();
+    pt.mint(&bob, 11_000).unwrap();
+    pt.mint(&carol, 11_000).unwrap();
+    pt.create_pool(&pool_owner, 1_000).unwrap();
+    pt.activate_stake_pool(&pool_owner).unwrap();
+
+    pt.create_stake_account(&pool_owner, &alice).unwrap();
+    pt.stake(&pool_owner, &alice, 5_000).unwrap();
+    assert!(!pt.pool_stats(&pool_owner).unwrap().has_controls());
+
+    // The cap limits the total staked of the pool
+    pt.change_pool_controls(&pool_owner, 8_000, false, false)
+        .unwrap();
+    assert!(pt.pool_stats(&pool_owner).unwrap().has_controls());
+    pt.create_stake_account(&pool_owner, &bob).unwrap();
+    assert_eq!(
+        pt.stake(&pool_owner, &bob, 5_000),
+        err(AccessError::StakePoolCapExceeded)
+    );
+    pt.stake(&pool_owner, &bob, 3_000).unwrap();
+    assert_eq!(pt.pool_stats(&pool_owner).unwrap().total_staked, 8_000);
+
+    // A paused pool rejects the new stakes and bonds, the stakers can still unstake
+    pt.change_pool_controls(&pool_owner, 0, true, false)
+        .unwrap();
+    assert_eq!(
+        pt.stake(&pool_owner, &bob, 1_000),
+        err(AccessError::StakePoolPaused)
+    );
+    assert_eq!(
+        pt.create_stake_account(&pool_owner, &carol),
+        err(AccessError::StakePoolPaused)
+    );
+    let unlock_date = Some(pt.clock().unix_timestamp + 10 * SECONDS_IN_DAY as i64);
+    pt.create_bond_v2(&carol, &pool_owner, unlock_date).unwrap();
+    assert_eq!(
+        pt.add_to_bond_v2(&carol, &carol, &pool_owner, 1_000, unlock_date),
+        err(AccessError::StakePoolPaused)
+    );
+    pt.unstake(&pool_owner, &bob, 3_000).unwrap();
+
+    // With the allowlist only the allowlisted stakers can stake
+    pt.change_pool_controls(&pool_owner, 0, false, true)
+        .unwrap();
+    assert_eq!(
+        pt.create_stake_account(&pool_owner, &carol),
+        err(AccessError::StakerNotAllowlisted)
+    );
+    assert_eq!(
+        pt.stake(&pool_owner, &alice, 1_000),
+        err(AccessError::StakerNotAllowlisted)
+    );
+    pt.set_pool_allowlist_entry(&pool_owner, &alice, true)
+        .unwrap();
+    pt.set_pool_allowlist_entry(&pool_owner, &carol, true)
+        .unwrap();
+    assert_eq!(
+        pt.set_pool_allowlist_entry(&pool_owner, &carol, true),
+        err(AccessError::NoOp)
+    );
+    pt.stake(&pool_owner, &alice, 1_000).unwrap();
+    pt.create_stake_account(&pool_owner, &carol).unwrap();
+    pt.add_to_bond_v2(&carol, &carol, &pool_owner, 1_000, unlock_date)
+        .unwrap();
+
+    // A removed staker cannot top up its positions
+    pt.set_pool_allowlist_entry(&pool_owner, &carol, false)
+        .unwrap();
+    assert_eq!(
+        pt.set_pool_allowlist_entry(&pool_owner, &carol, false),
+        err(AccessError::NoOp)
+    );
+    assert_eq!(
+        pt.add_to_bond_v2(&carol, &carol, &pool_owner, 1_000, unlock_date),
+        err(AccessError::StakerNotAllowlisted)
+    );
+    assert_eq!(
+        pt.stake(&pool_owner, &carol, 1_000),
+        err(AccessError::StakerNotAllowlisted)
+    );
+
+    // The allowlist applies to the buyers of receipts, not to the receipt mint owning their stake
+    pt.create_receipt_mint(&pool_owner).unwrap();
+    let receipt_mint = pt.get_receipt_mint(&pool_owner);
+    pt.set_pool_allowlist_entry(&pool_owner, &receipt_mint, true)
+        .unwrap();
+    assert_eq!(
+        pt.stake_for_receipts(&pool_owner, &carol, 1_000),
+        err(AccessError::StakerNotAllowlisted)
+    );
+    pt.stake_for_receipts(&pool_owner, &alice, 1_000).unwrap();
+
+    // The stakers keep claiming their rewards
+    pt.sleep_days(1);
+    pt.crank_pool(&pool_owner).unwrap();
+    let balance = pt.token_balance(&carol).unwrap();
+    pt.claim_bond_v2_rewards(&carol, &pool_owner, unlock_date)
+        .unwrap();
+    assert!(pt.token_balance(&carol).unwrap() > balance);
+}
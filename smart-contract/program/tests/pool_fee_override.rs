@@ -0,0 +1,104 @@
+use solana_sdk::signature::Signer;
+
+use crate::common::test_runner::TestRunner;
+
+pub mod common;
+
+#[tokio::test]
+async fn pool_fee_override() {
+    // Setup the token + basic accounts
+    let mut tr = TestRunner::new(1_000_000).await.unwrap();
+
+    let pool_owner = tr.create_user_with_ata().await.unwrap();
+    tr.create_pool(&pool_owner, 10_000_000)
+        .await
+        .unwrap();
+    tr.activate_stake_pool(&pool_owner.pubkey()).await.unwrap();
+
+    let partner_pool_owner = tr.create_user_with_ata().await.unwrap();
+    tr.create_pool(&partner_pool_owner, 10_000_000)
+        .await
+        .unwrap();
+    tr.activate_stake_pool(&partner_pool_owner.pubkey()).await.unwrap();
+
+    let staker = tr.create_user_with_ata().await.unwrap();
+    tr.mint(&staker.pubkey(), 100_000_000).await.unwrap();
+    tr.create_stake_account(&pool_owner.pubkey(), &staker.pubkey())
+        .await
+        .unwrap();
+    tr.create_stake_account(&partner_pool_owner.pubkey(), &staker.pubkey())
+        .await
+        .unwrap();
+
+    // No override - the default fee applies
+    assert!(tr.pool_fee_override_stats(&pool_owner.pubkey()).await.unwrap().is_none());
+    tr.stake(&pool_owner.pubkey(), &staker, 10_000_000)
+        .await
+        .unwrap();
+    let stats = tr.central_state_stats().await.unwrap();
+    assert_eq!(stats.balance, 200_000);
+
+    // Over 100% - should fail
+    tr.set_pool_fee_override(&pool_owner.pubkey(), Some(10_001))
+        .await
+        .unwrap_err();
+
+    // Zero fee promotional pool
+    tr.set_pool_fee_override(&pool_owner.pubkey(), Some(0))
+        .await
+        .unwrap();
+    let fee_override = tr.pool_fee_override_stats(&pool_owner.pubkey()).await.unwrap().unwrap();
+    assert_eq!(fee_override.fee_basis_points, 0);
+    tr.stake(&pool_owner.pubkey(), &staker, 10_000_000)
+        .await
+        .unwrap();
+    let stats = tr.central_state_stats().await.unwrap();
+    assert_eq!(stats.balance, 200_000);
+
+    // The other pool is not impacted
+    tr.stake(&partner_pool_owner.pubkey(), &staker, 10_000_000)
+        .await
+        .unwrap();
+    let stats = tr.central_state_stats().await.unwrap();
+    assert_eq!(stats.balance, 400_000);
+
+    // Reduced fee partner pool
+    tr.set_pool_fee_override(&partner_pool_owner.pubkey(), Some(50))
+        .await
+        .unwrap();
+    tr.sleep(1).await.unwrap();
+    tr.stake(&partner_pool_owner.pubkey(), &staker, 10_000_000)
+        .await
+        .unwrap();
+    let stats = tr.central_state_stats().await.unwrap();
+    assert_eq!(stats.balance, 450_000);
+
+    // An override can never raise the fee above the protocol one
+    tr.set_pool_fee_override(&partner_pool_owner.pubkey(), Some(5_000))
+        .await
+        .unwrap();
+    tr.sleep(1).await.unwrap();
+    tr.stake(&partner_pool_owner.pubkey(), &staker, 10_000_000)
+        .await
+        .unwrap();
+    let stats = tr.central_state_stats().await.unwrap();
+    assert_eq!(stats.balance, 650_000);
+
+    // Remove the override - the default fee applies again
+    tr.set_pool_fee_override(&pool_owner.pubkey(), None)
+        .await
+        .unwrap();
+    assert!(tr.pool_fee_override_stats(&pool_owner.pubkey()).await.unwrap().is_none());
+    tr.sleep(1).await.unwrap();
+    tr.stake(&pool_owner.pubkey(), &staker, 10_000_000)
+        .await
+        .unwrap();
+    let stats = tr.central_state_stats().await.unwrap();
+    assert_eq!(stats.balance, 850_000);
+
+    // Removing a non-existing override fails
+    tr.sleep(1).await.unwrap();
+    tr.set_pool_fee_override(&pool_owner.pubkey(), None)
+        .await
+        .unwrap_err();
+}
@@ -0,0 +1,50 @@
+use solana_sdk::signer::keypair::Keypair;
+use solana_sdk::signer::Signer;
+
+use crate::common::test_runner::TestRunner;
+
+pub mod common;
+
+#[tokio::test]
+async fn protocol_config() {
+    // Setup the token + basic accounts
+    let mut tr = TestRunner::new(1_000_000).await.unwrap();
+
+    // The protocol config is created together with the central state
+    let config = tr.protocol_config_stats().await.unwrap();
+    let initial_signer = config.access_nft_program_signer;
+
+    // Create users
+    let stake_pool_owner = tr.create_user_with_ata().await.unwrap();
+    let staker = tr.create_user_with_ata().await.unwrap();
+    tr.mint(&staker.pubkey(), 10_200).await.unwrap();
+
+    // Create and activate a stake pool
+    tr.create_pool(&stake_pool_owner, 10_000)
+        .await
+        .unwrap();
+    tr.activate_stake_pool(&stake_pool_owner.pubkey())
+        .await
+        .unwrap();
+    tr.create_stake_account(&stake_pool_owner.pubkey(), &staker.pubkey())
+        .await
+        .unwrap();
+    tr.stake(&stake_pool_owner.pubkey(), &staker, 10_000)
+        .await
+        .unwrap();
+
+    // Change the NFT program signer
+    let new_signer = Keypair::new().pubkey();
+    tr.set_protocol_config(&new_signer).await.unwrap();
+    let config = tr.protocol_config_stats().await.unwrap();
+    assert_eq!(config.access_nft_program_signer, new_signer);
+    assert_ne!(config.access_nft_program_signer, initial_signer);
+
+    // Claiming works with the new signer
+    tr.sleep(86400).await.unwrap();
+    tr.crank_pool(&stake_pool_owner.pubkey()).await.unwrap();
+    tr.claim_staker_rewards(&stake_pool_owner.pubkey(), &staker)
+        .await
+        .unwrap();
+    assert_eq!(tr.staker_stats(staker.pubkey()).await.unwrap().balance, 500_000);
+}
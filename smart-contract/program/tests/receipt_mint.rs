@@ -0,0 +1,129 @@
+use solana_program::program_error::ProgramError;
+
+use access_protocol::error::AccessError;
+use access_protocol::instruction::stake;
+use access_protocol::state::PoolFeeOverride;
+use access_protocol::testing::ProtocolTest;
+
+// Runs with `cargo test --features testing --test receipt_mint`, see `access_protocol::testing`
+
+fn err(e: AccessError) -> Result<(), ProgramError> {
+    Err(e.into())
+}
+
+#[test]
+fn receipt_mint() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+    let pool_owner = pt.create_user_with_ata();
+    let alice = pt.create_user_with_ata();
+    let bob = pt.create_user_with_ata();
+    let carol = pt.create_user_with_ata();
+    pt.mint(&alice, 11_000).unwrap();
+    pt.mint(&bob, 11_000).unwrap();
+    pt.mint(&carol, 1_000_000).unwrap();
+    pt.create_pool(&pool_owner, 1_000).unwrap();
+    pt.activate_stake_pool(&pool_owner).unwrap();
+
+    // The receipt mint can only be created once
+    pt.create_receipt_mint(&pool_owner).unwrap();
+    assert_eq!(
+        pt.create_receipt_mint(&pool_owner),
+        err(AccessError::WrongOwner)
+    );
+
+    // Alice stakes directly, Bob stakes the same amount for receipts
+    pt.create_stake_account(&pool_owner, &alice).unwrap();
+    pt.stake(&pool_owner, &alice, 10_000).unwrap();
+    pt.stake_for_receipts(&pool_owner, &bob, 10_000).unwrap();
+    let receipt_mint = pt.get_receipt_mint(&pool_owner);
+    let bob_receipts = pt.get_receipt_account(&bob, &pool_owner);
+    assert_eq!(pt.token_account_balance(&bob_receipts).unwrap(), 10_000);
+    assert_eq!(
+        pt.stake_account_stats(&receipt_mint, &pool_owner)
+            .unwrap()
+            .stake_amount,
+        10_000
+    );
+
+    // The stake of the receipt holders cannot be topped up without minting receipts
+    let stake_pool = pt.get_pool_pda(&pool_owner);
+    let ix = stake(
+        access_protocol::ID,
+        stake::Accounts {
+            stake_account: &pt.get_stake_account_pda(&stake_pool, &receipt_mint).0,
+            stake_pool: &stake_pool,
+            token_owner: &carol,
+            source_token: &pt.get_ata(&carol),
+            spl_token_program: &pt.token_program,
+            vault: &pt.get_ata(&stake_pool),
+            central_state: &pt.central_state,
+            central_state_vault: &pt.central_state_vault,
+            pool_fee_override: Some(
+                &PoolFeeOverride::create_key(&stake_pool, &access_protocol::ID).0,
+            ),
+            mint: &pt.mint,
+            pool_controls: None,
+            allowlist_entry: None,
+            receipt_mint: None,
+            receipt_account: None,
+            receipt_reserve: None,
+            transfer_hook_accounts: &[],
+        },
+        stake::Params { amount: 1_000 },
+    );
+    assert_eq!(
+        pt.process_instruction(ix),
+        err(AccessError::WrongReceiptMint)
+    );
+
+    let alice_balance = pt.token_balance(&alice).unwrap();
+    let bob_balance = pt.token_balance(&bob).unwrap();
+
+    // The rewards of the receipt holders are claimed to the reserve before redeeming
+    pt.sleep_days(1);
+    pt.crank_pool(&pool_owner).unwrap();
+    assert_eq!(
+        pt.redeem_receipts(&pool_owner, &bob, 10_000),
+        err(AccessError::UnclaimedRewards)
+    );
+    pt.claim_receipt_rewards(&pool_owner).unwrap();
+    pt.claim_staker_rewards(&pool_owner, &alice).unwrap();
+    let alice_reward = pt.token_balance(&alice).unwrap() - alice_balance;
+    let reserve = pt.token_balance(&receipt_mint).unwrap();
+    assert!(alice_reward > 0);
+    assert_eq!(reserve, alice_reward);
+
+    // New receipts pay the share of the reserve they are entitled to
+    let carol_balance = pt.token_balance(&carol).unwrap();
+    pt.stake_for_receipts(&pool_owner, &carol, 5_000).unwrap();
+    let premium = (5_000 * reserve + 9_999) / 10_000;
+    assert_eq!(
+        carol_balance - pt.token_balance(&carol).unwrap(),
+        5_000 + 100 + premium
+    );
+    assert_eq!(pt.token_balance(&receipt_mint).unwrap(), reserve + premium);
+
+    // Receipts cannot be redeemed beyond the balance of the holder
+    assert!(pt.redeem_receipts(&pool_owner, &carol, 5_001).is_err());
+
+    // Redeeming right away does not take any of the rewards accrued before minting
+    let carol_balance = pt.token_balance(&carol).unwrap();
+    pt.redeem_receipts(&pool_owner, &carol, 5_000).unwrap();
+    let carol_redeemed = pt.token_balance(&carol).unwrap() - carol_balance;
+    assert!(carol_redeemed <= 5_000 + premium);
+    assert!(carol_redeemed + 1 >= 5_000 + premium);
+
+    // Bob redeems his stake and the accrued rewards
+    pt.redeem_receipts(&pool_owner, &bob, 10_000).unwrap();
+    let bob_redeemed = pt.token_balance(&bob).unwrap() - bob_balance;
+    assert!(bob_redeemed >= 10_000 + alice_reward - 1);
+    assert!(bob_redeemed <= 10_000 + alice_reward + 1);
+    assert_eq!(pt.token_account_balance(&bob_receipts).unwrap(), 0);
+    assert_eq!(
+        pt.stake_account_stats(&receipt_mint, &pool_owner)
+            .unwrap()
+            .stake_amount,
+        0
+    );
+    assert_eq!(pt.pool_stats(&pool_owner).unwrap().total_staked, 10_000);
+}
@@ -0,0 +1,144 @@
+use solana_sdk::signer::Signer;
+
+use access_protocol::error::AccessError;
+use access_protocol::rewards::{
+    claimable_pool_rewards, claimable_staker_rewards, ClaimableRewards,
+};
+
+use crate::common::test_runner::TestRunner;
+
+pub mod common;
+
+#[tokio::test]
+async fn reward_calculator() {
+    // Setup the token + basic accounts
+    let mut tr = TestRunner::new(1_000_000).await.unwrap();
+
+    // Create users
+    let stake_pool_owner = tr.create_user_with_ata().await.unwrap();
+    let recommender = tr.create_user_with_ata().await.unwrap();
+    let staker = tr.create_user_with_ata().await.unwrap();
+
+    // The staker pays 10 % of the rewards as royalties
+    let start_time = tr.get_current_time().await;
+    tr.create_royalty(
+        &staker,
+        &recommender.pubkey(),
+        1000,
+        (start_time + 3 * 86_400) as u64,
+    )
+        .await
+        .unwrap();
+
+    // Setup a pool with one staker
+    tr.mint(&staker.pubkey(), 10_200).await.unwrap();
+    tr.create_pool(&stake_pool_owner, 10_000)
+        .await
+        .unwrap();
+    tr.activate_stake_pool(&stake_pool_owner.pubkey())
+        .await
+        .unwrap();
+    tr.create_stake_account(&stake_pool_owner.pubkey(), &staker.pubkey())
+        .await
+        .unwrap();
+    tr.stake(&stake_pool_owner.pubkey(), &staker, 10_000)
+        .await
+        .unwrap();
+
+    // Wait for 1 day
+    tr.sleep(86400).await.unwrap();
+    tr.crank_pool(&stake_pool_owner.pubkey()).await.unwrap();
+
+    // Compute the claimable amounts from the accounts
+    let central_state = tr.central_state_stats().await.unwrap().account;
+    let stake_pool = tr
+        .stake_pool_with_balances(stake_pool_owner.pubkey())
+        .await
+        .unwrap();
+    let stake_account = tr
+        .stake_account_stats(staker.pubkey(), stake_pool_owner.pubkey())
+        .await
+        .unwrap();
+    let royalty_account = tr.royalty_account_stats(staker.pubkey()).await.unwrap();
+    let now = tr.get_current_time().await;
+
+    let staker_claimable = claimable_staker_rewards(
+        &central_state,
+        &stake_pool,
+        &stake_account,
+        royalty_account.as_ref(),
+        now,
+        false,
+    )
+        .unwrap();
+    assert_eq!(
+        staker_claimable,
+        ClaimableRewards {
+            reward: 450_000,
+            royalty: 50_000,
+        }
+    );
+    let pool_claimable = claimable_pool_rewards(&central_state, &stake_pool, None, now).unwrap();
+    assert_eq!(
+        pool_claimable,
+        ClaimableRewards {
+            reward: 500_000,
+            royalty: 0,
+        }
+    );
+
+    // The claims mint exactly the computed amounts
+    tr.claim_staker_rewards(&stake_pool_owner.pubkey(), &staker)
+        .await
+        .unwrap();
+    tr.claim_pool_rewards(&stake_pool_owner).await.unwrap();
+    assert_eq!(
+        tr.staker_stats(staker.pubkey()).await.unwrap().balance,
+        staker_claimable.reward
+    );
+    assert_eq!(
+        tr.staker_stats(recommender.pubkey()).await.unwrap().balance,
+        staker_claimable.royalty
+    );
+    assert_eq!(
+        tr.pool_stats(stake_pool_owner.pubkey()).await.unwrap().balance,
+        pool_claimable.reward
+    );
+
+    // Nothing is left to claim, the calculator returns the error of the program
+    let claimed_stake_account = tr
+        .stake_account_stats(staker.pubkey(), stake_pool_owner.pubkey())
+        .await
+        .unwrap();
+    let err = claimable_staker_rewards(
+        &central_state,
+        &stake_pool,
+        &claimed_stake_account,
+        royalty_account.as_ref(),
+        now,
+        false,
+    )
+        .unwrap_err();
+    assert!(matches!(err, AccessError::NoOp));
+    tr.claim_staker_rewards(&stake_pool_owner.pubkey(), &staker)
+        .await
+        .unwrap_err();
+
+    // The royalty is ignored once expired
+    let expired = claimable_staker_rewards(
+        &central_state,
+        &stake_pool,
+        &stake_account,
+        royalty_account.as_ref(),
+        start_time + 4 * 86_400,
+        false,
+    )
+        .unwrap();
+    assert_eq!(
+        expired,
+        ClaimableRewards {
+            reward: 500_000,
+            royalty: 0,
+        }
+    );
+}
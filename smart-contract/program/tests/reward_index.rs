@@ -0,0 +1,236 @@
+use std::mem::size_of;
+
+use borsh::BorshSerialize;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+use access_protocol::error::AccessError;
+use access_protocol::state::{RewardIndex, StakeAccount, StakePoolHeader, STAKE_BUFFER_LEN};
+use access_protocol::testing::ProtocolTest;
+use access_protocol::versioning::{read_header, Migrate, Versioned};
+
+// Runs with `cargo test --features testing --test reward_index`, see `access_protocol::testing`
+
+struct Pool {
+    owner: Pubkey,
+    staker: Pubkey,
+    late_staker: Pubkey,
+}
+
+fn setup_pool(pt: &mut ProtocolTest) -> Pool {
+    let pool = Pool {
+        owner: pt.create_user_with_ata(),
+        staker: pt.create_user_with_ata(),
+        late_staker: pt.create_user_with_ata(),
+    };
+    pt.mint(&pool.staker, 100_000).unwrap();
+    pt.mint(&pool.late_staker, 100_000).unwrap();
+    pt.create_legacy_pool(&pool.owner, 1_000).unwrap();
+    pt.activate_stake_pool(&pool.owner).unwrap();
+    pt.create_stake_account(&pool.owner, &pool.staker).unwrap();
+    pt.create_stake_account(&pool.owner, &pool.late_staker)
+        .unwrap();
+    pt.stake(&pool.owner, &pool.staker, 10_000).unwrap();
+    pool
+}
+
+fn balances(pt: &ProtocolTest, pool: &Pool) -> [u64; 3] {
+    [
+        pt.token_balance(&pool.owner).unwrap(),
+        pt.token_balance(&pool.staker).unwrap(),
+        pt.token_balance(&pool.late_staker).unwrap(),
+    ]
+}
+
+fn claim_all(pt: &mut ProtocolTest, pool: &Pool) {
+    pt.claim_pool_rewards(&pool.owner).unwrap();
+    pt.claim_staker_rewards(&pool.owner, &pool.staker).unwrap();
+    pt.claim_staker_rewards(&pool.owner, &pool.late_staker)
+        .unwrap();
+}
+
+fn err(e: AccessError) -> Result<(), ProgramError> {
+    Err(e.into())
+}
+
+// The pool using the reward index pays the same rewards as an identical pool using the rewards buffer
+#[test]
+fn reward_index() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+    let indexed = setup_pool(&mut pt);
+    let buffered = setup_pool(&mut pt);
+    let pool_key = pt.get_pool_pda(&indexed.owner);
+    let (stake_account, _) = pt.get_stake_account_pda(&pool_key, &indexed.staker);
+
+    // Stake account created before the version byte and the reward index
+    let mut account = pt.account(&stake_account).unwrap().clone();
+    let legacy = StakeAccount::load(&account.data)
+        .unwrap()
+        .to_previous()
+        .unwrap();
+    account.data = legacy.try_to_vec().unwrap();
+    account
+        .data
+        .truncate(account.data.len() - size_of::<u128>());
+    pt.set_account(stake_account, account);
+
+    // Unclaimed days in the rewards buffer
+    for _ in 0..2 {
+        pt.sleep_days(1);
+        pt.crank_pool(&indexed.owner).unwrap();
+        pt.crank_pool(&buffered.owner).unwrap();
+    }
+
+    // Switch
+    let buffer_len = pt.account(&pool_key).unwrap().data.len();
+    assert_eq!(
+        pt.compact_stake_pool(&indexed.owner),
+        err(AccessError::RewardIndexNotEnabled)
+    );
+    pt.enable_reward_index(&indexed.owner).unwrap();
+    assert_eq!(
+        pt.enable_reward_index(&indexed.owner),
+        err(AccessError::RewardIndexAlreadyEnabled)
+    );
+    assert_eq!(
+        pt.account(&pool_key).unwrap().data.len(),
+        buffer_len + size_of::<RewardIndex>()
+    );
+    let pool = pt.stake_pool(&indexed.owner).unwrap();
+    assert_eq!(pool.header.reward_index_start, 2);
+    assert_eq!(pool.rewards_buffer().len(), STAKE_BUFFER_LEN as usize);
+
+    // A late staker only earns from the day of the stake
+    pt.sleep_days(1);
+    pt.crank_pool(&indexed.owner).unwrap();
+    pt.crank_pool(&buffered.owner).unwrap();
+    pt.stake(&indexed.owner, &indexed.late_staker, 10_000)
+        .unwrap();
+    pt.stake(&buffered.owner, &buffered.late_staker, 10_000)
+        .unwrap();
+
+    // The old stake account needs room for its checkpoint
+    pt.sleep_days(1);
+    pt.crank_pool(&indexed.owner).unwrap();
+    pt.crank_pool(&buffered.owner).unwrap();
+    assert_eq!(
+        pt.claim_staker_rewards(&indexed.owner, &indexed.staker),
+        err(AccessError::RewardCheckpointNotAllocated)
+    );
+    pt.migrate_account(&stake_account).unwrap();
+    assert_eq!(
+        pt.migrate_account(&stake_account),
+        err(AccessError::NoOp)
+    );
+    let data = &pt.account(&stake_account).unwrap().data;
+    assert_eq!(read_header(data).unwrap().1, StakeAccount::VERSION);
+    claim_all(&mut pt, &indexed);
+    claim_all(&mut pt, &buffered);
+    assert_eq!(balances(&pt, &indexed), balances(&pt, &buffered));
+    assert!(pt.token_balance(&indexed.staker).unwrap() > 90_000);
+
+    // Nothing more to claim on the same day
+    let balance = pt.token_balance(&indexed.staker).unwrap();
+    pt.claim_staker_rewards(&indexed.owner, &indexed.staker)
+        .unwrap();
+    assert_eq!(pt.token_balance(&indexed.staker).unwrap(), balance);
+
+    // The rewards buffer is kept while the days before the switch can be claimed
+    assert_eq!(
+        pt.compact_stake_pool(&indexed.owner),
+        err(AccessError::RewardsBufferStillClaimable)
+    );
+    pt.sleep_days(STAKE_BUFFER_LEN);
+    pt.crank_pool(&indexed.owner).unwrap();
+    pt.crank_pool(&buffered.owner).unwrap();
+    let owner_lamports = pt.account(&indexed.owner).map_or(0, |a| a.lamports);
+    pt.compact_stake_pool(&indexed.owner).unwrap();
+    assert_eq!(
+        pt.account(&pool_key).unwrap().data.len(),
+        size_of::<StakePoolHeader>() + size_of::<RewardIndex>()
+    );
+    assert!(pt.account(&indexed.owner).unwrap().lamports > owner_lamports);
+    assert!(pt
+        .stake_pool(&indexed.owner)
+        .unwrap()
+        .rewards_buffer()
+        .is_empty());
+
+    // Claims after the compaction
+    pt.sleep_days(1);
+    pt.crank_pool(&indexed.owner).unwrap();
+    pt.crank_pool(&buffered.owner).unwrap();
+    claim_all(&mut pt, &indexed);
+    claim_all(&mut pt, &buffered);
+    assert_eq!(balances(&pt, &indexed), balances(&pt, &buffered));
+
+    // Unstake with claimed rewards
+    pt.unstake(&indexed.owner, &indexed.staker, 10_000).unwrap();
+    assert_eq!(pt.pool_stats(&indexed.owner).unwrap().total_staked, 10_000);
+}
+
+// A new pool is created with the reward index and pays the same rewards as a pool created with
+// the rewards buffer
+#[test]
+fn new_pool() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+    let buffered = setup_pool(&mut pt);
+    pt.sleep_days(3);
+    pt.crank_pool(&buffered.owner).unwrap();
+    let created = Pool {
+        owner: pt.create_user_with_ata(),
+        staker: pt.create_user_with_ata(),
+        late_staker: pt.create_user_with_ata(),
+    };
+    pt.mint(&created.staker, 100_000).unwrap();
+    pt.mint(&created.late_staker, 100_000).unwrap();
+    pt.create_pool(&created.owner, 1_000).unwrap();
+    let pool_key = pt.get_pool_pda(&created.owner);
+    assert_eq!(
+        pt.account(&pool_key).unwrap().data.len(),
+        size_of::<StakePoolHeader>() + size_of::<RewardIndex>()
+    );
+    pt.activate_stake_pool(&created.owner).unwrap();
+    assert_eq!(
+        pt.enable_reward_index(&created.owner),
+        err(AccessError::RewardIndexAlreadyEnabled)
+    );
+    let pool = pt.stake_pool(&created.owner).unwrap();
+    assert!(pool.header.uses_reward_index());
+    assert_eq!(pool.header.reward_index_start, 3);
+    assert!(pool.rewards_buffer().is_empty());
+    assert_eq!(
+        pt.compact_stake_pool(&created.owner),
+        err(AccessError::NoOp)
+    );
+
+    // Same stakes in both pools from now on
+    claim_all(&mut pt, &buffered);
+    let start = balances(&pt, &buffered);
+    pt.create_stake_account(&created.owner, &created.staker)
+        .unwrap();
+    pt.create_stake_account(&created.owner, &created.late_staker)
+        .unwrap();
+    pt.stake(&created.owner, &created.staker, 10_000).unwrap();
+    let created_start = balances(&pt, &created);
+    for _ in 0..2 {
+        pt.sleep_days(1);
+        pt.crank_pool(&created.owner).unwrap();
+        pt.crank_pool(&buffered.owner).unwrap();
+    }
+    pt.stake(&created.owner, &created.late_staker, 10_000)
+        .unwrap();
+    pt.stake(&buffered.owner, &buffered.late_staker, 10_000)
+        .unwrap();
+    pt.sleep_days(1);
+    pt.crank_pool(&created.owner).unwrap();
+    pt.crank_pool(&buffered.owner).unwrap();
+    claim_all(&mut pt, &created);
+    claim_all(&mut pt, &buffered);
+    let earned = |pool: &Pool, start: [u64; 3]| {
+        let end = balances(&pt, pool);
+        [end[0] - start[0], end[1] - start[1], end[2] - start[2]]
+    };
+    assert_eq!(earned(&created, created_start), earned(&buffered, start));
+    assert!(pt.token_balance(&created.staker).unwrap() > 90_000);
+}
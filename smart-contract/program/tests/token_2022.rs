@@ -0,0 +1,61 @@
+use solana_sdk::signer::Signer;
+
+use crate::common::test_runner::TestRunner;
+
+pub mod common;
+
+#[tokio::test]
+async fn token_2022() {
+    // Setup the token + basic accounts with a Token-2022 mint
+    let mut tr = TestRunner::new_with_token_program(1_000_000, spl_token_2022::ID)
+        .await
+        .unwrap();
+
+    // Create users
+    let stake_pool_owner = tr.create_user_with_ata().await.unwrap();
+    let staker = tr.create_user_with_ata().await.unwrap();
+
+    // Mint
+    tr.mint(&staker.pubkey(), 10_200).await.unwrap();
+    assert_eq!(tr.staker_stats(staker.pubkey()).await.unwrap().balance, 10_200);
+
+    // Create and activate a stake pool
+    tr.create_pool(&stake_pool_owner, 10_000)
+        .await
+        .unwrap();
+    tr.activate_stake_pool(&stake_pool_owner.pubkey())
+        .await
+        .unwrap();
+
+    // Stake
+    tr.create_stake_account(&stake_pool_owner.pubkey(), &staker.pubkey())
+        .await
+        .unwrap();
+    tr.stake(&stake_pool_owner.pubkey(), &staker, 10_000)
+        .await
+        .unwrap();
+    assert_eq!(tr.staker_stats(staker.pubkey()).await.unwrap().balance, 0);
+    let pool_stats = tr.pool_stats(stake_pool_owner.pubkey()).await.unwrap();
+    assert_eq!(pool_stats.vault, 10_000);
+    let central_state_stats = tr.central_state_stats().await.unwrap();
+    assert_eq!(central_state_stats.balance, 200);
+
+    // Claim the rewards after a day
+    tr.sleep(86400).await.unwrap();
+    tr.crank_pool(&stake_pool_owner.pubkey()).await.unwrap();
+    tr.claim_staker_rewards(&stake_pool_owner.pubkey(), &staker)
+        .await
+        .unwrap();
+    tr.claim_pool_rewards(&stake_pool_owner).await.unwrap();
+    assert_eq!(tr.staker_stats(staker.pubkey()).await.unwrap().balance, 500_000);
+    let pool_stats = tr.pool_stats(stake_pool_owner.pubkey()).await.unwrap();
+    assert_eq!(pool_stats.balance, 500_000);
+
+    // Unstake everything
+    tr.unstake(&stake_pool_owner.pubkey(), &staker, 10_000)
+        .await
+        .unwrap();
+    assert_eq!(tr.staker_stats(staker.pubkey()).await.unwrap().balance, 510_000);
+    let pool_stats = tr.pool_stats(stake_pool_owner.pubkey()).await.unwrap();
+    assert_eq!(pool_stats.vault, 0);
+}
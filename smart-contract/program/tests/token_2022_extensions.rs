@@ -0,0 +1,66 @@
+use solana_program::program_error::ProgramError;
+
+use access_protocol::error::AccessError;
+use access_protocol::state::SECONDS_IN_DAY;
+use access_protocol::testing::{MintExtension, ProtocolTest};
+
+// Runs with `cargo test --features testing --test token_2022_extensions`, see `access_protocol::testing`
+
+#[test]
+fn transfer_fee_mint() {
+    // The vaults would receive less than the stakes and the bonds are credited with
+    let result = ProtocolTest::new_with_mint_extensions(1_000_000, &[MintExtension::TransferFee]);
+    assert_eq!(
+        result.err(),
+        Some(ProgramError::from(AccessError::UnsupportedTokenExtension))
+    );
+}
+
+#[test]
+fn transfer_hook_mint() {
+    let mut pt =
+        ProtocolTest::new_with_mint_extensions(1_000_000, &[MintExtension::TransferHook]).unwrap();
+    let pool_owner = pt.create_user_with_ata();
+    let staker = pt.create_user_with_ata();
+    pt.mint(&staker, 20_000).unwrap();
+    pt.create_pool(&pool_owner, 1_000).unwrap();
+    pt.activate_stake_pool(&pool_owner).unwrap();
+    pt.create_stake_account(&pool_owner, &staker).unwrap();
+    let vault = pt.get_ata(&pt.get_pool_pda(&pool_owner));
+
+    // The transfers fail without the accounts of the hook
+    let transfer_hook_accounts = std::mem::take(&mut pt.transfer_hook_accounts);
+    assert!(pt.stake(&pool_owner, &staker, 10_000).is_err());
+    pt.transfer_hook_accounts = transfer_hook_accounts[..2].to_vec();
+    assert!(pt.stake(&pool_owner, &staker, 10_000).is_err());
+    pt.transfer_hook_accounts = transfer_hook_accounts;
+
+    // The skipped receipt accounts are followed by the accounts of the hook
+    pt.stake(&pool_owner, &staker, 10_000).unwrap();
+    assert_eq!(pt.token_account_balance(&vault).unwrap(), 10_000);
+    let fees = pt.token_account_balance(&pt.central_state_vault).unwrap();
+    assert_eq!(pt.token_balance(&staker).unwrap(), 10_000 - fees);
+
+    pt.sleep_days(1);
+    pt.crank_pool(&pool_owner).unwrap();
+    pt.claim_staker_rewards(&pool_owner, &staker).unwrap();
+    let balance = pt.token_balance(&staker).unwrap();
+    pt.unstake(&pool_owner, &staker, 10_000).unwrap();
+    assert_eq!(pt.token_account_balance(&vault).unwrap(), 0);
+    assert_eq!(pt.token_balance(&staker).unwrap(), balance + 10_000);
+
+    // The bonds V2 lock and unlock their tokens through the hook
+    let unlock_date = Some(pt.clock().unix_timestamp + SECONDS_IN_DAY as i64);
+    pt.create_bond_v2(&staker, &pool_owner, unlock_date)
+        .unwrap();
+    pt.add_to_bond_v2(&staker, &staker, &pool_owner, 5_000, unlock_date)
+        .unwrap();
+    assert_eq!(pt.token_account_balance(&vault).unwrap(), 5_000);
+    pt.sleep_days(1);
+    pt.crank_pool(&pool_owner).unwrap();
+    pt.claim_bond_v2_rewards(&staker, &pool_owner, unlock_date)
+        .unwrap();
+    pt.unlock_bond_v2(&staker, &pool_owner, unlock_date)
+        .unwrap();
+    assert_eq!(pt.token_account_balance(&vault).unwrap(), 0);
+}
@@ -0,0 +1,155 @@
+use solana_sdk::signer::Signer;
+
+use access_protocol::state::BondV2Account;
+
+use crate::common::test_runner::TestRunner;
+
+pub mod common;
+
+#[tokio::test]
+async fn views() {
+    // Setup the token + basic accounts
+    let mut tr = TestRunner::new(1_000_000).await.unwrap();
+
+    // Create users
+    let stake_pool_owner = tr.create_user_with_ata().await.unwrap();
+    let staker = tr.create_user_with_ata().await.unwrap();
+    let recommender = tr.create_user_with_ata().await.unwrap();
+    let outsider = tr.create_user_with_ata().await.unwrap();
+
+    // Setup a pool with one staker who also locks tokens in a bond V2
+    tr.mint(&staker.pubkey(), 15_300).await.unwrap();
+    tr.create_pool(&stake_pool_owner, 5_000)
+        .await
+        .unwrap();
+    tr.activate_stake_pool(&stake_pool_owner.pubkey())
+        .await
+        .unwrap();
+    tr.create_stake_account(&stake_pool_owner.pubkey(), &staker.pubkey())
+        .await
+        .unwrap();
+    tr.stake(&stake_pool_owner.pubkey(), &staker, 10_000)
+        .await
+        .unwrap();
+    let start_time = tr.get_current_time().await;
+    let unlock_date = Some(start_time + 365 * 86_400);
+    tr.create_bond_v2(&staker.pubkey(), &stake_pool_owner.pubkey(), unlock_date)
+        .await
+        .unwrap();
+    tr.add_to_bond_v2(
+        &staker,
+        &staker.pubkey(),
+        &stake_pool_owner.pubkey(),
+        5_000,
+        unlock_date,
+    )
+        .await
+        .unwrap();
+    let stake_pool_key = tr.get_pool_pda(&stake_pool_owner.pubkey());
+    let (bond_key, _) = BondV2Account::create_key(
+        &staker.pubkey(),
+        &stake_pool_key,
+        unlock_date,
+        &tr.program_id,
+    );
+
+    // The staker pays 10 % of the rewards as royalties
+    tr.create_royalty(
+        &staker,
+        &recommender.pubkey(),
+        1000,
+        (start_time + 3 * 86_400) as u64,
+    )
+        .await
+        .unwrap();
+
+    // Central state info
+    let info = tr.view_central_state_info().await.unwrap();
+    assert_eq!(info.daily_inflation, 1_000_000);
+    assert_eq!(info.total_staked, 15_000);
+    assert_eq!(info.current_offset, 0);
+    assert_eq!(info.fee_basis_points, 200);
+
+    // Wait for 1 day
+    tr.sleep(86400).await.unwrap();
+
+    // The pool needs to be cranked before any reward is claimable
+    let claimable = tr
+        .view_claimable_rewards(&stake_pool_owner.pubkey(), &staker.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(claimable.reward, 0);
+    assert!(claimable.needs_crank);
+
+    tr.crank_pool(&stake_pool_owner.pubkey()).await.unwrap();
+
+    // Claimable rewards of the stake account, minus the royalties
+    let claimable = tr
+        .view_claimable_rewards(&stake_pool_owner.pubkey(), &staker.pubkey())
+        .await
+        .unwrap();
+    assert!(!claimable.needs_crank);
+    assert_eq!(claimable.offset, 1);
+    assert!(claimable.royalty > 0);
+    assert_eq!(claimable.reward + claimable.royalty, 333_333);
+
+    // APR of the pool
+    let apr = tr
+        .view_pool_apr(&stake_pool_owner.pubkey())
+        .await
+        .unwrap();
+    let stakers_daily_reward_fp32 = (1_000_000u128 << 32) * 50 / 100 / 15_000;
+    assert_eq!(apr.stakers_daily_reward_fp32, stakers_daily_reward_fp32);
+    assert_eq!(
+        apr.stakers_apr_basis_points as u128,
+        (stakers_daily_reward_fp32 * 365 * 10_000) >> 32
+    );
+    assert_eq!(apr.pool_daily_reward, 500_000);
+    assert_eq!(apr.total_staked, 15_000);
+
+    // Stake summary with the bond
+    let summary = tr
+        .view_stake_summary(&stake_pool_owner.pubkey(), &staker.pubkey(), &[bond_key])
+        .await
+        .unwrap();
+    assert_eq!(summary.owner, staker.pubkey());
+    assert_eq!(summary.stake_pool, stake_pool_key);
+    assert_eq!(summary.staked_amount, 10_000);
+    assert_eq!(summary.bonds_v2_amount, 5_000);
+    assert_eq!(summary.effective_stake, 15_000);
+    assert_eq!(summary.minimum_stake_amount, 5_000);
+    assert!(summary.meets_minimum);
+    assert!((499_999..=500_001).contains(&summary.claimable_rewards));
+
+    // The same bond cannot be counted twice
+    assert!(tr
+        .view_stake_summary(
+            &stake_pool_owner.pubkey(),
+            &staker.pubkey(),
+            &[bond_key, bond_key]
+        )
+        .await
+        .is_err());
+
+    // Bonds of another owner are rejected
+    assert!(tr
+        .view_stake_summary(&stake_pool_owner.pubkey(), &outsider.pubkey(), &[bond_key])
+        .await
+        .is_err());
+
+    // An owner without a stake account
+    let summary = tr
+        .view_stake_summary(&stake_pool_owner.pubkey(), &outsider.pubkey(), &[])
+        .await
+        .unwrap();
+    assert_eq!(summary.effective_stake, 0);
+    assert!(!summary.meets_minimum);
+    assert_eq!(summary.claimable_rewards, 0);
+
+    // The views do not change any state
+    let stake_account = tr
+        .stake_account_stats(staker.pubkey(), stake_pool_owner.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(stake_account.last_claimed_offset, 0);
+}
@@ -0,0 +1,141 @@
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+
+use access_protocol::error::AccessError;
+use access_protocol::governance::VoterWeightAction;
+use access_protocol::state::{BondV2Account, SECONDS_IN_DAY};
+use access_protocol::testing::ProtocolTest;
+
+// Runs with `cargo test --features testing --test voter_weight`, see `access_protocol::testing`
+
+fn err(e: AccessError) -> Result<(), ProgramError> {
+    Err(e.into())
+}
+
+#[test]
+fn voter_weight() {
+    let mut pt = ProtocolTest::new(1_000_000).unwrap();
+    let owner_a = pt.create_user_with_ata();
+    let owner_b = pt.create_user_with_ata();
+    let alice = pt.create_user_with_ata();
+    let bob = pt.create_user_with_ata();
+    pt.mint(&alice, 11_000).unwrap();
+    pt.mint(&bob, 11_000).unwrap();
+    for owner in [owner_a, owner_b] {
+        pt.create_pool(&owner, 1_000).unwrap();
+        pt.activate_stake_pool(&owner).unwrap();
+    }
+    let pool_a = pt.get_pool_pda(&owner_a);
+    let pool_b = pt.get_pool_pda(&owner_b);
+
+    // Alice stakes in the pool A and locks in the pool B for 100 days, Bob stakes in the pool A
+    pt.create_stake_account(&owner_a, &alice).unwrap();
+    pt.stake(&owner_a, &alice, 5_000).unwrap();
+    let unlock_date = Some(pt.clock().unix_timestamp + 100 * SECONDS_IN_DAY as i64);
+    pt.create_bond_v2(&alice, &owner_b, unlock_date).unwrap();
+    pt.add_to_bond_v2(&alice, &alice, &owner_b, 5_000, unlock_date)
+        .unwrap();
+    pt.create_stake_account(&owner_a, &bob).unwrap();
+    pt.stake(&owner_a, &bob, 10_000).unwrap();
+    let alice_stake = pt.get_stake_account_pda(&pool_a, &alice).0;
+    let alice_bond =
+        BondV2Account::create_key(&alice, &pool_b, unlock_date, &access_protocol::ID).0;
+    let bob_stake = pt.get_stake_account_pda(&pool_a, &bob).0;
+    let alice_accounts = [pool_a, alice_stake, pool_b, alice_bond];
+
+    // The records are only computed for the configured realm
+    assert!(pt
+        .update_voter_weight_record(&alice, &alice_accounts, None)
+        .is_err());
+    let realm = Pubkey::new_unique();
+    let mint = pt.mint;
+    pt.set_governance_config(realm, mint, false).unwrap();
+
+    // The voter weight is the sum of the stake accounts and the bonds V2
+    pt.update_voter_weight_record(&alice, &alice_accounts, Some(VoterWeightAction::CastVote))
+        .unwrap();
+    let record = pt.voter_weight_record(&alice).unwrap();
+    assert_eq!(record.realm, realm);
+    assert_eq!(record.governing_token_mint, mint);
+    assert_eq!(record.governing_token_owner, alice);
+    assert_eq!(record.voter_weight, 10_000);
+    assert_eq!(record.voter_weight_expiry, Some(pt.clock().slot));
+    assert_eq!(record.weight_action, Some(VoterWeightAction::CastVote));
+
+    // The accounts must follow their pool, belong to the voter and be passed once
+    assert_eq!(
+        pt.update_voter_weight_record(&alice, &[alice_stake], None),
+        err(AccessError::StakePoolMismatch)
+    );
+    assert_eq!(
+        pt.update_voter_weight_record(&alice, &[pool_b, alice_stake], None),
+        err(AccessError::StakePoolMismatch)
+    );
+    assert_eq!(
+        pt.update_voter_weight_record(&alice, &[pool_a, alice_stake, alice_stake], None),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        pt.update_voter_weight_record(&alice, &[pool_a, bob_stake], None),
+        err(AccessError::StakeAccountOwnerMismatch)
+    );
+    assert_eq!(
+        pt.update_voter_weight_record(&bob, &[pool_b, alice_bond], None),
+        err(AccessError::WrongOwner)
+    );
+
+    // The max voter weight is the total staked
+    pt.update_max_voter_weight_record().unwrap();
+    let max_record = pt.max_voter_weight_record().unwrap();
+    assert_eq!(max_record.realm, realm);
+    assert_eq!(max_record.max_voter_weight, 20_000);
+    assert_eq!(max_record.max_voter_weight_expiry, Some(pt.clock().slot));
+
+    // With the lock weighting the bonds V2 add their refreshed boost
+    pt.set_boost_config(100 * SECONDS_IN_DAY as i64, 10_000, 20_000)
+        .unwrap();
+    pt.refresh_bond_v2_boost(&alice, &owner_b, unlock_date)
+        .unwrap();
+    pt.update_voter_weight_record(&alice, &alice_accounts, None)
+        .unwrap();
+    assert_eq!(pt.voter_weight_record(&alice).unwrap().voter_weight, 10_000);
+    pt.update_max_voter_weight_record().unwrap();
+    assert_eq!(
+        pt.max_voter_weight_record().unwrap().max_voter_weight,
+        20_000
+    );
+    pt.set_governance_config(realm, mint, true).unwrap();
+    pt.update_voter_weight_record(&alice, &alice_accounts, None)
+        .unwrap();
+    assert_eq!(pt.voter_weight_record(&alice).unwrap().voter_weight, 15_000);
+    pt.update_max_voter_weight_record().unwrap();
+    assert_eq!(
+        pt.max_voter_weight_record().unwrap().max_voter_weight,
+        25_000
+    );
+
+    // A boost that was not refreshed is capped by the remaining lock time
+    pt.sleep_days(50);
+    pt.update_voter_weight_record(&alice, &alice_accounts, None)
+        .unwrap();
+    assert_eq!(pt.voter_weight_record(&alice).unwrap().voter_weight, 12_500);
+    assert_eq!(pt.voter_weight_record(&alice).unwrap().weight_action, None);
+
+    // The receipts count for the holder and must follow the pool of their receipt mint
+    pt.mint(&bob, 2_000).unwrap();
+    pt.crank_pool(&owner_a).unwrap();
+    pt.create_receipt_mint(&owner_a).unwrap();
+    pt.stake_for_receipts(&owner_a, &bob, 1_000).unwrap();
+    let bob_receipts = pt.get_receipt_account(&bob, &owner_a);
+    assert_eq!(
+        pt.update_voter_weight_record(&bob, &[pool_b, bob_receipts], None),
+        err(AccessError::WrongReceiptMint)
+    );
+    assert_eq!(
+        pt.update_voter_weight_record(&alice, &[pool_a, bob_receipts], None),
+        err(AccessError::WrongOwner)
+    );
+    pt.update_voter_weight_record(&bob, &[pool_a, bob_stake, bob_receipts], None)
+        .unwrap();
+    assert_eq!(pt.voter_weight_record(&bob).unwrap().voter_weight, 11_000);
+}
@@ -0,0 +1,13 @@
+//! Deterministic simulator of the Access Protocol economics
+//!
+//! Replays the instructions of the program over the pools, stakers and bonds of a [`Scenario`]
+//! to evaluate inflation and pool multiplier changes before they are voted.
+#![warn(missing_docs)]
+
+pub mod report;
+pub mod scenario;
+pub mod simulation;
+
+pub use report::Report;
+pub use scenario::{BondScenario, BoostScenario, ParameterChange, PoolScenario, Scenario};
+pub use simulation::{simulate, SimulationError};
@@ -0,0 +1,27 @@
+//! Runs a scenario file and prints the report as JSON
+//!
+//! Exits with the code 2 if the rewards of a day exceed the claimable rewards bound.
+use std::error::Error;
+
+use access_simulator::{simulate, Scenario};
+
+fn run() -> Result<bool, Box<dyn Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .ok_or("Usage: access-simulator <SCENARIO.json>")?;
+    let scenario = Scenario::from_json(&std::fs::read_to_string(path)?)?;
+    let report = simulate(&scenario)?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(report.invariant_violations.is_empty())
+}
+
+fn main() {
+    match run() {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(2),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            std::process::exit(1);
+        }
+    }
+}
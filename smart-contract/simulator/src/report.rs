@@ -0,0 +1,62 @@
+//! Results of a simulation
+use serde::Serialize;
+
+/// The outcome of a simulation
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct Report {
+    /// Protocol state at each simulated day
+    pub days: Vec<DayReport>,
+    /// Totals of each pool over the simulation
+    pub pools: Vec<PoolReport>,
+    /// Tokens minted by all the claims
+    pub total_emissions: u64,
+    /// Days on which the tokens claimable over all the pools exceed the bound of a crank
+    pub invariant_violations: Vec<InvariantViolation>,
+}
+
+/// The protocol state after the cranks of a day
+#[derive(Serialize, Debug, Clone)]
+pub struct DayReport {
+    /// The day offset
+    pub day: u64,
+    /// The daily inflation used by the cranks
+    pub daily_inflation: u64,
+    /// The total staked of the central state
+    pub total_staked: u64,
+    /// The tokens claimable for that day over all the pools
+    pub claimable: u128,
+}
+
+/// Totals of a pool over the simulation
+#[derive(Serialize, Debug, Clone)]
+pub struct PoolReport {
+    /// Name of the pool
+    pub name: String,
+    /// The amount staked and locked in bonds in the pool
+    pub total_staked: u64,
+    /// The boosts of the bonds of the pool at the end of the simulation
+    pub total_boost: u64,
+    /// The % of the pool rewards going to the stakers at the end of the simulation
+    pub stakers_part: u64,
+    /// Tokens claimed by all the stakers of the pool
+    pub stakers_rewards: u64,
+    /// Tokens claimed by all the bonds of the pool
+    pub bonds_rewards: u64,
+    /// Tokens claimed by the pool owner
+    pub owner_rewards: u64,
+    /// Annualized rewards of the stakers and bonds in %
+    pub stakers_apr: f64,
+    /// Share of the pool rewards claimed by the stakers and bonds in %
+    pub stakers_share: f64,
+}
+
+/// A day whose rewards over all the pools exceed `daily_inflation + 1_000_000`
+#[derive(Serialize, Debug, Clone)]
+pub struct InvariantViolation {
+    /// The day offset
+    pub day: u64,
+    /// The tokens claimable for that day over all the pools
+    pub total_claimable: u128,
+    /// The upper bound checked by the crank of each pool
+    pub max_claimable: u128,
+}
@@ -0,0 +1,146 @@
+//! Description of a simulation
+use access_protocol::state::{
+    BondV2Account, StakeAccount, StakePoolHeader, DEFAULT_STAKER_MULTIPLIER, SECONDS_IN_DAY,
+};
+use serde::{Deserialize, Serialize};
+
+/// The protocol parameters, pools and stakers replayed by a simulation
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Scenario {
+    /// Number of days to simulate
+    pub days: u64,
+    /// The initial daily inflation of the central state
+    pub daily_inflation: u64,
+    /// The tokens held outside of the pools, `change_inflation` caps the annual inflation to the supply
+    #[serde(default)]
+    pub supply: u64,
+    /// The simulated pools
+    pub pools: Vec<PoolScenario>,
+    /// Parameter changes applied during the simulation
+    #[serde(default)]
+    pub changes: Vec<ParameterChange>,
+    /// Number of days between two claims of every staker, bond and pool owner, at most `STAKE_BUFFER_LEN`
+    #[serde(default = "default_claim_interval")]
+    pub claim_interval: u64,
+    /// The boost curve of the bonds, the bonds are not boosted without it
+    #[serde(default)]
+    pub boost: Option<BoostScenario>,
+}
+
+/// A pool, the stake of each of its stakers and its bonds
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PoolScenario {
+    /// Name of the pool in the report
+    pub name: String,
+    /// The % of the pool rewards going to the stakers
+    #[serde(default = "default_stakers_part")]
+    pub stakers_part: u64,
+    /// The amount staked by each staker
+    pub stakes: Vec<u64>,
+    /// The bonds V2 locked in the pool
+    #[serde(default)]
+    pub bonds: Vec<BondScenario>,
+}
+
+/// A bond V2 created on day 0, it stays locked until the end of the simulation
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BondScenario {
+    /// The locked amount
+    pub amount: u64,
+    /// Number of days until the unlock date, a forever bond if absent
+    #[serde(default)]
+    pub lock_days: Option<u64>,
+}
+
+/// Models `admin_set_boost_config`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BoostScenario {
+    /// The lock duration in days from which a bond gets the maximum boost
+    pub max_lock_days: u64,
+    /// The boost of a bond locked for `max_lock_days` or more, in basis points of its amount
+    pub max_lock_boost_basis_points: u16,
+    /// The boost of a forever bond, in basis points of its amount
+    pub forever_boost_basis_points: u16,
+}
+
+/// A parameter change applied after the cranks of `day`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ParameterChange {
+    /// Models `change_inflation`
+    Inflation {
+        /// The day of the change
+        day: u64,
+        /// The new daily inflation
+        daily_inflation: u64,
+    },
+    /// Models `change_pool_multiplier`
+    PoolMultiplier {
+        /// The day of the change
+        day: u64,
+        /// The name of the pool
+        pool: String,
+        /// The new % of the pool rewards going to the stakers
+        stakers_part: u64,
+    },
+}
+
+impl ParameterChange {
+    /// The day of the change
+    pub fn day(&self) -> u64 {
+        match self {
+            ParameterChange::Inflation { day, .. } => *day,
+            ParameterChange::PoolMultiplier { day, .. } => *day,
+        }
+    }
+}
+
+fn default_claim_interval() -> u64 {
+    1
+}
+
+fn default_stakers_part() -> u64 {
+    DEFAULT_STAKER_MULTIPLIER
+}
+
+impl Scenario {
+    /// Parses a scenario from JSON
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+impl PoolScenario {
+    /// Loads a pool from a snapshot of its on-chain accounts taken at the unix timestamp `now`
+    ///
+    /// The empty stake accounts and bonds are skipped, the lock of the bonds is rounded up to
+    /// whole days.
+    pub fn from_accounts(
+        name: String,
+        header: &StakePoolHeader,
+        stake_accounts: &[StakeAccount],
+        bonds: &[BondV2Account],
+        now: i64,
+    ) -> Self {
+        Self {
+            name,
+            stakers_part: header.stakers_part,
+            stakes: stake_accounts
+                .iter()
+                .map(|s| s.stake_amount)
+                .filter(|amount| *amount > 0)
+                .collect(),
+            bonds: bonds
+                .iter()
+                .filter(|b| b.amount > 0)
+                .map(|b| BondScenario {
+                    amount: b.amount,
+                    lock_days: b.unlock_timestamp.map(|unlock| {
+                        let seconds = unlock.saturating_sub(now).max(0) as u64;
+                        seconds.div_ceil(SECONDS_IN_DAY)
+                    }),
+                })
+                .collect(),
+        }
+    }
+}
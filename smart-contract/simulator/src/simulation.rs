@@ -0,0 +1,332 @@
+//! Replay of the program over the days of a scenario
+//!
+//! The instructions run in-process on the [`ProtocolTest`] harness of the program, the cranks,
+//! claims and boost refreshes are the ones executed on-chain.
+use std::collections::HashSet;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use access_protocol::error::AccessError;
+use access_protocol::events::Event;
+use access_protocol::rewards::max_daily_claimable;
+use access_protocol::state::{SECONDS_IN_DAY, STAKE_BUFFER_LEN};
+use access_protocol::testing::ProtocolTest;
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use thiserror::Error;
+
+use crate::report::{DayReport, InvariantViolation, PoolReport, Report};
+use crate::scenario::{ParameterChange, Scenario};
+
+/// Errors returned by [`simulate`]
+#[derive(Error, Debug)]
+pub enum SimulationError {
+    /// The scenario cannot be replayed
+    #[error("Invalid scenario: {0}")]
+    InvalidScenario(String),
+    /// An instruction of the program failed
+    #[error("Program error: {0}")]
+    Program(#[from] ProgramError),
+    /// The crank of a pool failed its claimable rewards check
+    #[error("The crank of {pool} fails its claimable rewards check on day {day}")]
+    CrankFailed {
+        /// The day offset
+        day: u64,
+        /// Name of the pool
+        pool: String,
+    },
+}
+
+impl From<AccessError> for SimulationError {
+    fn from(e: AccessError) -> Self {
+        SimulationError::Program(e.into())
+    }
+}
+
+struct Bond {
+    owner: Pubkey,
+    unlock_timestamp: Option<i64>,
+}
+
+struct Pool {
+    name: String,
+    owner: Pubkey,
+    stakers: Vec<Pubkey>,
+    bonds: Vec<Bond>,
+    stakers_rewards: u64,
+    bonds_rewards: u64,
+    owner_rewards: u64,
+}
+
+fn validate(scenario: &Scenario) -> Result<(), SimulationError> {
+    let invalid = |reason: String| Err(SimulationError::InvalidScenario(reason));
+    if scenario.claim_interval == 0 || scenario.claim_interval > STAKE_BUFFER_LEN {
+        return invalid(format!(
+            "The claim interval must be between 1 and {} days",
+            STAKE_BUFFER_LEN
+        ));
+    }
+    let mut names = HashSet::new();
+    for pool in &scenario.pools {
+        if !names.insert(&pool.name) {
+            return invalid(format!("Duplicate pool {}", pool.name));
+        }
+        if pool.stakers_part > 100 {
+            return invalid(format!("The stakers part of {} is above 100", pool.name));
+        }
+        if pool.stakes.contains(&0) || pool.bonds.iter().any(|b| b.amount == 0) {
+            return invalid(format!("The pool {} has an empty stake or bond", pool.name));
+        }
+    }
+    for change in &scenario.changes {
+        if let ParameterChange::PoolMultiplier {
+            pool, stakers_part, ..
+        } = change
+        {
+            if !names.contains(pool) {
+                return invalid(format!("Unknown pool {}", pool));
+            }
+            if *stakers_part > 100 {
+                return invalid(format!("The stakers part of {} is above 100", pool));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Creates a user holding `amount` tokens and the fees of locking them
+fn funded_user(pt: &mut ProtocolTest, amount: u64) -> Result<Pubkey, SimulationError> {
+    let user = pt.create_user_with_ata();
+    pt.mint(&user, amount.checked_mul(2).ok_or(AccessError::Overflow)?)?;
+    Ok(user)
+}
+
+fn setup(pt: &mut ProtocolTest, scenario: &Scenario) -> Result<Vec<Pool>, SimulationError> {
+    if let Some(boost) = &scenario.boost {
+        let max_lock_duration = boost
+            .max_lock_days
+            .checked_mul(SECONDS_IN_DAY)
+            .ok_or(AccessError::Overflow)?;
+        pt.set_boost_config(
+            max_lock_duration as i64,
+            boost.max_lock_boost_basis_points,
+            boost.forever_boost_basis_points,
+        )?;
+    }
+
+    let now = pt.clock().unix_timestamp;
+    let mut pools = Vec::with_capacity(scenario.pools.len());
+    for p in &scenario.pools {
+        let owner = pt.create_user_with_ata();
+        pt.create_pool(&owner, 0)?;
+        pt.activate_stake_pool(&owner)?;
+        if p.stakers_part != pt.pool_stats(&owner)?.stakers_part {
+            pt.change_pool_multiplier(&owner, p.stakers_part)?;
+        }
+
+        let mut stakers = Vec::with_capacity(p.stakes.len());
+        for amount in &p.stakes {
+            let staker = funded_user(pt, *amount)?;
+            pt.create_stake_account(&owner, &staker)?;
+            pt.stake(&owner, &staker, *amount)?;
+            stakers.push(staker);
+        }
+
+        let mut bonds = Vec::with_capacity(p.bonds.len());
+        for b in &p.bonds {
+            let bond_owner = funded_user(pt, b.amount)?;
+            let unlock_timestamp = b
+                .lock_days
+                .map(|days| {
+                    days.checked_mul(SECONDS_IN_DAY)
+                        .and_then(|seconds| now.checked_add(seconds as i64))
+                        .ok_or(AccessError::Overflow)
+                })
+                .transpose()?;
+            pt.create_bond_v2(&bond_owner, &owner, unlock_timestamp)?;
+            pt.add_to_bond_v2(&bond_owner, &bond_owner, &owner, b.amount, unlock_timestamp)?;
+            bonds.push(Bond {
+                owner: bond_owner,
+                unlock_timestamp,
+            });
+        }
+
+        pools.push(Pool {
+            name: p.name.clone(),
+            owner,
+            stakers,
+            bonds,
+            stakers_rewards: 0,
+            bonds_rewards: 0,
+            owner_rewards: 0,
+        });
+    }
+    Ok(pools)
+}
+
+// Refreshes the boosts of the bonds, the refreshes of an unchanged boost fail with `NoOp`
+fn refresh_boosts(pt: &mut ProtocolTest, pool: &Pool) -> Result<(), SimulationError> {
+    for bond in &pool.bonds {
+        match pt.refresh_bond_v2_boost(&bond.owner, &pool.owner, bond.unlock_timestamp) {
+            Err(e) if e == AccessError::NoOp.into() => {}
+            result => result?,
+        }
+    }
+    Ok(())
+}
+
+// Claims the rewards of a user and returns the tokens received, the claims of nothing fail with `NoOp`
+fn claimed<F>(pt: &mut ProtocolTest, user: &Pubkey, claim: F) -> Result<u64, SimulationError>
+where
+    F: FnOnce(&mut ProtocolTest) -> Result<(), ProgramError>,
+{
+    let balance = pt.token_balance(user)?;
+    match claim(pt) {
+        Err(e) if e == AccessError::NoOp.into() => return Ok(0),
+        result => result?,
+    }
+    Ok(pt.token_balance(user)? - balance)
+}
+
+fn claim(pt: &mut ProtocolTest, pool: &mut Pool) -> Result<(), SimulationError> {
+    let owner = pool.owner;
+    for staker in &pool.stakers {
+        pool.stakers_rewards += claimed(pt, staker, |pt| pt.claim_staker_rewards(&owner, staker))?;
+    }
+    for bond in &pool.bonds {
+        pool.bonds_rewards += claimed(pt, &bond.owner, |pt| {
+            pt.claim_bond_v2_rewards(&bond.owner, &owner, bond.unlock_timestamp)
+        })?;
+    }
+    pool.owner_rewards += claimed(pt, &owner, |pt| pt.claim_pool_rewards(&owner))?;
+    Ok(())
+}
+
+// Cranks a pool and returns the tokens claimable for the day, the crank asserts its own bound
+fn crank(pt: &mut ProtocolTest, pool: &Pool, day: u64) -> Result<u128, SimulationError> {
+    catch_unwind(AssertUnwindSafe(|| pt.crank_pool(&pool.owner))).map_err(|_| {
+        SimulationError::CrankFailed {
+            day,
+            pool: pool.name.clone(),
+        }
+    })??;
+    pt.events()
+        .into_iter()
+        .find_map(|event| match event {
+            Event::Crank {
+                total_claimable, ..
+            } => Some(total_claimable),
+            _ => None,
+        })
+        .ok_or_else(|| ProgramError::InvalidAccountData.into())
+}
+
+/// Cranks every pool each day and claims all the rewards every `claim_interval` days
+///
+/// The pools, stakes and bonds are created on day 0, the parameter changes of a day follow its
+/// cranks and the boosts of the bonds are refreshed before each claim. The sum of the tokens
+/// claimable over all the pools is checked against the bound each crank checks for its own pool.
+pub fn simulate(scenario: &Scenario) -> Result<Report, SimulationError> {
+    validate(scenario)?;
+
+    let mut pt = ProtocolTest::new(scenario.daily_inflation)?;
+    let authority = pt.payer;
+    let treasury = pt.create_user_with_ata();
+    pt.mint(&treasury, scenario.supply)?;
+    let mut pools = setup(&mut pt, scenario)?;
+    if scenario.boost.is_some() {
+        for pool in &pools {
+            refresh_boosts(&mut pt, pool)?;
+        }
+    }
+
+    let supply = pt.supply()?;
+    let mut report = Report::default();
+
+    for day in 1..=scenario.days {
+        pt.sleep_days(1);
+
+        // The first crank of the day takes the snapshot of the total staked
+        let mut claimable = 0u128;
+        for pool in &pools {
+            claimable += crank(&mut pt, pool, day)?;
+        }
+
+        let central_state = pt.central_state_stats()?;
+        let max_claimable = max_daily_claimable(central_state.daily_inflation)?;
+        if claimable > max_claimable {
+            report.invariant_violations.push(InvariantViolation {
+                day,
+                total_claimable: claimable,
+                max_claimable,
+            });
+        }
+        report.days.push(DayReport {
+            day,
+            daily_inflation: central_state.daily_inflation,
+            total_staked: central_state.total_staked,
+            claimable,
+        });
+
+        // `change_inflation` requires the snapshot of the day, the changes apply to the next cranks
+        for change in scenario.changes.iter().filter(|c| c.day() == day) {
+            match change {
+                ParameterChange::Inflation {
+                    daily_inflation, ..
+                } => pt.change_inflation(&authority, *daily_inflation)?,
+                ParameterChange::PoolMultiplier {
+                    pool, stakers_part, ..
+                } => {
+                    for p in pools.iter().filter(|p| &p.name == pool) {
+                        pt.change_pool_multiplier(&p.owner, *stakers_part)?;
+                    }
+                }
+            }
+        }
+
+        if day % scenario.claim_interval == 0 || day == scenario.days {
+            for pool in pools.iter_mut() {
+                if scenario.boost.is_some() {
+                    refresh_boosts(&mut pt, pool)?;
+                }
+                claim(&mut pt, pool)?;
+            }
+        }
+    }
+    report.total_emissions = pt.supply()? - supply;
+
+    report.pools = pools
+        .iter()
+        .map(|p| {
+            let header = pt.pool_stats(&p.owner)?;
+            let total_boost = if header.uses_boost() {
+                pt.pool_boost_stats(&p.owner)?.total_boost
+            } else {
+                0
+            };
+            let stakers_rewards = p.stakers_rewards + p.bonds_rewards;
+            let pool_rewards = stakers_rewards + p.owner_rewards;
+            Ok(PoolReport {
+                name: p.name.clone(),
+                total_staked: header.total_staked,
+                total_boost,
+                stakers_part: header.stakers_part,
+                stakers_rewards: p.stakers_rewards,
+                bonds_rewards: p.bonds_rewards,
+                owner_rewards: p.owner_rewards,
+                stakers_apr: if header.total_staked == 0 || scenario.days == 0 {
+                    0.
+                } else {
+                    stakers_rewards as f64 / header.total_staked as f64 * 365.
+                        / scenario.days as f64
+                        * 100.
+                },
+                stakers_share: if pool_rewards == 0 {
+                    0.
+                } else {
+                    stakers_rewards as f64 / pool_rewards as f64 * 100.
+                },
+            })
+        })
+        .collect::<Result<Vec<_>, SimulationError>>()?;
+
+    Ok(report)
+}
@@ -0,0 +1,308 @@
+use access_protocol::state::{
+    BondV2Account, StakeAccount, StakePoolHeader, Tag, DEFAULT_STAKER_MULTIPLIER, SECONDS_IN_DAY,
+};
+use access_simulator::{
+    simulate, BondScenario, BoostScenario, ParameterChange, PoolScenario, Scenario, SimulationError,
+};
+use solana_program::pubkey::Pubkey;
+
+const DAILY_INFLATION: u64 = 1_000_000_000;
+
+fn pool(name: &str, stakers_part: u64, stakes: &[u64]) -> PoolScenario {
+    PoolScenario {
+        name: name.to_owned(),
+        stakers_part,
+        stakes: stakes.to_vec(),
+        bonds: vec![],
+    }
+}
+
+fn scenario(days: u64, pools: Vec<PoolScenario>) -> Scenario {
+    Scenario {
+        days,
+        daily_inflation: DAILY_INFLATION,
+        supply: 0,
+        pools,
+        changes: vec![],
+        claim_interval: 1,
+        boost: None,
+    }
+}
+
+fn assert_close(value: u64, expected: u64, tolerance: u64) {
+    assert!(
+        value.max(expected) - value.min(expected) <= tolerance,
+        "{} is not within {} of {}",
+        value,
+        tolerance,
+        expected
+    );
+}
+
+#[test]
+fn emissions_follow_the_inflation() {
+    let report = simulate(&scenario(
+        10,
+        vec![pool("a", 50, &[10_000, 30_000]), pool("b", 80, &[20_000])],
+    ))
+    .unwrap();
+
+    assert_eq!(report.days.len(), 10);
+    assert!(report.invariant_violations.is_empty());
+    for day in &report.days {
+        assert_eq!(day.daily_inflation, DAILY_INFLATION);
+        assert_eq!(day.total_staked, 60_000);
+        assert!(day.claimable <= DAILY_INFLATION as u128 + 1_000_000);
+        assert_close(day.claimable as u64, DAILY_INFLATION, 10);
+    }
+    assert_close(report.total_emissions, 10 * DAILY_INFLATION, 100);
+
+    // The pools share the inflation by stake and split it with their stakers part
+    let (a, b) = (&report.pools[0], &report.pools[1]);
+    assert_eq!((a.total_staked, b.total_staked), (40_000, 20_000));
+    assert_close(
+        a.stakers_rewards + a.owner_rewards,
+        10 * DAILY_INFLATION * 2 / 3,
+        100,
+    );
+    assert_close(a.stakers_rewards, a.owner_rewards, 100);
+    assert_close(b.stakers_rewards, 10 * DAILY_INFLATION / 3 * 80 / 100, 100);
+    assert!((b.stakers_share - 80.).abs() < 0.01);
+    assert_eq!(
+        a.stakers_rewards + a.owner_rewards + b.stakers_rewards + b.owner_rewards,
+        report.total_emissions
+    );
+}
+
+#[test]
+fn claim_interval() {
+    let mut daily = scenario(20, vec![pool("a", 70, &[10_000, 25_000])]);
+    let weekly_report = {
+        let mut weekly = daily.clone();
+        weekly.claim_interval = 7;
+        simulate(&weekly).unwrap()
+    };
+    daily.claim_interval = 1;
+    let daily_report = simulate(&daily).unwrap();
+
+    // The claims settle the same rewards whenever they happen
+    assert_close(
+        weekly_report.total_emissions,
+        daily_report.total_emissions,
+        50,
+    );
+    assert_eq!(weekly_report.pools[0].stakers_part, 70);
+}
+
+#[test]
+fn parameter_changes() {
+    let mut s = scenario(10, vec![pool("a", 50, &[10_000]), pool("b", 50, &[10_000])]);
+    // `change_inflation` caps the annual inflation to the supply
+    s.supply = 365 * 2 * DAILY_INFLATION;
+    s.changes = vec![
+        ParameterChange::Inflation {
+            day: 5,
+            daily_inflation: 2 * DAILY_INFLATION,
+        },
+        ParameterChange::PoolMultiplier {
+            day: 5,
+            pool: "b".to_owned(),
+            stakers_part: 100,
+        },
+    ];
+    let report = simulate(&s).unwrap();
+
+    assert!(report.days[..5]
+        .iter()
+        .all(|d| d.daily_inflation == DAILY_INFLATION));
+    assert!(report.days[5..]
+        .iter()
+        .all(|d| d.daily_inflation == 2 * DAILY_INFLATION));
+    assert!(report.invariant_violations.is_empty());
+    assert_close(report.total_emissions, 15 * DAILY_INFLATION, 100);
+
+    // The changes of day 5 apply from day 6, the owner of b stops earning
+    let (a, b) = (&report.pools[0], &report.pools[1]);
+    assert_eq!(b.stakers_part, 100);
+    assert_close(a.owner_rewards, 15 * DAILY_INFLATION / 4, 100);
+    assert_close(b.owner_rewards, 5 * DAILY_INFLATION / 4, 100);
+    assert_close(
+        b.stakers_rewards,
+        5 * DAILY_INFLATION / 4 + 5 * DAILY_INFLATION,
+        100,
+    );
+}
+
+#[test]
+fn bonds() {
+    let mut p = pool("a", 50, &[10_000]);
+    p.bonds = vec![
+        BondScenario {
+            amount: 10_000,
+            lock_days: None,
+        },
+        BondScenario {
+            amount: 20_000,
+            lock_days: Some(365),
+        },
+    ];
+    let report = simulate(&scenario(10, vec![p])).unwrap();
+
+    // Without boost the bonds earn like the stakes
+    let a = &report.pools[0];
+    assert_eq!(a.total_staked, 40_000);
+    assert_eq!(a.total_boost, 0);
+    assert_close(a.bonds_rewards, 3 * a.stakers_rewards, 100);
+    assert_close(a.stakers_rewards + a.bonds_rewards, a.owner_rewards, 100);
+    assert!(report.days.iter().all(|d| d.total_staked == 40_000));
+}
+
+#[test]
+fn boosts() {
+    let mut boosted = pool("boosted", 100, &[10_000]);
+    boosted.bonds = vec![BondScenario {
+        amount: 10_000,
+        lock_days: None,
+    }];
+    let mut s = scenario(10, vec![boosted, pool("plain", 100, &[20_000])]);
+    s.boost = Some(BoostScenario {
+        max_lock_days: 365,
+        max_lock_boost_basis_points: 10_000,
+        forever_boost_basis_points: 5_000,
+    });
+    let report = simulate(&s).unwrap();
+
+    // The forever bond weighs 1.5 times its amount in the rewards of its pool and of the system
+    let (boosted, plain) = (&report.pools[0], &report.pools[1]);
+    assert_eq!(boosted.total_boost, 5_000);
+    assert_close(boosted.bonds_rewards, boosted.stakers_rewards * 3 / 2, 100);
+    assert_close(
+        boosted.stakers_rewards + boosted.bonds_rewards,
+        plain.stakers_rewards * 5 / 4,
+        100,
+    );
+
+    // The inflation is shared by the boosted weights, not exceeded
+    assert!(report.invariant_violations.is_empty());
+    for day in &report.days {
+        assert!(day.claimable <= DAILY_INFLATION as u128 + 1_000_000);
+    }
+    assert_close(report.total_emissions, 10 * DAILY_INFLATION, 100);
+}
+
+#[test]
+fn decaying_boost() {
+    let mut p = pool("a", 100, &[10_000]);
+    p.bonds = vec![BondScenario {
+        amount: 10_000,
+        lock_days: Some(10),
+    }];
+    let mut s = scenario(20, vec![p]);
+    s.boost = Some(BoostScenario {
+        max_lock_days: 10,
+        max_lock_boost_basis_points: 10_000,
+        forever_boost_basis_points: 0,
+    });
+    let report = simulate(&s).unwrap();
+
+    // The boost is refreshed down to nothing at the unlock date
+    let a = &report.pools[0];
+    assert_eq!(a.total_boost, 0);
+    assert!(a.bonds_rewards > a.stakers_rewards);
+    assert!(report.invariant_violations.is_empty());
+}
+
+#[test]
+fn invalid_scenarios() {
+    let invalid = |s: Scenario| matches!(simulate(&s), Err(SimulationError::InvalidScenario(_)));
+
+    let mut s = scenario(1, vec![pool("a", 50, &[10_000])]);
+    s.claim_interval = 0;
+    assert!(invalid(s));
+
+    assert!(invalid(scenario(1, vec![pool("a", 101, &[10_000])])));
+    assert!(invalid(scenario(1, vec![pool("a", 50, &[0])])));
+    assert!(invalid(scenario(
+        1,
+        vec![pool("a", 50, &[10_000]), pool("a", 50, &[10_000])]
+    )));
+
+    let mut s = scenario(1, vec![pool("a", 50, &[10_000])]);
+    s.changes = vec![ParameterChange::PoolMultiplier {
+        day: 1,
+        pool: "b".to_owned(),
+        stakers_part: 50,
+    }];
+    assert!(invalid(s));
+}
+
+#[test]
+fn scenario_json() {
+    let s = Scenario::from_json(
+        r#"{
+            "days": 365,
+            "daily_inflation": 1000000,
+            "supply": 730000000,
+            "pools": [
+                {"name": "a", "stakes": [10000]},
+                {"name": "b", "stakers_part": 80, "stakes": [5000], "bonds": [{"amount": 5000, "lock_days": 30}]}
+            ],
+            "changes": [{"type": "inflation", "day": 30, "daily_inflation": 2000000}],
+            "boost": {"max_lock_days": 365, "max_lock_boost_basis_points": 10000, "forever_boost_basis_points": 5000}
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(s.claim_interval, 1);
+    assert_eq!(s.supply, 730_000_000);
+    assert_eq!(s.pools[0].stakers_part, DEFAULT_STAKER_MULTIPLIER);
+    assert!(s.pools[0].bonds.is_empty());
+    assert_eq!(s.pools[1].bonds[0].lock_days, Some(30));
+    assert!(matches!(
+        s.changes[0],
+        ParameterChange::Inflation {
+            day: 30,
+            daily_inflation: 2_000_000
+        }
+    ));
+    assert_eq!(s.boost.unwrap().forever_boost_basis_points, 5_000);
+}
+
+#[test]
+fn snapshot() {
+    let pool_key = Pubkey::new_unique();
+    let mut header =
+        StakePoolHeader::new(Pubkey::new_unique(), 0, Pubkey::new_unique(), 0).unwrap();
+    header.tag = Tag::StakePool as u8;
+    header.stakers_part = 60;
+    let stake = |amount| {
+        let mut s = StakeAccount::new(Pubkey::new_unique(), pool_key, 0);
+        s.stake_amount = amount;
+        s
+    };
+    let now = 1_700_000_000;
+    let bond = |amount, unlock_timestamp| BondV2Account {
+        amount,
+        unlock_timestamp,
+        ..BondV2Account::new(Pubkey::new_unique(), pool_key, 0, unlock_timestamp)
+    };
+
+    let p = PoolScenario::from_accounts(
+        "a".to_owned(),
+        &header,
+        &[stake(10_000), stake(0), stake(5_000)],
+        &[
+            bond(1_000, None),
+            bond(0, None),
+            bond(2_000, Some(now + SECONDS_IN_DAY as i64 + 1)),
+            bond(3_000, Some(now - 1)),
+        ],
+        now,
+    );
+    assert_eq!(p.stakers_part, 60);
+    assert_eq!(p.stakes, vec![10_000, 5_000]);
+    let bonds: Vec<_> = p.bonds.iter().map(|b| (b.amount, b.lock_days)).collect();
+    assert_eq!(
+        bonds,
+        vec![(1_000, None), (2_000, Some(2)), (3_000, Some(0))]
+    );
+}